@@ -0,0 +1,30 @@
+//! Outgoing robot echo server
+//!
+//! Run: `DINGTALK_APP_SECRET=<app secret> cargo run --example outgoing_robot --features axum`,
+//! then set `http://<host>:8080/dingtalk` as message receiving URL of the robot.
+use axum::Router;
+use dingtalk::outgoing::OutgoingMessage;
+use dingtalk::outgoing_axum::{outgoing_handler, ReplyHandle};
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let app_secret = std::env::var("DINGTALK_APP_SECRET").expect("DINGTALK_APP_SECRET is required");
+    let app = Router::new().route(
+        "/dingtalk",
+        outgoing_handler(
+            &app_secret,
+            |message: OutgoingMessage, reply: ReplyHandle| async move {
+                let text = format!(
+                    "{} said: {}",
+                    message.sender_nick,
+                    message.text().unwrap_or_default()
+                );
+                if let Err(e) = reply.send_text(&text).await {
+                    eprintln!("Reply failed: {}", e);
+                }
+            },
+        ),
+    );
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+    axum::serve(listener, app).await
+}
@@ -0,0 +1,39 @@
+//! Stream mode echo bot, no public HTTP endpoint is needed
+//!
+//! Run: `DINGTALK_APP_KEY=<app key> DINGTALK_APP_SECRET=<app secret> cargo run --example stream_echo_bot --features stream`,
+//! then @ the robot in a group or chat with it, press Ctrl-C to stop.
+use dingtalk::stream::StreamClient;
+use futures::StreamExt;
+
+#[tokio::main]
+async fn main() {
+    let app_key = std::env::var("DINGTALK_APP_KEY").expect("DINGTALK_APP_KEY is required");
+    let app_secret = std::env::var("DINGTALK_APP_SECRET").expect("DINGTALK_APP_SECRET is required");
+    let mut messages = StreamClient::new(&app_key, &app_secret).start();
+    loop {
+        let message = tokio::select! {
+            message = messages.next() => message,
+            _ = tokio::signal::ctrl_c() => None,
+        };
+        let message = match message {
+            Some(message) => message,
+            None => break,
+        };
+        let text = format!(
+            "{} said: {}",
+            message.sender_nick,
+            message.text().unwrap_or_default()
+        );
+        let reply = match message.reply_target() {
+            Ok(reply) => reply,
+            Err(e) => {
+                eprintln!("No reply target: {}", e);
+                continue;
+            }
+        };
+        if let Err(e) = reply.send_text(&text).await {
+            eprintln!("Reply failed: {}", e);
+        }
+    }
+    messages.shutdown().await;
+}
@@ -0,0 +1,144 @@
+use std::time::{Duration, SystemTime};
+use tokio::sync::Mutex;
+
+use crate::{
+    check_response_body, check_response_status, clock::system_now, is_success_status, transport,
+    DingTalkError, DingTalkResponse, XResult,
+};
+
+const DEFAULT_API_URL: &str = "https://oapi.dingtalk.com";
+/// Default margin before expiry to refresh cached access token
+pub(crate) const DEFAULT_TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+/// `errcode` of invalid access token
+const INVALID_TOKEN_ERRCODE: i64 = 40014;
+
+/// Access token of enterprise app, see `DingTalkApp::fetch_access_token` and
+/// `WeChatApp::fetch_access_token`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppAccessToken {
+    pub access_token: String,
+    pub expires_at: SystemTime,
+}
+
+/// DingTalk enterprise app, calls server APIs by access token of `appkey` and `appsecret`
+///
+/// Access token is cached and refreshed 5 minutes(see `DingTalkApp::with_token_refresh_margin`)
+/// before it expires, concurrent refreshes are serialized so only one `/gettoken` call is made.
+///
+/// Document https://open.dingtalk.com/document/orgapp/obtain-orgapp-token
+///
+/// Sample code:
+/// ```ignore
+/// let app = DingTalkApp::new("<appkey>", "<appsecret>");
+/// let access_token = app.access_token().await?;
+/// ```
+pub struct DingTalkApp {
+    pub appkey: String,
+    pub appsecret: String,
+    api_url: String,
+    token_refresh_margin: Duration,
+    pub(crate) client: reqwest::Client,
+    token: Mutex<Option<AppAccessToken>>,
+}
+
+impl DingTalkApp {
+    /// Create `DingTalkApp`
+    pub fn new(appkey: &str, appsecret: &str) -> Self {
+        DingTalkApp {
+            appkey: appkey.into(),
+            appsecret: appsecret.into(),
+            api_url: DEFAULT_API_URL.into(),
+            token_refresh_margin: DEFAULT_TOKEN_REFRESH_MARGIN,
+            client: reqwest::Client::new(),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Set API URL, default is `https://oapi.dingtalk.com`, e.g. mock server in tests
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Set margin before expiry to refresh cached access token, default is 5 minutes
+    pub fn with_token_refresh_margin(mut self, margin: Duration) -> Self {
+        self.token_refresh_margin = margin;
+        self
+    }
+
+    /// Access token, cached until `token_refresh_margin` before it expires
+    ///
+    /// Tasks call this at the same time wait for one refresh.
+    pub async fn access_token(&self) -> XResult<String> {
+        let mut token = self.token.lock().await;
+        if let Some(cached) = token.as_ref() {
+            if system_now() + self.token_refresh_margin < cached.expires_at {
+                return Ok(cached.access_token.clone());
+            }
+        }
+        let fetched = self.fetch_access_token().await?;
+        let access_token = fetched.access_token.clone();
+        *token = Some(fetched);
+        Ok(access_token)
+    }
+
+    /// Drop cached access token, the next `DingTalkApp::access_token` fetches a new one
+    ///
+    /// Called automatically when API replies `errcode` 40014(invalid access token).
+    pub async fn invalidate_token(&self) {
+        *self.token.lock().await = None;
+    }
+
+    /// Get new access token by `/gettoken`, not cached
+    pub async fn fetch_access_token(&self) -> XResult<AppAccessToken> {
+        let request = self.client.get(self.endpoint("/gettoken")).query(&[
+            ("appkey", self.appkey.as_str()),
+            ("appsecret", self.appsecret.as_str()),
+        ]);
+        let response = self.call(request).await?;
+        let access_token = match response.raw["access_token"].as_str() {
+            Some(access_token) if !access_token.is_empty() => access_token.to_owned(),
+            _ => {
+                return Err(DingTalkError::Server {
+                    errcode: -1,
+                    errmsg: format!("No access_token in response: {}", response.raw),
+                })
+            }
+        };
+        let expires_in = response.raw["expires_in"].as_u64().unwrap_or_default();
+        Ok(AppAccessToken {
+            access_token,
+            expires_at: system_now() + Duration::from_secs(expires_in),
+        })
+    }
+
+    pub(crate) fn endpoint(&self, path: &str) -> String {
+        format!("{}{}", self.api_url, path)
+    }
+
+    /// Send API request, HTTP status and `errcode` are checked, invalid access token is dropped
+    pub(crate) async fn call(&self, request: reqwest::RequestBuilder) -> XResult<DingTalkResponse> {
+        let result = call_api(request).await;
+        if let Err(DingTalkError::Server {
+            errcode: INVALID_TOKEN_ERRCODE,
+            ..
+        }) = result
+        {
+            self.invalidate_token().await;
+        }
+        result
+    }
+}
+
+/// Send API request once, HTTP status and `errcode` are checked
+pub(crate) async fn call_api(request: reqwest::RequestBuilder) -> XResult<DingTalkResponse> {
+    let response = request.send().await?;
+    let status = response.status().as_u16();
+    let body = if is_success_status(status) {
+        response.text().await?
+    } else {
+        transport::read_error_body(response).await
+    };
+    check_response_status(status, &body)?;
+    check_response_body(&body)
+}
@@ -0,0 +1,156 @@
+//! Send DingTalk, WeChat Work, Feishu, Slack or Discord message from shell, enabled by
+//! feature `cli`
+//!
+//! Robot is from `--token`(see `DingTalk::from_token`), or `--config` file(see
+//! `DingTalk::from_file`) with optional `--profile`, or `DINGTALK_*` environment variables(see
+//! `DingTalk::from_env`).
+//!
+//! Run: `dingtalk-send --token "dingtalk:<token>?<sec token>" --at-all text "disk full"`,
+//! exits with 1 and error(e.g. server errmsg) on stderr when send failed.
+//!
+//! Pipe: `some-job 2>&1 | dingtalk-send markdown --title "nightly job"`, see
+//! `DingTalk::send_reader`.
+use clap::{Parser, Subcommand};
+use dingtalk::{DingTalk, DingTalkError, DingTalkMessage, XResult};
+use serde_json::Value;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(
+    name = "dingtalk-send",
+    version,
+    about = "Send message by webhook robot"
+)]
+struct Cli {
+    /// Robot token, e.g. `dingtalk:<token>?<sec token>`, `wecom:<key>` or webhook URL
+    #[arg(long, global = true, conflicts_with = "config")]
+    token: Option<String>,
+    /// Config file, JSON, TOML or YAML
+    #[arg(long, global = true)]
+    config: Option<String>,
+    /// Profile of config file with `profiles`
+    #[arg(long, global = true, requires = "config")]
+    profile: Option<String>,
+    /// At all members
+    #[arg(long, global = true)]
+    at_all: bool,
+    /// At member by mobile, can be repeated
+    #[arg(long = "at-mobile", global = true, value_name = "MOBILE")]
+    at_mobiles: Vec<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Send text message
+    Text {
+        /// Text content
+        content: String,
+    },
+    /// Send markdown message
+    Markdown {
+        /// Title shown in notification
+        #[arg(long)]
+        title: String,
+        /// Markdown content, stdin is read until EOF and sent as code block when omitted
+        content: Option<String>,
+    },
+    /// Send link message
+    Link {
+        /// Link title
+        #[arg(long)]
+        title: String,
+        /// Link text
+        #[arg(long)]
+        text: String,
+        /// Picture URL
+        #[arg(long)]
+        pic_url: String,
+        /// URL opened by click
+        #[arg(long)]
+        message_url: String,
+    },
+    /// Send raw JSON payload as is
+    Raw {
+        /// JSON payload, e.g. `{"msgtype": "text", "text": {"content": "hello"}}`
+        json: String,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("dingtalk-send: create runtime failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match runtime.block_on(run(cli)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("dingtalk-send: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> XResult<()> {
+    let dt = match (&cli.token, &cli.config, &cli.profile) {
+        (Some(token), _, _) => DingTalk::from_token(token)?,
+        (None, Some(config), Some(profile)) => DingTalk::from_file_profile(config, profile)?,
+        (None, Some(config), None) => DingTalk::from_file(config)?,
+        (None, None, _) => DingTalk::from_env()?,
+    };
+    let message = match cli.command {
+        Command::Text { content } => DingTalkMessage::new_text(&content),
+        Command::Markdown {
+            title,
+            content: Some(content),
+        } => DingTalkMessage::new_markdown(&title, &content),
+        Command::Markdown {
+            title,
+            content: None,
+        } => {
+            if cli.at_all || !cli.at_mobiles.is_empty() {
+                return Err(DingTalkError::Validation(
+                    "--at-all and --at-mobile are not supported when reading stdin".into(),
+                ));
+            }
+            let response = dt.send_reader(&title, tokio::io::stdin()).await?;
+            if let Some(truncated_bytes) = response.truncated_bytes {
+                eprintln!(
+                    "dingtalk-send: input is truncated by {} bytes",
+                    truncated_bytes
+                );
+            }
+            return Ok(());
+        }
+        Command::Link {
+            title,
+            text,
+            pic_url,
+            message_url,
+        } => DingTalkMessage::new_link(&title, &text, &pic_url, &message_url),
+        Command::Raw { json } => {
+            if cli.at_all || !cli.at_mobiles.is_empty() {
+                return Err(DingTalkError::Validation(
+                    "--at-all and --at-mobile are not supported by raw, put `at` in JSON".into(),
+                ));
+            }
+            let json_value: Value = serde_json::from_str(&json)?;
+            return dt.send_value(&json_value).await;
+        }
+    };
+    let message = message.at_mobiles(&cli.at_mobiles);
+    let message = if cli.at_all {
+        message.at_all()
+    } else {
+        message
+    };
+    dt.send_message(message).await
+}
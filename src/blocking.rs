@@ -0,0 +1,232 @@
+use crate::*;
+#[cfg(feature = "blocking")]
+use std::io::Read;
+
+/// HTTP client of blocking API kept by `DingTalk`, reqwest blocking client
+#[cfg(feature = "blocking")]
+pub(crate) type BlockingClient = reqwest::blocking::Client;
+
+/// HTTP client of blocking API kept by `DingTalk`, ureq transport
+#[cfg(not(feature = "blocking"))]
+pub(crate) type BlockingClient = UreqTransport;
+
+/// Blocking API, enabled by feature `blocking`(reqwest) or `minimal-http`(ureq), reqwest is used
+/// when both are enabled
+///
+/// Blocking API does not wait for `DingTalk::rate_limit`, retries by `DingTalk::with_retry`
+/// block current thread.
+impl DingTalk {
+    /// Send DingTalk message, blocking
+    pub fn send_message_blocking(&self, dingtalk_message: DingTalkMessage) -> XResult<()> {
+        self.send_message_ref_blocking(&dingtalk_message)
+    }
+
+    /// Send DingTalk message by reference, blocking, see `DingTalk::send_message_ref`
+    pub fn send_message_ref_blocking(&self, dingtalk_message: &DingTalkMessage) -> XResult<()> {
+        self.send_message_with_response_ref_blocking(dingtalk_message)
+            .map(|_| ())
+    }
+
+    /// Send DingTalk message with extra headers, blocking, see
+    /// `DingTalk::send_message_with_headers`
+    pub fn send_message_with_headers_blocking(
+        &self,
+        dingtalk_message: &DingTalkMessage,
+        headers: HeaderMap,
+    ) -> XResult<()> {
+        self.clone()
+            .default_headers(headers)
+            .send_message_ref_blocking(dingtalk_message)
+    }
+
+    /// Send DingTalk message and returns parsed response, blocking
+    pub fn send_message_with_response_blocking(
+        &self,
+        dingtalk_message: DingTalkMessage,
+    ) -> XResult<DingTalkResponse> {
+        self.send_message_with_response_ref_blocking(&dingtalk_message)
+    }
+
+    /// Send DingTalk message by reference and returns parsed response, blocking
+    pub fn send_message_with_response_ref_blocking(
+        &self,
+        dingtalk_message: &DingTalkMessage,
+    ) -> XResult<DingTalkResponse> {
+        if self.auto_split {
+            if let Some(chunks) = self.split_message(dingtalk_message) {
+                let mut response = None;
+                for chunk in chunks {
+                    response = Some(self.send_with_response_blocking(&self.message_json(&chunk)?)?);
+                }
+                return response
+                    .ok_or_else(|| DingTalkError::Validation("No chunk to send".into()));
+            }
+        }
+        let (dingtalk_message, truncated_bytes) = self.truncate_message(dingtalk_message);
+        let mut response =
+            self.send_with_response_blocking(&self.message_json(&dingtalk_message)?)?;
+        response.truncated_bytes = truncated_bytes;
+        Ok(response)
+    }
+
+    /// Send text message, blocking
+    pub fn send_text_blocking(&self, text_message: &str) -> XResult<()> {
+        self.send_message_blocking(DingTalkMessage::new_text(text_message))
+    }
+
+    /// Send markdown message, blocking
+    pub fn send_markdown_blocking(&self, title: &str, text: &str) -> XResult<()> {
+        self.send_message_blocking(DingTalkMessage::new_markdown(title, text))
+    }
+
+    /// Direct send JSON message, blocking
+    pub fn send_blocking(&self, json_message: &str) -> XResult<()> {
+        self.send_with_response_blocking(json_message).map(|_| ())
+    }
+
+    /// Direct send JSON message and returns parsed response, blocking
+    pub fn send_with_response_blocking(&self, json_message: &str) -> XResult<DingTalkResponse> {
+        self.check_session_expired()?;
+        if self.dry_run {
+            return self.dry_run_response(json_message);
+        }
+        let dedup_key = match self.dedup_check(json_message) {
+            DedupOutcome::Send { key, summaries } => {
+                for summary in summaries {
+                    if let Ok(summary_json) =
+                        self.message_json(&DingTalkMessage::new_text(&summary))
+                    {
+                        let _ = self.send_retry_blocking(&summary_json);
+                    }
+                }
+                key
+            }
+            DedupOutcome::Suppressed(response) => return Ok(response),
+        };
+        let result = self.send_retry_blocking(json_message);
+        if result.is_err() {
+            self.dedup_forget(dedup_key);
+        }
+        result
+    }
+
+    fn send_retry_blocking(&self, json_message: &str) -> XResult<DingTalkResponse> {
+        let max_attempts = self.retry_max_attempts.max(1);
+        let mut delay = self.retry_initial_delay;
+        let mut attempts = 0_u32;
+        loop {
+            attempts += 1;
+            let started = clock::system_now();
+            let mut status = None;
+            let result = self.send_once_blocking(json_message, &mut status);
+            self.call_send_hooks(json_message, attempts, started, status, &result);
+            match result {
+                Ok(response) => return Ok(response),
+                Err(e) if attempts < max_attempts && e.is_retryable() => {
+                    std::thread::sleep(retry_wait(&e, delay));
+                    delay = (delay * 2).min(RETRY_MAX_DELAY);
+                }
+                Err(e) if attempts > 1 => {
+                    return Err(DingTalkError::Retry {
+                        attempts,
+                        source: Box::new(e),
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn send_once_blocking(
+        &self,
+        json_message: &str,
+        status: &mut Option<u16>,
+    ) -> XResult<DingTalkResponse> {
+        let url = SecretBuffer(self.endpoint_url()?);
+        let mut request = self.intercept(url.to_string(), self.request_body(json_message)?)?;
+        let response = self.post_blocking(&request);
+        wipe(&mut request.url);
+        let response = response?;
+        *status = Some(response.status);
+        check_throttled(
+            response.status,
+            &response.body,
+            response.header("Retry-After"),
+        )?;
+        check_response_status(response.status, &response.body)?;
+        check_response_body(&response.body)
+    }
+
+    /// Blocking client built on first blocking send, shared by clones, rebuilt after timeout,
+    /// proxy or user agent is changed
+    #[cfg(not(feature = "blocking"))]
+    fn post_blocking(&self, request: &RequestParts) -> XResult<TransportResponse> {
+        self.blocking_client
+            .get_or_init(|| {
+                UreqTransport::new()
+                    .timeout(self.timeout)
+                    .connect_timeout(self.connect_timeout)
+                    .proxy(self.proxy.as_deref())
+                    .user_agent(Some(self.user_agent()))
+            })
+            .post_request_blocking(request)
+    }
+
+    /// Blocking client built on first blocking send, shared by clones, rebuilt after timeout,
+    /// proxy or user agent is changed
+    ///
+    /// It is not built by `DingTalk::set_proxy`, building reqwest blocking client within a tokio
+    /// runtime panics.
+    #[cfg(feature = "blocking")]
+    fn blocking_client(&self) -> XResult<&reqwest::blocking::Client> {
+        if let Some(client) = self.blocking_client.get() {
+            return Ok(client);
+        }
+        let mut builder = reqwest::blocking::Client::builder();
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        let client = builder.build()?;
+        Ok(self.blocking_client.get_or_init(|| client))
+    }
+
+    #[cfg(feature = "blocking")]
+    fn post_blocking(&self, request_parts: &RequestParts) -> XResult<TransportResponse> {
+        let mut request = self
+            .blocking_client()?
+            .post(&request_parts.url)
+            .header(CONTENT_TYPE, APPLICATION_JSON_UTF8)
+            .header(USER_AGENT, self.user_agent())
+            .headers(request_parts.header_map()?)
+            .body(request_parts.body.clone());
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        let mut response = request.send()?;
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect();
+        let body = if is_success_status(status) {
+            response.text()?
+        } else {
+            // read error is ignored since the status is already an error
+            let mut bytes = vec![];
+            let _ = (&mut response)
+                .take(ERROR_BODY_MAX_BYTES as u64)
+                .read_to_end(&mut bytes);
+            String::from_utf8_lossy(&bytes).into_owned()
+        };
+        Ok(TransportResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
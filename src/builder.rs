@@ -0,0 +1,174 @@
+use crate::*;
+
+/// Builder of `DingTalk`, created by `DingTalk::builder()`
+///
+/// Sample code:
+/// ```ignore
+/// let dt = DingTalk::builder()
+///     .access_token("<token>")
+///     .sec_token("<sec token>")
+///     .timeout(Duration::from_secs(5))
+///     .build()?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DingTalkBuilder {
+    dingtalk_type: DingTalkType,
+    access_token: String,
+    sec_token: String,
+    webhook_url: String,
+    direct_url: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+    user_agent: Option<String>,
+}
+
+impl DingTalkBuilder {
+    /// Set robot type, default is `DingTalkType::DingTalk`
+    pub fn dingtalk_type(mut self, dingtalk_type: DingTalkType) -> Self {
+        self.dingtalk_type = dingtalk_type;
+        self
+    }
+
+    /// Set robot type to WeChat Work, access token is the webhook key
+    pub fn wechat(self) -> Self {
+        self.dingtalk_type(DingTalkType::WeChatWork)
+    }
+
+    /// Set access token
+    pub fn access_token(mut self, access_token: &str) -> Self {
+        self.access_token = access_token.into();
+        self
+    }
+
+    /// Set sec token, DingTalk and Feishu only
+    pub fn sec_token(mut self, sec_token: &str) -> Self {
+        self.sec_token = sec_token.into();
+        self
+    }
+
+    /// Set webhook URL, default is the robot type's default webhook URL
+    pub fn webhook_url(mut self, webhook_url: &str) -> Self {
+        self.webhook_url = webhook_url.into();
+        self
+    }
+
+    /// Set direct URL, used as is, cannot be used with access token or webhook URL
+    pub fn direct_url(mut self, direct_url: &str) -> Self {
+        self.direct_url = direct_url.into();
+        self
+    }
+
+    /// Set request timeout, same as `DingTalkBuilder::request_timeout`
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.request_timeout(timeout)
+    }
+
+    /// Set request timeout, see `DingTalk::set_request_timeout`
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set connect timeout, see `DingTalk::set_connect_timeout`
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Set HTTP or HTTPS proxy
+    pub fn proxy(mut self, proxy: &str) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Set `User-Agent` header, see `DingTalk::set_user_agent`
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Build `DingTalk`, returns `DingTalkError::Config` for invalid combination
+    pub fn build(self) -> XResult<DingTalk> {
+        self.validate()?;
+        let default_webhook_url = if self.webhook_url.is_empty() {
+            default_webhook_url(self.dingtalk_type).to_owned()
+        } else {
+            self.webhook_url
+        };
+        let mut dingtalk = DingTalk::with_robot(
+            self.dingtalk_type,
+            default_webhook_url,
+            self.access_token,
+            self.sec_token,
+            self.direct_url,
+        );
+        if let Some(timeout) = self.timeout {
+            dingtalk.set_request_timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            dingtalk.set_connect_timeout(connect_timeout)?;
+        }
+        if let Some(proxy) = &self.proxy {
+            dingtalk.set_proxy(proxy)?;
+        }
+        if let Some(user_agent) = &self.user_agent {
+            dingtalk.set_user_agent(user_agent)?;
+        }
+        Ok(dingtalk)
+    }
+
+    fn validate(&self) -> XResult<()> {
+        let has_direct_url = !self.direct_url.is_empty();
+        if has_direct_url && !self.access_token.is_empty() {
+            return Err(DingTalkError::Config(
+                "direct_url and access_token are mutually exclusive".to_owned(),
+            ));
+        }
+        if has_direct_url && !self.webhook_url.is_empty() {
+            return Err(DingTalkError::Config(
+                "direct_url and webhook_url are mutually exclusive".to_owned(),
+            ));
+        }
+        match self.dingtalk_type {
+            DingTalkType::DingTalk | DingTalkType::WeChatWork | DingTalkType::Feishu => {
+                if !has_direct_url && self.access_token.is_empty() {
+                    return Err(DingTalkError::Config(
+                        "access_token or direct_url is required".to_owned(),
+                    ));
+                }
+            }
+            DingTalkType::Slack | DingTalkType::Discord => {
+                if !self.access_token.is_empty() {
+                    return Err(DingTalkError::Config(format!(
+                        "access_token is not supported by robot type {:?}, use direct_url",
+                        self.dingtalk_type
+                    )));
+                }
+                if !has_direct_url && self.webhook_url.is_empty() {
+                    return Err(DingTalkError::Config(
+                        "direct_url or webhook_url is required".to_owned(),
+                    ));
+                }
+            }
+        }
+        if !self.sec_token.is_empty() {
+            match self.dingtalk_type {
+                // DingTalk signs URL, direct URL is used as is
+                DingTalkType::DingTalk if has_direct_url => {
+                    return Err(DingTalkError::Config(
+                        "sec_token is not applied to direct_url".to_owned(),
+                    ));
+                }
+                DingTalkType::DingTalk | DingTalkType::Feishu => {}
+                _ => {
+                    return Err(DingTalkError::Config(format!(
+                        "sec_token is not supported by robot type {:?}",
+                        self.dingtalk_type
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use crate::*;
+use serde_json::json;
+
+/// Receiver of `InteractiveCard`
+#[derive(Clone, Debug, PartialEq)]
+pub enum InteractiveCardReceiver {
+    /// Group chat by `openConversationId`
+    Group(String),
+    /// Single chat with users by `userId`
+    Users(Vec<String>),
+}
+
+/// DingTalk interactive card delivered by enterprise robot, send by
+/// `RobotCodeClient::send_interactive_card`
+///
+/// Card is created from card template `card_template_id` with `card_data`, and can be updated
+/// later by `out_track_id`. Clicks on card buttons are called back to route `callback_route_key`.
+///
+/// Document https://open.dingtalk.com/document/orgapp/robots-send-interactive-cards
+///
+/// Sample code:
+/// ```ignore
+/// let card = InteractiveCard::new("<template id>", "deploy-20240101-001")
+///     .card_data(json!({ "title": "Deploy", "status": "running" }))
+///     .callback_route_key("<route key>")
+///     .group("<open conversation id>");
+/// let out_track_id = robot.send_interactive_card(&card).await?;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct InteractiveCard {
+    pub card_template_id: String,
+    pub out_track_id: String,
+    pub card_data: Value,
+    pub callback_route_key: Option<String>,
+    pub receiver: Option<InteractiveCardReceiver>,
+}
+
+impl InteractiveCard {
+    /// New card of template `card_template_id`, `out_track_id` identifies the card for later
+    /// updates, use a unique one for each card
+    pub fn new(card_template_id: &str, out_track_id: &str) -> Self {
+        InteractiveCard {
+            card_template_id: card_template_id.into(),
+            out_track_id: out_track_id.into(),
+            card_data: Value::Object(Default::default()),
+            callback_route_key: None,
+            receiver: None,
+        }
+    }
+
+    /// Set card data, must be JSON object, values which are not string are sent as JSON string
+    pub fn card_data(mut self, card_data: Value) -> Self {
+        self.card_data = card_data;
+        self
+    }
+
+    /// Set route key of button callback
+    pub fn callback_route_key(mut self, callback_route_key: &str) -> Self {
+        self.callback_route_key = Some(callback_route_key.into());
+        self
+    }
+
+    /// Send to group chat by `openConversationId`
+    pub fn group(mut self, open_conversation_id: &str) -> Self {
+        self.receiver = Some(InteractiveCardReceiver::Group(open_conversation_id.into()));
+        self
+    }
+
+    /// Send to single chats with users by `userId`
+    pub fn users(mut self, user_ids: &[&str]) -> Self {
+        self.receiver = Some(InteractiveCardReceiver::Users(
+            user_ids.iter().map(|user_id| user_id.to_string()).collect(),
+        ));
+        self
+    }
+
+    /// Check required fields
+    pub fn validate(&self) -> XResult<()> {
+        let mut problems: Vec<String> = vec![];
+        if self.card_template_id.is_empty() {
+            problems.push("card_template_id is required".into());
+        }
+        if self.out_track_id.trim().is_empty() {
+            problems.push("out_track_id is required".into());
+        }
+        if !self.card_data.is_object() {
+            problems.push(format!(
+                "card_data must be JSON object, got {}",
+                json_type(&self.card_data)
+            ));
+        }
+        match &self.receiver {
+            None => problems.push("receiver is required, set group or users".into()),
+            Some(InteractiveCardReceiver::Group(open_conversation_id))
+                if open_conversation_id.is_empty() =>
+            {
+                problems.push("group openConversationId is required".into())
+            }
+            Some(InteractiveCardReceiver::Users(user_ids)) if user_ids.is_empty() => {
+                problems.push("users is empty".into())
+            }
+            Some(_) => {}
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(DingTalkError::Validation(format!(
+                "Invalid interactive card: {}",
+                problems.join("; ")
+            )))
+        }
+    }
+
+    /// Request body of `/v1.0/im/interactiveCards/send`
+    pub(crate) fn request_body(&self, robot_code: &str) -> Value {
+        let card_param_map = self
+            .card_data
+            .as_object()
+            .map(|card_data| {
+                card_data
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = match value {
+                            Value::String(value) => value.clone(),
+                            value => value.to_string(),
+                        };
+                        (key.clone(), Value::String(value))
+                    })
+                    .collect::<serde_json::Map<_, _>>()
+            })
+            .unwrap_or_default();
+        let mut body = json!({
+            "cardTemplateId": self.card_template_id,
+            "outTrackId": self.out_track_id,
+            "robotCode": robot_code,
+            "cardData": { "cardParamMap": card_param_map },
+        });
+        if let Some(callback_route_key) = &self.callback_route_key {
+            body["callbackRouteKey"] = callback_route_key.as_str().into();
+        }
+        match &self.receiver {
+            Some(InteractiveCardReceiver::Group(open_conversation_id)) => {
+                body["conversationType"] = 1.into();
+                body["openConversationId"] = open_conversation_id.as_str().into();
+            }
+            Some(InteractiveCardReceiver::Users(user_ids)) => {
+                body["conversationType"] = 0.into();
+                body["receiverUserIdList"] = user_ids.as_slice().into();
+            }
+            None => {}
+        }
+        body
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Interactive card, document https://open.dingtalk.com/document/orgapp/robots-send-interactive-cards
+impl RobotCodeClient {
+    /// Send interactive card by `/v1.0/im/interactiveCards/send`, returns `out_track_id` of card
+    ///
+    /// Card is checked by `InteractiveCard::validate` before sending.
+    pub async fn send_interactive_card(&self, card: &InteractiveCard) -> XResult<String> {
+        card.validate()?;
+        self.call(
+            "/v1.0/im/interactiveCards/send",
+            card.request_body(&self.robot_code),
+        )
+        .await?;
+        Ok(card.out_track_id.clone())
+    }
+}
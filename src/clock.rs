@@ -0,0 +1,44 @@
+use std::time::SystemTime;
+
+/// Time source of signing, used by `timestamp` of DingTalk signed URL and Feishu signed body
+///
+/// Default is `SystemClock`, fixed clock can be set by `DingTalk::with_clock` for tests,
+/// closure `Fn() -> SystemTime` is also a `Clock`.
+///
+/// Sample code:
+/// ```ignore
+/// let dt = DingTalk::new("<token>", "<sec token>")
+///     .with_clock(|| SystemTime::UNIX_EPOCH + Duration::from_millis(1600000000000));
+/// ```
+pub trait Clock: Send + Sync {
+    /// Current time
+    fn now(&self) -> SystemTime;
+}
+
+/// System clock, `SystemTime::now()`, on wasm32 it is `Date.now()` of JavaScript
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        system_now()
+    }
+}
+
+/// `SystemTime::now()`, which panics on wasm32, there it is `Date.now()` of JavaScript
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn system_now() -> SystemTime {
+    SystemTime::now()
+}
+
+/// `SystemTime::now()`, which panics on wasm32, there it is `Date.now()` of JavaScript
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn system_now() -> SystemTime {
+    SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(js_sys::Date::now() as u64)
+}
+
+impl<F: Fn() -> SystemTime + Send + Sync> Clock for F {
+    fn now(&self) -> SystemTime {
+        self()
+    }
+}
@@ -0,0 +1,133 @@
+use crate::*;
+
+/// Prefix of config value stored in OS keyring, `keyring:<service>[/<account>]`
+const KEYRING_PREFIX: &str = "keyring:";
+/// Account of `keyring:<service>` reference without account
+#[cfg(feature = "keyring")]
+const KEYRING_DEFAULT_ACCOUNT: &str = "default";
+/// Config keys which can reference OS keyring
+const KEYRING_KEYS: [&str; 2] = ["access_token", "sec_token"];
+
+/// OS keyring(macOS Keychain, Windows Credential Manager, Linux kernel keyutils), enabled by
+/// feature `keyring`
+///
+/// Keyring entry of `service` and `account` is JSON config of `DingTalk::from_json(json: &str)`,
+/// usually `{"access_token": "<access token>", "sec_token": "<sec token>"}`.
+#[cfg(feature = "keyring")]
+impl DingTalk {
+    /// Create `DingTalk` from JSON config in keyring entry of `service` and `account`, stored by
+    /// `DingTalk::store_to_keyring`
+    pub fn from_keyring(service: &str, account: &str) -> XResult<Self> {
+        let config = SecretBuffer(read_keyring(service, account)?);
+        let json_value: Value = serde_json::from_str(&config).map_err(|e| {
+            DingTalkError::Config(format!(
+                "Keyring entry service={} account={} is not JSON config: {}",
+                service, account, e
+            ))
+        })?;
+        Self::from_json_value(json_value, "Keyring JSON", "<keyring entry>")
+    }
+
+    /// Store `access_token` and `sec_token` to keyring entry of `service` and `account`,
+    /// existing entry is replaced
+    ///
+    /// Provision once, e.g. from a setup command, then load by `DingTalk::from_keyring` or
+    /// reference in config file as `"sec_token": "keyring:<service>/<account>"`.
+    pub fn store_to_keyring(&self, service: &str, account: &str) -> XResult<()> {
+        let config = SecretBuffer(serde_json::to_string(&serde_json::json!({
+            "access_token": self.access_token,
+            "sec_token": self.sec_token,
+        }))?);
+        keyring_entry(service, account)?
+            .set_password(&config)
+            .map_err(|e| keyring_error(service, account, e))
+    }
+}
+
+/// Resolve `access_token` and `sec_token` of config which are `keyring:<service>[/<account>]`
+/// to the same key in the keyring entry
+pub(crate) fn resolve_keyring_refs(json_map: &mut serde_json::Map<String, Value>) -> XResult<()> {
+    for key in KEYRING_KEYS {
+        let reference = match json_map.get(key).and_then(Value::as_str) {
+            Some(value) => match value.strip_prefix(KEYRING_PREFIX) {
+                Some(reference) => reference.to_owned(),
+                None => continue,
+            },
+            None => continue,
+        };
+        let value = resolve_keyring_ref(key, &reference)?;
+        json_map.insert(key.to_owned(), Value::String(value));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "keyring")]
+fn resolve_keyring_ref(key: &str, reference: &str) -> XResult<String> {
+    let (service, account) = reference
+        .split_once('/')
+        .unwrap_or((reference, KEYRING_DEFAULT_ACCOUNT));
+    let config = SecretBuffer(read_keyring(service, account)?);
+    let json_value: Value = serde_json::from_str(&config).map_err(|e| {
+        DingTalkError::Config(format!(
+            "Keyring entry service={} account={} is not JSON config: {}",
+            service, account, e
+        ))
+    })?;
+    match json_value[key].as_str() {
+        Some(value) => Ok(value.to_owned()),
+        None => Err(DingTalkError::Config(format!(
+            "Keyring entry service={} account={} has no {}, store it by \
+             DingTalk::store_to_keyring",
+            service, account, key
+        ))),
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+fn resolve_keyring_ref(key: &str, reference: &str) -> XResult<String> {
+    Err(DingTalkError::Unsupported(format!(
+        "Config {} references keyring {}{}, which requires feature `keyring`",
+        key, KEYRING_PREFIX, reference
+    )))
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_entry(service: &str, account: &str) -> XResult<keyring::Entry> {
+    keyring::Entry::new(service, account).map_err(|e| keyring_error(service, account, e))
+}
+
+#[cfg(feature = "keyring")]
+fn read_keyring(service: &str, account: &str) -> XResult<String> {
+    keyring_entry(service, account)?
+        .get_password()
+        .map_err(|e| keyring_error(service, account, e))
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_error(service: &str, account: &str, e: keyring::Error) -> DingTalkError {
+    let message = match e {
+        keyring::Error::NoEntry => format!(
+            "Keyring entry service={} account={} is not found, store it by \
+             DingTalk::store_to_keyring",
+            service, account
+        ),
+        keyring::Error::NoStorageAccess(e) => format!(
+            "Keyring is locked or access is denied, unlock it and retry, \
+             service={} account={}: {}",
+            service, account, e
+        ),
+        keyring::Error::BadEncoding(_) => format!(
+            "Keyring entry service={} account={} is not UTF-8",
+            service, account
+        ),
+        keyring::Error::Ambiguous(_) => format!(
+            "Keyring has more than one entry of service={} account={}, remove the duplicates",
+            service, account
+        ),
+        e => format!(
+            "Keyring error, service={} account={}: {}",
+            service, account, e
+        ),
+    };
+    DingTalkError::Config(message)
+}
@@ -0,0 +1,176 @@
+use serde_json::Value;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Mutex,
+};
+use tokio::time::Instant;
+
+use crate::*;
+
+/// Max chars of message text quoted in summary message
+const SUMMARY_LABEL_MAX_CHARS: usize = 40;
+
+/// Drops JSON messages identical to one sent within `window`, see `DingTalk::dedup_window`
+#[derive(Debug)]
+pub(crate) struct Deduplicator {
+    window: Duration,
+    entries: Mutex<HashMap<u64, DedupEntry>>,
+}
+
+#[derive(Debug)]
+struct DedupEntry {
+    sent_at: Instant,
+    suppressed: u64,
+    label: String,
+}
+
+/// Result of `DingTalk::dedup_check`
+pub(crate) enum DedupOutcome {
+    /// Send the message, `key` is set when deduplicated, `summaries` are texts of closed
+    /// windows which had duplicates
+    Send {
+        key: Option<u64>,
+        summaries: Vec<String>,
+    },
+    /// Duplicate within window, not sent
+    Suppressed(DingTalkResponse),
+}
+
+impl Deduplicator {
+    pub(crate) fn new(window: Duration) -> Self {
+        Deduplicator {
+            window,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Check message, expired entries are removed and summaries of them are returned
+    fn check(&self, json_message: &str) -> (Result<u64, u64>, Vec<String>) {
+        let mut hasher = DefaultHasher::new();
+        json_message.hash(&mut hasher);
+        let key = hasher.finish();
+
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        let mut summaries = vec![];
+        entries.retain(|_, entry| {
+            if now.duration_since(entry.sent_at) < self.window {
+                return true;
+            }
+            if entry.suppressed > 0 {
+                summaries.push(format!(
+                    "Suppressed {} duplicate message(s) in {:?}: {}",
+                    entry.suppressed, self.window, entry.label
+                ));
+            }
+            false
+        });
+        if let Some(entry) = entries.get_mut(&key) {
+            entry.suppressed += 1;
+            return (Err(entry.suppressed), summaries);
+        }
+        entries.insert(
+            key,
+            DedupEntry {
+                sent_at: now,
+                suppressed: 0,
+                label: summary_label(json_message),
+            },
+        );
+        (Ok(key), summaries)
+    }
+
+    /// Forget message failed to send, so it is not suppressed when sent again
+    fn forget(&self, key: u64) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.get(&key).is_some_and(|entry| entry.suppressed == 0) {
+            entries.remove(&key);
+        }
+    }
+}
+
+/// First line of the first text in JSON message, e.g. text content or markdown title
+fn summary_label(json_message: &str) -> String {
+    fn first_text(value: &Value) -> Option<&str> {
+        match value {
+            Value::String(s) if !s.trim().is_empty() => Some(s),
+            Value::Array(values) => values.iter().find_map(first_text),
+            Value::Object(map) => map
+                .iter()
+                .filter(|(k, _)| !matches!(k.as_str(), "msgtype" | "msg_type"))
+                .find_map(|(_, v)| first_text(v)),
+            _ => None,
+        }
+    }
+    let value = serde_json::from_str(json_message).unwrap_or(Value::Null);
+    let line = first_text(&value)
+        .and_then(|text| text.lines().map(str::trim).find(|l| !l.is_empty()))
+        .unwrap_or_default();
+    let mut label: String = line.chars().take(SUMMARY_LABEL_MAX_CHARS).collect();
+    if label.len() < line.len() {
+        label.push_str("...");
+    }
+    label
+}
+
+/// Duplicate suppression
+impl DingTalk {
+    /// Drop messages identical to one sent within `window`, default is off
+    ///
+    /// Messages are compared by content hash of JSON message before signing, suppressed send
+    /// returns `Ok` without request, response of `*_with_response` methods is `errcode` 0 with
+    /// `errmsg` `"suppressed"` and `raw` is `{"suppressed": true, "duplicates": <count>}`, see
+    /// `DingTalkResponse::is_suppressed`. Failed message is not recorded.
+    pub fn dedup_window(mut self, window: Duration) -> Self {
+        self.deduplicator = Some(Arc::new(Deduplicator::new(window)));
+        self
+    }
+
+    /// Send "Suppressed N duplicate message(s)" text after the window of a duplicated message
+    /// closes, default is off
+    ///
+    /// Summary is sent before the next send, since no background task is kept, failed summary
+    /// is ignored.
+    pub fn dedup_summary(mut self, dedup_summary: bool) -> Self {
+        self.dedup_summary = dedup_summary;
+        self
+    }
+
+    /// Check message by `DingTalk::dedup_window`
+    pub(crate) fn dedup_check(&self, json_message: &str) -> DedupOutcome {
+        let deduplicator = match &self.deduplicator {
+            Some(deduplicator) => deduplicator,
+            None => {
+                return DedupOutcome::Send {
+                    key: None,
+                    summaries: vec![],
+                }
+            }
+        };
+        match deduplicator.check(json_message) {
+            (Ok(key), summaries) => DedupOutcome::Send {
+                key: Some(key),
+                summaries: if self.dedup_summary {
+                    summaries
+                } else {
+                    vec![]
+                },
+            },
+            (Err(duplicates), _) => DedupOutcome::Suppressed(DingTalkResponse {
+                errcode: 0,
+                errmsg: "suppressed".to_owned(),
+                raw: serde_json::json!({ "suppressed": true, "duplicates": duplicates }),
+                truncated_bytes: None,
+                rate_limit_delay: None,
+            }),
+        }
+    }
+
+    /// Forget message failed to send, see `DingTalk::dedup_window`
+    pub(crate) fn dedup_forget(&self, key: Option<u64>) {
+        if let (Some(deduplicator), Some(key)) = (&self.deduplicator, key) {
+            deduplicator.forget(key);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::*;
+
+/// Path of DingTalk robot webhook, `access_token` in its query is taken as access token
+const DINGTALK_ROBOT_SEND_PATH: &str = "/robot/send";
+
+impl DingTalk {
+    /// Create `DingTalk` from url, checked version of `DingTalk::from_url`
+    ///
+    /// Spaces around url are ignored, url must be `http` or `https` URL with host. DingTalk
+    /// webhook with `access_token`, e.g. `https://oapi.dingtalk.com/robot/send?access_token=...`,
+    /// sets `access_token` and `default_webhook_url` instead of `direct_url`, so URL is signed
+    /// after `sec_token` is set.
+    pub fn try_from_url(url: &str) -> XResult<Self> {
+        let url = url.trim();
+        let mut parsed_url = reqwest::Url::parse(url)
+            .map_err(|e| DingTalkError::Config(format!("Direct URL is malformed: {}", e)))?;
+        if !matches!(parsed_url.scheme(), "http" | "https") {
+            return Err(DingTalkError::Config(format!(
+                "Direct URL scheme must be http or https: {}",
+                redact::redact_url(url)
+            )));
+        }
+        if parsed_url.host_str().is_none_or(str::is_empty) {
+            return Err(DingTalkError::Config(format!(
+                "Direct URL has no host: {}",
+                redact::redact_url(url)
+            )));
+        }
+
+        let access_token = parsed_url
+            .query_pairs()
+            .find(|(k, _)| k == "access_token")
+            .map(|(_, v)| v.into_owned());
+        match access_token {
+            Some(access_token) if parsed_url.path().ends_with(DINGTALK_ROBOT_SEND_PATH) => {
+                let pairs: Vec<(String, String)> = parsed_url
+                    .query_pairs()
+                    .filter(|(k, _)| k != "access_token")
+                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                    .collect();
+                if pairs.is_empty() {
+                    parsed_url.set_query(None);
+                } else {
+                    parsed_url.query_pairs_mut().clear().extend_pairs(pairs);
+                }
+                Ok(Self::with_robot(
+                    DingTalkType::DingTalk,
+                    parsed_url.to_string(),
+                    access_token,
+                    String::new(),
+                    String::new(),
+                ))
+            }
+            _ => Ok(Self::with_robot(
+                DingTalkType::DingTalk,
+                String::new(),
+                String::new(),
+                String::new(),
+                url.to_owned(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::*;
+use serde_json::json;
+
+const DISCORD_EMBED_MAX_COUNT: usize = 10;
+
+/// Discord webhook, document https://discord.com/developers/docs/resources/webhook#execute-webhook
+impl DingTalk {
+    /// Create Discord message JSON
+    ///
+    /// * Text - `content`
+    /// * Markdown - `content`, title in bold
+    /// * Link - one embed with title, url, description and thumbnail
+    /// * FeedCard - one embed per link with title, url and thumbnail, at most 10 links
+    pub(crate) fn discord_message_json(
+        &self,
+        dingtalk_message: &DingTalkMessage,
+    ) -> XResult<Value> {
+        match dingtalk_message.message_type {
+            DingTalkMessageType::Text => {
+                let mut content = dingtalk_message.text_content.clone();
+                for user_id in &dingtalk_message.at_user_ids {
+                    content.push_str(&format!(" <@{}>", user_id));
+                }
+                if dingtalk_message.at_all {
+                    content.push_str(" @everyone");
+                }
+                Ok(json!({ "content": content }))
+            }
+            DingTalkMessageType::Markdown => {
+                let content = if dingtalk_message.markdown_title.is_empty() {
+                    dingtalk_message.markdown_content.clone()
+                } else {
+                    format!(
+                        "**{}**\n{}",
+                        dingtalk_message.markdown_title, dingtalk_message.markdown_content
+                    )
+                };
+                Ok(json!({ "content": content }))
+            }
+            DingTalkMessageType::Link => {
+                let mut embed = discord_embed(
+                    &dingtalk_message.link_title,
+                    &dingtalk_message.link_message_url,
+                    &dingtalk_message.link_pic_url,
+                );
+                if !dingtalk_message.link_text.is_empty() {
+                    embed["description"] = dingtalk_message.link_text.as_str().into();
+                }
+                Ok(json!({ "embeds": [embed] }))
+            }
+            DingTalkMessageType::FeedCard => {
+                if dingtalk_message.feed_card_links.len() > DISCORD_EMBED_MAX_COUNT {
+                    return Err(DingTalkError::Validation(format!(
+                        "Discord message has {} embeds, at most {}",
+                        dingtalk_message.feed_card_links.len(),
+                        DISCORD_EMBED_MAX_COUNT
+                    )));
+                }
+                let embeds: Vec<Value> = dingtalk_message
+                    .feed_card_links
+                    .iter()
+                    .map(|link| discord_embed(&link.title, &link.message_url, &link.pic_url))
+                    .collect();
+                Ok(json!({ "embeds": embeds }))
+            }
+            message_type => Err(DingTalkError::Unsupported(format!(
+                "Message type {:?} is unsupported for robot type {:?}",
+                message_type, self.dingtalk_type
+            ))),
+        }
+    }
+}
+
+fn discord_embed(title: &str, url: &str, thumbnail_url: &str) -> Value {
+    let mut embed = json!({ "title": title });
+    if !url.is_empty() {
+        embed["url"] = url.into();
+    }
+    if !thumbnail_url.is_empty() {
+        embed["thumbnail"] = json!({ "url": thumbnail_url });
+    }
+    embed
+}
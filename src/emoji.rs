@@ -0,0 +1,136 @@
+//! Emoji shortcode conversion, e.g. `:fire:` to `🔥`, usable without `DingTalk`
+//!
+//! Sample code:
+//! ```ignore
+//! assert_eq!("🔥 disk full", dingtalk::emoji::replace_shortcodes(":fire: disk full"));
+//! ```
+use std::borrow::Cow;
+
+use crate::*;
+
+/// Supported shortcodes(without colons) and emoji, names follow Slack and GitHub
+pub const SHORTCODES: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("alarm_clock", "⏰"),
+    ("bell", "🔔"),
+    ("bug", "🐛"),
+    ("chart_with_downwards_trend", "📉"),
+    ("chart_with_upwards_trend", "📈"),
+    ("check", "✔️"),
+    ("clap", "👏"),
+    ("construction", "🚧"),
+    ("eyes", "👀"),
+    ("fire", "🔥"),
+    ("green_circle", "🟢"),
+    ("heavy_check_mark", "✔️"),
+    ("hourglass", "⌛"),
+    ("information_source", "ℹ️"),
+    ("large_blue_circle", "🔵"),
+    ("lock", "🔒"),
+    ("memo", "📝"),
+    ("no_entry", "⛔"),
+    ("ok", "🆗"),
+    ("ok_hand", "👌"),
+    ("pray", "🙏"),
+    ("question", "❓"),
+    ("red_circle", "🔴"),
+    ("rocket", "🚀"),
+    ("rotating_light", "🚨"),
+    ("siren", "🚨"),
+    ("skull", "💀"),
+    ("smile", "😄"),
+    ("sos", "🆘"),
+    ("star", "⭐"),
+    ("tada", "🎉"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("warning", "⚠️"),
+    ("white_check_mark", "✅"),
+    ("x", "❌"),
+    ("yellow_circle", "🟡"),
+    ("zap", "⚡"),
+];
+
+/// Emoji of shortcode `name`(without colons), `None` when unknown
+pub fn shortcode(name: &str) -> Option<&'static str> {
+    SHORTCODES
+        .iter()
+        .find(|(shortcode, _)| *shortcode == name)
+        .map(|(_, emoji)| *emoji)
+}
+
+/// Replace known `:name:` shortcodes in `text` with emoji, unknown ones are kept as is
+pub fn replace_shortcodes(text: &str) -> String {
+    replace_shortcodes_cow(text).into_owned()
+}
+
+fn replace_shortcodes_cow(text: &str) -> Cow<'_, str> {
+    if !text.contains(':') {
+        return Cow::Borrowed(text);
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(':') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let name_len = after
+            .find(|c: char| !is_shortcode_char(c))
+            .unwrap_or(after.len());
+        match shortcode(&after[..name_len]) {
+            Some(emoji) if after[name_len..].starts_with(':') => {
+                result.push_str(emoji);
+                rest = &after[name_len + 1..];
+            }
+            // closing colon may start the next shortcode, e.g. `:unknown:fire:`
+            _ => {
+                result.push(':');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    Cow::Owned(result)
+}
+
+fn is_shortcode_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '+' || c == '-'
+}
+
+impl DingTalk {
+    /// Replace `:name:` shortcodes with emoji in text, markdown and action card before sending,
+    /// see `emoji::replace_shortcodes`. Default is off.
+    pub fn convert_emoji_shortcodes(mut self, convert_emoji_shortcodes: bool) -> Self {
+        self.convert_emoji_shortcodes = convert_emoji_shortcodes;
+        self
+    }
+
+    /// Message with shortcodes replaced when `DingTalk::convert_emoji_shortcodes` is on
+    pub(crate) fn apply_emoji_shortcodes<'a>(
+        &self,
+        dingtalk_message: &'a DingTalkMessage,
+    ) -> Cow<'a, DingTalkMessage> {
+        if !self.convert_emoji_shortcodes {
+            return Cow::Borrowed(dingtalk_message);
+        }
+        let mut converted = dingtalk_message.clone();
+        let fields = match converted.message_type {
+            DingTalkMessageType::Text => vec![&mut converted.text_content],
+            DingTalkMessageType::Markdown => vec![
+                &mut converted.markdown_title,
+                &mut converted.markdown_content,
+            ],
+            DingTalkMessageType::ActionCard => vec![
+                &mut converted.action_card_title,
+                &mut converted.action_card_text,
+            ],
+            _ => return Cow::Borrowed(dingtalk_message),
+        };
+        for field in fields {
+            if let Cow::Owned(replaced) = replace_shortcodes_cow(field) {
+                *field = replaced;
+            }
+        }
+        Cow::Owned(converted)
+    }
+}
@@ -0,0 +1,228 @@
+use std::{env, fmt, io, time::Duration};
+
+/// DingTalk error
+#[derive(Debug)]
+pub enum DingTalkError {
+    /// HTTP request failed (connect, read body, ...)
+    Http(reqwest::Error),
+    /// HTTP request timed out, see `DingTalk::set_request_timeout`
+    Timeout(reqwest::Error),
+    /// Connect to server timed out, see `DingTalk::set_connect_timeout`
+    ConnectTimeout(reqwest::Error),
+    /// HTTP request of transport other than reqwest failed, e.g. `UreqTransport`
+    Transport(String),
+    /// Server replied unexpected HTTP status, `body` is at most 8KB of response body,
+    /// invalid UTF-8 is replaced
+    Status { status: u16, body: String },
+    /// Robot is throttled, HTTP 429 or DingTalk `errcode` 130101 or WeChat Work `errcode` 45009,
+    /// `retry_after` is from header `Retry-After`
+    RateLimited { retry_after: Option<Duration> },
+    /// Server replied non-zero `errcode`
+    Server { errcode: i64, errmsg: String },
+    /// Bad configuration, e.g. token format, JSON config
+    Config(String),
+    /// Message is invalid, detected before send
+    Validation(String),
+    /// Message field exceeds length limit of robot type, `len` and `max` are in bytes
+    TooLong {
+        field: String,
+        len: usize,
+        max: usize,
+    },
+    /// Message type is unsupported for robot type
+    Unsupported(String),
+    /// Calculate sign failed
+    Signature(String),
+    /// Timestamp of incoming outgoing robot request is out of 1 hour window,
+    /// see `outgoing::verify_signature`
+    SignatureExpired { timestamp: i64 },
+    /// Sign of incoming outgoing robot request does not match, see `outgoing::verify_signature`
+    SignatureMismatch,
+    /// Session webhook of outgoing robot is expired, see `DingTalk::from_session_webhook`
+    SessionExpired { expired_at_ms: i64 },
+    /// JSON serialize or deserialize failed
+    Serialization(serde_json::Error),
+    /// Render template failed, `line` is 1-based line in template when known
+    Template {
+        name: String,
+        line: Option<usize>,
+        message: String,
+    },
+    /// IO error, e.g. read config file
+    Io(io::Error),
+    /// Background queue is full, closed or its task failed, see `DingTalkQueue`
+    Queue(String),
+    /// Send still failed after retries
+    Retry {
+        attempts: u32,
+        source: Box<DingTalkError>,
+    },
+}
+
+impl DingTalkError {
+    /// Is error transient: network or transport error, 5xx status or rate limited
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DingTalkError::Http(_)
+            | DingTalkError::Timeout(_)
+            | DingTalkError::ConnectTimeout(_)
+            | DingTalkError::Transport(_)
+            | DingTalkError::RateLimited { .. } => true,
+            DingTalkError::Status { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Delay required by server before next send, from `Retry-After` of `RateLimited`
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            DingTalkError::RateLimited { retry_after } => *retry_after,
+            DingTalkError::Retry { source, .. } => source.retry_after(),
+            _ => None,
+        }
+    }
+
+    /// Server `errcode`, also of the last attempt of `DingTalkError::Retry`
+    #[cfg(any(feature = "tracing", feature = "log"))]
+    pub(crate) fn server_errcode(&self) -> Option<i64> {
+        match self {
+            DingTalkError::Server { errcode, .. } => Some(*errcode),
+            DingTalkError::Retry { source, .. } => source.server_errcode(),
+            _ => None,
+        }
+    }
+
+    /// Short name of error variant, logged instead of message which may contain webhook URL
+    #[cfg(any(feature = "tracing", feature = "log"))]
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            DingTalkError::Http(_) => "http",
+            DingTalkError::Timeout(_) => "timeout",
+            DingTalkError::ConnectTimeout(_) => "connect_timeout",
+            DingTalkError::Transport(_) => "transport",
+            DingTalkError::Status { .. } => "status",
+            DingTalkError::RateLimited { .. } => "rate_limited",
+            DingTalkError::Server { .. } => "server",
+            DingTalkError::Config(_) => "config",
+            DingTalkError::Validation(_) => "validation",
+            DingTalkError::TooLong { .. } => "too_long",
+            DingTalkError::Unsupported(_) => "unsupported",
+            DingTalkError::Signature(_) => "signature",
+            DingTalkError::SignatureExpired { .. } => "signature_expired",
+            DingTalkError::SignatureMismatch => "signature_mismatch",
+            DingTalkError::SessionExpired { .. } => "session_expired",
+            DingTalkError::Serialization(_) => "serialization",
+            DingTalkError::Template { .. } => "template",
+            DingTalkError::Io(_) => "io",
+            DingTalkError::Queue(_) => "queue",
+            DingTalkError::Retry { .. } => "retry",
+        }
+    }
+}
+
+impl fmt::Display for DingTalkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DingTalkError::Http(e) => write!(f, "HTTP error: {}", e),
+            DingTalkError::Timeout(e) => write!(f, "Timeout error: {}", e),
+            DingTalkError::ConnectTimeout(e) => write!(f, "Connect timeout error: {}", e),
+            DingTalkError::Transport(msg) => write!(f, "Transport error: {}", msg),
+            DingTalkError::Status { status, body } if body.is_empty() => {
+                write!(f, "Unknown status: {}", status)
+            }
+            DingTalkError::Status { status, body } => {
+                write!(f, "Unknown status: {}, body: {}", status, body)
+            }
+            DingTalkError::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(f, "Rate limited, retry after {:?}", retry_after),
+            DingTalkError::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            DingTalkError::Server { errcode, errmsg } => {
+                write!(f, "Server error, errcode: {}, errmsg: {}", errcode, errmsg)
+            }
+            DingTalkError::Config(msg) => write!(f, "Config error: {}", msg),
+            DingTalkError::Validation(msg) => write!(f, "Validation error: {}", msg),
+            DingTalkError::TooLong { field, len, max } => write!(
+                f,
+                "Too long error: {} is {} bytes, exceeds limit {} bytes",
+                field, len, max
+            ),
+            DingTalkError::Unsupported(msg) => write!(f, "Unsupported error: {}", msg),
+            DingTalkError::Signature(msg) => write!(f, "Signature error: {}", msg),
+            DingTalkError::SignatureExpired { timestamp } => {
+                write!(f, "Signature expired, timestamp: {}", timestamp)
+            }
+            DingTalkError::SignatureMismatch => write!(f, "Signature mismatch"),
+            DingTalkError::SessionExpired { expired_at_ms } => {
+                write!(f, "Session webhook expired at {}", expired_at_ms)
+            }
+            DingTalkError::Serialization(e) => write!(f, "Serialization error: {}", e),
+            DingTalkError::Template {
+                name,
+                line: Some(line),
+                message,
+            } => write!(f, "Template error: {} line {}: {}", name, line, message),
+            DingTalkError::Template {
+                name,
+                line: None,
+                message,
+            } => write!(f, "Template error: {}: {}", name, message),
+            DingTalkError::Io(e) => write!(f, "IO error: {}", e),
+            DingTalkError::Queue(msg) => write!(f, "Queue error: {}", msg),
+            DingTalkError::Retry { attempts, source } => {
+                write!(f, "Failed after {} attempts: {}", attempts, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DingTalkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DingTalkError::Http(e)
+            | DingTalkError::Timeout(e)
+            | DingTalkError::ConnectTimeout(e) => Some(e),
+            DingTalkError::Serialization(e) => Some(e),
+            DingTalkError::Io(e) => Some(e),
+            DingTalkError::Retry { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for DingTalkError {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_connect() && e.is_timeout() {
+            DingTalkError::ConnectTimeout(e)
+        } else if e.is_timeout() {
+            DingTalkError::Timeout(e)
+        } else {
+            DingTalkError::Http(e)
+        }
+    }
+
+    /// fetch API has no connect phase and timeout
+    #[cfg(target_arch = "wasm32")]
+    fn from(e: reqwest::Error) -> Self {
+        DingTalkError::Http(e)
+    }
+}
+
+impl From<serde_json::Error> for DingTalkError {
+    fn from(e: serde_json::Error) -> Self {
+        DingTalkError::Serialization(e)
+    }
+}
+
+impl From<io::Error> for DingTalkError {
+    fn from(e: io::Error) -> Self {
+        DingTalkError::Io(e)
+    }
+}
+
+impl From<env::VarError> for DingTalkError {
+    fn from(e: env::VarError) -> Self {
+        DingTalkError::Config(format!("Read env error: {}", e))
+    }
+}
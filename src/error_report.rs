@@ -0,0 +1,138 @@
+use std::{error::Error, fmt::Write};
+
+use crate::*;
+
+/// Causes deeper than this are omitted from error report
+const MAX_ERROR_CHAIN_DEPTH: usize = 10;
+/// Environment variables of host name, `HOSTNAME` on Unix and `COMPUTERNAME` on Windows
+const HOSTNAME_ENVS: [&str; 2] = ["HOSTNAME", "COMPUTERNAME"];
+
+impl DingTalk {
+    /// Send `err` and its `source()` chain as markdown, titled by `context`, e.g. what was
+    /// being done when failed
+    ///
+    /// Each cause is nested one level deeper in the list, causes deeper than 10 are omitted.
+    /// Report also has UTC time of `DingTalk::with_clock` and host name from `HOSTNAME`,
+    /// `COMPUTERNAME` or `/etc/hostname`.
+    ///
+    /// Sample code:
+    /// ```ignore
+    /// if let Err(e) = sync_orders().await {
+    ///     dt.send_error_report("Sync orders failed", &e).await?;
+    /// }
+    /// ```
+    pub async fn send_error_report(&self, context: &str, err: &dyn Error) -> XResult<()> {
+        self.send_error_report_with(context, err, &[]).await
+    }
+
+    /// Same as `DingTalk::send_error_report`, with extra key value context rendered as a table,
+    /// e.g. `&[("order", "1024"), ("region", "cn")]`
+    pub async fn send_error_report_with(
+        &self,
+        context: &str,
+        err: &dyn Error,
+        extra: &[(&str, &str)],
+    ) -> XResult<()> {
+        let markdown = render_error_report(context, err, extra, self.since_epoch(), &hostname());
+        self.send_markdown(context, &markdown).await
+    }
+}
+
+fn render_error_report(
+    context: &str,
+    err: &dyn Error,
+    extra: &[(&str, &str)],
+    since_epoch: Duration,
+    hostname: &str,
+) -> String {
+    let mut markdown = format!(
+        "#### {}\n\n- time: `{}`\n- host: `{}`",
+        context,
+        format_utc(since_epoch),
+        hostname
+    );
+    if !extra.is_empty() {
+        markdown.push_str("\n\n| key | value |\n| --- | --- |");
+        for (key, value) in extra {
+            let _ = write!(
+                markdown,
+                "\n| {} | {} |",
+                table_cell(key),
+                table_cell(value)
+            );
+        }
+    }
+    markdown.push_str("\n\n**Error:**\n");
+    let mut cause = Some(err);
+    let mut depth = 0;
+    while let Some(e) = cause {
+        let indent = "  ".repeat(depth);
+        if depth == MAX_ERROR_CHAIN_DEPTH {
+            let _ = write!(markdown, "\n{}- …(more causes are omitted)", indent);
+            break;
+        }
+        let _ = write!(markdown, "\n{}- {}", indent, single_line(&e.to_string()));
+        cause = e.source();
+        depth += 1;
+    }
+    markdown
+}
+
+/// Join lines of error message, a line break ends the list item
+fn single_line(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escape `|` and line breaks, which end the table cell
+fn table_cell(text: &str) -> String {
+    single_line(text).replace('|', "\\|")
+}
+
+/// Host name from environment variables, or `/etc/hostname`, `unknown` when not found
+fn hostname() -> String {
+    let found = HOSTNAME_ENVS
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|hostname| !hostname.trim().is_empty());
+    #[cfg(not(target_arch = "wasm32"))]
+    let found = found.or_else(|| fs::read_to_string("/etc/hostname").ok());
+    match found {
+        Some(hostname) if !hostname.trim().is_empty() => hostname.trim().to_owned(),
+        _ => "unknown".to_owned(),
+    }
+}
+
+/// Format duration since UNIX epoch as `2020-09-13 12:26:40 UTC`
+fn format_utc(since_epoch: Duration) -> String {
+    let seconds = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days(seconds / 86400);
+    let seconds_of_day = seconds % 86400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+/// Gregorian date of days since 1970-01-01
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,98 @@
+use crate::*;
+
+/// Config keys which can be read from environment variable named by `<key>_env`
+const ENV_KEYS: [&str; 2] = ["access_token", "sec_token"];
+
+/// Expand environment variables in config value
+///
+/// * `${VAR}` - value of `VAR`, error if `VAR` is not set
+/// * `${VAR:-default}` - value of `VAR`, `default` if `VAR` is not set or empty
+/// * `$$` - literal `$`
+///
+/// Other `$` is kept as is.
+pub(crate) fn expand_env_vars(value: &str) -> XResult<String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(pos) = rest.find('$') {
+        expanded.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if let Some(after) = rest.strip_prefix("$$") {
+            expanded.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("${") {
+            let end = after.find('}').ok_or_else(|| {
+                DingTalkError::Config(format!("Unclosed `${{` in config value: {}", value))
+            })?;
+            expanded.push_str(&expand_env_var(&after[..end])?);
+            rest = &after[end + 1..];
+        } else {
+            expanded.push('$');
+            rest = &rest[1..];
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+fn expand_env_var(expr: &str) -> XResult<String> {
+    let (name, default) = match expr.split_once(":-") {
+        Some((name, default)) => (name, Some(default)),
+        None => (expr, None),
+    };
+    if name.is_empty() {
+        return Err(DingTalkError::Config(
+            "Empty environment variable name in config value".to_owned(),
+        ));
+    }
+    match (env::var(name), default) {
+        (Ok(v), Some(default)) if v.is_empty() => Ok(default.to_owned()),
+        (Ok(v), _) => Ok(v),
+        (Err(env::VarError::NotPresent), Some(default)) => Ok(default.to_owned()),
+        (Err(env::VarError::NotPresent), None) => Err(DingTalkError::Config(format!(
+            "Environment variable {} in config value is not set",
+            name
+        ))),
+        (Err(e), _) => Err(DingTalkError::Config(format!(
+            "Read env {} error: {}",
+            name, e
+        ))),
+    }
+}
+
+/// Resolve `access_token_env` and `sec_token_env` of config, e.g. `"sec_token_env": "DT_SEC"`
+/// reads `sec_token` from environment variable `DT_SEC`
+///
+/// Non-empty literal `access_token` or `sec_token` wins over `_env` key, which is ignored with
+/// `warn` event(feature `tracing`). Error names the variable when it is not set or empty.
+pub(crate) fn resolve_env_keys(json_map: &mut serde_json::Map<String, Value>) -> XResult<()> {
+    for key in ENV_KEYS {
+        let env_key = format!("{}_env", key);
+        let name = match json_map.remove(&env_key) {
+            Some(Value::String(name)) if !name.is_empty() => name,
+            None => continue,
+            Some(_) => {
+                return Err(DingTalkError::Config(format!(
+                    "Config {} must be environment variable name",
+                    env_key
+                )))
+            }
+        };
+        if json_map
+            .get(key)
+            .and_then(Value::as_str)
+            .is_some_and(|literal| !literal.is_empty())
+        {
+            #[cfg(feature = "tracing")]
+            trace::config_env_key_ignored(key, &env_key);
+            continue;
+        }
+        let value = read_env("", &name)?.ok_or_else(|| {
+            DingTalkError::Config(format!(
+                "Environment variable {} of config {} is not set",
+                name, env_key
+            ))
+        })?;
+        json_map.insert(key.to_owned(), Value::String(value));
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::*;
+use serde_json::json;
+
+/// Feishu(Lark) robot, document https://open.feishu.cn/document/client-docs/bot-v3/add-custom-bot
+impl DingTalk {
+    /// Create Feishu message JSON, supports text and markdown(interactive card with markdown element)
+    pub(crate) fn feishu_message_json(&self, dingtalk_message: &DingTalkMessage) -> XResult<Value> {
+        match dingtalk_message.message_type {
+            DingTalkMessageType::Text => {
+                let mut text = dingtalk_message.text_content.clone();
+                for user_id in &dingtalk_message.at_user_ids {
+                    text.push_str(&format!(r#" <at user_id="{}"></at>"#, user_id));
+                }
+                if dingtalk_message.at_all {
+                    text.push_str(r#" <at user_id="all">所有人</at>"#);
+                }
+                Ok(json!({
+                    "msg_type": "text",
+                    "content": { "text": text },
+                }))
+            }
+            DingTalkMessageType::Markdown => Ok(json!({
+                "msg_type": "interactive",
+                "card": {
+                    "header": {
+                        "title": {
+                            "tag": "plain_text",
+                            "content": dingtalk_message.markdown_title,
+                        },
+                    },
+                    "elements": [{
+                        "tag": "markdown",
+                        "content": dingtalk_message.markdown_content,
+                    }],
+                },
+            })),
+            message_type => Err(DingTalkError::Unsupported(format!(
+                "Message type {:?} is unsupported for robot type {:?}",
+                message_type, self.dingtalk_type
+            ))),
+        }
+    }
+
+    /// Add `timestamp` and `sign` to Feishu message JSON
+    ///
+    /// Sign is base64 of HmacSHA256 keyed by `"{timestamp}\n{secret}"` of empty message
+    pub(crate) fn feishu_signed_body(&self, json_message: &str) -> XResult<String> {
+        let mut message_json: Value = serde_json::from_str(json_message)?;
+        let timestamp = self.since_epoch().as_secs();
+        let timestamp_and_secret = SecretBuffer(format!("{}\n{}", timestamp, self.sec_token));
+        let sign = base64::encode(&calc_hmac_sha256(timestamp_and_secret.as_bytes(), b"")?[..]);
+        match message_json.as_object_mut() {
+            Some(m) => {
+                m.insert("timestamp".into(), Value::String(timestamp.to_string()));
+                m.insert("sign".into(), Value::String(sign));
+            }
+            None => {
+                return Err(DingTalkError::Validation(
+                    "Feishu message must be JSON object".to_owned(),
+                ))
+            }
+        }
+        Ok(serde_json::to_string(&message_json)?)
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{sync::mpsc, time::Instant};
+
+use crate::{DingTalk, DingTalkMessage, RateLimiter};
+
+/// Targets of crates used to send, their records are never forwarded so sending does not log
+/// records which are forwarded again
+const IGNORED_TARGETS: [&str; 6] = ["dingtalk", "reqwest", "hyper", "h2", "rustls", "native_tls"];
+
+tokio::task_local! {
+    /// Set within background task, records of the task itself(e.g. logged by interceptor) are
+    /// never forwarded
+    static FORWARDING: ();
+}
+
+/// Log record or event rendered in logging thread, sent to background task
+pub(crate) struct ForwardRecord {
+    /// 1 is error, 5 is trace
+    pub(crate) severity: u8,
+    /// Level name, e.g. `ERROR`
+    pub(crate) level: &'static str,
+    pub(crate) markdown: String,
+}
+
+/// Settings of background task shared by `DingTalkLogger` and `DingTalkLayer`
+#[derive(Clone, Debug)]
+pub(crate) struct ForwardConfig {
+    pub(crate) batch_window: Duration,
+    pub(crate) max_batch_records: usize,
+    pub(crate) max_messages_per_minute: usize,
+    pub(crate) capacity: usize,
+}
+
+impl Default for ForwardConfig {
+    fn default() -> Self {
+        ForwardConfig {
+            batch_window: Duration::from_secs(5),
+            max_batch_records: 20,
+            max_messages_per_minute: 10,
+            capacity: 1000,
+        }
+    }
+}
+
+/// Sender of records to background task, records are dropped when channel is full
+pub(crate) struct ForwardSender {
+    sender: mpsc::Sender<ForwardRecord>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl ForwardSender {
+    /// Spawn background task batches records and sends them, must be called within a tokio
+    /// runtime
+    pub(crate) fn spawn(dingtalk: Arc<DingTalk>, config: ForwardConfig) -> Self {
+        let (sender, receiver) = mpsc::channel(config.capacity.max(1));
+        let dropped = Arc::new(AtomicU64::new(0));
+        let forwarder = Forwarder {
+            dingtalk,
+            batch_window: config.batch_window,
+            max_batch_records: config.max_batch_records.max(1),
+            rate_limiter: RateLimiter::new(config.max_messages_per_minute, Duration::from_secs(60)),
+            dropped: dropped.clone(),
+        };
+        tokio::spawn(FORWARDING.scope((), forwarder.run(receiver)));
+        ForwardSender { sender, dropped }
+    }
+
+    /// Put record into channel without waiting
+    pub(crate) fn send(&self, record: ForwardRecord) {
+        if self.sender.try_send(record).is_err() {
+            self.dropped.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// How many records were dropped because channel or batch was full
+    pub(crate) fn dropped_count(&self) -> u64 {
+        self.dropped.load(Ordering::SeqCst)
+    }
+}
+
+/// Is current code running within background task
+pub(crate) fn is_forwarding() -> bool {
+    FORWARDING.try_with(|_| ()).is_ok()
+}
+
+/// Is `target` of crate used to send, see `IGNORED_TARGETS`
+pub(crate) fn is_ignored_target(target: &str) -> bool {
+    IGNORED_TARGETS
+        .iter()
+        .any(|ignored| is_target_of(target, ignored))
+}
+
+/// Is `target` same as `prefix` or its sub module
+pub(crate) fn is_target_of(target: &str, prefix: &str) -> bool {
+    target
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Background task batches records and sends them
+struct Forwarder {
+    dingtalk: Arc<DingTalk>,
+    batch_window: Duration,
+    max_batch_records: usize,
+    rate_limiter: RateLimiter,
+    dropped: Arc<AtomicU64>,
+}
+
+impl Forwarder {
+    async fn run(self, mut receiver: mpsc::Receiver<ForwardRecord>) {
+        // dropped count already told by previous messages
+        let mut reported = 0;
+        while let Some(first) = receiver.recv().await {
+            let mut batch = vec![first];
+            let deadline = Instant::now() + self.batch_window;
+            while let Ok(Some(record)) = tokio::time::timeout_at(deadline, receiver.recv()).await {
+                if batch.len() < self.max_batch_records {
+                    batch.push(record);
+                } else {
+                    self.dropped.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+            self.rate_limiter.acquire().await;
+            let dropped = self.dropped.load(Ordering::SeqCst);
+            self.dingtalk
+                .send_message_silent(batch_message(&batch, dropped - reported))
+                .await;
+            reported = dropped;
+        }
+    }
+}
+
+/// Markdown message of records, title is the highest level and count of records
+fn batch_message(batch: &[ForwardRecord], dropped: u64) -> DingTalkMessage {
+    let level = batch
+        .iter()
+        .min_by_key(|record| record.severity)
+        .map(|record| record.level)
+        .unwrap_or("ERROR");
+    let title = match batch.len() {
+        1 => format!("[{}] log record", level),
+        n => format!("[{}] {} log records", level, n),
+    };
+    let mut markdown = batch
+        .iter()
+        .map(|record| record.markdown.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n---\n\n");
+    if dropped > 0 {
+        markdown.push_str(&format!("\n\n*{} records dropped*", dropped));
+    }
+    DingTalkMessage::new_markdown(&title, &markdown)
+}
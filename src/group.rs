@@ -0,0 +1,133 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use futures::future::join_all;
+use serde_json::Value;
+
+use crate::{DingTalk, DingTalkError, DingTalkMessage, XResult};
+
+/// Named robots, broadcasts one message to all of them concurrently
+///
+/// Message is converted by each robot's type, e.g. DingTalk and WeChat Work payloads.
+/// Failure of one robot does not stop the others.
+///
+/// Sample code:
+/// ```ignore
+/// let group = DingTalkGroup::new()
+///     .add("ops", DingTalk::new("<token>", "<sec token>"))
+///     .add("wechat", DingTalk::new_wechat("<key>"));
+/// for (name, result) in group.send_text("Hello world!").await {
+///     if let Err(e) = result {
+///         eprintln!("Send to {} failed: {}", name, e);
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct DingTalkGroup {
+    robots: Vec<(String, Arc<DingTalk>)>,
+}
+
+impl DingTalkGroup {
+    /// New empty group
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create group from JSON array, each item is a `DingTalk::from_json` config with `name`
+    ///
+    /// Format:
+    /// ```json
+    /// [
+    ///     {"name": "ops", "access_token": "<access token>", "sec_token": "<sec token>"},
+    ///     {"name": "wechat", "type": "wechat", "access_token": "<key>"}
+    /// ]
+    /// ```
+    pub fn from_json(json: &str) -> XResult<Self> {
+        let json_value: Value = serde_json::from_str(json)?;
+        let items = match json_value {
+            Value::Array(items) => items,
+            _ => {
+                return Err(DingTalkError::Config(
+                    "Group config must be JSON array".to_owned(),
+                ))
+            }
+        };
+        let mut group = Self::new();
+        for item in items {
+            let name = match item["name"].as_str() {
+                Some(name) if !name.is_empty() => name.to_owned(),
+                _ => {
+                    return Err(DingTalkError::Config(
+                        "Group robot config requires name".to_owned(),
+                    ))
+                }
+            };
+            if group.get(&name).is_some() {
+                return Err(DingTalkError::Config(format!(
+                    "Group robot name is duplicated: {}",
+                    name
+                )));
+            }
+            let config = item.to_string();
+            let dingtalk = DingTalk::from_json_value(item, "JSON", &config)?;
+            group = group.add(&name, dingtalk);
+        }
+        Ok(group)
+    }
+
+    /// Add robot, robot with the same name is replaced
+    pub fn add(mut self, name: &str, dingtalk: impl Into<Arc<DingTalk>>) -> Self {
+        let dingtalk = dingtalk.into();
+        match self.robots.iter_mut().find(|(n, _)| n == name) {
+            Some(robot) => robot.1 = dingtalk,
+            None => self.robots.push((name.to_owned(), dingtalk)),
+        }
+        self
+    }
+
+    /// Get robot by name
+    pub fn get(&self, name: &str) -> Option<&DingTalk> {
+        self.robots
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, dingtalk)| dingtalk.as_ref())
+    }
+
+    /// Robot names in added order
+    pub fn names(&self) -> Vec<&str> {
+        self.robots.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Robot count
+    pub fn len(&self) -> usize {
+        self.robots.len()
+    }
+
+    /// Is group empty
+    pub fn is_empty(&self) -> bool {
+        self.robots.is_empty()
+    }
+
+    /// Send message to all robots concurrently, returns result of each robot by name
+    pub async fn send_message(
+        &self,
+        dingtalk_message: &DingTalkMessage,
+    ) -> BTreeMap<String, XResult<()>> {
+        let sends = self.robots.iter().map(|(name, dingtalk)| async move {
+            let result = dingtalk.send_message_ref(dingtalk_message).await;
+            (name.clone(), result)
+        });
+        join_all(sends).await.into_iter().collect()
+    }
+
+    /// Send text message to all robots, see `DingTalkGroup::send_message`
+    pub async fn send_text(&self, text_message: &str) -> BTreeMap<String, XResult<()>> {
+        self.send_message(&DingTalkMessage::new_text(text_message))
+            .await
+    }
+
+    /// Send markdown message to all robots, see `DingTalkGroup::send_message`
+    pub async fn send_markdown(&self, title: &str, text: &str) -> BTreeMap<String, XResult<()>> {
+        self.send_message(&DingTalkMessage::new_markdown(title, text))
+            .await
+    }
+}
@@ -0,0 +1,49 @@
+use crate::*;
+
+/// Custom request headers
+impl DingTalk {
+    /// Add `headers` to all requests of `DingTalk::send` and WeChat Work media upload, e.g.
+    /// token required by a gateway
+    ///
+    /// Headers of the same name set before are replaced. `Content-Type` is always
+    /// `application/json` and is ignored here(logged as warning with feature `tracing`),
+    /// `User-Agent` replaces `DingTalk::set_user_agent`.
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(without_content_type(headers));
+        self
+    }
+
+    /// Send DingTalk message with extra `headers`, same as `DingTalk::send_message_ref`
+    ///
+    /// `headers` replace default headers of the same name, `Content-Type` is ignored, see
+    /// `DingTalk::default_headers`.
+    pub async fn send_message_with_headers(
+        &self,
+        dingtalk_message: &DingTalkMessage,
+        headers: HeaderMap,
+    ) -> XResult<()> {
+        self.clone()
+            .default_headers(headers)
+            .send_message_ref(dingtalk_message)
+            .await
+    }
+
+    /// Default headers as name and value pairs, start of `RequestParts::headers`
+    pub(crate) fn default_header_pairs(&self) -> Vec<(String, String)> {
+        self.default_headers
+            .iter()
+            .map(|(name, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                (name.as_str().to_owned(), value)
+            })
+            .collect()
+    }
+}
+
+fn without_content_type(mut headers: HeaderMap) -> HeaderMap {
+    if headers.remove(reqwest::header::CONTENT_TYPE).is_some() {
+        #[cfg(feature = "tracing")]
+        trace::content_type_header_ignored();
+    }
+    headers
+}
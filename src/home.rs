@@ -0,0 +1,91 @@
+use std::{
+    env,
+    path::{self, PathBuf},
+};
+
+use crate::{DingTalkError, XResult};
+
+/// Resolve `~` or `~/...` in `path` with home directory of current user
+pub(crate) fn resolve_home_path(path: &str) -> XResult<PathBuf> {
+    expand_home(path, home_dir)
+}
+
+/// Home directory, `HOME` on Unix, `USERPROFILE`(then `HOMEDRIVE` + `HOMEPATH`, then `HOME`)
+/// on Windows, empty value is treated as not set
+fn home_dir() -> Option<PathBuf> {
+    let var = |name: &str| env::var_os(name).filter(|v| !v.is_empty());
+    if cfg!(windows) {
+        var("USERPROFILE")
+            .map(PathBuf::from)
+            .or_else(|| match (var("HOMEDRIVE"), var("HOMEPATH")) {
+                (Some(drive), Some(path)) => Some(PathBuf::from(drive).join(path)),
+                _ => None,
+            })
+            .or_else(|| var("HOME").map(PathBuf::from))
+    } else {
+        var("HOME").map(PathBuf::from)
+    }
+}
+
+fn expand_home<F>(path: &str, home_dir: F) -> XResult<PathBuf>
+where
+    F: FnOnce() -> Option<PathBuf>,
+{
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() => rest,
+        Some(rest) if rest.starts_with(path::is_separator) => &rest[1..],
+        // `~user/...` or `~file`, not home directory
+        _ => return Ok(PathBuf::from(path)),
+    };
+    let home = home_dir().ok_or_else(|| {
+        DingTalkError::Config(format!(
+            "Cannot determine home directory for path: {}",
+            path
+        ))
+    })?;
+    Ok(if rest.is_empty() {
+        home
+    } else {
+        home.join(rest)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn home() -> Option<PathBuf> {
+        Some(PathBuf::from("/home/alice"))
+    }
+
+    #[test]
+    fn expand_home_paths() {
+        assert_eq!(
+            PathBuf::from("/home/alice/.dingtalk-token.json"),
+            expand_home("~/.dingtalk-token.json", home).unwrap()
+        );
+        assert_eq!(
+            PathBuf::from("/home/alice"),
+            expand_home("~", home).unwrap()
+        );
+        assert_eq!(
+            PathBuf::from("/etc/dingtalk.json"),
+            expand_home("/etc/dingtalk.json", || panic!("home not needed")).unwrap()
+        );
+        assert_eq!(
+            PathBuf::from("~bob/dingtalk.json"),
+            expand_home("~bob/dingtalk.json", || panic!("home not needed")).unwrap()
+        );
+    }
+
+    #[test]
+    fn expand_home_missing() {
+        match expand_home("~/.dingtalk-token.json", || None) {
+            Err(DingTalkError::Config(message)) => assert_eq!(
+                "Cannot determine home directory for path: ~/.dingtalk-token.json",
+                message
+            ),
+            _ => panic!("missing home should be config error"),
+        }
+    }
+}
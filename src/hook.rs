@@ -0,0 +1,97 @@
+use serde::Deserialize;
+use std::{
+    panic::{self, AssertUnwindSafe},
+    time::SystemTime,
+};
+
+use crate::*;
+
+/// Hook of `DingTalk::on_send`
+pub(crate) type SendHook = dyn Fn(&SendAttempt) + Send + Sync;
+
+/// One webhook attempt, passed to hooks of `DingTalk::on_send`
+///
+/// Retries by `DingTalk::with_retry` are separate attempts, dry run and suppressed duplicates
+/// make no attempt.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct SendAttempt<'a> {
+    /// `msgtype` of payload, `None` when payload has no known `msgtype`, e.g. Feishu or Slack
+    pub message_type: Option<DingTalkMessageType>,
+    /// Bytes of JSON payload
+    pub payload_bytes: usize,
+    /// 1-based attempt number of this send
+    pub attempt: u32,
+    /// Time of the HTTP request, including reading response
+    pub duration: Duration,
+    /// HTTP status, `None` when no response, e.g. connect failed
+    pub status: Option<u16>,
+    /// `errcode` of response, `None` when response has no `errcode`
+    pub errcode: Option<i64>,
+    /// Error of this attempt, `None` when succeeded
+    pub error: Option<&'a DingTalkError>,
+}
+
+impl SendAttempt<'_> {
+    /// Attempt succeeded
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Send hooks
+impl DingTalk {
+    /// Call `hook` after each webhook attempt, e.g. metrics or audit, hooks are called in order
+    ///
+    /// Hook gets `SendAttempt` by reference and can not change the result. Panic in hook is
+    /// caught, the panic message is printed by panic hook of std(and logged with feature
+    /// `tracing`), other hooks and the send are not affected.
+    pub fn on_send<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&SendAttempt) + Send + Sync + 'static,
+    {
+        self.send_hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Call hooks of `DingTalk::on_send` with result of one attempt
+    pub(crate) fn call_send_hooks(
+        &self,
+        json_message: &str,
+        attempt: u32,
+        started: SystemTime,
+        status: Option<u16>,
+        result: &XResult<DingTalkResponse>,
+    ) {
+        if self.send_hooks.is_empty() {
+            return;
+        }
+        let message_type = serde_json::from_str::<Value>(json_message)
+            .ok()
+            .and_then(|value| DingTalkMessageType::deserialize(&value["msgtype"]).ok());
+        let (errcode, error) = match result {
+            Ok(response) => (Some(response.errcode), None),
+            Err(e @ DingTalkError::Server { errcode, .. }) => (Some(*errcode), Some(e)),
+            Err(e) => (None, Some(e)),
+        };
+        let send_attempt = SendAttempt {
+            message_type,
+            payload_bytes: json_message.len(),
+            attempt,
+            duration: clock::system_now()
+                .duration_since(started)
+                .unwrap_or_default(),
+            status,
+            errcode,
+            error,
+        };
+        for (index, hook) in self.send_hooks.iter().enumerate() {
+            if panic::catch_unwind(AssertUnwindSafe(|| hook(&send_attempt))).is_err() {
+                #[cfg(feature = "tracing")]
+                trace::send_hook_panicked(index);
+                #[cfg(not(feature = "tracing"))]
+                let _ = index;
+            }
+        }
+    }
+}
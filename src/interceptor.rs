@@ -0,0 +1,173 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::fmt;
+
+use crate::*;
+
+/// Webhook request passed to `Interceptor::before_send`
+///
+/// `headers` are extra headers, starting with `DingTalk::default_headers`, `Content-Type` is
+/// always `application/json` and can not be changed, `User-Agent` replaces
+/// `DingTalk::set_user_agent`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestParts {
+    /// Webhook URL, signed URL contains access token and sign
+    pub url: String,
+    /// Extra headers, name is case-insensitive
+    pub headers: Vec<(String, String)>,
+    /// JSON body
+    pub body: String,
+}
+
+impl RequestParts {
+    /// New request without extra headers
+    pub fn new(url: &str, body: &str) -> Self {
+        RequestParts {
+            url: url.into(),
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+
+    /// Set header, existing headers of the same name are replaced
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Get first header value by name, name is case-insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Extra headers as `HeaderMap`, `Content-Type` is skipped
+    pub(crate) fn header_map(&self) -> XResult<HeaderMap> {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in &self.headers {
+            if name.eq_ignore_ascii_case(CONTENT_TYPE) {
+                continue;
+            }
+            let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                DingTalkError::Config(format!("Header name is invalid: {:?}, {}", name, e))
+            })?;
+            let header_value = HeaderValue::from_str(value).map_err(|e| {
+                DingTalkError::Config(format!("Header value of {} is invalid: {}", name, e))
+            })?;
+            header_map.append(header_name, header_value);
+        }
+        Ok(header_map)
+    }
+}
+
+/// Interceptor of webhook requests, installed by `DingTalk::with_interceptor`
+///
+/// `before_send` is called before each HTTP request(including retries) and can change URL,
+/// headers and body, e.g. add a signed header or route through a gateway. Error aborts the
+/// request, it is retried by `DingTalk::with_retry` only when `DingTalkError::is_retryable`.
+///
+/// Closure `Fn(RequestParts) -> XResult<RequestParts>` is an interceptor.
+///
+/// Sample code:
+/// ```ignore
+/// let dt = DingTalk::new("<token>", "").with_interceptor(|request: RequestParts| {
+///     let sign = sign_internal(&request.body);
+///     Ok(request.with_header("X-Internal-Sign", &sign))
+/// });
+/// ```
+pub trait Interceptor: Send + Sync {
+    /// Inspect or change request before it is sent
+    fn before_send(&self, request: RequestParts) -> XResult<RequestParts>;
+}
+
+impl<F> Interceptor for F
+where
+    F: Fn(RequestParts) -> XResult<RequestParts> + Send + Sync,
+{
+    fn before_send(&self, request: RequestParts) -> XResult<RequestParts> {
+        self(request)
+    }
+}
+
+/// Logger of `LoggingInterceptor`
+type Logger = dyn Fn(&str) + Send + Sync;
+
+/// Interceptor logs each request, URL is redacted, header values are not logged
+///
+/// Logs to stderr by default, e.g.
+/// `POST https://oapi.dingtalk.com/robot/send?access_token=***, headers: [X-Trace-Id], body: 45 bytes`
+#[derive(Clone, Default)]
+pub struct LoggingInterceptor {
+    logger: Option<Arc<Logger>>,
+}
+
+impl LoggingInterceptor {
+    /// Log to stderr
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Log by `logger`, e.g. `log::info!` or collecting lines in tests
+    pub fn with_logger<F>(logger: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        LoggingInterceptor {
+            logger: Some(Arc::new(logger)),
+        }
+    }
+}
+
+impl fmt::Debug for LoggingInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoggingInterceptor")
+            .field("logger", &self.logger.as_ref().map(|_| "Fn(&str)"))
+            .finish()
+    }
+}
+
+impl Interceptor for LoggingInterceptor {
+    fn before_send(&self, request: RequestParts) -> XResult<RequestParts> {
+        let header_names = request
+            .headers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        let line = format!(
+            "POST {}, headers: [{}], body: {} bytes",
+            redact::redact_url(&request.url),
+            header_names.join(", "),
+            request.body.len()
+        );
+        match &self.logger {
+            Some(logger) => logger(&line),
+            None => eprintln!("{}", line),
+        }
+        Ok(request)
+    }
+}
+
+/// Request interceptors
+impl DingTalk {
+    /// Add interceptor, interceptors are applied in added order before each request of
+    /// `DingTalk::send` and WeChat Work media upload, see `Interceptor`
+    pub fn with_interceptor<I: Interceptor + 'static>(mut self, interceptor: I) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Apply interceptors to request of `url` and `body`
+    pub(crate) fn intercept(&self, url: String, body: String) -> XResult<RequestParts> {
+        let mut request = RequestParts {
+            url,
+            headers: self.default_header_pairs(),
+            body,
+        };
+        for interceptor in &self.interceptors {
+            request = interceptor.before_send(request)?;
+        }
+        Ok(request)
+    }
+}
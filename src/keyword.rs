@@ -0,0 +1,69 @@
+use std::borrow::Cow;
+
+use crate::*;
+
+/// Custom keywords security setting of robot, messages without keyword are rejected
+impl DingTalk {
+    /// Prefix `[keyword] ` to text, markdown and action card text when the message does not
+    /// contain `security_keyword`, e.g. `[ALERT] disk full`
+    ///
+    /// Robot with custom keywords security setting rejects message without keyword by errcode
+    /// 310000. Title of markdown and action card are also searched for the keyword.
+    pub fn security_keyword(mut self, security_keyword: &str) -> Self {
+        self.security_keyword = Some(security_keyword.into());
+        self
+    }
+
+    /// Do not modify message missing `security_keyword`, only log `warn` event with feature
+    /// `tracing`. Default is off.
+    pub fn security_keyword_warn_only(mut self, security_keyword_warn_only: bool) -> Self {
+        self.security_keyword_warn_only = security_keyword_warn_only;
+        self
+    }
+
+    /// Message with `security_keyword` prefixed when it is missing
+    pub(crate) fn apply_security_keyword<'a>(
+        &self,
+        dingtalk_message: &'a DingTalkMessage,
+    ) -> Cow<'a, DingTalkMessage> {
+        let keyword = match self.security_keyword.as_deref() {
+            Some(keyword) if !keyword.is_empty() => keyword,
+            _ => return Cow::Borrowed(dingtalk_message),
+        };
+        let (title, content) = match dingtalk_message.message_type {
+            DingTalkMessageType::Text => ("", &dingtalk_message.text_content),
+            DingTalkMessageType::Markdown => (
+                dingtalk_message.markdown_title.as_str(),
+                &dingtalk_message.markdown_content,
+            ),
+            DingTalkMessageType::ActionCard => (
+                dingtalk_message.action_card_title.as_str(),
+                &dingtalk_message.action_card_text,
+            ),
+            _ => return Cow::Borrowed(dingtalk_message),
+        };
+        if title.contains(keyword) || content.contains(keyword) {
+            return Cow::Borrowed(dingtalk_message);
+        }
+        #[cfg(feature = "tracing")]
+        trace::missing_security_keyword(dingtalk_message.message_type, keyword);
+        if self.security_keyword_warn_only {
+            return Cow::Borrowed(dingtalk_message);
+        }
+
+        // markdown heading must start the line, keyword goes to a line of its own
+        let separator = if content.starts_with('#') {
+            "\n\n"
+        } else {
+            " "
+        };
+        let prefixed = format!("[{}]{}{}", keyword, separator, content);
+        let mut dingtalk_message = dingtalk_message.clone();
+        match dingtalk_message.message_type {
+            DingTalkMessageType::Text => dingtalk_message.text_content = prefixed,
+            DingTalkMessageType::Markdown => dingtalk_message.markdown_content = prefixed,
+            _ => dingtalk_message.action_card_text = prefixed,
+        }
+        Cow::Owned(dingtalk_message)
+    }
+}
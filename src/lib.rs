@@ -1,52 +1,240 @@
 use hmac::{Hmac, Mac, NewMac};
+use md5::{Digest, Md5};
 use serde_json::Value;
 use sha2::Sha256;
 use std::{
-    env, fs,
-    io::{Error, ErrorKind},
-    path::PathBuf,
-    time::SystemTime,
+    borrow::Cow,
+    collections::HashMap,
+    env,
+    str::FromStr,
+    sync::{atomic::AtomicU64, Arc},
+    time::{Duration, SystemTime},
 };
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs, path::PathBuf};
 
+mod app;
+#[cfg(any(feature = "blocking", feature = "minimal-http"))]
+mod blocking;
+mod builder;
+mod card;
+mod clock;
+mod credential;
+mod dedup;
+mod direct_url;
+mod discord;
+pub mod emoji;
+mod error;
+mod error_report;
+mod expand;
+mod feishu;
+#[cfg(all(
+    any(feature = "log-forward", feature = "tracing-forward"),
+    not(target_arch = "wasm32")
+))]
+mod forward;
+mod group;
+mod headers;
+#[cfg(not(target_arch = "wasm32"))]
+mod home;
+mod hook;
+mod interceptor;
+mod keyword;
+mod limits;
+#[cfg(all(feature = "log-forward", not(target_arch = "wasm32")))]
+mod log_forward;
+mod markdown;
+mod media;
 mod msg;
+pub mod outgoing;
+#[cfg(feature = "axum")]
+pub mod outgoing_axum;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod panic_hook;
+mod pc_slide;
+mod pool;
+mod profile;
+#[cfg(not(target_arch = "wasm32"))]
+mod queue;
+mod rate_limit;
+mod reader;
+mod redact;
+mod response;
+mod retry_after;
+mod robot_code;
+#[cfg(not(target_arch = "wasm32"))]
+mod schedule;
+mod secret;
+mod sender;
+pub mod sign;
+mod silent;
+mod slack;
+mod split;
+#[cfg(all(feature = "stream", not(target_arch = "wasm32")))]
+pub mod stream;
+mod template;
+mod template_card;
+#[cfg(feature = "tera")]
+mod tera_template;
+#[cfg(feature = "tracing")]
+mod trace;
+mod transport;
+#[cfg(all(feature = "tracing-forward", not(target_arch = "wasm32")))]
+mod tracing_forward;
+#[cfg(feature = "minimal-http")]
+mod ureq_transport;
+mod validate;
+mod wechat_app;
+mod work_notification;
+use dedup::{DedupOutcome, Deduplicator};
+use hook::SendHook;
 use msg::*;
+use secret::{wipe, SecretBuffer};
+
+pub use app::{AppAccessToken, DingTalkApp};
+pub use builder::DingTalkBuilder;
+pub use card::{InteractiveCard, InteractiveCardReceiver};
+pub use clock::{Clock, SystemClock};
+pub use error::DingTalkError;
+pub use group::DingTalkGroup;
+pub use hook::SendAttempt;
+pub use interceptor::{Interceptor, LoggingInterceptor, RequestParts};
+pub use limits::DingTalkLengthLimits;
+#[cfg(all(feature = "log-forward", not(target_arch = "wasm32")))]
+pub use log_forward::{DingTalkLogger, DingTalkLoggerBuilder};
+pub use markdown::MarkdownBuilder;
+pub use media::{MediaId, MediaType};
+pub use pool::{DingTalkPool, PoolDelivery};
+#[cfg(not(target_arch = "wasm32"))]
+pub use queue::DingTalkQueue;
+pub use rate_limit::RateLimiter;
+pub use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+pub use response::DingTalkResponse;
+pub use robot_code::RobotCodeClient;
+#[cfg(not(target_arch = "wasm32"))]
+pub use schedule::ScheduledSend;
+pub use sender::WebhookSender;
+pub use template_card::{
+    WeChatTemplateCard, WeChatTemplateCardAction, WeChatTemplateCardHorizontalContent,
+    WeChatTemplateCardImage, WeChatTemplateCardSource, WeChatTemplateCardTitle,
+    WeChatTemplateCardType,
+};
+#[cfg(feature = "tera")]
+pub use tera_template::TemplateStore;
+pub use transport::{
+    RecordedRequest, RecordingTransport, ReqwestTransport, Transport, TransportFuture,
+    TransportResponse,
+};
+#[cfg(all(feature = "tracing-forward", not(target_arch = "wasm32")))]
+pub use tracing_forward::{DingTalkLayer, DingTalkLayerBuilder};
+#[cfg(feature = "minimal-http")]
+pub use ureq_transport::UreqTransport;
+pub use wechat_app::WeChatApp;
 
 pub use msg::{
     DingTalkMessage, DingTalkMessageActionCardBtn, DingTalkMessageActionCardBtnOrientation,
-    DingTalkMessageActionCardHideAvatar, DingTalkMessageFeedCardLink, DingTalkMessageType,
-    DingTalkType,
+    DingTalkMessageActionCardHideAvatar, DingTalkMessageFeedCardLink, DingTalkMessageNewsArticle,
+    DingTalkMessageType, DingTalkType,
 };
 
 type HmacSha256 = Hmac<Sha256>;
-pub type XResult<T> = Result<T, Box<dyn std::error::Error>>;
+pub type XResult<T> = Result<T, DingTalkError>;
 
 const CONTENT_TYPE: &str = "Content-Type";
 const APPLICATION_JSON_UTF8: &str = "application/json; charset=utf-8";
+const USER_AGENT: &str = "User-Agent";
+const DEFAULT_USER_AGENT: &str = concat!("dingtalk-rs/", env!("CARGO_PKG_VERSION"));
 
 const DEFAULT_DINGTALK_ROBOT_URL: &str = "https://oapi.dingtalk.com/robot/send";
 const DEFAULT_WECHAT_WORK_ROBOT_URL: &str = "https://qyapi.weixin.qq.com/cgi-bin/webhook/send";
+const DEFAULT_FEISHU_ROBOT_URL: &str = "https://open.feishu.cn/open-apis/bot/v2/hook/";
+
+const WECHAT_WORK_IMAGE_MAX_BYTES: usize = 2 * 1024 * 1024;
+const WECHAT_WORK_NEWS_MAX_ARTICLES: usize = 8;
+const ACTION_CARD_MAX_BTNS: usize = 5;
+const FEED_CARD_MAX_LINKS: usize = 10;
+const WECHAT_WORK_FILE_MAX_BYTES: usize = 20 * 1024 * 1024;
+const WECHAT_WORK_VOICE_MAX_BYTES: usize = 2 * 1024 * 1024;
+const WECHAT_WORK_VOICE_MAX_SECONDS: usize = 60;
+const AMR_HEADER: &[u8] = b"#!AMR\n";
+// AMR-NB frame size(without the 1 byte frame header) by frame type, each frame is 20ms
+const AMR_FRAME_SIZES: [usize; 16] = [12, 13, 15, 17, 19, 20, 26, 31, 5, 0, 0, 0, 0, 0, 0, 0];
+
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+/// DingTalk and WeChat Work `errcode` of sending too frequently
+const THROTTLED_ERRCODES: [i64; 2] = [130101, 45009];
+/// Max bytes of response body read and kept in `DingTalkError::Status`
+const ERROR_BODY_MAX_BYTES: usize = 8 * 1024;
 
 /// `DingTalk` is a simple SDK for DingTalk webhook robot
 ///
 /// Document https://ding-doc.dingtalk.com/doc#/serverapi2/qf2nxq
 ///
 /// Sample code:
-/// ```ignore
-/// let dt = DingTalk::new("<token>", "");
-/// dt.send_text("Hello world!")?;
+/// ```no_run
+/// # use dingtalk::*;
+/// # async fn run() -> XResult<()> {
+/// let dt = DingTalk::builder().access_token("<token>").build()?;
+/// dt.send_text("Hello world!").await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
+/// `DingTalk::builder()` is the preferred way to create `DingTalk`, it validates settings
+/// such as `direct_url` and `access_token` are not both set.
+///
 /// At all sample:
-/// ```ignore
-/// dt.send_message(&DingTalkMessage::new_text("Hello World!").at_all())?;
+/// ```no_run
+/// # use dingtalk::*;
+/// # async fn run(dt: DingTalk) -> XResult<()> {
+/// dt.send_message(DingTalkMessage::new_text("Hello World!").at_all()).await?;
+/// # Ok(())
+/// # }
 /// ```
-#[derive(Default)]
+///
+/// One HTTP client is kept by each `DingTalk` so keep-alive connections are reused across
+/// sends, `DingTalk` is `Send + Sync` and can be shared in an `Arc`.
+///
+/// Clone is cheap, clones share the HTTP client(and its connection pool), rate limiter,
+/// deduplicator, transport and sender, e.g. move a clone into each spawned task. Settings
+/// changed on a clone later, e.g. `DingTalk::set_proxy`, apply to that clone only.
+#[derive(Clone, Default)]
 pub struct DingTalk {
     pub dingtalk_type: DingTalkType,
     pub default_webhook_url: String,
     pub access_token: String,
     pub sec_token: String,
     pub direct_url: String,
+    /// Keyword of custom keywords security setting, see `DingTalk::security_keyword`
+    pub security_keyword: Option<String>,
+    retry_max_attempts: u32,
+    retry_initial_delay: Duration,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    deduplicator: Option<Arc<Deduplicator>>,
+    dedup_summary: bool,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+    user_agent: Option<String>,
+    sender: Option<Arc<dyn WebhookSender>>,
+    transport: Option<Arc<dyn Transport>>,
+    send_hooks: Vec<Arc<SendHook>>,
+    default_headers: HeaderMap,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    silent_failures: Arc<AtomicU64>,
+    clock: Option<Arc<dyn Clock>>,
+    session_expired_at: Option<i64>,
+    strict_validation: bool,
+    dry_run: bool,
+    enforce_length_limits: bool,
+    auto_split: bool,
+    truncate_to_limit: bool,
+    truncate_ellipsis: Option<String>,
+    security_keyword_warn_only: bool,
+    convert_emoji_shortcodes: bool,
+    client: reqwest::Client,
+    #[cfg(any(feature = "blocking", feature = "minimal-http"))]
+    blocking_client: Arc<std::sync::OnceLock<blocking::BlockingClient>>,
 }
 
 impl DingTalkMessage {
@@ -55,6 +243,40 @@ impl DingTalkMessage {
         Self::new(DingTalkMessageType::Text).text(text_content)
     }
 
+    /// Create DingTalk message from JSON, field names are same as `DingTalkMessage` fields
+    ///
+    /// Sample:
+    /// ```json
+    /// {
+    ///     "message_type": "actionCard",
+    ///     "action_card_title": "title",
+    ///     "action_card_text": "text",
+    ///     "action_card_btns": [{"title": "Open", "action_url": "https://example.com/"}],
+    ///     "at_mobiles": ["13800000000"]
+    /// }
+    /// ```
+    pub fn from_json(json: &str) -> XResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Render template for text or markdown content
+    ///
+    /// * `{{name}}` - value of `name` in `vars`, spaces around `name` are ignored
+    /// * `\{{` - literal `{{`
+    ///
+    /// Error lists all unresolved placeholders.
+    pub fn render_template(template: &str, vars: &HashMap<&str, &str>) -> XResult<String> {
+        template::render_template(template, vars)
+    }
+
+    /// Split text into chunks of at most `max_bytes` bytes with ` (1/3)` style suffix
+    ///
+    /// Text is split on line breaks when possible, otherwise on char boundaries, text within
+    /// `max_bytes` is returned as is.
+    pub fn split_text(text: &str, max_bytes: usize) -> Vec<String> {
+        split::split_text(text, max_bytes)
+    }
+
     /// New markdown DingTalk message
     pub fn new_markdown(markdown_title: &str, markdown_content: &str) -> Self {
         Self::new(DingTalkMessageType::Markdown).markdown(markdown_title, markdown_content)
@@ -88,6 +310,36 @@ impl DingTalkMessage {
         Self::new(DingTalkMessageType::FeedCard)
     }
 
+    /// New WeChat Work image message, image(JPG or PNG) is at most 2MB
+    pub fn new_wechat_image(image_bytes: &[u8]) -> Self {
+        let mut s = Self::new(DingTalkMessageType::Image);
+        s.image_base64 = base64::encode(image_bytes);
+        s.image_md5 = Md5::digest(image_bytes)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        s
+    }
+
+    /// New WeChat Work news message, add 1 to 8 articles by `add_news_article`
+    pub fn new_news() -> Self {
+        Self::new(DingTalkMessageType::News)
+    }
+
+    /// New WeChat Work file message, `media_id` is from `DingTalk::upload_wechat_media`
+    pub fn new_wechat_file(media_id: &str) -> Self {
+        let mut s = Self::new(DingTalkMessageType::File);
+        s.media_id = media_id.into();
+        s
+    }
+
+    /// New WeChat Work voice message, `media_id` is from `DingTalk::upload_wechat_voice`
+    pub fn new_wechat_voice(media_id: &str) -> Self {
+        let mut s = Self::new(DingTalkMessageType::Voice);
+        s.media_id = media_id.into();
+        s
+    }
+
     /// New DingTalk message
     pub fn new(message_type: DingTalkMessageType) -> Self {
         DingTalkMessage {
@@ -98,14 +350,13 @@ impl DingTalkMessage {
 
     /// Set text
     pub fn text(mut self, text_content: &str) -> Self {
-        self.text_content = text_content.into();
+        self.set_text(text_content);
         self
     }
 
     /// Set markdown
     pub fn markdown(mut self, markdown_title: &str, markdown_content: &str) -> Self {
-        self.markdown_title = markdown_title.into();
-        self.markdown_content = markdown_content.into();
+        self.set_markdown(markdown_title, markdown_content);
         self
     }
 
@@ -117,52 +368,55 @@ impl DingTalkMessage {
         link_pic_url: &str,
         link_message_url: &str,
     ) -> Self {
-        self.link_title = link_title.into();
-        self.link_text = link_text.into();
-        self.link_pic_url = link_pic_url.into();
-        self.link_message_url = link_message_url.into();
+        self.set_link(link_title, link_text, link_pic_url, link_message_url);
         self
     }
 
     /// Set action card show avator(default show)
     pub fn action_card_show_avatar(mut self) -> Self {
-        self.action_card_hide_avatar = DingTalkMessageActionCardHideAvatar::Show;
+        self.set_action_card_hide_avatar(false);
         self
     }
 
     /// Set action card hide avator
     pub fn action_card_hide_avatar(mut self) -> Self {
-        self.action_card_hide_avatar = DingTalkMessageActionCardHideAvatar::Hide;
+        self.set_action_card_hide_avatar(true);
         self
     }
 
     /// Set action card btn vertical(default vertical)
     pub fn action_card_btn_vertical(mut self) -> Self {
-        self.action_card_btn_orientation = DingTalkMessageActionCardBtnOrientation::Vertical;
+        self.set_action_card_btn_landscape(false);
         self
     }
 
     /// Set action card btn landscape
     pub fn action_card_btn_landscape(mut self) -> Self {
-        self.action_card_btn_orientation = DingTalkMessageActionCardBtnOrientation::Landscape;
+        self.set_action_card_btn_landscape(true);
         self
     }
 
-    /// Set action card single btn
-    pub fn set_action_card_signle_btn(mut self, btn: DingTalkMessageActionCardBtn) -> Self {
+    /// Set action card single btn, it can not be used with `DingTalkMessage::add_action_card_btn`
+    pub fn set_action_card_single_btn(mut self, btn: DingTalkMessageActionCardBtn) -> Self {
         self.action_card_single_btn = Some(btn);
         self
     }
 
+    /// Set action card single btn
+    #[deprecated(note = "use `set_action_card_single_btn`")]
+    pub fn set_action_card_signle_btn(self, btn: DingTalkMessageActionCardBtn) -> Self {
+        self.set_action_card_single_btn(btn)
+    }
+
     /// Add action card btn
     pub fn add_action_card_btn(mut self, btn: DingTalkMessageActionCardBtn) -> Self {
-        self.action_card_btns.push(btn);
+        self.push_action_card_btn(btn);
         self
     }
 
     /// Add feed card link
     pub fn add_feed_card_link(mut self, link: DingTalkMessageFeedCardLink) -> Self {
-        self.feed_card_links.push(link);
+        self.push_feed_card_link(link);
         self
     }
 
@@ -175,62 +429,311 @@ impl DingTalkMessage {
         })
     }
 
+    /// Add news article
+    pub fn add_news_article(
+        mut self,
+        title: &str,
+        description: &str,
+        url: &str,
+        pic_url: &str,
+    ) -> Self {
+        self.push_news_article(title, description, url, pic_url);
+        self
+    }
+
     /// At all
     pub fn at_all(mut self) -> Self {
-        self.at_all = true;
+        self.set_at_all(true);
         self
     }
 
-    /// At mobiles
-    pub fn at_mobiles(mut self, mobiles: &[String]) -> Self {
+    /// At mobiles, accepts `&[String]`, `&[&str]`, `Vec<String>` or iterator of strings
+    pub fn at_mobiles<I, S>(mut self, mobiles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
         for m in mobiles {
-            self.at_mobiles.push(m.clone());
+            self.push_at_mobile(m.as_ref());
+        }
+        self
+    }
+
+    /// At user id
+    pub fn at_user_id(mut self, user_id: &str) -> Self {
+        self.push_at_user_id(user_id);
+        self
+    }
+
+    /// At user ids, accepts `&[String]`, `&[&str]`, `Vec<String>` or iterator of strings
+    pub fn at_user_ids<I, S>(mut self, user_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for u in user_ids {
+            self.push_at_user_id(u.as_ref());
         }
         self
     }
+
+    /// Set text, `&mut self` version of `text`
+    pub fn set_text(&mut self, text_content: &str) -> &mut Self {
+        self.text_content = text_content.into();
+        self
+    }
+
+    /// Set markdown, `&mut self` version of `markdown`
+    pub fn set_markdown(&mut self, markdown_title: &str, markdown_content: &str) -> &mut Self {
+        self.markdown_title = markdown_title.into();
+        self.markdown_content = markdown_content.into();
+        self
+    }
+
+    /// Set link, `&mut self` version of `link`
+    pub fn set_link(
+        &mut self,
+        link_title: &str,
+        link_text: &str,
+        link_pic_url: &str,
+        link_message_url: &str,
+    ) -> &mut Self {
+        self.link_title = link_title.into();
+        self.link_text = link_text.into();
+        self.link_pic_url = link_pic_url.into();
+        self.link_message_url = link_message_url.into();
+        self
+    }
+
+    /// Set action card hide or show avatar, `&mut self` version of `action_card_hide_avatar`
+    /// and `action_card_show_avatar`
+    pub fn set_action_card_hide_avatar(&mut self, hide_avatar: bool) -> &mut Self {
+        self.action_card_hide_avatar = if hide_avatar {
+            DingTalkMessageActionCardHideAvatar::Hide
+        } else {
+            DingTalkMessageActionCardHideAvatar::Show
+        };
+        self
+    }
+
+    /// Set action card btn landscape or vertical, `&mut self` version of
+    /// `action_card_btn_landscape` and `action_card_btn_vertical`
+    pub fn set_action_card_btn_landscape(&mut self, landscape: bool) -> &mut Self {
+        self.action_card_btn_orientation = if landscape {
+            DingTalkMessageActionCardBtnOrientation::Landscape
+        } else {
+            DingTalkMessageActionCardBtnOrientation::Vertical
+        };
+        self
+    }
+
+    /// Push action card btn, `&mut self` version of `add_action_card_btn`
+    pub fn push_action_card_btn(&mut self, btn: DingTalkMessageActionCardBtn) -> &mut Self {
+        self.action_card_btns.push(btn);
+        self
+    }
+
+    /// Push feed card link, `&mut self` version of `add_feed_card_link`
+    pub fn push_feed_card_link(&mut self, link: DingTalkMessageFeedCardLink) -> &mut Self {
+        self.feed_card_links.push(link);
+        self
+    }
+
+    /// Push news article, `&mut self` version of `add_news_article`
+    pub fn push_news_article(
+        &mut self,
+        title: &str,
+        description: &str,
+        url: &str,
+        pic_url: &str,
+    ) -> &mut Self {
+        self.news_articles.push(DingTalkMessageNewsArticle {
+            title: title.into(),
+            description: description.into(),
+            url: url.into(),
+            pic_url: pic_url.into(),
+        });
+        self
+    }
+
+    /// Set at all, `&mut self` version of `at_all`
+    pub fn set_at_all(&mut self, at_all: bool) -> &mut Self {
+        self.at_all = at_all;
+        self
+    }
+
+    /// Push at mobile, `&mut self` version of `at_mobiles`
+    pub fn push_at_mobile(&mut self, mobile: &str) -> &mut Self {
+        self.at_mobiles.push(mobile.into());
+        self
+    }
+
+    /// Push at user id, `&mut self` version of `at_user_id`
+    pub fn push_at_user_id(&mut self, user_id: &str) -> &mut Self {
+        self.at_user_ids.push(user_id.into());
+        self
+    }
+}
+
+/// Parse `DingTalk` from token, same as `DingTalk::from_token`, e.g. value of command line
+/// argument or environment variable
+impl FromStr for DingTalk {
+    type Err = DingTalkError;
+
+    fn from_str(token: &str) -> XResult<Self> {
+        Self::from_token(token)
+    }
 }
 
 impl DingTalk {
-    /// Create `DingTalk` from token:
+    /// Create `DingTalk` from token, spaces around token are ignored:
     /// wechatwork:access_token
+    /// wecom:access_token
     /// dingtalk:access_token?sec_token
+    /// feishu:access_token?sec_token
+    /// https://oapi.dingtalk.com/robot/send?access_token=..., same as `DingTalk::from_url`
     pub fn from_token(token: &str) -> XResult<Self> {
-        if token.starts_with("dingtalk:") {
-            let token_and_or_sec = &token["dingtalk:".len()..];
+        let token = token.trim();
+        if token.starts_with("https://") || token.starts_with("http://") {
+            Self::try_from_url(token)
+        } else if let Some(token_and_or_sec) = token.strip_prefix("dingtalk:") {
             let mut token_and_or_sec_vec = token_and_or_sec.split('?');
             let access_token = match token_and_or_sec_vec.next() {
                 Some(t) => t,
                 None => token_and_or_sec,
             };
-            let sec_token = match token_and_or_sec_vec.next() {
-                Some(t) => t,
-                None => "",
-            };
+            let sec_token = token_and_or_sec_vec.next().unwrap_or_default();
             Ok(Self::new(access_token, sec_token))
-        } else if token.starts_with("wechatwork:") {
-            Ok(Self::new_wechat(&token["wechatwork:".len()..]))
-        } else if token.starts_with("wecom:") {
-            Ok(Self::new_wechat(&token["wecom:".len()..]))
+        } else if let Some(key) = token.strip_prefix("wechatwork:") {
+            Ok(Self::new_wechat(key))
+        } else if let Some(key) = token.strip_prefix("wecom:") {
+            Ok(Self::new_wechat(key))
+        } else if let Some(token_and_or_sec) = token.strip_prefix("feishu:") {
+            let (access_token, sec_token) = token_and_or_sec
+                .split_once('?')
+                .unwrap_or((token_and_or_sec, ""));
+            Ok(Self::new_feishu(access_token, sec_token))
         } else {
-            Err(Box::new(Error::new(
-                ErrorKind::Other,
-                format!("Tokne format erorr: {}", token),
+            Err(DingTalkError::Config(format!(
+                "Token format error: {}, expected dingtalk:<access_token>[?<sec_token>], \
+                 wechatwork:<key>, wecom:<key>, feishu:<access_token>[?<sec_token>] \
+                 or http(s) webhook URL",
+                token
             )))
         }
     }
 
-    /// Create `DingTalk` from file
+    /// Create `DingTalk` from file, `~` in file path is resolved to home directory
+    ///
+    /// File with `.toml` extension is read as TOML, format see `DingTalk::from_toml(toml: &str)`,
+    /// `.yaml` or `.yml` extension is read as YAML(feature `yaml`), format see
+    /// `DingTalk::from_yaml(yaml: &str)`, other files are read as JSON, format see
+    /// `DingTalk::from_json(json: &str)`
     ///
-    /// Format see `DingTalk::from_json(json: &str)`
+    /// Profile of file with `profiles` is selected by environment variable `DINGTALK_PROFILE`,
+    /// see `DingTalk::from_file_profile`.
+    ///
+    /// Not available on wasm32, use `DingTalk::from_json` or `DingTalk::from_toml` instead.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn from_file(f: &str) -> XResult<Self> {
-        let f_path_buf = if f.starts_with("~/") {
-            let home = PathBuf::from(env::var("HOME")?);
-            home.join(f.chars().skip(2).collect::<String>())
-        } else {
-            PathBuf::from(f)
+        let (json_value, format, f_content) = read_config_file(f)?;
+        Self::from_json_value(json_value, format, &f_content)
+    }
+
+    /// Create `DingTalk` from profile `profile` of file with top-level `profiles`, file format
+    /// see `DingTalk::from_file(f: &str)`
+    ///
+    /// Format:
+    /// ```json
+    /// {
+    ///     "default_profile": "dev", // option, used when DINGTALK_PROFILE is not set
+    ///     "proxy": "http://proxy.corp:3128", // option, keys shared by all profiles
+    ///     "profiles": {
+    ///         "prod": { "access_token": "<access token>", "sec_token": "<sec token>" },
+    ///         "dev": { "access_token": "<access token>" }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Keys of profile override the shared ones. `DingTalk::from_file` and `DingTalk::from_json`
+    /// select profile by environment variable `DINGTALK_PROFILE`, or `default_profile`, and
+    /// load config without `profiles` as is. Error of missing profile lists available profiles.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_file_profile(f: &str, profile: &str) -> XResult<Self> {
+        let (json_value, format, f_content) = read_config_file(f)?;
+        let json_value = profile::select_profile(json_value, Some(profile))?;
+        Self::from_json_value(json_value, format, &f_content)
+    }
+
+    /// Create `DingTalk` from TOML string, keys are same as `DingTalk::from_json(json: &str)`
+    ///
+    /// Format:
+    /// ```toml
+    /// type = "wechat" # option
+    /// access_token = "<access token>"
+    /// sec_token = "<sec token>" # option
+    /// ```
+    pub fn from_toml(toml: &str) -> XResult<Self> {
+        Self::from_json_value(parse_toml(toml)?, "TOML", toml)
+    }
+
+    /// Create `DingTalk` from YAML string, keys are same as `DingTalk::from_json(json: &str)`,
+    /// enabled by feature `yaml`
+    ///
+    /// Format:
+    /// ```yaml
+    /// type: wechat # option
+    /// access_token: <access token>
+    /// sec_token: <sec token> # option
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(yaml: &str) -> XResult<Self> {
+        Self::from_json_value(parse_yaml(yaml)?, "YAML", yaml)
+    }
+
+    /// Create `DingTalk` from environment variables
+    ///
+    /// * `DINGTALK_ACCESS_TOKEN` - required, except Slack and Discord
+    /// * `DINGTALK_SEC_TOKEN` - option
+    /// * `DINGTALK_WEBHOOK_URL` - option, required by Slack and Discord
+    /// * `DINGTALK_TYPE` - option, `dingtalk`(default), `wechatwork`, same as `type` in JSON
+    pub fn from_env() -> XResult<Self> {
+        Self::from_env_prefixed("DINGTALK_")
+    }
+
+    /// Create `DingTalk` from environment variables with `prefix`, e.g. `MYAPP_ACCESS_TOKEN`
+    /// when `prefix` is `"MYAPP_"`
+    ///
+    /// Variables see `DingTalk::from_env()`
+    pub fn from_env_prefixed(prefix: &str) -> XResult<Self> {
+        let dingtalk_type = parse_dingtalk_type(&read_env(prefix, "TYPE")?.unwrap_or_default());
+        let webhook_url = read_env(prefix, "WEBHOOK_URL")?;
+        let access_token = read_env(prefix, "ACCESS_TOKEN")?;
+        let access_token = match (dingtalk_type, access_token) {
+            (_, Some(access_token)) => access_token,
+            (DingTalkType::Slack | DingTalkType::Discord, None) if webhook_url.is_some() => {
+                String::new()
+            }
+            (DingTalkType::Slack | DingTalkType::Discord, None) => {
+                return Err(DingTalkError::Config(format!(
+                    "Environment variable {}WEBHOOK_URL is not set",
+                    prefix
+                )));
+            }
+            (_, None) => {
+                return Err(DingTalkError::Config(format!(
+                    "Environment variable {}ACCESS_TOKEN is not set",
+                    prefix
+                )));
+            }
         };
-        let f_content = fs::read_to_string(f_path_buf)?;
-        Self::from_json(&f_content)
+        Ok(DingTalk::with_robot(
+            dingtalk_type,
+            webhook_url.unwrap_or_else(|| default_webhook_url(dingtalk_type).to_owned()),
+            access_token,
+            read_env(prefix, "SEC_TOKEN")?.unwrap_or_default(),
+            String::new(),
+        ))
     }
 
     /// Create `DingTalk` from JSON string
@@ -240,32 +743,63 @@ impl DingTalk {
     /// {
     ///     "default_webhook_url": "", // option
     ///     "access_token": "<access token>",
-    ///     "sec_token": "<sec token>" // option
+    ///     "sec_token": "<sec token>", // option
+    ///     "access_token_env": "DT_TOKEN", // option, read access_token from environment variable
+    ///     "sec_token_env": "DT_SEC", // option, read sec_token from environment variable
+    ///     "proxy": "http://proxy.corp:3128", // option
+    ///     "security_keyword": "ALERT", // option, see `DingTalk::security_keyword`
+    ///     "security_keyword_warn_only": false, // option
+    ///     "expand_env": true // option, default true
     /// }
     /// ```
+    ///
+    /// String values expand environment variables `${VAR}` and `${VAR:-default}`, `$$` is
+    /// literal `$`, set `"expand_env": false` to turn it off.
+    ///
+    /// `access_token_env` and `sec_token_env` name environment variables of the tokens, so the
+    /// config has no secret. Non-empty literal `access_token` or `sec_token` wins, error names
+    /// the variable when it is not set.
+    ///
+    /// `access_token` and `sec_token` can be `keyring:<service>[/<account>]`, the same key of OS
+    /// keyring entry(feature `keyring`), account is `default` when omitted, see
+    /// `DingTalk::from_keyring`.
+    ///
+    /// JSON with top-level `profiles` is selected by `DINGTALK_PROFILE` or `default_profile`,
+    /// see `DingTalk::from_file_profile`.
     pub fn from_json(json: &str) -> XResult<Self> {
         let json_value: Value = serde_json::from_str(json)?;
-        if !json_value.is_object() {
-            return Err(Box::new(Error::new(
-                ErrorKind::Other,
-                format!("JSON format erorr: {}", json),
-            )));
-        }
-        let type_str = json_value["type"]
-            .as_str()
-            .unwrap_or_default()
-            .to_lowercase();
-        let dingtalk_type = match type_str.as_str() {
-            "wechat" | "wechatwork" | "wecom" => DingTalkType::WeChatWork,
-            _ => DingTalkType::DingTalk,
+        Self::from_json_value(json_value, "JSON", json)
+    }
+
+    pub(crate) fn from_json_value(json_value: Value, format: &str, config: &str) -> XResult<Self> {
+        let mut json_value = profile::select_profile(json_value, None)?;
+        let json_map = match json_value.as_object_mut() {
+            Some(json_map) => json_map,
+            None => {
+                return Err(DingTalkError::Config(format!(
+                    "{} format error: {}",
+                    format, config
+                )))
+            }
         };
+        if json_map
+            .get("expand_env")
+            .and_then(Value::as_bool)
+            .unwrap_or(true)
+        {
+            for v in json_map.values_mut() {
+                if let Value::String(s) = v {
+                    *s = expand::expand_env_vars(s)?;
+                }
+            }
+        }
+        expand::resolve_env_keys(json_map)?;
+        credential::resolve_keyring_refs(json_map)?;
+        let dingtalk_type = parse_dingtalk_type(json_value["type"].as_str().unwrap_or_default());
 
         let default_webhook_url = json_value["default_webhook_url"]
             .as_str()
-            .unwrap_or_else(|| match dingtalk_type {
-                DingTalkType::DingTalk => DEFAULT_DINGTALK_ROBOT_URL,
-                DingTalkType::WeChatWork => DEFAULT_WECHAT_WORK_ROBOT_URL,
-            })
+            .unwrap_or_else(|| default_webhook_url(dingtalk_type))
             .to_owned();
         let access_token = json_value["access_token"]
             .as_str()
@@ -279,141 +813,894 @@ impl DingTalk {
             .as_str()
             .unwrap_or_default()
             .to_owned();
+        let security_keyword = json_value["security_keyword"].as_str().map(str::to_owned);
 
-        Ok(DingTalk {
+        let mut dingtalk = DingTalk::with_robot(
             dingtalk_type,
             default_webhook_url,
             access_token,
             sec_token,
             direct_url,
-        })
+        );
+        dingtalk.security_keyword = security_keyword;
+        dingtalk.security_keyword_warn_only = json_value["security_keyword_warn_only"]
+            .as_bool()
+            .unwrap_or_default();
+        if let Some(proxy) = json_value["proxy"].as_str() {
+            dingtalk.set_proxy(proxy)?;
+        }
+        Ok(dingtalk)
+    }
+
+    /// Create `DingTalkBuilder`
+    pub fn builder() -> DingTalkBuilder {
+        DingTalkBuilder::default()
     }
 
     /// Create `DingTalk` from url, for outgoing robot
+    ///
+    /// Same as `DingTalk::try_from_url`, invalid url is kept as `direct_url` as is, and fails
+    /// debug assertion.
     pub fn from_url(direct_url: &str) -> Self {
-        DingTalk {
-            direct_url: direct_url.into(),
-            ..Default::default()
-        }
+        let dingtalk = Self::try_from_url(direct_url);
+        debug_assert!(
+            dingtalk.is_ok(),
+            "Invalid url: {:?}",
+            dingtalk.as_ref().err()
+        );
+        dingtalk.unwrap_or_else(|_| {
+            Self::with_robot(
+                DingTalkType::DingTalk,
+                String::new(),
+                String::new(),
+                String::new(),
+                direct_url.into(),
+            )
+        })
+    }
+
+    /// Create `DingTalk` from `sessionWebhook` of outgoing robot message, the URL is not signed
+    ///
+    /// `expired_at_ms` is `sessionWebhookExpiredTime`, milliseconds since UNIX epoch, sends after
+    /// it fail with `DingTalkError::SessionExpired`, see `OutgoingMessage::reply_target`.
+    pub fn from_session_webhook(session_webhook: &str, expired_at_ms: i64) -> Self {
+        let mut dingtalk = Self::with_robot(
+            DingTalkType::DingTalk,
+            String::new(),
+            String::new(),
+            String::new(),
+            session_webhook.into(),
+        );
+        dingtalk.session_expired_at = Some(expired_at_ms);
+        dingtalk
     }
 
     /// Create `DingTalk`
     /// `access_token` is access token, `sec_token` can be empty `""`
     pub fn new(access_token: &str, sec_token: &str) -> Self {
-        DingTalk {
-            default_webhook_url: DEFAULT_DINGTALK_ROBOT_URL.into(),
-            access_token: access_token.into(),
-            sec_token: sec_token.into(),
-            ..Default::default()
-        }
+        Self::with_robot(
+            DingTalkType::DingTalk,
+            DEFAULT_DINGTALK_ROBOT_URL.into(),
+            access_token.into(),
+            sec_token.into(),
+            String::new(),
+        )
     }
 
     /// Create `DingTalk` for WeChat Work
     pub fn new_wechat(key: &str) -> Self {
-        DingTalk {
-            default_webhook_url: DEFAULT_WECHAT_WORK_ROBOT_URL.into(),
-            dingtalk_type: DingTalkType::WeChatWork,
-            access_token: key.into(),
-            ..Default::default()
-        }
+        Self::with_robot(
+            DingTalkType::WeChatWork,
+            DEFAULT_WECHAT_WORK_ROBOT_URL.into(),
+            key.into(),
+            String::new(),
+            String::new(),
+        )
     }
 
-    /// Set default webhook url
-    pub fn set_default_webhook_url(&mut self, default_webhook_url: &str) {
-        self.default_webhook_url = default_webhook_url.into();
+    /// Create `DingTalk` for Feishu, `sec_token` can be empty `""`
+    pub fn new_feishu(access_token: &str, sec_token: &str) -> Self {
+        Self::with_robot(
+            DingTalkType::Feishu,
+            DEFAULT_FEISHU_ROBOT_URL.into(),
+            access_token.into(),
+            sec_token.into(),
+            String::new(),
+        )
     }
 
-    /// Send DingTalk message
-    ///
-    /// 1. Create DingTalk JSON message
-    /// 2. POST JSON message to DingTalk server
-    pub async fn send_message(&self, dingtalk_message: DingTalkMessage) -> XResult<()> {
-        let mut message_json = match dingtalk_message.message_type {
-            DingTalkMessageType::Text => serde_json::to_value(InnerTextMessage {
-                msgtype: DingTalkMessageType::Text,
-                text: InnerTextMessageText {
-                    content: dingtalk_message.text_content,
-                },
-            }),
-            DingTalkMessageType::Link => serde_json::to_value(InnerLinkMessage {
-                msgtype: DingTalkMessageType::Link,
-                link: InnerLinkMessageLink {
-                    title: dingtalk_message.link_title,
-                    text: dingtalk_message.link_text,
-                    pic_url: dingtalk_message.link_pic_url,
-                    message_url: dingtalk_message.link_message_url,
-                },
-            }),
-            DingTalkMessageType::Markdown => serde_json::to_value(InnerMarkdownMessage {
-                msgtype: DingTalkMessageType::Markdown,
-                markdown: InnerMarkdownMessageMarkdown {
-                    title: dingtalk_message.markdown_title,
-                    text: dingtalk_message.markdown_content,
-                },
-            }),
-            DingTalkMessageType::ActionCard => serde_json::to_value(InnerActionCardMessage {
-                msgtype: DingTalkMessageType::ActionCard,
-                action_card: InnerActionCardMessageActionCard {
-                    title: dingtalk_message.action_card_title,
-                    text: dingtalk_message.action_card_text,
-                    hide_avatar: dingtalk_message.action_card_hide_avatar,
-                    btn_orientation: dingtalk_message.action_card_btn_orientation,
-                },
-            }),
-            DingTalkMessageType::FeedCard => serde_json::to_value(InnerFeedCardMessage {
-                msgtype: DingTalkMessageType::FeedCard,
-                feed_card: InnerFeedCardMessageFeedCard {
-                    links: {
-                        let mut links: Vec<InnerFeedCardMessageFeedCardLink> = vec![];
-                        for feed_card_link in &dingtalk_message.feed_card_links {
-                            links.push(InnerFeedCardMessageFeedCardLink {
-                                title: feed_card_link.title.clone(),
-                                message_url: feed_card_link.message_url.clone(),
-                                pic_url: feed_card_link.pic_url.clone(),
-                            });
-                        }
-                        links
-                    },
-                },
-            }),
-        }?;
-        if DingTalkMessageType::ActionCard == dingtalk_message.message_type {
-            if dingtalk_message.action_card_single_btn.is_some() {
-                if let Some(single_btn) = dingtalk_message.action_card_single_btn.as_ref() {
-                    message_json["actionCard"]["singleTitle"] = single_btn.title.as_str().into();
-                    message_json["actionCard"]["singleURL"] = single_btn.action_url.as_str().into();
-                };
-            } else {
-                let mut btns: Vec<InnerActionCardMessageBtn> = vec![];
-                for action_card_btn in &dingtalk_message.action_card_btns {
-                    btns.push(InnerActionCardMessageBtn {
-                        title: action_card_btn.title.clone(),
-                        action_url: action_card_btn.action_url.clone(),
-                    });
-                }
-                message_json["actionCard"]["btns"] = serde_json::to_value(btns)?;
-            }
-        }
-        if dingtalk_message.at_all || !dingtalk_message.at_mobiles.is_empty() {
-            if let Some(m) = message_json.as_object_mut() {
-                let mut at_mobiles: Vec<Value> = vec![];
-                for m in &dingtalk_message.at_mobiles {
-                    at_mobiles.push(Value::String(m.clone()));
-                }
-                let mut at_map = serde_json::Map::new();
-                at_map.insert("atMobiles".into(), Value::Array(at_mobiles));
-                at_map.insert("isAtAll".into(), Value::Bool(dingtalk_message.at_all));
-
-                m.insert("at".into(), Value::Object(at_map));
-            }
-        }
-        self.send(&serde_json::to_string(&message_json)?).await
+    /// Create `DingTalk` for Slack incoming webhook, `webhook_url` is the full webhook URL
+    pub fn new_slack(webhook_url: &str) -> Self {
+        Self::with_robot(
+            DingTalkType::Slack,
+            String::new(),
+            String::new(),
+            String::new(),
+            webhook_url.into(),
+        )
     }
 
-    /// Send text message
-    pub async fn send_text(&self, text_message: &str) -> XResult<()> {
-        self.send_message(DingTalkMessage::new_text(text_message))
-            .await
+    /// Create `DingTalk` for Discord webhook, `webhook_url` is the full webhook URL
+    pub fn new_discord(webhook_url: &str) -> Self {
+        Self::with_robot(
+            DingTalkType::Discord,
+            String::new(),
+            String::new(),
+            String::new(),
+            webhook_url.into(),
+        )
+    }
+
+    /// Create `DingTalk` of robot settings, other settings are default
+    ///
+    /// Struct update syntax is not used since `DingTalk` zeroizes secrets on drop with feature
+    /// `zeroize`.
+    #[allow(clippy::field_reassign_with_default)]
+    pub(crate) fn with_robot(
+        dingtalk_type: DingTalkType,
+        default_webhook_url: String,
+        access_token: String,
+        sec_token: String,
+        direct_url: String,
+    ) -> Self {
+        let mut dingtalk = DingTalk::default();
+        dingtalk.dingtalk_type = dingtalk_type;
+        dingtalk.default_webhook_url = default_webhook_url;
+        dingtalk.access_token = access_token;
+        dingtalk.sec_token = sec_token;
+        dingtalk.direct_url = direct_url;
+        dingtalk
+    }
+
+    /// Set default webhook url
+    pub fn set_default_webhook_url(&mut self, default_webhook_url: &str) {
+        self.default_webhook_url = default_webhook_url.into();
+    }
+
+    /// Set request timeout, same as `DingTalk::set_request_timeout`
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.set_request_timeout(timeout);
+    }
+
+    /// Set request timeout, from connecting to reading the whole response,
+    /// default uses reqwest's default timeout
+    ///
+    /// Timed out request returns `DingTalkError::Timeout`, ignored on wasm32.
+    pub fn set_request_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+        self.reset_blocking_client();
+    }
+
+    /// Set connect timeout, covers DNS resolving and TCP/TLS connecting
+    ///
+    /// Timed out connecting returns `DingTalkError::ConnectTimeout`, the request timeout still
+    /// limits the whole request. Ignored on wasm32.
+    pub fn set_connect_timeout(&mut self, connect_timeout: Duration) -> XResult<()> {
+        self.connect_timeout = Some(connect_timeout);
+        self.client = self.build_client()?;
+        self.reset_blocking_client();
+        Ok(())
+    }
+
+    /// Set HTTP or HTTPS proxy, e.g. `http://proxy.corp:3128`, unsupported on wasm32
+    ///
+    /// Proxy is checked by both async and blocking clients here, so a bad proxy never fails
+    /// a send. Blocking sends by ureq of feature `minimal-http` support HTTP proxy only.
+    pub fn set_proxy(&mut self, proxy: &str) -> XResult<()> {
+        let proxy_url = reqwest::Url::parse(proxy)
+            .map_err(|e| DingTalkError::Config(format!("Proxy URL error: {}, {}", proxy, e)))?;
+        if !matches!(proxy_url.scheme(), "http" | "https") || proxy_url.host_str().is_none() {
+            return Err(DingTalkError::Config(format!(
+                "Proxy URL error: {}, http or https proxy required",
+                proxy
+            )));
+        }
+        #[cfg(all(feature = "minimal-http", not(feature = "blocking")))]
+        ureq_transport::parse_proxy(proxy)?;
+        self.proxy = Some(proxy.into());
+        self.client = self.build_client()?;
+        self.reset_blocking_client();
+        Ok(())
+    }
+
+    /// Set `User-Agent` header of webhook and upload requests, default is `dingtalk-rs/<version>`
+    ///
+    /// Custom `Transport` installed by `DingTalk::with_transport` sets its own headers.
+    pub fn set_user_agent(&mut self, user_agent: &str) -> XResult<()> {
+        if let Err(e) = reqwest::header::HeaderValue::from_str(user_agent) {
+            return Err(DingTalkError::Config(format!(
+                "User agent is not a valid header value: {:?}, {}",
+                user_agent, e
+            )));
+        }
+        self.user_agent = Some(user_agent.into());
+        self.reset_blocking_client();
+        Ok(())
+    }
+
+    /// `User-Agent` header of requests, see `DingTalk::set_user_agent`
+    pub fn user_agent(&self) -> &str {
+        self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT)
+    }
+
+    /// Blocking HTTP client is rebuilt on next blocking send, after its settings are changed
+    fn reset_blocking_client(&mut self) {
+        #[cfg(any(feature = "blocking", feature = "minimal-http"))]
+        {
+            self.blocking_client = Default::default();
+        }
+    }
+
+    /// Build HTTP client with proxy
+    #[cfg(not(target_arch = "wasm32"))]
+    fn build_client(&self) -> XResult<reqwest::Client> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Build HTTP client of fetch API, which has no proxy or connect timeout
+    #[cfg(target_arch = "wasm32")]
+    fn build_client(&self) -> XResult<reqwest::Client> {
+        if self.proxy.is_some() {
+            return Err(DingTalkError::Unsupported(
+                "Proxy is unsupported on wasm32".into(),
+            ));
+        }
+        Ok(reqwest::Client::builder().build()?)
+    }
+
+    /// Dry run when `dry_run` is true, sends build signed URL and body but make no HTTP request
+    ///
+    /// Message validation and signing errors are still returned. Rate limit and retry are
+    /// skipped, response is `errcode` 0 with `errmsg` `"dry run"` and `raw` is
+    /// `{"dry_run": true, "body": <request body>}`. With feature `tracing`, the body is logged
+    /// as `info` event.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Retry send on network errors, 5xx responses and rate limited, at most `max_attempts` times
+    ///
+    /// Delay starts from `initial_delay` and doubles after each attempt, capped at 30 seconds,
+    /// rate limited response with `Retry-After` waits that long instead, also capped at 30 seconds.
+    /// Default sends only once.
+    pub fn with_retry(mut self, max_attempts: u32, initial_delay: Duration) -> Self {
+        self.retry_max_attempts = max_attempts;
+        self.retry_initial_delay = initial_delay;
+        self
+    }
+
+    /// Limit send to at most `max_count` messages in every `per` duration, e.g. `rate_limit(20, Duration::from_secs(60))`
+    ///
+    /// Sends exceed the limit wait until a slot is available, the limiter is shared by all
+    /// tasks using this `DingTalk` instance. Every attempt of `DingTalk::with_retry` takes a
+    /// slot, time waited by a send is `DingTalkResponse::rate_limit_delay`.
+    pub fn rate_limit(mut self, max_count: usize, per: Duration) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(max_count, per)));
+        self
+    }
+
+    /// Check message by `DingTalkMessage::validate` before sending, invalid message returns
+    /// `DingTalkError::Validation` without request. Default is off.
+    pub fn strict_validation(mut self, strict_validation: bool) -> Self {
+        self.strict_validation = strict_validation;
+        self
+    }
+
+    /// Check message fields by `DingTalkType::length_limits` of `dingtalk_type` before sending,
+    /// too long field returns `DingTalkError::TooLong` without request. Default is off.
+    ///
+    /// Fields are checked as sent, e.g. markdown content with appended `@mobile` mentions.
+    pub fn enforce_length_limits(mut self, enforce_length_limits: bool) -> Self {
+        self.enforce_length_limits = enforce_length_limits;
+        self
+    }
+
+    /// Split text message exceeds `text_content` of `DingTalkType::length_limits` and send
+    /// chunks in order, see `DingTalkMessage::split_text`. Default is off.
+    ///
+    /// Mentions are only in the last chunk, sending stops at the first failed chunk and response
+    /// of the last chunk is returned. Chunks wait for `DingTalk::rate_limit` like other sends,
+    /// use `DingTalk::send_text_chunked` for pacing.
+    pub fn auto_split(mut self, auto_split: bool) -> Self {
+        self.auto_split = auto_split;
+        self
+    }
+
+    /// Cut text and markdown content exceeds `DingTalkType::length_limits` to fit, with
+    /// ellipsis suffix appended. Default is off.
+    ///
+    /// Content is cut on grapheme boundary by bytes, response `truncated_bytes` tells how many
+    /// bytes were cut. Text split by `DingTalk::auto_split` is not truncated.
+    pub fn truncate_to_limit(mut self, truncate_to_limit: bool) -> Self {
+        self.truncate_to_limit = truncate_to_limit;
+        self
+    }
+
+    /// Set suffix of truncated content, default is `…(truncated)`, see `DingTalk::truncate_to_limit`
+    pub fn truncate_ellipsis(mut self, truncate_ellipsis: &str) -> Self {
+        self.truncate_ellipsis = Some(truncate_ellipsis.into());
+        self
+    }
+
+    /// Get rate limiter, returns `None` when `DingTalk::rate_limit` is not set
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    /// Register custom `WebhookSender`, payload and endpoint URL of all sends come from it
+    ///
+    /// Retry, rate limit, timeout and proxy settings of `DingTalk` still apply.
+    pub fn with_sender<S: WebhookSender + 'static>(mut self, sender: S) -> Self {
+        self.sender = Some(Arc::new(sender));
+        self
+    }
+
+    /// Use custom HTTP transport for `DingTalk::send`, default is `ReqwestTransport`
+    ///
+    /// Timeout and proxy settings only apply to the default transport, file upload and
+    /// blocking sends always use `reqwest`.
+    pub fn with_transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Use custom time source for signing and session webhook expiry, default is `SystemClock`
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Duration since UNIX epoch of `DingTalk::with_clock` or system clock, zero when before epoch
+    pub(crate) fn since_epoch(&self) -> Duration {
+        let now = match &self.clock {
+            Some(clock) => clock.now(),
+            None => SystemClock.now(),
+        };
+        now.duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+
+    /// Session webhook of `DingTalk::from_session_webhook` can not be sent after expired
+    pub(crate) fn check_session_expired(&self) -> XResult<()> {
+        match self.session_expired_at {
+            Some(expired_at_ms)
+                if self.since_epoch().as_millis() >= expired_at_ms.max(0) as u128 =>
+            {
+                Err(DingTalkError::SessionExpired { expired_at_ms })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Send DingTalk message
+    ///
+    /// 1. Create DingTalk JSON message
+    /// 2. POST JSON message to DingTalk server
+    ///
+    /// With feature `tracing`, send is in span `dingtalk.send_message` with field `message_type`.
+    pub async fn send_message(&self, dingtalk_message: DingTalkMessage) -> XResult<()> {
+        self.send_message_ref(&dingtalk_message).await
+    }
+
+    /// Send DingTalk message by reference, same as `DingTalk::send_message`
+    ///
+    /// Message is not consumed, so it can be sent again after a failure.
+    pub async fn send_message_ref(&self, dingtalk_message: &DingTalkMessage) -> XResult<()> {
+        self.send_message_with_response_ref(dingtalk_message)
+            .await
+            .map(|_| ())
+    }
+
+    /// Send DingTalk message, returns parsed response
+    ///
+    /// Same as `DingTalk::send_message`, response `errcode` and `errmsg` are kept for logging.
+    pub async fn send_message_with_response(
+        &self,
+        dingtalk_message: DingTalkMessage,
+    ) -> XResult<DingTalkResponse> {
+        self.send_message_with_response_ref(&dingtalk_message).await
+    }
+
+    /// Send DingTalk message by reference, returns parsed response
+    pub async fn send_message_with_response_ref(
+        &self,
+        dingtalk_message: &DingTalkMessage,
+    ) -> XResult<DingTalkResponse> {
+        if self.auto_split {
+            if let Some(chunks) = self.split_message(dingtalk_message) {
+                return self.send_chunks(chunks, None).await;
+            }
+        }
+        let (dingtalk_message, truncated_bytes) = self.truncate_message(dingtalk_message);
+        let mut response = self.send_single_message(&dingtalk_message).await?;
+        response.truncated_bytes = truncated_bytes;
+        Ok(response)
+    }
+
+    async fn send_single_message(
+        &self,
+        dingtalk_message: &DingTalkMessage,
+    ) -> XResult<DingTalkResponse> {
+        let json_message = self.message_json(dingtalk_message)?;
+        let send = self.send_with_response(&json_message);
+        #[cfg(feature = "tracing")]
+        let send = tracing::Instrument::instrument(
+            send,
+            tracing::info_span!(
+                "dingtalk.send_message",
+                message_type = ?dingtalk_message.message_type
+            ),
+        );
+        send.await
+    }
+
+    /// Send messages in order, sleeps `pacing` between sends
+    ///
+    /// Returns result of each message in the same order, failed message does not stop
+    /// the following ones, see `DingTalk::send_messages_stop_on_error`.
+    pub async fn send_messages(
+        &self,
+        dingtalk_messages: Vec<DingTalkMessage>,
+        pacing: Option<Duration>,
+    ) -> Vec<XResult<()>> {
+        self.send_messages_paced(dingtalk_messages, pacing, false)
+            .await
+    }
+
+    /// Send messages in order like `DingTalk::send_messages`, but stop at the first failed
+    /// message, returned results end with the error and messages after it are not sent
+    pub async fn send_messages_stop_on_error(
+        &self,
+        dingtalk_messages: Vec<DingTalkMessage>,
+        pacing: Option<Duration>,
+    ) -> Vec<XResult<()>> {
+        self.send_messages_paced(dingtalk_messages, pacing, true)
+            .await
+    }
+
+    async fn send_messages_paced(
+        &self,
+        dingtalk_messages: Vec<DingTalkMessage>,
+        pacing: Option<Duration>,
+        stop_on_error: bool,
+    ) -> Vec<XResult<()>> {
+        let mut results = Vec::with_capacity(dingtalk_messages.len());
+        for (i, dingtalk_message) in dingtalk_messages.into_iter().enumerate() {
+            if i > 0 {
+                if let Some(pacing) = pacing {
+                    tokio::time::sleep(pacing).await;
+                }
+            }
+            let result = self.send_message_ref(&dingtalk_message).await;
+            let failed = result.is_err();
+            results.push(result);
+            if stop_on_error && failed {
+                break;
+            }
+        }
+        results
+    }
+
+    /// Create JSON payload of message, `send_message` sends exactly this payload
+    ///
+    /// Payload is created by `dingtalk_type`, or by custom `WebhookSender` when registered,
+    /// `DingTalk::strict_validation` is checked first, after `DingTalk::convert_emoji_shortcodes`
+    /// and `DingTalk::security_keyword` are applied. `DingTalk::enforce_length_limits` checks
+    /// created payload, or message fields for custom `WebhookSender`.
+    pub fn build_payload(&self, dingtalk_message: &DingTalkMessage) -> XResult<Value> {
+        let dingtalk_message = &*self.apply_emoji_shortcodes(dingtalk_message);
+        let dingtalk_message = &*self.apply_security_keyword(dingtalk_message);
+        if self.strict_validation {
+            dingtalk_message.validate()?;
+        }
+        match &self.sender {
+            Some(sender) => {
+                if self.enforce_length_limits {
+                    self.dingtalk_type.length_limits().check(dingtalk_message)?;
+                }
+                sender.build_payload(dingtalk_message)
+            }
+            None => self.message_value(dingtalk_message),
+        }
+    }
+
+    /// Create DingTalk JSON message
+    fn message_json(&self, dingtalk_message: &DingTalkMessage) -> XResult<String> {
+        Ok(serde_json::to_string(
+            &self.build_payload(dingtalk_message)?,
+        )?)
+    }
+
+    /// Create built-in robot type JSON message
+    fn message_value(&self, dingtalk_message: &DingTalkMessage) -> XResult<Value> {
+        self.check_message_supported(dingtalk_message.message_type)?;
+        validate::check_action_card_btns(dingtalk_message)?;
+        match self.dingtalk_type {
+            DingTalkType::Feishu => return self.feishu_message_json(dingtalk_message),
+            DingTalkType::Slack => return self.slack_message_json(dingtalk_message),
+            DingTalkType::Discord => return self.discord_message_json(dingtalk_message),
+            DingTalkType::DingTalk | DingTalkType::WeChatWork => {}
+        }
+        if DingTalkMessageType::Image == dingtalk_message.message_type {
+            let image_len = base64_decoded_len(&dingtalk_message.image_base64);
+            if image_len > WECHAT_WORK_IMAGE_MAX_BYTES {
+                return Err(DingTalkError::Validation(format!(
+                    "Image size {} bytes exceeds limit {} bytes",
+                    image_len, WECHAT_WORK_IMAGE_MAX_BYTES
+                )));
+            }
+        }
+        if DingTalkMessageType::News == dingtalk_message.message_type {
+            let articles_len = dingtalk_message.news_articles.len();
+            if articles_len == 0 || articles_len > WECHAT_WORK_NEWS_MAX_ARTICLES {
+                return Err(DingTalkError::Validation(format!(
+                    "News articles count {} is not in range 1 to {}",
+                    articles_len, WECHAT_WORK_NEWS_MAX_ARTICLES
+                )));
+            }
+        }
+        if DingTalkMessageType::FeedCard == dingtalk_message.message_type {
+            let links_len = dingtalk_message.feed_card_links.len();
+            if links_len == 0 || links_len > FEED_CARD_MAX_LINKS {
+                return Err(DingTalkError::Validation(format!(
+                    "Feed card links count {} is not in range 1 to {}",
+                    links_len, FEED_CARD_MAX_LINKS
+                )));
+            }
+        }
+        let mut message_json = match dingtalk_message.message_type {
+            DingTalkMessageType::Text => serde_json::to_value(InnerTextMessage {
+                msgtype: DingTalkMessageType::Text,
+                text: InnerTextMessageText {
+                    content: dingtalk_message.text_content.clone(),
+                },
+            }),
+            DingTalkMessageType::Link => serde_json::to_value(InnerLinkMessage {
+                msgtype: DingTalkMessageType::Link,
+                link: InnerLinkMessageLink {
+                    title: dingtalk_message.link_title.clone(),
+                    text: dingtalk_message.link_text.clone(),
+                    pic_url: dingtalk_message.link_pic_url.clone(),
+                    message_url: dingtalk_message.link_message_url.clone(),
+                },
+            }),
+            DingTalkMessageType::Markdown => match self.dingtalk_type {
+                DingTalkType::DingTalk => serde_json::to_value(InnerMarkdownMessage {
+                    msgtype: DingTalkMessageType::Markdown,
+                    markdown: InnerMarkdownMessageMarkdown {
+                        title: dingtalk_message.markdown_title.clone(),
+                        text: markdown_with_at_mobiles(dingtalk_message),
+                    },
+                }),
+                // WeChat Work markdown has no title, fold title into content as heading
+                DingTalkType::WeChatWork => serde_json::to_value(InnerWeChatWorkMarkdownMessage {
+                    msgtype: DingTalkMessageType::Markdown,
+                    markdown: InnerWeChatWorkMarkdownMessageMarkdown {
+                        content: if dingtalk_message.markdown_title.is_empty() {
+                            dingtalk_message.markdown_content.clone()
+                        } else {
+                            format!(
+                                "# {}\n{}",
+                                dingtalk_message.markdown_title, dingtalk_message.markdown_content
+                            )
+                        },
+                    },
+                }),
+                DingTalkType::Feishu | DingTalkType::Slack | DingTalkType::Discord => {
+                    unreachable!("message JSON of {:?} is returned above", self.dingtalk_type)
+                }
+            },
+            DingTalkMessageType::ActionCard => serde_json::to_value(InnerActionCardMessage {
+                msgtype: DingTalkMessageType::ActionCard,
+                action_card: InnerActionCardMessageActionCard {
+                    title: dingtalk_message.action_card_title.clone(),
+                    text: dingtalk_message.action_card_text.clone(),
+                    hide_avatar: dingtalk_message.action_card_hide_avatar.into(),
+                    btn_orientation: dingtalk_message.action_card_btn_orientation.into(),
+                },
+            }),
+            DingTalkMessageType::FeedCard => serde_json::to_value(InnerFeedCardMessage {
+                msgtype: DingTalkMessageType::FeedCard,
+                feed_card: InnerFeedCardMessageFeedCard {
+                    links: {
+                        let mut links: Vec<InnerFeedCardMessageFeedCardLink> = vec![];
+                        for feed_card_link in &dingtalk_message.feed_card_links {
+                            links.push(InnerFeedCardMessageFeedCardLink {
+                                title: feed_card_link.title.clone(),
+                                message_url: feed_card_link.message_url.clone(),
+                                pic_url: feed_card_link.pic_url.clone(),
+                            });
+                        }
+                        links
+                    },
+                },
+            }),
+            DingTalkMessageType::Image => serde_json::to_value(InnerWeChatWorkImageMessage {
+                msgtype: DingTalkMessageType::Image,
+                image: InnerWeChatWorkImageMessageImage {
+                    base64: dingtalk_message.image_base64.clone(),
+                    md5: dingtalk_message.image_md5.clone(),
+                },
+            }),
+            DingTalkMessageType::News => serde_json::to_value(InnerWeChatWorkNewsMessage {
+                msgtype: DingTalkMessageType::News,
+                news: InnerWeChatWorkNewsMessageNews {
+                    articles: dingtalk_message
+                        .news_articles
+                        .iter()
+                        .map(|article| InnerWeChatWorkNewsMessageArticle {
+                            title: article.title.clone(),
+                            description: article.description.clone(),
+                            url: article.url.clone(),
+                            picurl: article.pic_url.clone(),
+                        })
+                        .collect(),
+                },
+            }),
+            DingTalkMessageType::File => serde_json::to_value(InnerWeChatWorkFileMessage {
+                msgtype: DingTalkMessageType::File,
+                file: InnerWeChatWorkMedia {
+                    media_id: dingtalk_message.media_id.clone(),
+                },
+            }),
+            DingTalkMessageType::Voice => serde_json::to_value(InnerWeChatWorkVoiceMessage {
+                msgtype: DingTalkMessageType::Voice,
+                voice: InnerWeChatWorkMedia {
+                    media_id: dingtalk_message.media_id.clone(),
+                },
+            }),
+        }?;
+        if DingTalkMessageType::ActionCard == dingtalk_message.message_type {
+            if dingtalk_message.action_card_single_btn.is_some() {
+                if let Some(single_btn) = dingtalk_message.action_card_single_btn.as_ref() {
+                    message_json["actionCard"]["singleTitle"] = single_btn.title.as_str().into();
+                    message_json["actionCard"]["singleURL"] = single_btn.action_url.as_str().into();
+                };
+            } else {
+                let mut btns: Vec<InnerActionCardMessageBtn> = vec![];
+                for action_card_btn in &dingtalk_message.action_card_btns {
+                    btns.push(InnerActionCardMessageBtn {
+                        title: action_card_btn.title.clone(),
+                        action_url: action_card_btn.action_url.clone(),
+                    });
+                }
+                message_json["actionCard"]["btns"] = serde_json::to_value(btns)?;
+            }
+        }
+        let has_at = dingtalk_message.at_all
+            || !dingtalk_message.at_mobiles.is_empty()
+            || !dingtalk_message.at_user_ids.is_empty();
+        match self.dingtalk_type {
+            DingTalkType::DingTalk => {
+                if has_at {
+                    if let Some(m) = message_json.as_object_mut() {
+                        let mut at_mobiles: Vec<Value> = vec![];
+                        for m in &dingtalk_message.at_mobiles {
+                            at_mobiles.push(Value::String(m.clone()));
+                        }
+                        let mut at_map = serde_json::Map::new();
+                        at_map.insert("atMobiles".into(), Value::Array(at_mobiles));
+                        if !dingtalk_message.at_user_ids.is_empty() {
+                            let at_user_ids = dingtalk_message
+                                .at_user_ids
+                                .iter()
+                                .map(|u| Value::String(u.clone()))
+                                .collect();
+                            at_map.insert("atUserIds".into(), Value::Array(at_user_ids));
+                        }
+                        at_map.insert("isAtAll".into(), Value::Bool(dingtalk_message.at_all));
+
+                        m.insert("at".into(), Value::Object(at_map));
+                    }
+                }
+            }
+            DingTalkType::WeChatWork => {
+                // WeChat Work only supports mentions in text message: user ids in `mentioned_list`,
+                // mobiles in `mentioned_mobile_list`, and `"@all"` for at all
+                if has_at && DingTalkMessageType::Text == dingtalk_message.message_type {
+                    let mut mentioned_list = dingtalk_message.at_user_ids.clone();
+                    if dingtalk_message.at_all {
+                        mentioned_list.push("@all".into());
+                    }
+                    if !mentioned_list.is_empty() {
+                        message_json["text"]["mentioned_list"] =
+                            serde_json::to_value(mentioned_list)?;
+                    }
+                    if !dingtalk_message.at_mobiles.is_empty() {
+                        message_json["text"]["mentioned_mobile_list"] =
+                            serde_json::to_value(&dingtalk_message.at_mobiles)?;
+                    }
+                }
+            }
+            DingTalkType::Feishu | DingTalkType::Slack | DingTalkType::Discord => {
+                unreachable!("message JSON of {:?} is returned above", self.dingtalk_type)
+            }
+        }
+        if self.enforce_length_limits {
+            self.dingtalk_type
+                .length_limits()
+                .check_payload(&message_json)?;
+        }
+        Ok(message_json)
+    }
+
+    /// Check message type is supported by robot type
+    fn check_message_supported(&self, message_type: DingTalkMessageType) -> XResult<()> {
+        let supported = match self.dingtalk_type {
+            DingTalkType::DingTalk => !matches!(
+                message_type,
+                DingTalkMessageType::Image
+                    | DingTalkMessageType::News
+                    | DingTalkMessageType::File
+                    | DingTalkMessageType::Voice
+            ),
+            DingTalkType::WeChatWork => true,
+            DingTalkType::Feishu => matches!(
+                message_type,
+                DingTalkMessageType::Text | DingTalkMessageType::Markdown
+            ),
+            DingTalkType::Slack => matches!(
+                message_type,
+                DingTalkMessageType::Text
+                    | DingTalkMessageType::Markdown
+                    | DingTalkMessageType::Link
+                    | DingTalkMessageType::ActionCard
+            ),
+            DingTalkType::Discord => matches!(
+                message_type,
+                DingTalkMessageType::Text
+                    | DingTalkMessageType::Markdown
+                    | DingTalkMessageType::Link
+                    | DingTalkMessageType::FeedCard
+            ),
+        };
+        if supported {
+            Ok(())
+        } else {
+            Err(DingTalkError::Unsupported(format!(
+                "Message type {:?} is unsupported for robot type {:?}",
+                message_type, self.dingtalk_type
+            )))
+        }
+    }
+
+    /// Send text message
+    pub async fn send_text(&self, text_message: &str) -> XResult<()> {
+        self.send_message(DingTalkMessage::new_text(text_message))
+            .await
+    }
+
+    /// Send text message, text exceeds `text_content` of `DingTalkType::length_limits` is split
+    /// and sent in order, sleeps `pacing` between chunks, see `DingTalk::auto_split`
+    pub async fn send_text_chunked(
+        &self,
+        text_message: &str,
+        pacing: Option<Duration>,
+    ) -> XResult<()> {
+        let dingtalk_message = DingTalkMessage::new_text(text_message);
+        match self.split_message(&dingtalk_message) {
+            Some(chunks) => self.send_chunks(chunks, pacing).await.map(|_| ()),
+            None => self.send_message_ref(&dingtalk_message).await,
+        }
+    }
+
+    /// Truncate text or markdown content by `DingTalk::truncate_to_limit`, returns bytes cut,
+    /// message is cloned only when truncated
+    pub(crate) fn truncate_message<'a>(
+        &self,
+        dingtalk_message: &'a DingTalkMessage,
+    ) -> (Cow<'a, DingTalkMessage>, Option<usize>) {
+        match self.truncated_content(dingtalk_message) {
+            Some((truncated, truncated_bytes)) => {
+                let mut truncated_message = dingtalk_message.clone();
+                match truncated_message.message_type {
+                    DingTalkMessageType::Text => truncated_message.text_content = truncated,
+                    _ => truncated_message.markdown_content = truncated,
+                }
+                (Cow::Owned(truncated_message), Some(truncated_bytes))
+            }
+            None => (Cow::Borrowed(dingtalk_message), None),
+        }
+    }
+
+    /// Truncated text or markdown content and bytes cut, `None` when not truncated
+    fn truncated_content(&self, dingtalk_message: &DingTalkMessage) -> Option<(String, usize)> {
+        if !self.truncate_to_limit {
+            return None;
+        }
+        let limits = self.dingtalk_type.length_limits();
+        let (content, max_bytes) = match dingtalk_message.message_type {
+            DingTalkMessageType::Text => (&dingtalk_message.text_content, limits.text_content?),
+            DingTalkMessageType::Markdown => {
+                (&dingtalk_message.markdown_content, limits.markdown_content?)
+            }
+            _ => return None,
+        };
+        let ellipsis = self
+            .truncate_ellipsis
+            .as_deref()
+            .unwrap_or(limits::DEFAULT_TRUNCATE_ELLIPSIS);
+        limits::truncate_text(content, max_bytes, ellipsis)
+    }
+
+    /// Split text message exceeds length limit, mentions are kept in the last chunk
+    fn split_message(&self, dingtalk_message: &DingTalkMessage) -> Option<Vec<DingTalkMessage>> {
+        let max_bytes = self.dingtalk_type.length_limits().text_content?;
+        if dingtalk_message.message_type != DingTalkMessageType::Text
+            || dingtalk_message.text_content.len() <= max_bytes
+        {
+            return None;
+        }
+        let texts = split::split_text(&dingtalk_message.text_content, max_bytes);
+        let count = texts.len();
+        let chunks = texts
+            .into_iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let mut chunk = dingtalk_message.clone();
+                chunk.text_content = text;
+                if i + 1 < count {
+                    chunk.at_all = false;
+                    chunk.at_mobiles.clear();
+                    chunk.at_user_ids.clear();
+                }
+                chunk
+            })
+            .collect();
+        Some(chunks)
+    }
+
+    /// Send chunks in order and stop at the first failure, returns response of the last chunk
+    async fn send_chunks(
+        &self,
+        chunks: Vec<DingTalkMessage>,
+        pacing: Option<Duration>,
+    ) -> XResult<DingTalkResponse> {
+        let mut response = None;
+        for (i, chunk) in chunks.iter().enumerate() {
+            if i > 0 {
+                if let Some(pacing) = pacing {
+                    tokio::time::sleep(pacing).await;
+                }
+            }
+            response = Some(self.send_single_message(chunk).await?);
+        }
+        response.ok_or_else(|| DingTalkError::Validation("No chunk to send".into()))
+    }
+
+    /// Send text message rendered by `DingTalkMessage::render_template`
+    pub async fn send_text_template(
+        &self,
+        template: &str,
+        vars: &HashMap<&str, &str>,
+    ) -> XResult<()> {
+        self.send_text(&DingTalkMessage::render_template(template, vars)?)
+            .await
+    }
+
+    /// Send text message and at mobiles, at all when `at_all` is true
+    ///
+    /// Empty `mobiles` and `at_all` false is same as `DingTalk::send_text`.
+    pub async fn send_text_at(
+        &self,
+        text_message: &str,
+        mobiles: &[&str],
+        at_all: bool,
+    ) -> XResult<()> {
+        self.send_message(with_at(
+            DingTalkMessage::new_text(text_message),
+            mobiles,
+            at_all,
+        ))
+        .await
     }
 
     /// Send markdown message
@@ -422,6 +1709,25 @@ impl DingTalk {
             .await
     }
 
+    /// Send markdown message and at mobiles, at all when `at_all` is true
+    ///
+    /// DingTalk only notifies mobiles which are in markdown text, so missing `@mobile` is
+    /// appended to the text. Empty `mobiles` and `at_all` false is same as `DingTalk::send_markdown`.
+    pub async fn send_markdown_at(
+        &self,
+        title: &str,
+        text: &str,
+        mobiles: &[&str],
+        at_all: bool,
+    ) -> XResult<()> {
+        self.send_message(with_at(
+            DingTalkMessage::new_markdown(title, text),
+            mobiles,
+            at_all,
+        ))
+        .await
+    }
+
     /// Send link message
     pub async fn send_link(
         &self,
@@ -439,42 +1745,408 @@ impl DingTalk {
         .await
     }
 
+    /// Send WeChat Work file message, `media_id` is from `DingTalk::upload_wechat_media`
+    pub async fn send_wechat_file(&self, media_id: &str) -> XResult<()> {
+        self.send_message(DingTalkMessage::new_wechat_file(media_id))
+            .await
+    }
+
+    /// Send WeChat Work template card message, card is validated before send
+    pub async fn send_template_card(&self, template_card: &WeChatTemplateCard) -> XResult<()> {
+        if !matches!(self.dingtalk_type, DingTalkType::WeChatWork) {
+            return Err(DingTalkError::Unsupported(format!(
+                "Template card is unsupported for robot type {:?}",
+                self.dingtalk_type
+            )));
+        }
+        template_card.validate()?;
+        let message_json = serde_json::json!({
+            "msgtype": "template_card",
+            "template_card": template_card,
+        });
+        self.send(&serde_json::to_string(&message_json)?).await
+    }
+
+    /// Send WeChat Work voice message, `media_id` is from `DingTalk::upload_wechat_voice`
+    pub async fn send_wechat_voice(&self, media_id: &str) -> XResult<()> {
+        self.send_message(DingTalkMessage::new_wechat_voice(media_id))
+            .await
+    }
+
+    /// Upload voice to WeChat Work, returns `media_id`
+    ///
+    /// Voice must be AMR format, at most 2MB and 60 seconds.
+    pub async fn upload_wechat_voice(
+        &self,
+        file_name: &str,
+        voice_bytes: Vec<u8>,
+    ) -> XResult<String> {
+        if !matches!(self.dingtalk_type, DingTalkType::WeChatWork) {
+            return Err(DingTalkError::Unsupported(format!(
+                "Upload media is unsupported for robot type {:?}",
+                self.dingtalk_type
+            )));
+        }
+        let seconds = amr_duration_seconds(&voice_bytes)?;
+        if seconds > WECHAT_WORK_VOICE_MAX_SECONDS {
+            return Err(DingTalkError::Validation(format!(
+                "Voice duration {} seconds exceeds limit {} seconds",
+                seconds, WECHAT_WORK_VOICE_MAX_SECONDS
+            )));
+        }
+        self.upload_wechat_media_type("voice", file_name, voice_bytes, WECHAT_WORK_VOICE_MAX_BYTES)
+            .await
+    }
+
+    /// Upload file then send WeChat Work file message, not available on wasm32
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn send_file(&self, file_path: &str) -> XResult<()> {
+        let file_name = PathBuf::from(file_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| file_path.into());
+        let file_bytes = fs::read(file_path)?;
+        let media_id = self.upload_wechat_media(&file_name, file_bytes).await?;
+        self.send_wechat_file(&media_id).await
+    }
+
+    /// Upload file to WeChat Work, returns `media_id`, file is at most 20MB
+    ///
+    /// `DingTalk::default_headers` and interceptors apply, interceptors get empty body since
+    /// file is sent as multipart form.
+    ///
+    /// Document https://developer.work.weixin.qq.com/document/path/91770
+    pub async fn upload_wechat_media(
+        &self,
+        file_name: &str,
+        file_bytes: Vec<u8>,
+    ) -> XResult<String> {
+        self.upload_wechat_media_type("file", file_name, file_bytes, WECHAT_WORK_FILE_MAX_BYTES)
+            .await
+    }
+
+    async fn upload_wechat_media_type(
+        &self,
+        media_type: &str,
+        file_name: &str,
+        file_bytes: Vec<u8>,
+        max_bytes: usize,
+    ) -> XResult<String> {
+        if !matches!(self.dingtalk_type, DingTalkType::WeChatWork) {
+            return Err(DingTalkError::Unsupported(format!(
+                "Upload media is unsupported for robot type {:?}",
+                self.dingtalk_type
+            )));
+        }
+        if file_bytes.is_empty() || file_bytes.len() > max_bytes {
+            return Err(DingTalkError::Validation(format!(
+                "Media size {} bytes is not in range 1 to {} bytes",
+                file_bytes.len(),
+                max_bytes
+            )));
+        }
+        let upload_url = format!(
+            "{}?key={}&type={}",
+            self.wechat_upload_media_url(),
+            urlencoding::encode(&self.access_token),
+            media_type
+        );
+        let form = reqwest::multipart::Form::new().part(
+            "media",
+            reqwest::multipart::Part::bytes(file_bytes).file_name(file_name.to_owned()),
+        );
+        let mut request_parts = self.intercept(upload_url, String::new())?;
+        #[allow(unused_mut)]
+        let mut request = self
+            .client
+            .post(&request_parts.url)
+            .header(USER_AGENT, self.user_agent())
+            .headers(request_parts.header_map()?)
+            .multipart(form);
+        wipe(&mut request_parts.url);
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request.send().await?;
+
+        let status = response.status().as_u16();
+        let body = if is_success_status(status) {
+            response.text().await?
+        } else {
+            transport::read_error_body(response).await
+        };
+        check_response_status(status, &body)?;
+        let upload_response = check_response_body(&body)?;
+        match upload_response.raw["media_id"].as_str() {
+            Some(media_id) => Ok(media_id.to_owned()),
+            None => Err(DingTalkError::Server {
+                errcode: -1,
+                errmsg: format!("No media_id in response: {}", body),
+            }),
+        }
+    }
+
+    /// WeChat Work upload media URL: `.../webhook/send` -> `.../webhook/upload_media`
+    fn wechat_upload_media_url(&self) -> String {
+        let webhook_url = self
+            .default_webhook_url
+            .split('?')
+            .next()
+            .unwrap_or_default();
+        match webhook_url.strip_suffix("/send") {
+            Some(prefix) => format!("{}/upload_media", prefix),
+            None => webhook_url.to_owned(),
+        }
+    }
+
+    /// Send JSON value, e.g. message type not modeled by `DingTalkMessage`, see `DingTalk::send`
+    ///
+    /// Value must be JSON object, with `msgtype` for DingTalk and WeChat Work, or `msg_type`
+    /// for Feishu.
+    pub async fn send_value(&self, json_value: &Value) -> XResult<()> {
+        self.send_value_with_response(json_value).await.map(|_| ())
+    }
+
+    /// Send JSON value, returns parsed response, see `DingTalk::send_value`
+    pub async fn send_value_with_response(&self, json_value: &Value) -> XResult<DingTalkResponse> {
+        self.check_json_value(json_value)?;
+        self.send_with_response(&serde_json::to_string(json_value)?)
+            .await
+    }
+
+    fn check_json_value(&self, json_value: &Value) -> XResult<()> {
+        let json_object = match json_value.as_object() {
+            Some(json_object) => json_object,
+            None => {
+                return Err(DingTalkError::Validation(
+                    "JSON message must be object".to_owned(),
+                ))
+            }
+        };
+        let type_key = match self.dingtalk_type {
+            DingTalkType::DingTalk | DingTalkType::WeChatWork => "msgtype",
+            DingTalkType::Feishu => "msg_type",
+            DingTalkType::Slack | DingTalkType::Discord => return Ok(()),
+        };
+        match json_object.get(type_key) {
+            Some(Value::String(message_type)) if !message_type.is_empty() => Ok(()),
+            _ => Err(DingTalkError::Validation(format!(
+                "JSON message requires {}",
+                type_key
+            ))),
+        }
+    }
+
     /// Direct send JSON message
+    ///
+    /// Both DingTalk and WeChat Work reply HTTP 200 with body like
+    /// `{"errcode":310000,"errmsg":"sign not match"}` when the message is rejected,
+    /// so a non-zero `errcode` is treated as error. Non-JSON bodies (e.g. from a
+    /// custom `direct_url`) only check the HTTP status.
+    ///
+    /// Waits for `DingTalk::rate_limit` and retries according to `DingTalk::with_retry`.
+    ///
+    /// With feature `tracing`, send is in span `dingtalk.send` with fields `robot_type`,
+    /// `payload_bytes`, `status`, `errcode`, `attempts` and `elapsed_ms`, retryable
+    /// failures are logged as `warn` events. Tokens and signed URL are never recorded.
     pub async fn send(&self, json_message: &str) -> XResult<()> {
-        let client = reqwest::Client::new();
-        let response = match client
-            .post(&self.generate_signed_url()?)
-            .header(CONTENT_TYPE, APPLICATION_JSON_UTF8)
-            .body(json_message.as_bytes().to_vec())
-            .send()
-            .await
+        self.send_with_response(json_message).await.map(|_| ())
+    }
+
+    /// Direct send JSON message, returns parsed response, see `DingTalk::send`
+    pub async fn send_with_response(&self, json_message: &str) -> XResult<DingTalkResponse> {
+        self.check_session_expired()?;
+        if self.dry_run {
+            return self.dry_run_response(json_message);
+        }
+        let dedup_key = match self.dedup_check(json_message) {
+            DedupOutcome::Send { key, summaries } => {
+                for summary in summaries {
+                    if let Ok(summary_json) =
+                        self.message_json(&DingTalkMessage::new_text(&summary))
+                    {
+                        let _ = self.send_limited(&summary_json).await;
+                    }
+                }
+                key
+            }
+            DedupOutcome::Suppressed(response) => return Ok(response),
+        };
+        let result = self.send_limited(json_message).await;
+        if result.is_err() {
+            self.dedup_forget(dedup_key);
+        }
+        result
+    }
+
+    /// Send with retries by `DingTalk::with_retry`, every attempt waits for rate limiter
+    async fn send_limited(&self, json_message: &str) -> XResult<DingTalkResponse> {
+        #[cfg(feature = "tracing")]
         {
-            Ok(r) => r,
-            Err(e) => {
-                return Err(Box::new(Error::new(
-                    ErrorKind::Other,
-                    format!("Unknown error: {}", e),
-                )) as Box<dyn std::error::Error>);
+            let span = trace::send_span(self.dingtalk_type, json_message.len());
+            let started = std::time::Instant::now();
+            let mut attempts = 0_u32;
+            let result = tracing::Instrument::instrument(
+                self.send_with_retry(json_message, &mut attempts),
+                span.clone(),
+            )
+            .await;
+            trace::record_send(&span, &result, attempts, started);
+            result
+        }
+        #[cfg(not(feature = "tracing"))]
+        self.send_with_retry(json_message, &mut 0).await
+    }
+
+    async fn send_with_retry(
+        &self,
+        json_message: &str,
+        attempts: &mut u32,
+    ) -> XResult<DingTalkResponse> {
+        let max_attempts = self.retry_max_attempts.max(1);
+        let mut delay = self.retry_initial_delay;
+        let mut rate_limit_delay = Duration::ZERO;
+        loop {
+            *attempts += 1;
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limit_delay += rate_limiter.acquire().await;
+            }
+            let started = clock::system_now();
+            let mut status = None;
+            let result = self.send_once(json_message, &mut status).await;
+            self.call_send_hooks(json_message, *attempts, started, status, &result);
+            match result {
+                Ok(mut response) => {
+                    if !rate_limit_delay.is_zero() {
+                        response.rate_limit_delay = Some(rate_limit_delay);
+                    }
+                    return Ok(response);
+                }
+                Err(e) if *attempts < max_attempts && e.is_retryable() => {
+                    let wait = retry_wait(&e, delay);
+                    #[cfg(feature = "tracing")]
+                    trace::retry_failure(*attempts, &e, wait);
+                    tokio::time::sleep(wait).await;
+                    delay = (delay * 2).min(RETRY_MAX_DELAY);
+                }
+                Err(e) => {
+                    #[cfg(feature = "tracing")]
+                    trace::final_failure(*attempts, &e);
+                    if *attempts > 1 {
+                        return Err(DingTalkError::Retry {
+                            attempts: *attempts,
+                            source: Box::new(e),
+                        });
+                    }
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Send once, `status` is set when server replied
+    async fn send_once(
+        &self,
+        json_message: &str,
+        status: &mut Option<u16>,
+    ) -> XResult<DingTalkResponse> {
+        let url = SecretBuffer(self.endpoint_url()?);
+        let body = self.request_body(json_message)?;
+        let mut request = self.intercept(url.to_string(), body)?;
+        let response = match &self.transport {
+            Some(transport) => transport.post_request(&request).await,
+            None => {
+                ReqwestTransport::new(self.client.clone())
+                    .timeout(self.timeout)
+                    .user_agent(Some(self.user_agent()))
+                    .post_request(&request)
+                    .await
             }
         };
+        wipe(&mut request.url);
+        let response = response?;
 
-        match response.status().as_u16() {
-            200_u16 => Ok(()),
-            _ => Err(Box::new(Error::new(
-                ErrorKind::Other,
-                format!("Unknown status: {}", response.status().as_u16()),
-            )) as Box<dyn std::error::Error>),
+        *status = Some(response.status);
+        #[cfg(feature = "tracing")]
+        trace::record_status(response.status);
+        check_throttled(
+            response.status,
+            &response.body,
+            response.header("Retry-After"),
+        )?;
+        check_response_status(response.status, &response.body)?;
+        check_response_body(&response.body)
+    }
+
+    /// Build URL and body without sending, see `DingTalk::dry_run`
+    pub(crate) fn dry_run_response(&self, json_message: &str) -> XResult<DingTalkResponse> {
+        self.endpoint_url()?;
+        let body = self.request_body(json_message)?;
+        #[cfg(feature = "tracing")]
+        trace::dry_run(self.dingtalk_type, &body);
+        let body = serde_json::from_str(&body).unwrap_or(Value::String(body));
+        Ok(DingTalkResponse {
+            errcode: 0,
+            errmsg: "dry run".to_owned(),
+            raw: serde_json::json!({ "dry_run": true, "body": body }),
+            truncated_bytes: None,
+            rate_limit_delay: None,
+        })
+    }
+
+    /// Request body, Feishu signs in body instead of URL, custom `WebhookSender` body is unchanged
+    fn request_body(&self, json_message: &str) -> XResult<String> {
+        if self.sender.is_some() {
+            return Ok(json_message.to_owned());
+        }
+        match self.dingtalk_type {
+            DingTalkType::Feishu if !self.sec_token.is_empty() => {
+                self.feishu_signed_body(json_message)
+            }
+            _ => Ok(json_message.to_owned()),
         }
     }
 
     /// Generate signed dingtalk webhook URL
+    ///
+    /// Feishu webhook URL is `default_webhook_url` followed by access token, sign is in body.
+    /// Slack and Discord webhook URL is not signed.
     pub fn generate_signed_url(&self) -> XResult<String> {
+        let mut signed_url = self.webhook_url();
+        if self.direct_url.is_empty()
+            && !self.sec_token.is_empty()
+            && matches!(
+                self.dingtalk_type,
+                DingTalkType::DingTalk | DingTalkType::WeChatWork
+            )
+        {
+            let timestamp = self.since_epoch().as_millis();
+            signed_url.push('&');
+            signed_url.push_str(&sign::signed_query(&self.sec_token, timestamp)?);
+        }
+        Ok(signed_url)
+    }
+
+    /// Webhook URL with access token, before signing
+    fn webhook_url(&self) -> String {
         if !self.direct_url.is_empty() {
-            return Ok(self.direct_url.clone());
+            return self.direct_url.clone();
+        }
+        if let DingTalkType::Slack | DingTalkType::Discord = self.dingtalk_type {
+            return self.default_webhook_url.clone();
         }
         let mut signed_url = String::with_capacity(1024);
         signed_url.push_str(&self.default_webhook_url);
 
+        if let DingTalkType::Feishu = self.dingtalk_type {
+            if !signed_url.ends_with('/') {
+                signed_url.push('/');
+            }
+            signed_url.push_str(&urlencoding::encode(&self.access_token));
+            return signed_url;
+        }
+
         if self.default_webhook_url.ends_with('?') {
             // Just Ok
         } else if self.default_webhook_url.contains('?') {
@@ -488,41 +2160,204 @@ impl DingTalk {
         match self.dingtalk_type {
             DingTalkType::DingTalk => signed_url.push_str("access_token="),
             DingTalkType::WeChatWork => signed_url.push_str("key="),
+            DingTalkType::Feishu | DingTalkType::Slack | DingTalkType::Discord => {}
         }
         signed_url.push_str(&urlencoding::encode(&self.access_token));
+        signed_url
+    }
+}
 
-        if !self.sec_token.is_empty() {
-            let timestamp = &format!(
-                "{}",
-                SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis()
-            );
-            let timestamp_and_secret = &format!("{}\n{}", timestamp, self.sec_token);
-            let hmac_sha256 = base64::encode(
-                &calc_hmac_sha256(self.sec_token.as_bytes(), timestamp_and_secret.as_bytes())?[..],
-            );
-
-            signed_url.push_str("&timestamp=");
-            signed_url.push_str(timestamp);
-            signed_url.push_str("&sign=");
-            signed_url.push_str(&urlencoding::encode(&hmac_sha256));
+/// Message with at mobiles and at all
+fn with_at(
+    mut dingtalk_message: DingTalkMessage,
+    mobiles: &[&str],
+    at_all: bool,
+) -> DingTalkMessage {
+    for mobile in mobiles {
+        dingtalk_message.push_at_mobile(mobile);
+    }
+    dingtalk_message.set_at_all(at_all);
+    dingtalk_message
+}
+
+/// DingTalk markdown only notifies mobiles which appear as `@mobile` in text, append missing ones
+fn markdown_with_at_mobiles(dingtalk_message: &DingTalkMessage) -> String {
+    let mut text = dingtalk_message.markdown_content.clone();
+    for mobile in &dingtalk_message.at_mobiles {
+        let at_mobile = format!("@{}", mobile);
+        if !contains_mention(&text, &at_mobile) {
+            text.push_str("\n\n");
+            text.push_str(&at_mobile);
         }
+    }
+    text
+}
 
-        Ok(signed_url)
+/// Delay before next retry, `Retry-After` of rate limited error capped by `RETRY_MAX_DELAY`, or
+/// backoff `delay`
+fn retry_wait(e: &DingTalkError, delay: Duration) -> Duration {
+    e.retry_after()
+        .map_or(delay, |retry_after| retry_after.min(RETRY_MAX_DELAY))
+}
+
+/// Is `at_mobile` in `text` as a whole mention, `@1380000` is not in `@13800001111`
+fn contains_mention(text: &str, at_mobile: &str) -> bool {
+    text.match_indices(at_mobile).any(|(i, _)| {
+        !text[i + at_mobile.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_digit())
+    })
+}
+
+/// Parse robot type name, unknown name is DingTalk
+fn parse_dingtalk_type(type_str: &str) -> DingTalkType {
+    match type_str.to_lowercase().as_str() {
+        "wechat" | "wechatwork" | "wecom" => DingTalkType::WeChatWork,
+        "feishu" | "lark" => DingTalkType::Feishu,
+        "slack" => DingTalkType::Slack,
+        "discord" => DingTalkType::Discord,
+        _ => DingTalkType::DingTalk,
     }
 }
 
+/// Read config file as JSON value by extension, returns value, format name and file content
+#[cfg(not(target_arch = "wasm32"))]
+fn read_config_file(f: &str) -> XResult<(Value, &'static str, String)> {
+    let f_path_buf = home::resolve_home_path(f)?;
+    let ext = f_path_buf
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let f_content = fs::read_to_string(&f_path_buf)?;
+    let (json_value, format) = match ext.as_str() {
+        "toml" => (parse_toml(&f_content)?, "TOML"),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => (parse_yaml(&f_content)?, "YAML"),
+        #[cfg(not(feature = "yaml"))]
+        "yaml" | "yml" => {
+            return Err(DingTalkError::Unsupported(format!(
+                "YAML config requires feature `yaml`: {}",
+                f_path_buf.display()
+            )))
+        }
+        _ => (serde_json::from_str(&f_content)?, "JSON"),
+    };
+    Ok((json_value, format, f_content))
+}
+
+fn parse_toml(toml: &str) -> XResult<Value> {
+    let toml_value: toml::Value = toml::from_str(toml)
+        .map_err(|e| DingTalkError::Config(format!("TOML format error: {}", e)))?;
+    Ok(serde_json::to_value(toml_value)?)
+}
+
+#[cfg(feature = "yaml")]
+fn parse_yaml(yaml: &str) -> XResult<Value> {
+    let yaml_value: serde_yaml::Value = serde_yaml::from_str(yaml)
+        .map_err(|e| DingTalkError::Config(format!("YAML format error: {}", e)))?;
+    Ok(serde_json::to_value(yaml_value)?)
+}
+
+/// Read environment variable `{prefix}{name}`, empty value is treated as not set
+fn read_env(prefix: &str, name: &str) -> XResult<Option<String>> {
+    let key = format!("{}{}", prefix, name);
+    match env::var(&key) {
+        Ok(value) if value.is_empty() => Ok(None),
+        Ok(value) => Ok(Some(value)),
+        Err(env::VarError::NotPresent) => Ok(None),
+        Err(e) => Err(DingTalkError::Config(format!(
+            "Read env {} error: {}",
+            key, e
+        ))),
+    }
+}
+
+/// Default webhook URL of robot type, Slack and Discord have no default URL
+fn default_webhook_url(dingtalk_type: DingTalkType) -> &'static str {
+    match dingtalk_type {
+        DingTalkType::DingTalk => DEFAULT_DINGTALK_ROBOT_URL,
+        DingTalkType::WeChatWork => DEFAULT_WECHAT_WORK_ROBOT_URL,
+        DingTalkType::Feishu => DEFAULT_FEISHU_ROBOT_URL,
+        DingTalkType::Slack | DingTalkType::Discord => "",
+    }
+}
+
+/// HTTP status 200 and 204(Discord webhook) are success
+fn is_success_status(status: u16) -> bool {
+    matches!(status, 200_u16 | 204_u16)
+}
+
+/// check HTTP status, error keeps at most `ERROR_BODY_MAX_BYTES` of body
+fn check_response_status(status: u16, body: &str) -> XResult<()> {
+    if is_success_status(status) {
+        return Ok(());
+    }
+    let mut end = body.len().min(ERROR_BODY_MAX_BYTES);
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    Err(DingTalkError::Status {
+        status,
+        body: body[..end].to_owned(),
+    })
+}
+
+/// check throttled: HTTP 429 or `errcode` in `THROTTLED_ERRCODES`, `retry_after` is header `Retry-After`
+fn check_throttled(status: u16, body: &str, retry_after: Option<&str>) -> XResult<()> {
+    if status == 429 || THROTTLED_ERRCODES.contains(&DingTalkResponse::from_body(body).errcode) {
+        return Err(DingTalkError::RateLimited {
+            retry_after: retry_after.and_then(retry_after::parse_retry_after),
+        });
+    }
+    Ok(())
+}
+
+/// check `errcode`(or Feishu `code`) in response body, body which is not JSON is treated as success
+fn check_response_body(body: &str) -> XResult<DingTalkResponse> {
+    let response = DingTalkResponse::from_body(body);
+    if response.is_success() {
+        Ok(response)
+    } else {
+        Err(DingTalkError::Server {
+            errcode: response.errcode,
+            errmsg: response.errmsg,
+        })
+    }
+}
+
+/// calc AMR-NB duration in seconds(rounded up), error when not AMR format
+fn amr_duration_seconds(amr_bytes: &[u8]) -> XResult<usize> {
+    let frames = match amr_bytes.strip_prefix(AMR_HEADER) {
+        Some(frames) => frames,
+        None => {
+            return Err(DingTalkError::Validation(
+                "Voice is not AMR format".to_owned(),
+            ))
+        }
+    };
+    let mut frame_count = 0_usize;
+    let mut pos = 0_usize;
+    while pos < frames.len() {
+        let frame_type = ((frames[pos] >> 3) & 0x0f) as usize;
+        pos += 1 + AMR_FRAME_SIZES[frame_type];
+        frame_count += 1;
+    }
+    Ok((frame_count * 20).div_ceil(1000))
+}
+
+/// calc decoded bytes length of base64 string
+fn base64_decoded_len(base64_str: &str) -> usize {
+    let padding = base64_str.bytes().rev().take_while(|b| *b == b'=').count();
+    (base64_str.len() / 4 * 3).saturating_sub(padding)
+}
+
 /// calc hma_sha256 digest
 fn calc_hmac_sha256(key: &[u8], message: &[u8]) -> XResult<Vec<u8>> {
     let mut mac = match HmacSha256::new_from_slice(key) {
         Ok(m) => m,
         Err(e) => {
-            return Err(Box::new(Error::new(
-                ErrorKind::Other,
-                format!("Hmac error: {}", e),
-            )));
+            return Err(DingTalkError::Signature(format!("Hmac error: {}", e)));
         }
     };
     mac.update(message);
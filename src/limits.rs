@@ -0,0 +1,221 @@
+use serde_json::Value;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::*;
+
+const DINGTALK_TEXT_MAX_BYTES: usize = 20000;
+const WECHAT_WORK_TEXT_MAX_BYTES: usize = 2048;
+const WECHAT_WORK_MARKDOWN_MAX_BYTES: usize = 4096;
+const WECHAT_WORK_NEWS_TITLE_MAX_BYTES: usize = 128;
+const WECHAT_WORK_NEWS_DESCRIPTION_MAX_BYTES: usize = 512;
+/// Default suffix of truncated content, see `DingTalk::truncate_to_limit`
+pub(crate) const DEFAULT_TRUNCATE_ELLIPSIS: &str = "…(truncated)";
+
+/// Length limits of message fields in bytes, `None` is not limited
+///
+/// Get limits by `DingTalkType::length_limits`, enforced by `DingTalk::enforce_length_limits`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DingTalkLengthLimits {
+    pub text_content: Option<usize>,
+    pub markdown_title: Option<usize>,
+    /// Markdown content as sent, with appended `@mobile` mentions and WeChat Work title heading
+    pub markdown_content: Option<usize>,
+    pub link_title: Option<usize>,
+    pub link_text: Option<usize>,
+    pub action_card_text: Option<usize>,
+    /// Title of each feed card link
+    pub feed_card_title: Option<usize>,
+    /// Title of each news article
+    pub news_title: Option<usize>,
+    /// Description of each news article
+    pub news_description: Option<usize>,
+}
+
+impl DingTalkType {
+    /// Length limits of robot type
+    ///
+    /// * DingTalk - every field 20000 bytes
+    /// * WeChatWork - text 2048 bytes, markdown content 4096 bytes, news article title 128 bytes
+    ///   and description 512 bytes
+    /// * Feishu, Slack, Discord - not limited
+    pub fn length_limits(&self) -> DingTalkLengthLimits {
+        match self {
+            DingTalkType::DingTalk => DingTalkLengthLimits {
+                text_content: Some(DINGTALK_TEXT_MAX_BYTES),
+                markdown_title: Some(DINGTALK_TEXT_MAX_BYTES),
+                markdown_content: Some(DINGTALK_TEXT_MAX_BYTES),
+                link_title: Some(DINGTALK_TEXT_MAX_BYTES),
+                link_text: Some(DINGTALK_TEXT_MAX_BYTES),
+                action_card_text: Some(DINGTALK_TEXT_MAX_BYTES),
+                feed_card_title: Some(DINGTALK_TEXT_MAX_BYTES),
+                ..Default::default()
+            },
+            DingTalkType::WeChatWork => DingTalkLengthLimits {
+                text_content: Some(WECHAT_WORK_TEXT_MAX_BYTES),
+                markdown_content: Some(WECHAT_WORK_MARKDOWN_MAX_BYTES),
+                news_title: Some(WECHAT_WORK_NEWS_TITLE_MAX_BYTES),
+                news_description: Some(WECHAT_WORK_NEWS_DESCRIPTION_MAX_BYTES),
+                ..Default::default()
+            },
+            DingTalkType::Feishu | DingTalkType::Slack | DingTalkType::Discord => {
+                DingTalkLengthLimits::default()
+            }
+        }
+    }
+}
+
+impl DingTalkLengthLimits {
+    /// Check message fields, returns `DingTalkError::TooLong` of the first field exceeds limit
+    ///
+    /// Fields are checked as they are, `DingTalk::enforce_length_limits` checks payload of
+    /// built-in robot types instead, after mentions are appended to markdown content.
+    pub fn check(&self, dingtalk_message: &DingTalkMessage) -> XResult<()> {
+        match dingtalk_message.message_type {
+            DingTalkMessageType::Text => check_len(
+                "text_content",
+                &dingtalk_message.text_content,
+                self.text_content,
+            ),
+            DingTalkMessageType::Markdown => {
+                check_len(
+                    "markdown_title",
+                    &dingtalk_message.markdown_title,
+                    self.markdown_title,
+                )?;
+                check_len(
+                    "markdown_content",
+                    &dingtalk_message.markdown_content,
+                    self.markdown_content,
+                )
+            }
+            DingTalkMessageType::Link => {
+                check_len("link_title", &dingtalk_message.link_title, self.link_title)?;
+                check_len("link_text", &dingtalk_message.link_text, self.link_text)
+            }
+            DingTalkMessageType::ActionCard => check_len(
+                "action_card_text",
+                &dingtalk_message.action_card_text,
+                self.action_card_text,
+            ),
+            DingTalkMessageType::FeedCard => {
+                for (i, link) in dingtalk_message.feed_card_links.iter().enumerate() {
+                    check_len(
+                        &format!("feed_card_links[{}] title", i),
+                        &link.title,
+                        self.feed_card_title,
+                    )?;
+                }
+                Ok(())
+            }
+            DingTalkMessageType::News => {
+                for (i, article) in dingtalk_message.news_articles.iter().enumerate() {
+                    check_len(
+                        &format!("news_articles[{}] title", i),
+                        &article.title,
+                        self.news_title,
+                    )?;
+                    check_len(
+                        &format!("news_articles[{}] description", i),
+                        &article.description,
+                        self.news_description,
+                    )?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check DingTalk or WeChat Work JSON payload, field names are same as `check`
+    pub(crate) fn check_payload(&self, payload: &Value) -> XResult<()> {
+        let fields = [
+            ("text_content", "/text/content", self.text_content),
+            ("markdown_title", "/markdown/title", self.markdown_title),
+            ("markdown_content", "/markdown/text", self.markdown_content),
+            (
+                "markdown_content",
+                "/markdown/content",
+                self.markdown_content,
+            ),
+            ("link_title", "/link/title", self.link_title),
+            ("link_text", "/link/text", self.link_text),
+            (
+                "action_card_text",
+                "/actionCard/text",
+                self.action_card_text,
+            ),
+        ];
+        for (field, pointer, max) in fields {
+            if let Some(value) = payload.pointer(pointer).and_then(Value::as_str) {
+                check_len(field, value, max)?;
+            }
+        }
+        let items = |pointer| {
+            payload
+                .pointer(pointer)
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+        };
+        for (i, link) in items("/feedCard/links").iter().enumerate() {
+            check_value(
+                &format!("feed_card_links[{}] title", i),
+                &link["title"],
+                self.feed_card_title,
+            )?;
+        }
+        for (i, article) in items("/news/articles").iter().enumerate() {
+            check_value(
+                &format!("news_articles[{}] title", i),
+                &article["title"],
+                self.news_title,
+            )?;
+            check_value(
+                &format!("news_articles[{}] description", i),
+                &article["description"],
+                self.news_description,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Cut `text` on grapheme boundary so it is at most `max_bytes` bytes with `ellipsis` appended,
+/// returns cut text and bytes removed from `text`, `None` when `text` fits
+///
+/// `ellipsis` is omitted when it does not fit in `max_bytes` itself.
+pub(crate) fn truncate_text(
+    text: &str,
+    max_bytes: usize,
+    ellipsis: &str,
+) -> Option<(String, usize)> {
+    if text.len() <= max_bytes {
+        return None;
+    }
+    let (budget, ellipsis) = match max_bytes.checked_sub(ellipsis.len()) {
+        Some(budget) => (budget, ellipsis),
+        None => (max_bytes, ""),
+    };
+    let end = text
+        .grapheme_indices(true)
+        .map(|(i, grapheme)| i + grapheme.len())
+        .take_while(|end| *end <= budget)
+        .last()
+        .unwrap_or(0);
+    Some((format!("{}{}", &text[..end], ellipsis), text.len() - end))
+}
+
+fn check_value(field: &str, value: &Value, max: Option<usize>) -> XResult<()> {
+    check_len(field, value.as_str().unwrap_or_default(), max)
+}
+
+fn check_len(field: &str, value: &str, max: Option<usize>) -> XResult<()> {
+    match max {
+        Some(max) if value.len() > max => Err(DingTalkError::TooLong {
+            field: field.into(),
+            len: value.len(),
+            max,
+        }),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,166 @@
+use log::{LevelFilter, Log, Metadata, Record};
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    forward::{self, ForwardConfig, ForwardRecord, ForwardSender},
+    DingTalk, DingTalkError, XResult,
+};
+
+/// `log::Log` forwards records at or above level to DingTalk as markdown, enabled by feature
+/// `log-forward`
+///
+/// `log` only puts record into a channel, records are sent by a background tokio task so
+/// logging never waits for HTTP. Records within `batch_window` are sent in one message, at most
+/// `max_messages_per_minute` messages are sent. Records are dropped when channel or batch is
+/// full, count of dropped records is in the next message. Send failures are counted by
+/// `DingTalk::silent_failure_count`.
+///
+/// Records of `dingtalk`, `reqwest`, `hyper`, `h2`, `rustls` and `native_tls`, and records
+/// logged within background task are ignored so records logged while sending are not forwarded
+/// again.
+///
+/// Sample code:
+/// ```ignore
+/// DingTalkLogger::builder(dt)
+///     .level(LevelFilter::Error)
+///     .target("myapp")
+///     .init()?;
+/// log::error!("disk full");
+/// ```
+pub struct DingTalkLogger {
+    level: LevelFilter,
+    targets: Vec<String>,
+    sender: ForwardSender,
+}
+
+/// Builder of `DingTalkLogger`, created by `DingTalkLogger::builder`
+pub struct DingTalkLoggerBuilder {
+    dingtalk: Arc<DingTalk>,
+    level: LevelFilter,
+    targets: Vec<String>,
+    config: ForwardConfig,
+}
+
+impl DingTalkLogger {
+    /// Create builder, default level is `Warn`, all targets, batch window 5s, at most 20
+    /// records per message and 10 messages per minute, channel capacity 1000 records
+    pub fn builder(dingtalk: impl Into<Arc<DingTalk>>) -> DingTalkLoggerBuilder {
+        DingTalkLoggerBuilder {
+            dingtalk: dingtalk.into(),
+            level: LevelFilter::Warn,
+            targets: vec![],
+            config: ForwardConfig::default(),
+        }
+    }
+
+    /// How many records were dropped because channel or batch was full
+    pub fn dropped_count(&self) -> u64 {
+        self.sender.dropped_count()
+    }
+
+    fn is_target_enabled(&self, target: &str) -> bool {
+        !forward::is_ignored_target(target)
+            && (self.targets.is_empty()
+                || self
+                    .targets
+                    .iter()
+                    .any(|prefix| forward::is_target_of(target, prefix)))
+    }
+}
+
+impl DingTalkLoggerBuilder {
+    /// Forward records at or above `level`
+    pub fn level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Forward records of `target` and its sub modules only, e.g. `myapp` matches `myapp` and
+    /// `myapp::db`, can be called multiple times. Default forwards all targets.
+    pub fn target(mut self, target: &str) -> Self {
+        self.targets.push(target.into());
+        self
+    }
+
+    /// Records within `batch_window` after the first one are sent in one message
+    pub fn batch_window(mut self, batch_window: Duration) -> Self {
+        self.config.batch_window = batch_window;
+        self
+    }
+
+    /// At most `max_batch_records` records in one message, less than 1 is treated as 1
+    pub fn max_batch_records(mut self, max_batch_records: usize) -> Self {
+        self.config.max_batch_records = max_batch_records.max(1);
+        self
+    }
+
+    /// At most `max_messages_per_minute` messages are sent in every minute, robot allows 20
+    pub fn max_messages_per_minute(mut self, max_messages_per_minute: usize) -> Self {
+        self.config.max_messages_per_minute = max_messages_per_minute;
+        self
+    }
+
+    /// At most `capacity` records waiting in channel, less than 1 is treated as 1
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.config.capacity = capacity.max(1);
+        self
+    }
+
+    /// Create logger and spawn background task, must be called within a tokio runtime
+    pub fn build(self) -> DingTalkLogger {
+        DingTalkLogger {
+            level: self.level,
+            targets: self.targets,
+            sender: ForwardSender::spawn(self.dingtalk, self.config),
+        }
+    }
+
+    /// Build logger and install it as global logger of `log`, max level of `log` is raised to
+    /// `level` when lower
+    pub fn init(self) -> XResult<()> {
+        let logger = self.build();
+        let level = logger.level;
+        log::set_boxed_logger(Box::new(logger))
+            .map_err(|e| DingTalkError::Config(format!("Install logger failed: {}", e)))?;
+        if log::max_level() < level {
+            log::set_max_level(level);
+        }
+        Ok(())
+    }
+}
+
+impl Log for DingTalkLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level && self.is_target_enabled(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) || forward::is_forwarding() {
+            return;
+        }
+        self.sender.send(ForwardRecord {
+            severity: record.level() as u8,
+            level: record.level().as_str(),
+            markdown: render_record(record),
+        });
+    }
+
+    /// Records are sent by background task, nothing to flush in logging thread
+    fn flush(&self) {}
+}
+
+/// Render record as markdown with level, target, file:line and message
+fn render_record(record: &Record) -> String {
+    let location = match (record.file(), record.line()) {
+        (Some(file), Some(line)) => format!("{}:{}", file, line),
+        (Some(file), None) => file.to_owned(),
+        _ => "unknown".to_owned(),
+    };
+    format!(
+        "#### {} {}\n\n- location: `{}`\n\n{}",
+        record.level(),
+        record.target(),
+        location,
+        record.args()
+    )
+}
@@ -0,0 +1,122 @@
+use crate::*;
+
+/// Build markdown content with images which render on both mobile and desktop DingTalk clients
+///
+/// Every block(heading, text, image) is a paragraph separated by blank line, images not on
+/// their own paragraph are not rendered by some mobile clients.
+///
+/// Image URLs must be `https`, some clients refuse `http` images, invalid URLs are reported by
+/// `MarkdownBuilder::build`.
+///
+/// Sample code:
+/// ```ignore
+/// let markdown = MarkdownBuilder::for_type(dt.dingtalk_type)
+///     .heading(3, "CPU usage")
+///     .text("web-01 is **95%** busy")
+///     .image_sized("https://grafana.example.com/render/cpu.png", 400)
+///     .build()?;
+/// dt.send_markdown("CPU usage", &markdown).await?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownBuilder {
+    blocks: Vec<String>,
+    problems: Vec<String>,
+    /// Robot does not render HTML in markdown, sized images are plain markdown images
+    no_html: bool,
+}
+
+impl MarkdownBuilder {
+    /// New empty markdown builder for DingTalk robot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New empty markdown builder for robot type, only DingTalk renders HTML in markdown
+    pub fn for_type(dingtalk_type: DingTalkType) -> Self {
+        MarkdownBuilder {
+            no_html: dingtalk_type != DingTalkType::DingTalk,
+            ..Self::default()
+        }
+    }
+
+    /// Add heading, `level` is clamped to 1..=6
+    pub fn heading(mut self, level: usize, text: &str) -> Self {
+        self.blocks
+            .push(format!("{} {}", "#".repeat(level.clamp(1, 6)), text));
+        self
+    }
+
+    /// Add text paragraph, `text` is markdown and added as is
+    pub fn text(mut self, text: &str) -> Self {
+        self.blocks.push(text.to_owned());
+        self
+    }
+
+    /// Add image as `![image](<url>)`, shown in original size scaled down to message width
+    pub fn image(mut self, url: &str) -> Self {
+        match image_url(url) {
+            Ok(url) => self.blocks.push(format!("![image]({})", url)),
+            Err(problem) => self.problems.push(problem),
+        }
+        self
+    }
+
+    /// Add image with width hint in pixels as `<img src="<url>" width="<width>" />`
+    ///
+    /// Builder of `MarkdownBuilder::for_type` other than DingTalk adds `![image](<url>)` without
+    /// width instead, their robots show HTML as text.
+    pub fn image_sized(mut self, url: &str, width: u32) -> Self {
+        if width == 0 {
+            self.problems
+                .push(format!("image width must be positive: {}", url));
+            return self;
+        }
+        if self.no_html {
+            return self.image(url);
+        }
+        match image_url(url) {
+            Ok(url) => self
+                .blocks
+                .push(format!("<img src=\"{}\" width=\"{}\" />", url, width)),
+            Err(problem) => self.problems.push(problem),
+        }
+        self
+    }
+
+    /// Build markdown content, `DingTalkError::Validation` lists every invalid image
+    pub fn build(&self) -> XResult<String> {
+        if !self.problems.is_empty() {
+            return Err(DingTalkError::Validation(format!(
+                "Invalid markdown: {}",
+                self.problems.join("; ")
+            )));
+        }
+        Ok(self.blocks.join("\n\n"))
+    }
+}
+
+impl DingTalkMessage {
+    /// New markdown DingTalk message of `text` followed by image `image_url`, see
+    /// `MarkdownBuilder`
+    pub fn markdown_with_image(title: &str, text: &str, image_url: &str) -> XResult<Self> {
+        let markdown = MarkdownBuilder::new().text(text).image(image_url).build()?;
+        Ok(Self::new_markdown(title, &markdown))
+    }
+}
+
+/// Check image URL is `https` with host, returns URL escaped for markdown and HTML attribute
+fn image_url(url: &str) -> Result<String, String> {
+    let parsed =
+        reqwest::Url::parse(url).map_err(|e| format!("image URL is malformed: {}, {}", url, e))?;
+    if parsed.scheme() != "https" {
+        return Err(format!("image URL must be https: {}", url));
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err(format!("image URL has no host: {}", url));
+    }
+    Ok(parsed
+        .as_str()
+        .replace('(', "%28")
+        .replace(')', "%29")
+        .replace('"', "%22"))
+}
@@ -0,0 +1,90 @@
+use crate::*;
+
+const MEDIA_IMAGE_MAX_BYTES: usize = 20 * 1024 * 1024;
+const MEDIA_VOICE_MAX_BYTES: usize = 2 * 1024 * 1024;
+const MEDIA_FILE_MAX_BYTES: usize = 20 * 1024 * 1024;
+
+/// Media type of `DingTalkApp::upload_media`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MediaType {
+    /// At most 20MB
+    Image,
+    /// At most 2MB
+    Voice,
+    /// At most 20MB
+    File,
+}
+
+impl MediaType {
+    /// `type` parameter of `media/upload`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaType::Image => "image",
+            MediaType::Voice => "voice",
+            MediaType::File => "file",
+        }
+    }
+
+    /// Max size in bytes
+    pub fn max_bytes(&self) -> usize {
+        match self {
+            MediaType::Image => MEDIA_IMAGE_MAX_BYTES,
+            MediaType::Voice => MEDIA_VOICE_MAX_BYTES,
+            MediaType::File => MEDIA_FILE_MAX_BYTES,
+        }
+    }
+}
+
+/// Uploaded media, returned by `DingTalkApp::upload_media`
+#[derive(Clone, Debug, PartialEq)]
+pub struct MediaId {
+    pub media_id: String,
+    /// Milliseconds since UNIX epoch
+    pub created_at: i64,
+}
+
+/// Media upload, document https://open.dingtalk.com/document/orgapp/upload-media-files
+impl DingTalkApp {
+    /// Upload media by `media/upload`, `media_id` is used by work notifications and robot messages
+    pub async fn upload_media(
+        &self,
+        media_type: MediaType,
+        file_name: &str,
+        file_bytes: Vec<u8>,
+    ) -> XResult<MediaId> {
+        if file_bytes.is_empty() || file_bytes.len() > media_type.max_bytes() {
+            return Err(DingTalkError::Validation(format!(
+                "Media size {} bytes is not in range 1 to {} bytes",
+                file_bytes.len(),
+                media_type.max_bytes()
+            )));
+        }
+        let access_token = self.access_token().await?;
+        let form = reqwest::multipart::Form::new().part(
+            "media",
+            reqwest::multipart::Part::bytes(file_bytes).file_name(file_name.to_owned()),
+        );
+        let request = self
+            .client
+            .post(self.endpoint("/media/upload"))
+            .query(&[
+                ("access_token", access_token.as_str()),
+                ("type", media_type.as_str()),
+            ])
+            .multipart(form);
+        let response = self.call(request).await?;
+        match response.raw["media_id"].as_str() {
+            Some(media_id) => Ok(MediaId {
+                media_id: media_id.to_owned(),
+                created_at: match &response.raw["created_at"] {
+                    Value::String(created_at) => created_at.parse().unwrap_or_default(),
+                    created_at => created_at.as_i64().unwrap_or_default(),
+                },
+            }),
+            None => Err(DingTalkError::Server {
+                errcode: -1,
+                errmsg: format!("No media_id in response: {}", response.raw),
+            }),
+        }
+    }
+}
@@ -1,12 +1,18 @@
 use serde::{ Serialize, Deserialize };
 
-/// Send Dingtalk or WeChatWork message
-#[derive(Clone, Copy, Debug)]
+/// Send Dingtalk, WeChatWork, Feishu, Slack or Discord message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DingTalkType {
     /// DingTalk
     DingTalk,
     /// WeChatWork
     WeChatWork,
+    /// Feishu(Lark)
+    Feishu,
+    /// Slack incoming webhook, uses full webhook URL
+    Slack,
+    /// Discord webhook, uses full webhook URL
+    Discord,
 }
 
 /// Default DingTalkType is DingTalk
@@ -20,7 +26,11 @@ impl Default for DingTalkType {
 /// * Link - link message
 /// * ActionCard - action card message
 /// * FeedCard - feed card message
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+/// * Image - image message, WeChat Work only
+/// * News - news message, WeChat Work only
+/// * File - file message, WeChat Work only
+/// * Voice - voice message, WeChat Work only
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DingTalkMessageType {
     #[serde(rename = "text")]
     Text,
@@ -32,6 +42,14 @@ pub enum DingTalkMessageType {
     ActionCard,
     #[serde(rename = "feedCard")]
     FeedCard,
+    #[serde(rename = "image")]
+    Image,
+    #[serde(rename = "news")]
+    News,
+    #[serde(rename = "file")]
+    File,
+    #[serde(rename = "voice")]
+    Voice,
 }
 
 /// Default DingTalkMessageType is Text
@@ -39,26 +57,26 @@ impl Default for DingTalkMessageType {
     fn default() -> Self { DingTalkMessageType::Text }
 }
 
-/// DingTalk messge action card avatar
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+/// DingTalk messge action card avatar, default value is Show
+///
+/// Serialized as `"hide"`/`"show"`, wire value `"1"`/`"0"` is also accepted when deserialize
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DingTalkMessageActionCardHideAvatar {
-    #[serde(rename = "1")]
+    #[serde(rename = "hide", alias = "1")]
     Hide,
-    #[serde(rename = "0")]
+    #[serde(rename = "show", alias = "0")]
+    #[default]
     Show,
 }
 
-// default value
-impl Default for DingTalkMessageActionCardHideAvatar {
-    fn default() -> Self { DingTalkMessageActionCardHideAvatar::Show }
-}
-
 /// DingTalk message action card orientation
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+///
+/// Serialized as `"vertical"`/`"landscape"`, wire value `"0"`/`"1"` is also accepted when deserialize
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DingTalkMessageActionCardBtnOrientation {
-    #[serde(rename = "0")]
+    #[serde(rename = "vertical", alias = "0")]
     Vertical,
-    #[serde(rename = "1")]
+    #[serde(rename = "landscape", alias = "1")]
     Landscape,
 }
 
@@ -68,22 +86,32 @@ impl Default for DingTalkMessageActionCardBtnOrientation {
 }
 
 /// DingTalk message action card btn
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DingTalkMessageActionCardBtn {
     pub title: String,
     pub action_url: String,
 }
 
 /// DingTalk message feed card link
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DingTalkMessageFeedCardLink {
     pub title: String,
     pub message_url: String,
     pub pic_url: String,
 }
 
-/// DingTalk message
-#[derive(Debug, Default)]
+/// WeChat Work news message article
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DingTalkMessageNewsArticle {
+    pub title: String,
+    pub description: String,
+    pub url: String,
+    pub pic_url: String,
+}
+
+/// DingTalk message, deserialized fields are same as struct fields, omitted fields are default
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DingTalkMessage {
     pub message_type: DingTalkMessageType,
     pub text_content: String,
@@ -100,8 +128,13 @@ pub struct DingTalkMessage {
     pub action_card_single_btn: Option<DingTalkMessageActionCardBtn>,
     pub action_card_btns: Vec<DingTalkMessageActionCardBtn>,
     pub feed_card_links: Vec<DingTalkMessageFeedCardLink>,
+    pub image_base64: String,
+    pub image_md5: String,
+    pub news_articles: Vec<DingTalkMessageNewsArticle>,
+    pub media_id: String,
     pub at_all: bool,
     pub at_mobiles: Vec<String>,
+    pub at_user_ids: Vec<String>,
 }
 
 ///////////////////////////////////////////////////////////////////////////////////////
@@ -145,13 +178,58 @@ pub struct InnerMarkdownMessage {
     pub markdown: InnerMarkdownMessageMarkdown,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerWeChatWorkMarkdownMessageMarkdown {
+    pub content: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerWeChatWorkMarkdownMessage {
+    pub msgtype: DingTalkMessageType,
+    pub markdown: InnerWeChatWorkMarkdownMessageMarkdown,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InnerActionCardMessageActionCard {
     pub title: String,
     pub text: String,
-    pub hide_avatar: DingTalkMessageActionCardHideAvatar,
-    pub btn_orientation: DingTalkMessageActionCardBtnOrientation,
+    pub hide_avatar: InnerActionCardHideAvatar,
+    pub btn_orientation: InnerActionCardBtnOrientation,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum InnerActionCardHideAvatar {
+    #[serde(rename = "1")]
+    Hide,
+    #[serde(rename = "0")]
+    Show,
+}
+
+impl From<DingTalkMessageActionCardHideAvatar> for InnerActionCardHideAvatar {
+    fn from(hide_avatar: DingTalkMessageActionCardHideAvatar) -> Self {
+        match hide_avatar {
+            DingTalkMessageActionCardHideAvatar::Hide => InnerActionCardHideAvatar::Hide,
+            DingTalkMessageActionCardHideAvatar::Show => InnerActionCardHideAvatar::Show,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum InnerActionCardBtnOrientation {
+    #[serde(rename = "0")]
+    Vertical,
+    #[serde(rename = "1")]
+    Landscape,
+}
+
+impl From<DingTalkMessageActionCardBtnOrientation> for InnerActionCardBtnOrientation {
+    fn from(btn_orientation: DingTalkMessageActionCardBtnOrientation) -> Self {
+        match btn_orientation {
+            DingTalkMessageActionCardBtnOrientation::Vertical => InnerActionCardBtnOrientation::Vertical,
+            DingTalkMessageActionCardBtnOrientation::Landscape => InnerActionCardBtnOrientation::Landscape,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -187,4 +265,52 @@ pub struct InnerFeedCardMessageFeedCard {
 pub struct InnerFeedCardMessage {
     pub msgtype: DingTalkMessageType,
     pub feed_card: InnerFeedCardMessageFeedCard,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerWeChatWorkImageMessageImage {
+    pub base64: String,
+    pub md5: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerWeChatWorkImageMessage {
+    pub msgtype: DingTalkMessageType,
+    pub image: InnerWeChatWorkImageMessageImage,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerWeChatWorkNewsMessageArticle {
+    pub title: String,
+    pub description: String,
+    pub url: String,
+    pub picurl: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerWeChatWorkNewsMessageNews {
+    pub articles: Vec<InnerWeChatWorkNewsMessageArticle>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerWeChatWorkNewsMessage {
+    pub msgtype: DingTalkMessageType,
+    pub news: InnerWeChatWorkNewsMessageNews,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerWeChatWorkMedia {
+    pub media_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerWeChatWorkFileMessage {
+    pub msgtype: DingTalkMessageType,
+    pub file: InnerWeChatWorkMedia,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerWeChatWorkVoiceMessage {
+    pub msgtype: DingTalkMessageType,
+    pub voice: InnerWeChatWorkMedia,
 }
\ No newline at end of file
@@ -0,0 +1,234 @@
+//! DingTalk outgoing robot, verify `timestamp` and `sign` headers of HTTP callback from DingTalk
+//! and parse its body by `OutgoingMessage`
+//!
+//! Document https://open.dingtalk.com/document/orgapp/receive-message
+//!
+//! Sample code:
+//! ```ignore
+//! match dingtalk::outgoing::verify_signature(app_secret, timestamp_header, sign_header) {
+//!     Ok(()) => { /* handle message */ }
+//!     Err(DingTalkError::SignatureExpired { .. }) => { /* reply 401, maybe replayed */ }
+//!     Err(_) => { /* reply 403 */ }
+//! }
+//! ```
+use hmac::{Mac, NewMac};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{clock::system_now, DingTalk, DingTalkError, HmacSha256, XResult};
+
+/// Max difference between `timestamp` header and now, in milliseconds
+const TIMESTAMP_WINDOW_MS: i128 = 60 * 60 * 1000;
+
+/// Verify outgoing robot request, `sign` is base64 of HmacSHA256 of `"{timestamp}\n{app_secret}"`
+/// keyed by `app_secret`, `timestamp` is milliseconds and must be within 1 hour of now
+///
+/// Returns `DingTalkError::SignatureExpired` when timestamp is out of window,
+/// `DingTalkError::SignatureMismatch` when sign does not match, sign is compared in constant time.
+pub fn verify_signature(
+    app_secret: &str,
+    timestamp_header: &str,
+    sign_header: &str,
+) -> XResult<()> {
+    verify_signature_at(app_secret, timestamp_header, sign_header, system_now())
+}
+
+/// Same as `verify_signature`, with `now` as current time
+pub fn verify_signature_at(
+    app_secret: &str,
+    timestamp_header: &str,
+    sign_header: &str,
+    now: SystemTime,
+) -> XResult<()> {
+    let timestamp_header = timestamp_header.trim();
+    let timestamp: i64 = timestamp_header.parse().map_err(|_| {
+        DingTalkError::Validation(format!("Invalid timestamp header: {}", timestamp_header))
+    })?;
+    let now_ms = match now.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_millis() as i128,
+        Err(e) => -(e.duration().as_millis() as i128),
+    };
+    if (now_ms - timestamp as i128).abs() > TIMESTAMP_WINDOW_MS {
+        return Err(DingTalkError::SignatureExpired { timestamp });
+    }
+    let sign = base64::decode(sign_header.trim()).map_err(|_| DingTalkError::SignatureMismatch)?;
+    let mut mac = HmacSha256::new_from_slice(app_secret.as_bytes())
+        .map_err(|e| DingTalkError::Signature(format!("Hmac error: {}", e)))?;
+    mac.update(format!("{}\n{}", timestamp_header, app_secret).as_bytes());
+    mac.verify(&sign)
+        .map_err(|_| DingTalkError::SignatureMismatch)
+}
+
+/// Message POSTed by DingTalk when outgoing robot is @-mentioned, missing fields are default
+///
+/// Sample code:
+/// ```ignore
+/// let message = OutgoingMessage::from_json(&body)?;
+/// println!("{} says: {}", message.sender_nick, message.text().unwrap_or_default());
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutgoingMessage {
+    #[serde(default)]
+    pub msg_id: String,
+    #[serde(default)]
+    pub conversation_id: String,
+    /// `"1"` is single chat, `"2"` is group chat
+    #[serde(default)]
+    pub conversation_type: String,
+    #[serde(default)]
+    pub conversation_title: String,
+    #[serde(default)]
+    pub sender_id: String,
+    #[serde(default)]
+    pub sender_nick: String,
+    /// Sender user id of the enterprise, empty for users outside of the enterprise
+    #[serde(default)]
+    pub sender_staff_id: String,
+    #[serde(default)]
+    pub chatbot_user_id: String,
+    #[serde(default)]
+    pub at_users: Vec<OutgoingAtUser>,
+    #[serde(default)]
+    pub is_in_at_list: bool,
+    /// URL to reply this conversation, expires at `session_webhook_expired_time`
+    #[serde(default)]
+    pub session_webhook: String,
+    /// Milliseconds since UNIX epoch
+    #[serde(default)]
+    pub session_webhook_expired_time: i64,
+    /// Milliseconds since UNIX epoch
+    #[serde(default)]
+    pub create_at: i64,
+    /// Content by `msgtype`
+    #[serde(flatten)]
+    pub content: OutgoingContent,
+}
+
+/// User @-mentioned in `OutgoingMessage`
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutgoingAtUser {
+    #[serde(default)]
+    pub dingtalk_id: String,
+    #[serde(default)]
+    pub staff_id: String,
+}
+
+/// Content of `OutgoingMessage`
+#[derive(Clone, Debug, PartialEq)]
+pub enum OutgoingContent {
+    /// `msgtype` `text`, `text.content`
+    Text(String),
+    /// `msgtype` `richText`, `content.richText`
+    RichText(Vec<OutgoingRichTextItem>),
+    /// Other `msgtype`, value is JSON object of fields not in `OutgoingMessage`, including `msgtype`
+    Other(Value),
+}
+
+/// Item of `OutgoingContent::RichText`
+#[derive(Clone, Debug, PartialEq)]
+pub enum OutgoingRichTextItem {
+    Text(String),
+    Picture { download_code: String },
+    Other(Value),
+}
+
+impl OutgoingMessage {
+    /// Parse message from request body
+    pub fn from_json(json: &str) -> XResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Is from group chat
+    pub fn is_group(&self) -> bool {
+        self.conversation_type == "2"
+    }
+
+    /// `DingTalk` to reply this conversation by `session_webhook`, see `DingTalk::from_session_webhook`
+    ///
+    /// Sample code:
+    /// ```ignore
+    /// message.reply_target()?.send_markdown("Deploy", "Deploy **started**").await?;
+    /// ```
+    pub fn reply_target(&self) -> XResult<DingTalk> {
+        if self.session_webhook.is_empty() {
+            return Err(DingTalkError::Validation(
+                "Outgoing message has no sessionWebhook".to_owned(),
+            ));
+        }
+        Ok(DingTalk::from_session_webhook(
+            &self.session_webhook,
+            self.session_webhook_expired_time,
+        ))
+    }
+
+    /// Text content as received, text items of rich text are joined, `None` for other types
+    pub fn raw_text(&self) -> Option<String> {
+        match &self.content {
+            OutgoingContent::Text(content) => Some(content.clone()),
+            OutgoingContent::RichText(items) => Some(
+                items
+                    .iter()
+                    .filter_map(|item| match item {
+                        OutgoingRichTextItem::Text(text) => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            OutgoingContent::Other(_) => None,
+        }
+    }
+
+    /// Text content with leading `@robot` mention and surrounding whitespaces removed
+    pub fn text(&self) -> Option<String> {
+        self.raw_text()
+            .map(|text| strip_leading_mention(&text).to_owned())
+    }
+}
+
+fn strip_leading_mention(text: &str) -> &str {
+    let text = text.trim();
+    match text.strip_prefix('@') {
+        Some(mentioned) => match mentioned.find(char::is_whitespace) {
+            Some(end) => mentioned[end..].trim_start(),
+            None => "",
+        },
+        None => text,
+    }
+}
+
+impl<'de> Deserialize<'de> for OutgoingContent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let content = match value["msgtype"].as_str() {
+            Some("text") => OutgoingContent::Text(
+                value["text"]["content"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_owned(),
+            ),
+            Some("richText") => OutgoingContent::RichText(
+                value["content"]["richText"]
+                    .as_array()
+                    .map(|items| items.iter().map(rich_text_item).collect())
+                    .unwrap_or_default(),
+            ),
+            _ => OutgoingContent::Other(value),
+        };
+        Ok(content)
+    }
+}
+
+fn rich_text_item(item: &Value) -> OutgoingRichTextItem {
+    if let Some(text) = item["text"].as_str() {
+        return OutgoingRichTextItem::Text(text.to_owned());
+    }
+    match (item["type"].as_str(), item["downloadCode"].as_str()) {
+        (Some("picture"), Some(download_code)) => OutgoingRichTextItem::Picture {
+            download_code: download_code.to_owned(),
+        },
+        _ => OutgoingRichTextItem::Other(item.clone()),
+    }
+}
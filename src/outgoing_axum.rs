@@ -0,0 +1,103 @@
+//! axum handler of outgoing robot, feature `axum`
+//!
+//! Sample code:
+//! ```ignore
+//! let app = Router::new().route(
+//!     "/dingtalk",
+//!     outgoing_handler("<app secret>", |message: OutgoingMessage, reply: ReplyHandle| async move {
+//!         let _ = reply.send_text(&format!("Got: {}", message.text().unwrap_or_default())).await;
+//!     }),
+//! );
+//! ```
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{post, MethodRouter},
+};
+use std::{future::Future, sync::Arc};
+
+use crate::outgoing::{verify_signature, OutgoingMessage};
+use crate::{DingTalk, DingTalkMessage, DingTalkResponse, XResult};
+
+/// Reply to conversation of outgoing robot message by its `sessionWebhook`
+pub struct ReplyHandle {
+    dingtalk: DingTalk,
+}
+
+impl ReplyHandle {
+    /// Create reply handle of message, fails when message has no `sessionWebhook`
+    pub fn new(message: &OutgoingMessage) -> XResult<Self> {
+        Ok(ReplyHandle {
+            dingtalk: message.reply_target()?,
+        })
+    }
+
+    /// `DingTalk` of session webhook, see `DingTalk::from_session_webhook`
+    pub fn dingtalk(&self) -> &DingTalk {
+        &self.dingtalk
+    }
+
+    /// Reply message
+    pub async fn send_message(
+        &self,
+        dingtalk_message: DingTalkMessage,
+    ) -> XResult<DingTalkResponse> {
+        self.dingtalk
+            .send_message_with_response(dingtalk_message)
+            .await
+    }
+
+    /// Reply text message
+    pub async fn send_text(&self, text_message: &str) -> XResult<DingTalkResponse> {
+        self.send_message(DingTalkMessage::new_text(text_message))
+            .await
+    }
+
+    /// Reply markdown message
+    pub async fn send_markdown(&self, title: &str, text: &str) -> XResult<DingTalkResponse> {
+        self.send_message(DingTalkMessage::new_markdown(title, text))
+            .await
+    }
+}
+
+/// POST handler of outgoing robot callback
+///
+/// Headers `timestamp` and `sign` are verified by `outgoing::verify_signature`, invalid or
+/// missing ones reply `401 Unauthorized`. Body is parsed by `OutgoingMessage::from_json`,
+/// malformed body or missing `sessionWebhook` replies `400 Bad Request`. Otherwise `handler`
+/// is called and its output is the response.
+pub fn outgoing_handler<S, F, Fut, R>(app_secret: &str, handler: F) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+    F: Fn(OutgoingMessage, ReplyHandle) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = R> + Send + 'static,
+    R: IntoResponse,
+{
+    let app_secret: Arc<str> = app_secret.into();
+    post(move |headers: HeaderMap, body: Bytes| async move {
+        match verify_request(&app_secret, &headers, &body) {
+            Ok((message, reply)) => handler(message, reply).await.into_response(),
+            Err(status) => status.into_response(),
+        }
+    })
+}
+
+fn verify_request(
+    app_secret: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<(OutgoingMessage, ReplyHandle), StatusCode> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(StatusCode::UNAUTHORIZED)
+    };
+    verify_signature(app_secret, header("timestamp")?, header("sign")?)
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let body = std::str::from_utf8(body).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let message = OutgoingMessage::from_json(body).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let reply = ReplyHandle::new(&message).map_err(|_| StatusCode::BAD_REQUEST)?;
+    Ok((message, reply))
+}
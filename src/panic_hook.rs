@@ -0,0 +1,123 @@
+//! Report panics to robot, e.g. of a daemon, so a panic is known at once instead of in logs
+//!
+//! Sample code:
+//! ```ignore
+//! dingtalk::panic_hook::install(dt, "billing-daemon");
+//! ```
+use std::{
+    any::Any,
+    backtrace::{Backtrace, BacktraceStatus},
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc,
+    thread,
+};
+
+use crate::*;
+
+/// Panicking thread waits at most this long for report is sent
+const REPORT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Max bytes of backtrace in report
+const BACKTRACE_MAX_BYTES: usize = 4000;
+
+thread_local! {
+    /// Set in reporting thread, panics while reporting are swallowed and never reported again
+    static REPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Install panic hook reports panics as markdown of `service_name`, the previous hook is still
+/// invoked first
+///
+/// Report has panic message, location, thread name, and backtrace cut to 4000 bytes when
+/// `RUST_BACKTRACE` is set. It is sent by a new thread with its own tokio runtime, so it works
+/// within a tokio runtime, outside of it and while unwinding, panicking thread waits at most 5
+/// seconds for it. Best-effort, send failures are counted by `DingTalk::silent_failure_count`,
+/// panics while reporting are swallowed.
+pub fn install(dingtalk: impl Into<Arc<DingTalk>>, service_name: &str) {
+    let dingtalk = dingtalk.into();
+    let service_name = service_name.to_owned();
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if REPORTING.try_with(Cell::get).unwrap_or(false) {
+            return;
+        }
+        previous(info);
+        let report = PanicReport {
+            service_name: service_name.clone(),
+            thread_name: thread::current().name().unwrap_or("<unnamed>").to_owned(),
+            message: payload_message(info.payload()),
+            location: info
+                .location()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| "unknown".to_owned()),
+            backtrace: Backtrace::capture(),
+        };
+        report.send(dingtalk.clone());
+    }));
+}
+
+/// Panic captured in hook, rendered and sent in reporting thread
+struct PanicReport {
+    service_name: String,
+    thread_name: String,
+    message: String,
+    location: String,
+    backtrace: Backtrace,
+}
+
+impl PanicReport {
+    /// Send report by a new thread and wait it at most `REPORT_TIMEOUT`
+    fn send(self, dingtalk: Arc<DingTalk>) {
+        let (done_sender, done_receiver) = mpsc::channel::<()>();
+        let spawned = thread::Builder::new()
+            .name("dingtalk-panic-report".into())
+            .spawn(move || {
+                REPORTING.with(|reporting| reporting.set(true));
+                let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+                    let message = self.message();
+                    if let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                    {
+                        runtime.block_on(dingtalk.send_message_silent(message));
+                    }
+                }));
+                let _ = done_sender.send(());
+            });
+        if spawned.is_ok() {
+            let _ = done_receiver.recv_timeout(REPORT_TIMEOUT);
+        }
+    }
+
+    fn message(&self) -> DingTalkMessage {
+        let title = format!("{} panicked", self.service_name);
+        let mut markdown = format!(
+            "#### {}\n\n- thread: `{}`\n- location: `{}`\n\n{}",
+            title, self.thread_name, self.location, self.message
+        );
+        if self.backtrace.status() == BacktraceStatus::Captured {
+            let backtrace = self.backtrace.to_string();
+            let backtrace = match limits::truncate_text(
+                &backtrace,
+                BACKTRACE_MAX_BYTES,
+                limits::DEFAULT_TRUNCATE_ELLIPSIS,
+            ) {
+                Some((truncated, _)) => truncated,
+                None => backtrace,
+            };
+            markdown.push_str(&format!("\n\n```\n{}\n```", backtrace.trim_end()));
+        }
+        DingTalkMessage::new_markdown(&title, &markdown)
+    }
+}
+
+/// Message of `panic!`, payload is `&str` or `String` unless `std::panic::panic_any` is used
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".to_owned()
+    }
+}
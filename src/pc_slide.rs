@@ -0,0 +1,43 @@
+use crate::*;
+
+/// Deep link opening URL in DingTalk, `pc_slide=true` opens it in side panel of PC client
+const DINGTALK_PAGE_LINK_URL: &str = "dingtalk://dingtalkclient/page/link";
+
+impl DingTalkMessageActionCardBtn {
+    /// New button opens `url` in side panel of DingTalk PC client, see
+    /// `DingTalkMessageActionCardBtn::pc_slide_url`
+    pub fn with_pc_slide(title: &str, url: &str) -> Self {
+        DingTalkMessageActionCardBtn {
+            title: title.into(),
+            action_url: Self::pc_slide_url(url),
+        }
+    }
+
+    /// Wrap `url` as `dingtalk://dingtalkclient/page/link?url=<encoded url>&pc_slide=true`,
+    /// `dingtalk://` URL is returned as is
+    pub fn pc_slide_url(url: &str) -> String {
+        if url.starts_with("dingtalk://") {
+            return url.to_owned();
+        }
+        format!(
+            "{}?url={}&pc_slide=true",
+            DINGTALK_PAGE_LINK_URL,
+            urlencoding::encode(url)
+        )
+    }
+}
+
+impl DingTalkMessage {
+    /// Open all action card buttons added so far in side panel of DingTalk PC client, see
+    /// `DingTalkMessageActionCardBtn::pc_slide_url`
+    pub fn action_card_pc_slide(mut self) -> Self {
+        let btns = self
+            .action_card_single_btn
+            .iter_mut()
+            .chain(self.action_card_btns.iter_mut());
+        for btn in btns {
+            btn.action_url = DingTalkMessageActionCardBtn::pc_slide_url(&btn.action_url);
+        }
+        self
+    }
+}
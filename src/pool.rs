@@ -0,0 +1,240 @@
+use std::{collections::VecDeque, sync::Mutex, time::Duration};
+use tokio::time::Instant;
+
+use crate::{DingTalk, DingTalkError, DingTalkMessage, DingTalkResponse, XResult};
+
+/// Default window of recent send counts, DingTalk robot allows 20 messages per minute
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+/// Throttled robot is skipped for this long when server does not reply `Retry-After`
+const DEFAULT_THROTTLE: Duration = Duration::from_secs(60);
+
+/// Result of `DingTalkPool` send, tells which robot delivered the message
+#[derive(Clone, Debug)]
+pub struct PoolDelivery {
+    /// Index of robot in pool
+    pub index: usize,
+    /// Access token of robot
+    pub access_token: String,
+    pub response: DingTalkResponse,
+}
+
+#[derive(Default)]
+struct RobotState {
+    sent_instants: VecDeque<Instant>,
+    throttled_until: Option<Instant>,
+}
+
+struct PoolState {
+    robots: Vec<RobotState>,
+    next: usize,
+}
+
+/// Robots in the same group, each send picks the least used robot in recent window, ties are
+/// taken round-robin, throttled robots are skipped
+///
+/// When a robot replies rate limited(`DingTalkError::RateLimited`), it is marked throttled for
+/// `Retry-After`(default 60 seconds) and the message is sent by the next robot.
+///
+/// Sample code:
+/// ```ignore
+/// let pool = DingTalkPool::new(&[("<token 1>", "<sec token 1>"), ("<token 2>", "<sec token 2>")]);
+/// let delivery = pool.send_text("Hello world!").await?;
+/// println!("Delivered by robot #{}", delivery.index);
+/// ```
+pub struct DingTalkPool {
+    robots: Vec<DingTalk>,
+    window: Duration,
+    state: Mutex<PoolState>,
+}
+
+impl DingTalkPool {
+    /// Create pool of DingTalk robots by `(access_token, sec_token)` pairs
+    pub fn new(tokens: &[(&str, &str)]) -> Self {
+        Self::from_dingtalks(
+            tokens
+                .iter()
+                .map(|(access_token, sec_token)| DingTalk::new(access_token, sec_token))
+                .collect(),
+        )
+    }
+
+    /// Create pool of robots, e.g. with custom webhook URL or retry
+    pub fn from_dingtalks(robots: Vec<DingTalk>) -> Self {
+        let state = PoolState {
+            robots: robots.iter().map(|_| RobotState::default()).collect(),
+            next: 0,
+        };
+        DingTalkPool {
+            robots,
+            window: DEFAULT_WINDOW,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Set window of recent send counts, default is 60 seconds
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Robots in pool
+    pub fn robots(&self) -> &[DingTalk] {
+        &self.robots
+    }
+
+    /// Send count of each robot in recent window
+    pub fn recent_send_counts(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        state
+            .robots
+            .iter_mut()
+            .map(|robot| {
+                self.prune(robot, now);
+                robot.sent_instants.len()
+            })
+            .collect()
+    }
+
+    /// Is robot marked throttled now
+    pub fn is_throttled(&self, index: usize) -> bool {
+        let state = self.state.lock().unwrap();
+        state
+            .robots
+            .get(index)
+            .and_then(|robot| robot.throttled_until)
+            .map(|until| until > Instant::now())
+            .unwrap_or(false)
+    }
+
+    /// Send message by picked robot, see `DingTalkPool`
+    ///
+    /// Returns `DingTalkError::RateLimited` when all robots are throttled.
+    pub async fn send_message(&self, dingtalk_message: DingTalkMessage) -> XResult<PoolDelivery> {
+        self.send_message_ref(&dingtalk_message).await
+    }
+
+    /// Send message by reference, see `DingTalkPool::send_message`
+    pub async fn send_message_ref(
+        &self,
+        dingtalk_message: &DingTalkMessage,
+    ) -> XResult<PoolDelivery> {
+        let mut last_error = None;
+        for _ in 0..self.robots.len() {
+            let index = self.pick()?;
+            let dingtalk = &self.robots[index];
+            match dingtalk
+                .send_message_with_response_ref(dingtalk_message)
+                .await
+            {
+                Ok(response) => {
+                    return Ok(PoolDelivery {
+                        index,
+                        access_token: dingtalk.access_token.clone(),
+                        response,
+                    })
+                }
+                Err(e) if is_rate_limited(&e) => {
+                    self.mark_throttled(index, e.retry_after());
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| DingTalkError::Config("Pool is empty".to_owned())))
+    }
+
+    /// Send text message, see `DingTalkPool::send_message`
+    pub async fn send_text(&self, text_message: &str) -> XResult<PoolDelivery> {
+        self.send_message(DingTalkMessage::new_text(text_message))
+            .await
+    }
+
+    /// Send markdown message, see `DingTalkPool::send_message`
+    pub async fn send_markdown(&self, title: &str, text: &str) -> XResult<PoolDelivery> {
+        self.send_message(DingTalkMessage::new_markdown(title, text))
+            .await
+    }
+
+    /// Send link message, see `DingTalkPool::send_message`
+    pub async fn send_link(
+        &self,
+        link_title: &str,
+        link_text: &str,
+        link_pic_url: &str,
+        link_message_url: &str,
+    ) -> XResult<PoolDelivery> {
+        self.send_message(DingTalkMessage::new_link(
+            link_title,
+            link_text,
+            link_pic_url,
+            link_message_url,
+        ))
+        .await
+    }
+
+    /// Pick the least used robot which is not throttled, the send is counted at once
+    fn pick(&self) -> XResult<usize> {
+        if self.robots.is_empty() {
+            return Err(DingTalkError::Config("Pool is empty".to_owned()));
+        }
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let robot_count = state.robots.len();
+        let mut picked: Option<(usize, usize)> = None;
+        let mut throttled_wait: Option<Duration> = None;
+        for offset in 0..robot_count {
+            let index = (state.next + offset) % robot_count;
+            let robot = &mut state.robots[index];
+            self.prune(robot, now);
+            if let Some(until) = robot.throttled_until {
+                if until > now {
+                    let wait = until - now;
+                    throttled_wait = Some(throttled_wait.map_or(wait, |w| w.min(wait)));
+                    continue;
+                }
+                robot.throttled_until = None;
+            }
+            let count = robot.sent_instants.len();
+            let less_used = match picked {
+                Some((min_count, _)) => count < min_count,
+                None => true,
+            };
+            if less_used {
+                picked = Some((count, index));
+            }
+        }
+        match picked {
+            Some((_, index)) => {
+                state.robots[index].sent_instants.push_back(now);
+                state.next = (index + 1) % robot_count;
+                Ok(index)
+            }
+            None => Err(DingTalkError::RateLimited {
+                retry_after: throttled_wait,
+            }),
+        }
+    }
+
+    fn mark_throttled(&self, index: usize, retry_after: Option<Duration>) {
+        let until = Instant::now() + retry_after.unwrap_or(DEFAULT_THROTTLE);
+        self.state.lock().unwrap().robots[index].throttled_until = Some(until);
+    }
+
+    fn prune(&self, robot: &mut RobotState, now: Instant) {
+        while let Some(sent) = robot.sent_instants.front() {
+            if now.duration_since(*sent) < self.window {
+                break;
+            }
+            robot.sent_instants.pop_front();
+        }
+    }
+}
+
+fn is_rate_limited(error: &DingTalkError) -> bool {
+    match error {
+        DingTalkError::RateLimited { .. } => true,
+        DingTalkError::Retry { source, .. } => is_rate_limited(source),
+        _ => false,
+    }
+}
@@ -0,0 +1,70 @@
+use crate::*;
+
+/// Environment variable of profile selected by `DingTalk::from_file` and `DingTalk::from_json`
+pub(crate) const PROFILE_ENV: &str = "DINGTALK_PROFILE";
+
+/// Select profile of config with top-level `profiles` object, config without `profiles` is
+/// returned as is
+///
+/// Profile is `profile`, or environment variable `DINGTALK_PROFILE`, or `default_profile` of
+/// config. Top-level keys are shared by all profiles, keys of the selected profile override
+/// them.
+pub(crate) fn select_profile(mut json_value: Value, profile: Option<&str>) -> XResult<Value> {
+    let json_map = match json_value.as_object_mut() {
+        Some(json_map) => json_map,
+        None => return Ok(json_value),
+    };
+    let profiles = match json_map.remove("profiles") {
+        Some(Value::Object(profiles)) => profiles,
+        Some(_) => {
+            return Err(DingTalkError::Config(
+                "Config profiles must be object of profile name to config".to_owned(),
+            ))
+        }
+        None => {
+            return match profile {
+                Some(profile) => Err(DingTalkError::Config(format!(
+                    "Profile {} is not found, config has no profiles",
+                    profile
+                ))),
+                None => Ok(json_value),
+            }
+        }
+    };
+    let default_profile = json_map.remove("default_profile");
+    let available = || profiles.keys().cloned().collect::<Vec<_>>().join(", ");
+    let name = match profile {
+        Some(profile) => profile.to_owned(),
+        None => match read_env("", PROFILE_ENV)? {
+            Some(profile) => profile,
+            None => match default_profile.as_ref().and_then(Value::as_str) {
+                Some(profile) => profile.to_owned(),
+                None => {
+                    return Err(DingTalkError::Config(format!(
+                        "Config has profiles, select one by DingTalk::from_file_profile, \
+                         {} or default_profile, available profiles: {}",
+                        PROFILE_ENV,
+                        available()
+                    )))
+                }
+            },
+        },
+    };
+    match profiles.get(&name) {
+        Some(Value::Object(selected)) => {
+            for (key, value) in selected {
+                json_map.insert(key.clone(), value.clone());
+            }
+            Ok(json_value)
+        }
+        Some(_) => Err(DingTalkError::Config(format!(
+            "Profile {} must be object",
+            name
+        ))),
+        None => Err(DingTalkError::Config(format!(
+            "Profile {} is not found, available profiles: {}",
+            name,
+            available()
+        ))),
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use crate::{DingTalk, DingTalkError, DingTalkMessage, XResult};
+
+type QueueCallback = dyn Fn(DingTalkMessage, XResult<()>) + Send + Sync;
+
+/// Background queue sender, messages are sent in order by a spawned tokio task
+///
+/// `enqueue` returns immediately, call `shutdown` to wait for queued messages being sent.
+/// Dropping the queue without `shutdown` still sends queued messages in background.
+///
+/// Sample code:
+/// ```ignore
+/// let queue = DingTalkQueue::with_callback(dt, 100, |message, result| {
+///     if let Err(e) = result {
+///         eprintln!("Send {:?} failed: {}", message.message_type, e);
+///     }
+/// });
+/// queue.enqueue(DingTalkMessage::new_text("Hello world!"))?;
+/// queue.shutdown().await?;
+/// ```
+pub struct DingTalkQueue {
+    sender: mpsc::Sender<DingTalkMessage>,
+    worker: JoinHandle<()>,
+}
+
+impl DingTalkQueue {
+    /// Create queue with at most `capacity` pending messages, send results are dropped
+    ///
+    /// Must be called within a tokio runtime.
+    pub fn new(dingtalk: impl Into<Arc<DingTalk>>, capacity: usize) -> Self {
+        Self::start(dingtalk.into(), capacity, None)
+    }
+
+    /// Create queue like `DingTalkQueue::new`, `callback` is called with each message and its
+    /// send result in the background task
+    pub fn with_callback<F>(
+        dingtalk: impl Into<Arc<DingTalk>>,
+        capacity: usize,
+        callback: F,
+    ) -> Self
+    where
+        F: Fn(DingTalkMessage, XResult<()>) + Send + Sync + 'static,
+    {
+        Self::start(dingtalk.into(), capacity, Some(Box::new(callback)))
+    }
+
+    fn start(
+        dingtalk: Arc<DingTalk>,
+        capacity: usize,
+        callback: Option<Box<QueueCallback>>,
+    ) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<DingTalkMessage>(capacity.max(1));
+        let worker = tokio::spawn(async move {
+            while let Some(dingtalk_message) = receiver.recv().await {
+                match &callback {
+                    Some(callback) => {
+                        let result = dingtalk.send_message_ref(&dingtalk_message).await;
+                        callback(dingtalk_message, result);
+                    }
+                    None => {
+                        let _ = dingtalk.send_message(dingtalk_message).await;
+                    }
+                }
+            }
+        });
+        DingTalkQueue { sender, worker }
+    }
+
+    /// Enqueue message without waiting, returns `DingTalkError::Queue` when queue is full
+    pub fn enqueue(&self, dingtalk_message: DingTalkMessage) -> XResult<()> {
+        self.sender.try_send(dingtalk_message).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => DingTalkError::Queue("Queue is full".to_owned()),
+            mpsc::error::TrySendError::Closed(_) => {
+                DingTalkError::Queue("Queue is closed".to_owned())
+            }
+        })
+    }
+
+    /// Count of messages waiting in queue, message being sent is not counted
+    pub fn len(&self) -> usize {
+        self.sender.max_capacity() - self.sender.capacity()
+    }
+
+    /// Is no message waiting in queue
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stop accepting messages and wait until all queued messages are sent
+    ///
+    /// Cancel safe, queued messages are still sent in background if this future is dropped.
+    pub async fn shutdown(self) -> XResult<()> {
+        drop(self.sender);
+        self.worker
+            .await
+            .map_err(|e| DingTalkError::Queue(format!("Queue task failed: {}", e)))
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+use tokio::time::Instant;
+
+/// Sliding window rate limiter, allows at most `max_count` sends in every `per` duration
+///
+/// DingTalk and WeChat Work robots both allow 20 messages per minute.
+#[derive(Debug)]
+pub struct RateLimiter {
+    max_count: usize,
+    per: Duration,
+    sent_instants: Mutex<VecDeque<Instant>>,
+    delayed_count: AtomicU64,
+}
+
+impl RateLimiter {
+    /// Create `RateLimiter`, `max_count` less than 1 is treated as 1
+    pub fn new(max_count: usize, per: Duration) -> Self {
+        RateLimiter {
+            max_count: max_count.max(1),
+            per,
+            sent_instants: Mutex::new(VecDeque::new()),
+            delayed_count: AtomicU64::new(0),
+        }
+    }
+
+    /// Wait until a slot is available, returns waited duration(zero when not delayed)
+    pub async fn acquire(&self) -> Duration {
+        let start = Instant::now();
+        let mut delayed = false;
+        loop {
+            let wait_until = {
+                let now = Instant::now();
+                let mut sent_instants = self.sent_instants.lock().unwrap();
+                while let Some(front) = sent_instants.front() {
+                    if now.duration_since(*front) >= self.per {
+                        sent_instants.pop_front();
+                    } else {
+                        break;
+                    }
+                }
+                if sent_instants.len() < self.max_count {
+                    sent_instants.push_back(now);
+                    if !delayed {
+                        return Duration::ZERO;
+                    }
+                    self.delayed_count.fetch_add(1, Ordering::SeqCst);
+                    return now.duration_since(start);
+                }
+                sent_instants[0] + self.per
+            };
+            delayed = true;
+            tokio::time::sleep_until(wait_until).await;
+        }
+    }
+
+    /// How many sends were delayed by this limiter
+    pub fn delayed_count(&self) -> u64 {
+        self.delayed_count.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,77 @@
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::*;
+
+/// Max bytes of markdown sent by `DingTalk::send_reader` when robot type has no limit
+const READER_MAX_BYTES: usize = 20000;
+/// Bytes read from reader at a time
+const READER_CHUNK_BYTES: usize = 8 * 1024;
+
+impl DingTalk {
+    /// Read `reader` until EOF and send it as markdown code block, e.g. output of a job piped
+    /// to stdin
+    ///
+    /// Content is cut to markdown limit of robot type(20000 bytes when not limited) with
+    /// ellipsis of `DingTalk::truncate_ellipsis`, response `truncated_bytes` tells how many bytes
+    /// were cut, the rest of reader is still read and dropped. Invalid UTF-8 is replaced by
+    /// `U+FFFD`. Empty or blank input is `DingTalkError::Validation` and nothing is sent.
+    pub async fn send_reader<R>(&self, title: &str, mut reader: R) -> XResult<DingTalkResponse>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let max_bytes = self
+            .dingtalk_type
+            .length_limits()
+            .markdown_content
+            .unwrap_or(READER_MAX_BYTES);
+        let mut kept = Vec::new();
+        let mut dropped_bytes = 0;
+        let mut chunk = vec![0; READER_CHUNK_BYTES];
+        loop {
+            let n = reader.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            let keep = n.min(max_bytes - kept.len());
+            kept.extend_from_slice(&chunk[..keep]);
+            dropped_bytes += n - keep;
+        }
+        let text = String::from_utf8_lossy(&kept);
+        let text = text.trim_end();
+        if text.trim().is_empty() {
+            return Err(DingTalkError::Validation(
+                "Input is empty, message is not sent".into(),
+            ));
+        }
+
+        let fence = code_fence(text);
+        let budget = max_bytes.saturating_sub(fence.len() * 2 + 2);
+        let ellipsis = self
+            .truncate_ellipsis
+            .as_deref()
+            .unwrap_or(limits::DEFAULT_TRUNCATE_ELLIPSIS);
+        // input dropped while reading leaves `max_bytes` kept, it is over `budget` and cut here
+        let (text, cut_bytes) = match limits::truncate_text(text, budget, ellipsis) {
+            Some((truncated, cut_bytes)) => (Cow::Owned(truncated), cut_bytes),
+            None => (Cow::Borrowed(text), 0),
+        };
+        let markdown = format!("{}\n{}\n{}", fence, text, fence);
+        let message = DingTalkMessage::new_markdown(title, &markdown);
+        let mut response = self.send_message_with_response_ref(&message).await?;
+        let truncated_bytes = dropped_bytes + cut_bytes;
+        if truncated_bytes > 0 {
+            response.truncated_bytes = Some(truncated_bytes);
+        }
+        Ok(response)
+    }
+}
+
+/// Code fence longer than any backtick run in `text`, at least 3 backticks
+fn code_fence(text: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat(longest.max(2) + 1)
+}
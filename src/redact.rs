@@ -0,0 +1,91 @@
+use std::fmt;
+
+use crate::*;
+
+/// Mask of secrets in `Debug` output and redacted URLs
+const REDACTED: &str = "***";
+/// Query parameters of webhook URLs which carry secrets
+const SECRET_QUERY_KEYS: &[&str] = &["access_token", "key", "token", "sign", "session"];
+/// Path prefixes of webhook URLs whose last segment is secret, Feishu, Discord and Slack
+const SECRET_PATH_PREFIXES: &[&str] = &["/open-apis/bot/v2/hook/", "/api/webhooks/", "/services/"];
+
+/// Keep first and last 4 chars of token, short token is fully masked
+pub(crate) fn redact_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 8 {
+        return REDACTED.to_owned();
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}{}{}", head, REDACTED, tail)
+}
+
+/// Mask secret query parameters and hook path token of URL, URL can not be parsed is fully masked
+pub(crate) fn redact_url(url: &str) -> String {
+    let mut parsed_url = match reqwest::Url::parse(url.trim()) {
+        Ok(parsed_url) => parsed_url,
+        Err(_) => return REDACTED.to_owned(),
+    };
+    if parsed_url.query().is_some() {
+        let pairs: Vec<(String, String)> = parsed_url
+            .query_pairs()
+            .map(|(k, v)| {
+                if SECRET_QUERY_KEYS.contains(&k.as_ref()) {
+                    (k.into_owned(), REDACTED.to_owned())
+                } else {
+                    (k.into_owned(), v.into_owned())
+                }
+            })
+            .collect();
+        parsed_url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    let path = parsed_url.path().to_owned();
+    if SECRET_PATH_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix) && path.len() > prefix.len())
+    {
+        let trimmed_path = path.trim_end_matches('/');
+        if let Some(last_slash) = trimmed_path.rfind('/') {
+            parsed_url.set_path(&format!("{}/{}", &trimmed_path[..last_slash], REDACTED));
+        }
+    }
+    parsed_url.to_string()
+}
+
+/// Secrets are redacted, `access_token` keeps first and last 4 chars, `sec_token` and tokens in
+/// `direct_url` are masked
+impl fmt::Debug for DingTalk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DingTalk")
+            .field("dingtalk_type", &self.dingtalk_type)
+            .field("default_webhook_url", &self.default_webhook_url)
+            .field(
+                "access_token",
+                &redact_secret(&self.access_token, redact_token),
+            )
+            .field(
+                "sec_token",
+                &redact_secret(&self.sec_token, |_| REDACTED.into()),
+            )
+            .field("direct_url", &redact_secret(&self.direct_url, redact_url))
+            .field("security_keyword", &self.security_keyword)
+            .finish_non_exhaustive()
+    }
+}
+
+fn redact_secret(secret: &str, redact: impl Fn(&str) -> String) -> String {
+    if secret.is_empty() {
+        String::new()
+    } else {
+        redact(secret)
+    }
+}
+
+impl DingTalk {
+    /// Webhook URL safe to log, tokens are masked and URL is not signed
+    ///
+    /// e.g. `https://oapi.dingtalk.com/robot/send?access_token=***`
+    pub fn redacted_webhook_url(&self) -> String {
+        redact_url(&self.webhook_url())
+    }
+}
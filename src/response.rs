@@ -0,0 +1,63 @@
+use serde_json::Value;
+use std::time::Duration;
+
+/// Response of send, returned by `DingTalk::send_message_with_response`
+///
+/// `errcode` and `errmsg` are read from DingTalk and WeChat Work `errcode`/`errmsg`, or
+/// Feishu `code`/`msg`, missing ones are `0` and `""`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DingTalkResponse {
+    pub errcode: i64,
+    pub errmsg: String,
+    /// Response body JSON, or `Value::String` of body which is not JSON, e.g. Slack `"ok"`
+    pub raw: Value,
+    /// Bytes cut from message content by `DingTalk::truncate_to_limit`, `None` when not truncated
+    pub truncated_bytes: Option<usize>,
+    /// Time waited for `DingTalk::rate_limit` by this send(all attempts of retries), `None` when
+    /// not delayed
+    pub rate_limit_delay: Option<Duration>,
+}
+
+impl DingTalkResponse {
+    /// Parse response body
+    pub fn from_body(body: &str) -> Self {
+        let raw: Value = match serde_json::from_str(body) {
+            Ok(v) => v,
+            Err(_) => {
+                return DingTalkResponse {
+                    errcode: 0,
+                    errmsg: String::new(),
+                    raw: Value::String(body.to_owned()),
+                    truncated_bytes: None,
+                    rate_limit_delay: None,
+                }
+            }
+        };
+        let (errcode, errmsg) = match raw["errcode"].as_i64() {
+            Some(errcode) => (errcode, &raw["errmsg"]),
+            None => (raw["code"].as_i64().unwrap_or_default(), &raw["msg"]),
+        };
+        DingTalkResponse {
+            errcode,
+            errmsg: errmsg.as_str().unwrap_or_default().to_owned(),
+            raw,
+            truncated_bytes: None,
+            rate_limit_delay: None,
+        }
+    }
+
+    /// Is `errcode` zero
+    pub fn is_success(&self) -> bool {
+        self.errcode == 0
+    }
+
+    /// Is message content truncated, see `DingTalk::truncate_to_limit`
+    pub fn is_truncated(&self) -> bool {
+        self.truncated_bytes.is_some()
+    }
+
+    /// Is message not sent since it is a duplicate, see `DingTalk::dedup_window`
+    pub fn is_suppressed(&self) -> bool {
+        self.raw["suppressed"] == true
+    }
+}
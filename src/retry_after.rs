@@ -0,0 +1,98 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::clock::system_now;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Parse `Retry-After` header value, delay seconds or HTTP-date(IMF-fixdate) like
+/// `Sun, 06 Nov 1994 08:49:37 GMT`, HTTP-date in the past is zero delay
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    parse_retry_after_at(value, system_now())
+}
+
+fn parse_retry_after_at(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = parse_http_date(value)?;
+    Some(date.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    if parts.len() != 6 || !parts[0].ends_with(',') || parts[5] != "GMT" {
+        return None;
+    }
+    let day: u64 = parts[1].parse().ok()?;
+    let month = MONTHS.iter().position(|m| *m == parts[2])? as u64 + 1;
+    let year: u64 = parts[3].parse().ok()?;
+    let mut hms = parts[4].splitn(3, ':').map(|p| p.parse::<u64>().ok());
+    let (hour, minute, second) = (hms.next()??, hms.next()??, hms.next()??);
+    if year < 1970 || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let seconds = days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second;
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+/// Days since 1970-01-01 of Gregorian date, year is at least 1970
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn retry_after_seconds() {
+        assert_eq!(Some(Duration::from_secs(120)), parse_retry_after(" 120 "));
+        assert_eq!(Some(Duration::ZERO), parse_retry_after("0"));
+    }
+
+    #[test]
+    fn retry_after_http_date() {
+        assert_eq!(
+            Some(Duration::from_secs(30)),
+            parse_retry_after_at("Sun, 06 Nov 1994 08:49:37 GMT", at(784111747))
+        );
+        assert_eq!(
+            Some(Duration::from_secs(60)),
+            parse_retry_after_at("Wed, 21 Oct 2015 07:28:00 GMT", at(1445412420))
+        );
+        assert_eq!(
+            Some(Duration::from_secs(1)),
+            parse_retry_after_at("Thu, 29 Feb 2024 00:00:00 GMT", at(1709164799))
+        );
+    }
+
+    #[test]
+    fn retry_after_http_date_in_past() {
+        assert_eq!(
+            Some(Duration::ZERO),
+            parse_retry_after_at("Sun, 06 Nov 1994 08:49:37 GMT", at(784111778))
+        );
+    }
+
+    #[test]
+    fn retry_after_invalid() {
+        assert_eq!(None, parse_retry_after(""));
+        assert_eq!(None, parse_retry_after("-1"));
+        assert_eq!(None, parse_retry_after("soon"));
+        assert_eq!(None, parse_retry_after("Sun, 06 Foo 1994 08:49:37 GMT"));
+        assert_eq!(None, parse_retry_after("Sun, 06 Nov 1994 08:49 GMT"));
+        assert_eq!(None, parse_retry_after("Sun, 06 Nov 1994 08:49:37 UTC"));
+    }
+}
@@ -0,0 +1,144 @@
+use serde_json::json;
+
+use crate::{app::call_api, *};
+
+const DEFAULT_ROBOT_API_URL: &str = "https://api.dingtalk.com";
+/// Header of access token of new API(`api.dingtalk.com`)
+const ACCESS_TOKEN_HEADER: &str = "x-acs-dingtalk-access-token";
+/// Max `userIds` size of one `oToMessages/batchSend` call
+const OTO_MAX_USER_IDS: usize = 20;
+/// HTTP status of invalid or expired access token
+const UNAUTHORIZED_STATUS: u16 = 401;
+
+/// Enterprise robot by `robotCode`, sends 1:1 messages to users by access token of
+/// `DingTalkApp`
+///
+/// Document https://open.dingtalk.com/document/orgapp/chatbots-send-one-on-one-chat-messages-in-batches
+///
+/// Sample code:
+/// ```ignore
+/// let app = Arc::new(DingTalkApp::new("<appkey>", "<appsecret>"));
+/// let robot = RobotCodeClient::new(app, "<robot code>");
+/// let keys = robot.send_oto_text(&["<userid>"], "Hello world!").await?;
+/// ```
+pub struct RobotCodeClient {
+    pub robot_code: String,
+    app: Arc<DingTalkApp>,
+    api_url: String,
+}
+
+impl RobotCodeClient {
+    /// Create `RobotCodeClient`, access token of `app` is shared with other users of `app`
+    pub fn new(app: Arc<DingTalkApp>, robot_code: &str) -> Self {
+        RobotCodeClient {
+            robot_code: robot_code.into(),
+            app,
+            api_url: DEFAULT_ROBOT_API_URL.into(),
+        }
+    }
+
+    /// Set API URL, default is `https://api.dingtalk.com`, e.g. mock server in tests
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Send text(`sampleText`) to users, returns `processQueryKey` of each batch
+    ///
+    /// Users are sent in batches of 20, see `RobotCodeClient::send_oto`.
+    pub async fn send_oto_text(&self, user_ids: &[&str], text: &str) -> XResult<Vec<String>> {
+        self.send_oto(user_ids, "sampleText", json!({ "content": text }))
+            .await
+    }
+
+    /// Send markdown(`sampleMarkdown`) to users, returns `processQueryKey` of each batch
+    pub async fn send_oto_markdown(
+        &self,
+        user_ids: &[&str],
+        title: &str,
+        text: &str,
+    ) -> XResult<Vec<String>> {
+        self.send_oto(
+            user_ids,
+            "sampleMarkdown",
+            json!({ "title": title, "text": text }),
+        )
+        .await
+    }
+
+    /// Send message template `msg_key` with `msg_param` to users by
+    /// `/v1.0/robot/oToMessages/batchSend`, returns `processQueryKey` of each batch
+    ///
+    /// Users are sent in batches of 20. Failed batch returns its error, batches before it are
+    /// already sent. Unknown or invalid `robotCode` returns `DingTalkError::Config`.
+    pub async fn send_oto(
+        &self,
+        user_ids: &[&str],
+        msg_key: &str,
+        msg_param: Value,
+    ) -> XResult<Vec<String>> {
+        if user_ids.is_empty() {
+            return Err(DingTalkError::Validation(
+                "Robot 1:1 message requires userIds".to_owned(),
+            ));
+        }
+        let msg_param = msg_param.to_string();
+        let mut process_query_keys = Vec::with_capacity(user_ids.len().div_ceil(OTO_MAX_USER_IDS));
+        for batch in user_ids.chunks(OTO_MAX_USER_IDS) {
+            let body = json!({
+                "robotCode": self.robot_code,
+                "userIds": batch,
+                "msgKey": msg_key,
+                "msgParam": msg_param,
+            });
+            let response = self.call("/v1.0/robot/oToMessages/batchSend", body).await?;
+            match response.raw["processQueryKey"].as_str() {
+                Some(process_query_key) => process_query_keys.push(process_query_key.to_owned()),
+                None => {
+                    return Err(DingTalkError::Server {
+                        errcode: -1,
+                        errmsg: format!("No processQueryKey in response: {}", response.raw),
+                    })
+                }
+            }
+        }
+        Ok(process_query_keys)
+    }
+
+    /// POST `body` to new API, invalid access token is dropped, invalid `robotCode` is
+    /// `DingTalkError::Config`
+    pub(crate) async fn call(&self, path: &str, body: Value) -> XResult<DingTalkResponse> {
+        let access_token = self.app.access_token().await?;
+        let request = self
+            .app
+            .client
+            .post(format!("{}{}", self.api_url, path))
+            .header(ACCESS_TOKEN_HEADER, access_token)
+            .header(CONTENT_TYPE, APPLICATION_JSON_UTF8)
+            .body(body.to_string());
+        match call_api(request).await {
+            Err(DingTalkError::Status { status, body }) => {
+                if status == UNAUTHORIZED_STATUS {
+                    self.app.invalidate_token().await;
+                } else if let Some(message) = invalid_robot_code_message(&body) {
+                    return Err(DingTalkError::Config(format!(
+                        "Invalid robotCode {}: {}",
+                        self.robot_code, message
+                    )));
+                }
+                Err(DingTalkError::Status { status, body })
+            }
+            result => result,
+        }
+    }
+}
+
+/// `message` of error about `robotCode`, new API replies errors as
+/// `{"code":"invalidParameter.robotCode","message":"..."}`
+fn invalid_robot_code_message(body: &str) -> Option<String> {
+    let error: Value = serde_json::from_str(body).ok()?;
+    let code = error["code"].as_str()?;
+    let message = error["message"].as_str().unwrap_or(code);
+    let is_robot_code = |s: &str| s.to_ascii_lowercase().contains("robotcode");
+    (is_robot_code(code) || is_robot_code(message)).then(|| message.to_owned())
+}
@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use tokio::task::JoinHandle;
+
+use crate::*;
+
+/// Message is waiting for its time
+const PENDING: u8 = 0;
+/// Message is being sent or sent, it can not be cancelled any more
+const SENDING: u8 = 1;
+/// Message is cancelled, it is never sent
+const CANCELLED: u8 = 2;
+
+/// Handle of message scheduled by `DingTalk::send_message_at` or `DingTalk::send_message_after`
+///
+/// Dropping handle does not cancel, the message is still sent at its time. Pending messages are
+/// lost when the runtime shuts down.
+pub struct ScheduledSend {
+    state: Arc<AtomicU8>,
+    task: JoinHandle<()>,
+}
+
+impl ScheduledSend {
+    /// Cancel pending message, `true` guarantees it is never sent, `false` when it is already
+    /// being sent, sent or cancelled
+    pub fn cancel(&self) -> bool {
+        let cancelled = self
+            .state
+            .compare_exchange(PENDING, CANCELLED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok();
+        if cancelled {
+            self.task.abort();
+        }
+        cancelled
+    }
+
+    /// Is message waiting for its time, neither sent nor cancelled
+    pub fn is_pending(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == PENDING
+    }
+}
+
+impl DingTalk {
+    /// Send message at `when` by a background tokio task, e.g. "maintenance finished" at the end
+    /// of maintenance window, `when` in the past is sent at once
+    ///
+    /// Delay is `when` minus now of `DingTalk::with_clock` or system clock, and is waited by tokio
+    /// timer, later changes of system clock are not followed. Same as
+    /// `DingTalk::send_message_after` otherwise.
+    pub fn send_message_at(
+        &self,
+        dingtalk_message: DingTalkMessage,
+        when: SystemTime,
+    ) -> ScheduledSend {
+        let now = SystemTime::UNIX_EPOCH + self.since_epoch();
+        let delay = when.duration_since(now).unwrap_or_default();
+        self.send_message_after(dingtalk_message, delay)
+    }
+
+    /// Send message after `delay` by a background tokio task, returns handle to cancel it
+    ///
+    /// Sent by `DingTalk::send_message_silent`, failures are counted by
+    /// `DingTalk::silent_failure_count`. Panics when called outside of a tokio runtime, same as
+    /// `tokio::spawn`.
+    ///
+    /// Sample code:
+    /// ```ignore
+    /// let message = DingTalkMessage::new_text("maintenance finished");
+    /// let scheduled = dt.send_message_after(message, Duration::from_secs(3600));
+    /// // maintenance is extended
+    /// scheduled.cancel();
+    /// ```
+    pub fn send_message_after(
+        &self,
+        dingtalk_message: DingTalkMessage,
+        delay: Duration,
+    ) -> ScheduledSend {
+        let dingtalk = self.clone();
+        let state = Arc::new(AtomicU8::new(PENDING));
+        let task_state = state.clone();
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            // cancel after this point returns false, so a cancelled message is never sent
+            if task_state
+                .compare_exchange(PENDING, SENDING, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                dingtalk.send_message_silent(dingtalk_message).await;
+            }
+        });
+        ScheduledSend { state, task }
+    }
+}
@@ -0,0 +1,44 @@
+use std::ops::Deref;
+
+#[cfg(feature = "zeroize")]
+use crate::DingTalk;
+
+/// Buffer containing secret, e.g. signed URL or string to sign, wiped on drop with feature
+/// `zeroize`
+pub(crate) struct SecretBuffer(pub(crate) String);
+
+impl Deref for SecretBuffer {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Drop for SecretBuffer {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+/// Zeroize `secret` with feature `zeroize`, otherwise do nothing
+pub(crate) fn wipe(secret: &mut String) {
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(secret);
+    #[cfg(not(feature = "zeroize"))]
+    let _ = secret;
+}
+
+/// Secrets are zeroized on drop with feature `zeroize`
+///
+/// `access_token`, `sec_token`, `direct_url` and `default_webhook_url`(full webhook URL of Slack
+/// and Discord) are wiped, copies made by reqwest or by caller are out of scope.
+#[cfg(feature = "zeroize")]
+impl Drop for DingTalk {
+    fn drop(&mut self) {
+        wipe(&mut self.access_token);
+        wipe(&mut self.sec_token);
+        wipe(&mut self.direct_url);
+        wipe(&mut self.default_webhook_url);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::*;
+
+/// Webhook platform, creates message payload and endpoint URL
+///
+/// `DingTalk` implements it for built-in robot types(`DingTalkType`), custom platform can be
+/// registered by `DingTalk::with_sender`.
+///
+/// Sample code:
+/// ```ignore
+/// struct MyChat { url: String }
+///
+/// impl WebhookSender for MyChat {
+///     fn build_payload(&self, dingtalk_message: &DingTalkMessage) -> XResult<Value> {
+///         Ok(serde_json::json!({ "msg": dingtalk_message.text_content }))
+///     }
+///
+///     fn endpoint_url(&self) -> XResult<String> {
+///         Ok(self.url.clone())
+///     }
+/// }
+///
+/// let dt = DingTalk::default().with_sender(MyChat { url: "https://chat.example.com/hook".into() });
+/// dt.send_text("Hello world!").await?;
+/// ```
+pub trait WebhookSender: Send + Sync {
+    /// Create JSON payload of message, it is POSTed as is
+    fn build_payload(&self, dingtalk_message: &DingTalkMessage) -> XResult<Value>;
+
+    /// Webhook endpoint URL, called before each request so signed URL can be fresh
+    fn endpoint_url(&self) -> XResult<String>;
+}
+
+impl WebhookSender for DingTalk {
+    fn build_payload(&self, dingtalk_message: &DingTalkMessage) -> XResult<Value> {
+        DingTalk::build_payload(self, dingtalk_message)
+    }
+
+    fn endpoint_url(&self) -> XResult<String> {
+        match &self.sender {
+            Some(sender) => sender.endpoint_url(),
+            None => self.generate_signed_url(),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! DingTalk robot signing, usable without `DingTalk`, e.g. generate signed webhook URL elsewhere
+//!
+//! Sample code:
+//! ```ignore
+//! let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+//! let url = format!(
+//!     "https://oapi.dingtalk.com/robot/send?access_token={}&{}",
+//!     access_token,
+//!     dingtalk::sign::signed_query(sec_token, timestamp)?
+//! );
+//! ```
+use crate::{calc_hmac_sha256, secret::SecretBuffer, XResult};
+
+/// Sign of DingTalk robot, base64 of HmacSHA256 of `"{timestamp}\n{secret}"` keyed by secret
+///
+/// `timestamp_ms` is milliseconds since UNIX epoch, the result is NOT percent-encoded.
+pub fn compute_sign(secret: &str, timestamp_ms: u128) -> XResult<String> {
+    let timestamp_and_secret = SecretBuffer(format!("{}\n{}", timestamp_ms, secret));
+    Ok(base64::encode(
+        &calc_hmac_sha256(secret.as_bytes(), timestamp_and_secret.as_bytes())?[..],
+    ))
+}
+
+/// Query `timestamp={timestamp_ms}&sign={sign}` of signed webhook URL, sign is percent-encoded
+pub fn signed_query(secret: &str, timestamp_ms: u128) -> XResult<String> {
+    let sign = compute_sign(secret, timestamp_ms)?;
+    Ok(format!(
+        "timestamp={}&sign={}",
+        timestamp_ms,
+        urlencoding::encode(&sign)
+    ))
+}
@@ -0,0 +1,48 @@
+use std::sync::atomic::Ordering;
+
+use crate::*;
+
+/// Best-effort send, errors are not returned
+impl DingTalk {
+    /// Send DingTalk message, best-effort, errors are dropped
+    ///
+    /// Delivery is not guaranteed, a failed message is neither returned nor resent(except retries
+    /// by `DingTalk::with_retry`). Failures are counted by `DingTalk::silent_failure_count`,
+    /// logged as `warn` event with feature `tracing` and as `warn` log record of target
+    /// `dingtalk::silent` with feature `log-forward`. Without both features the counter is the
+    /// only record. Use `DingTalk::send_message` when the result matters.
+    pub async fn send_message_silent(&self, dingtalk_message: DingTalkMessage) {
+        if let Err(e) = self.send_message_ref(&dingtalk_message).await {
+            self.silent_failures.fetch_add(1, Ordering::SeqCst);
+            #[cfg(feature = "tracing")]
+            trace::silent_failure(&e);
+            #[cfg(feature = "log")]
+            log::warn!(
+                "silent send failed, error is dropped, error_kind: {}, errcode: {:?}",
+                e.kind(),
+                e.server_errcode()
+            );
+            #[cfg(not(any(feature = "tracing", feature = "log")))]
+            let _ = e;
+        }
+    }
+
+    /// Spawn `DingTalk::send_message_silent` on current tokio runtime, best-effort
+    ///
+    /// Returns without waiting, the returned handle can be awaited or dropped. Panics when called
+    /// outside of a tokio runtime, same as `tokio::spawn`. Pending sends are lost when the
+    /// runtime shuts down.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn_send_message_silent(
+        &self,
+        dingtalk_message: DingTalkMessage,
+    ) -> tokio::task::JoinHandle<()> {
+        let dingtalk = self.clone();
+        tokio::spawn(async move { dingtalk.send_message_silent(dingtalk_message).await })
+    }
+
+    /// How many sends of `DingTalk::send_message_silent` failed, shared by clones
+    pub fn silent_failure_count(&self) -> u64 {
+        self.silent_failures.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,91 @@
+use crate::*;
+use serde_json::json;
+
+/// Slack incoming webhook, document https://api.slack.com/messaging/webhooks
+impl DingTalk {
+    /// Create Slack message JSON
+    ///
+    /// * Text - `text`
+    /// * Markdown - section block with mrkdwn text, title in bold
+    /// * Link - attachment with title, title link, text and thumb
+    /// * ActionCard - section block with mrkdwn text and actions block with buttons
+    pub(crate) fn slack_message_json(&self, dingtalk_message: &DingTalkMessage) -> XResult<Value> {
+        match dingtalk_message.message_type {
+            DingTalkMessageType::Text => {
+                let mut text = dingtalk_message.text_content.clone();
+                for user_id in &dingtalk_message.at_user_ids {
+                    text.push_str(&format!(" <@{}>", user_id));
+                }
+                if dingtalk_message.at_all {
+                    text.push_str(" <!channel>");
+                }
+                Ok(json!({ "text": text }))
+            }
+            DingTalkMessageType::Markdown => Ok(json!({
+                "text": dingtalk_message.markdown_title,
+                "blocks": [slack_mrkdwn_section(
+                    &dingtalk_message.markdown_title,
+                    &dingtalk_message.markdown_content,
+                )],
+            })),
+            DingTalkMessageType::Link => {
+                let mut attachment = json!({
+                    "title": dingtalk_message.link_title,
+                    "title_link": dingtalk_message.link_message_url,
+                    "text": dingtalk_message.link_text,
+                });
+                if !dingtalk_message.link_pic_url.is_empty() {
+                    attachment["thumb_url"] = dingtalk_message.link_pic_url.as_str().into();
+                }
+                Ok(json!({
+                    "text": dingtalk_message.link_title,
+                    "attachments": [attachment],
+                }))
+            }
+            DingTalkMessageType::ActionCard => {
+                let btns: Vec<&DingTalkMessageActionCardBtn> =
+                    match &dingtalk_message.action_card_single_btn {
+                        Some(single_btn) => vec![single_btn],
+                        None => dingtalk_message.action_card_btns.iter().collect(),
+                    };
+                let mut blocks = vec![slack_mrkdwn_section(
+                    &dingtalk_message.action_card_title,
+                    &dingtalk_message.action_card_text,
+                )];
+                if !btns.is_empty() {
+                    let elements: Vec<Value> = btns
+                        .iter()
+                        .map(|btn| {
+                            json!({
+                                "type": "button",
+                                "text": { "type": "plain_text", "text": btn.title },
+                                "url": btn.action_url,
+                            })
+                        })
+                        .collect();
+                    blocks.push(json!({ "type": "actions", "elements": elements }));
+                }
+                Ok(json!({
+                    "text": dingtalk_message.action_card_title,
+                    "blocks": blocks,
+                }))
+            }
+            message_type => Err(DingTalkError::Unsupported(format!(
+                "Message type {:?} is unsupported for robot type {:?}",
+                message_type, self.dingtalk_type
+            ))),
+        }
+    }
+}
+
+fn slack_mrkdwn_section(title: &str, text: &str) -> Value {
+    let mrkdwn = if title.is_empty() {
+        text.to_owned()
+    } else {
+        format!("*{}*\n{}", title, text)
+    };
+    json!({
+        "type": "section",
+        "text": { "type": "mrkdwn", "text": mrkdwn },
+    })
+}
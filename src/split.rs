@@ -0,0 +1,52 @@
+/// Split `text` into chunks with ` (i/n)` suffix, each chunk is at most `max_bytes` bytes,
+/// see `DingTalkMessage::split_text`
+pub(crate) fn split_text(text: &str, max_bytes: usize) -> Vec<String> {
+    if text.len() <= max_bytes {
+        return vec![text.to_owned()];
+    }
+    // suffix length depends on digits of chunk count, grows until chunk count fits
+    let mut digits = 1;
+    loop {
+        let suffix_len = " (/)".len() + 2 * digits;
+        let chunks = split_chunks(text, max_bytes.saturating_sub(suffix_len));
+        let count = chunks.len();
+        if count.to_string().len() <= digits {
+            return chunks
+                .iter()
+                .enumerate()
+                .map(|(i, chunk)| format!("{} ({}/{})", chunk, i + 1, count))
+                .collect();
+        }
+        digits = count.to_string().len();
+    }
+}
+
+/// Split on the last line break within `max_bytes`, or on char boundary when a line is too long,
+/// chunk has at least one char even `max_bytes` is less than it
+fn split_chunks(text: &str, max_bytes: usize) -> Vec<&str> {
+    let mut chunks = vec![];
+    let mut rest = text;
+    while rest.len() > max_bytes {
+        let mut end = max_bytes;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            end = rest.chars().next().map_or(1, char::len_utf8);
+        }
+        match rest[..end].rfind('\n') {
+            Some(line_end) if line_end > 0 => {
+                chunks.push(rest[..line_end].trim_end_matches('\r'));
+                rest = &rest[line_end + 1..];
+            }
+            _ => {
+                chunks.push(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
@@ -0,0 +1,326 @@
+//! Stream mode client of robot messages, feature `stream`
+//!
+//! Stream mode receives robot messages over a WebSocket opened by client, no public HTTP endpoint
+//! is needed. Document https://open.dingtalk.com/document/orgapp/stream
+//!
+//! Sample code:
+//! ```ignore
+//! let mut messages = StreamClient::new("<appkey>", "<appsecret>").start();
+//! while let Some(message) = messages.next().await {
+//!     message.reply_target()?.send_text(&message.text().unwrap_or_default()).await?;
+//! }
+//! ```
+use futures::{SinkExt, Stream, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::{mpsc, watch},
+    task::JoinHandle,
+};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::app::call_api;
+use crate::outgoing::OutgoingMessage;
+#[cfg(feature = "tracing")]
+use crate::trace;
+use crate::{DingTalkError, XResult, APPLICATION_JSON_UTF8, CONTENT_TYPE, DEFAULT_USER_AGENT};
+
+const DEFAULT_STREAM_API_URL: &str = "https://api.dingtalk.com";
+/// Topic of robot message callback
+pub const BOT_MESSAGE_TOPIC: &str = "/v1.0/im/bot/messages/get";
+const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_RECONNECT_INITIAL_DELAY: Duration = Duration::from_secs(1);
+const DEFAULT_RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+/// Messages buffered before `StreamMessages` is polled
+const MESSAGE_BUFFER: usize = 64;
+
+/// WebSocket endpoint returned by `/v1.0/gateway/connections/open`
+#[derive(Clone, Debug, PartialEq)]
+pub struct StreamConnection {
+    pub endpoint: String,
+    pub ticket: String,
+}
+
+impl StreamConnection {
+    /// WebSocket URL, endpoint with ticket
+    pub fn url(&self) -> String {
+        let separator = if self.endpoint.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        format!(
+            "{}{}ticket={}",
+            self.endpoint,
+            separator,
+            urlencoding::encode(&self.ticket)
+        )
+    }
+}
+
+/// Stream mode client, subscribes robot messages by `appkey` and `appsecret`
+///
+/// Each connection is opened by `/v1.0/gateway/connections/open` with a new ticket, so the
+/// subscription is renewed after reconnecting. Connection is checked by WebSocket ping every 30
+/// seconds(see `StreamClient::with_ping_interval`), no frame between two pings is treated as
+/// disconnected. Reconnect waits 1 second and doubles up to 60 seconds(see
+/// `StreamClient::with_reconnect_delay`), the delay is reset after a connection is established.
+pub struct StreamClient {
+    pub appkey: String,
+    pub appsecret: String,
+    api_url: String,
+    ping_interval: Duration,
+    reconnect_initial_delay: Duration,
+    reconnect_max_delay: Duration,
+    client: reqwest::Client,
+}
+
+impl StreamClient {
+    /// Create `StreamClient`, `appkey` and `appsecret` are the same as `DingTalkApp`
+    pub fn new(appkey: &str, appsecret: &str) -> Self {
+        StreamClient {
+            appkey: appkey.into(),
+            appsecret: appsecret.into(),
+            api_url: DEFAULT_STREAM_API_URL.into(),
+            ping_interval: DEFAULT_PING_INTERVAL,
+            reconnect_initial_delay: DEFAULT_RECONNECT_INITIAL_DELAY,
+            reconnect_max_delay: DEFAULT_RECONNECT_MAX_DELAY,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Set API URL, default is `https://api.dingtalk.com`, e.g. mock server in tests
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Set interval of WebSocket ping, default is 30 seconds
+    pub fn with_ping_interval(mut self, ping_interval: Duration) -> Self {
+        self.ping_interval = ping_interval;
+        self
+    }
+
+    /// Set first and max delay before reconnecting, default is 1 and 60 seconds
+    pub fn with_reconnect_delay(mut self, initial_delay: Duration, max_delay: Duration) -> Self {
+        self.reconnect_initial_delay = initial_delay;
+        self.reconnect_max_delay = max_delay.max(initial_delay);
+        self
+    }
+
+    /// Get WebSocket endpoint and ticket subscribing robot messages
+    pub async fn open_connection(&self) -> XResult<StreamConnection> {
+        let body = json!({
+            "clientId": self.appkey,
+            "clientSecret": self.appsecret,
+            "subscriptions": [{ "type": "CALLBACK", "topic": BOT_MESSAGE_TOPIC }],
+            "ua": DEFAULT_USER_AGENT,
+        });
+        let request = self
+            .client
+            .post(format!("{}/v1.0/gateway/connections/open", self.api_url))
+            .header(CONTENT_TYPE, APPLICATION_JSON_UTF8)
+            .body(body.to_string());
+        let response = call_api(request).await?;
+        match (
+            response.raw["endpoint"].as_str(),
+            response.raw["ticket"].as_str(),
+        ) {
+            (Some(endpoint), Some(ticket)) if !endpoint.is_empty() => Ok(StreamConnection {
+                endpoint: endpoint.to_owned(),
+                ticket: ticket.to_owned(),
+            }),
+            _ => Err(DingTalkError::Server {
+                errcode: -1,
+                errmsg: format!("No endpoint or ticket in response: {}", response.raw),
+            }),
+        }
+    }
+
+    /// Connect in a task spawned on current tokio runtime, robot messages are yielded by
+    /// returned `StreamMessages`
+    ///
+    /// Panics when called outside of a tokio runtime. Connection is kept(and reconnected) until
+    /// `StreamMessages::shutdown` or `StreamMessages` is dropped.
+    pub fn start(self) -> StreamMessages {
+        let (sender, receiver) = mpsc::channel(MESSAGE_BUFFER);
+        let (shutdown, shutdown_receiver) = watch::channel(false);
+        let worker = tokio::spawn(self.run(sender, shutdown_receiver));
+        StreamMessages {
+            receiver,
+            shutdown,
+            worker,
+        }
+    }
+
+    async fn run(self, sender: mpsc::Sender<OutgoingMessage>, mut shutdown: watch::Receiver<bool>) {
+        let mut delay = self.reconnect_initial_delay;
+        loop {
+            match self.run_connection(&sender, &mut shutdown).await {
+                Ok(ConnectionEnd::Shutdown) => return,
+                Ok(ConnectionEnd::Disconnected) => delay = self.reconnect_initial_delay,
+                Err(e) => {
+                    #[cfg(feature = "tracing")]
+                    trace::stream_connect_failed(&e);
+                    #[cfg(not(feature = "tracing"))]
+                    let _ = e;
+                }
+            }
+            if *shutdown.borrow() || sender.is_closed() {
+                return;
+            }
+            #[cfg(feature = "tracing")]
+            trace::stream_reconnect(delay);
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.changed() => return,
+                _ = sender.closed() => return,
+            }
+            delay = (delay * 2).min(self.reconnect_max_delay);
+        }
+    }
+
+    /// Open and read one connection, error is returned only when connection is not established
+    async fn run_connection(
+        &self,
+        sender: &mpsc::Sender<OutgoingMessage>,
+        shutdown: &mut watch::Receiver<bool>,
+    ) -> XResult<ConnectionEnd> {
+        let connection = self.open_connection().await?;
+        let (websocket, _) = tokio_tungstenite::connect_async(connection.url())
+            .await
+            .map_err(|e| DingTalkError::Transport(format!("WebSocket connect failed: {}", e)))?;
+        let (mut write, mut read) = websocket.split();
+        let mut ping = tokio::time::interval(self.ping_interval);
+        ping.tick().await;
+        let mut alive = true;
+        loop {
+            tokio::select! {
+                _ = shutdown.changed() => {
+                    let _ = write.send(Message::Close(None)).await;
+                    return Ok(ConnectionEnd::Shutdown);
+                }
+                _ = sender.closed() => {
+                    let _ = write.send(Message::Close(None)).await;
+                    return Ok(ConnectionEnd::Shutdown);
+                }
+                _ = ping.tick() => {
+                    if !alive || write.send(Message::Ping(vec![])).await.is_err() {
+                        return Ok(ConnectionEnd::Disconnected);
+                    }
+                    alive = false;
+                }
+                frame = read.next() => {
+                    alive = true;
+                    let text = match frame {
+                        Some(Ok(Message::Text(text))) => text,
+                        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                            return Ok(ConnectionEnd::Disconnected);
+                        }
+                        Some(Ok(_)) => continue,
+                    };
+                    let frame = match serde_json::from_str::<StreamFrame>(&text) {
+                        Ok(frame) => frame,
+                        Err(_) => continue,
+                    };
+                    match (frame.frame_type.as_str(), frame.headers.topic.as_str()) {
+                        ("SYSTEM", "ping") => {
+                            let ack = frame.ack(&frame.data);
+                            if write.send(Message::Text(ack)).await.is_err() {
+                                return Ok(ConnectionEnd::Disconnected);
+                            }
+                        }
+                        ("SYSTEM", "disconnect") => return Ok(ConnectionEnd::Disconnected),
+                        ("CALLBACK", BOT_MESSAGE_TOPIC) => {
+                            if let Ok(message) = OutgoingMessage::from_json(&frame.data) {
+                                if sender.send(message).await.is_err() {
+                                    let _ = write.send(Message::Close(None)).await;
+                                    return Ok(ConnectionEnd::Shutdown);
+                                }
+                            }
+                            let ack = frame.ack(r#"{"response":null}"#);
+                            if write.send(Message::Text(ack)).await.is_err() {
+                                return Ok(ConnectionEnd::Disconnected);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+enum ConnectionEnd {
+    Shutdown,
+    Disconnected,
+}
+
+/// Frame of stream mode, `data` is JSON string
+#[derive(Deserialize)]
+struct StreamFrame {
+    #[serde(rename = "type")]
+    frame_type: String,
+    #[serde(default)]
+    headers: StreamFrameHeaders,
+    #[serde(default)]
+    data: String,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StreamFrameHeaders {
+    #[serde(default)]
+    topic: String,
+    #[serde(default)]
+    message_id: String,
+}
+
+impl StreamFrame {
+    /// Acknowledge frame by `messageId`, server resends frame not acknowledged
+    fn ack(&self, data: &str) -> String {
+        json!({
+            "code": 200,
+            "headers": {
+                "contentType": "application/json",
+                "messageId": self.headers.message_id,
+            },
+            "message": "OK",
+            "data": data,
+        })
+        .to_string()
+    }
+}
+
+/// Robot messages of `StreamClient::start`, ends after `StreamMessages::shutdown`
+///
+/// Messages are acknowledged after they are buffered, at most 64 messages are buffered before
+/// reading from WebSocket is paused.
+pub struct StreamMessages {
+    receiver: mpsc::Receiver<OutgoingMessage>,
+    shutdown: watch::Sender<bool>,
+    worker: JoinHandle<()>,
+}
+
+impl StreamMessages {
+    /// Close WebSocket and stop reconnecting, waits until the connection task exits
+    ///
+    /// Buffered messages are dropped.
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        let _ = self.worker.await;
+    }
+}
+
+impl Stream for StreamMessages {
+    type Item = OutgoingMessage;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
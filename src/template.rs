@@ -0,0 +1,43 @@
+use crate::*;
+use std::collections::HashMap;
+
+/// Render `{{name}}` placeholders by `vars`, see `DingTalkMessage::render_template`
+pub(crate) fn render_template(template: &str, vars: &HashMap<&str, &str>) -> XResult<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut unresolved: Vec<&str> = vec![];
+    let mut rest = template;
+    while let Some(pos) = rest.find("{{") {
+        if rest[..pos].ends_with('\\') {
+            rendered.push_str(&rest[..pos - 1]);
+            rendered.push_str("{{");
+            rest = &rest[pos + 2..];
+            continue;
+        }
+        rendered.push_str(&rest[..pos]);
+        let after = &rest[pos + 2..];
+        let end = after.find("}}").ok_or_else(|| {
+            DingTalkError::Validation(format!("Unclosed `{{{{` in template: {}", template))
+        })?;
+        let name = after[..end].trim();
+        if name.is_empty() {
+            return Err(DingTalkError::Validation(format!(
+                "Empty placeholder in template: {}",
+                template
+            )));
+        }
+        match vars.get(name) {
+            Some(value) => rendered.push_str(value),
+            None if !unresolved.contains(&name) => unresolved.push(name),
+            None => {}
+        }
+        rest = &after[end + 2..];
+    }
+    if !unresolved.is_empty() {
+        return Err(DingTalkError::Validation(format!(
+            "Unresolved template placeholders: {}",
+            unresolved.join(", ")
+        )));
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
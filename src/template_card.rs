@@ -0,0 +1,295 @@
+use crate::{DingTalkError, XResult};
+use serde::Serialize;
+
+const HORIZONTAL_CONTENT_MAX_COUNT: usize = 6;
+const JUMP_MAX_COUNT: usize = 3;
+
+/// WeChat Work template card type
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum WeChatTemplateCardType {
+    #[serde(rename = "text_notice")]
+    TextNotice,
+    #[serde(rename = "news_notice")]
+    NewsNotice,
+}
+
+/// Template card source
+#[derive(Clone, Debug, Serialize)]
+pub struct WeChatTemplateCardSource {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub icon_url: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub desc: String,
+}
+
+/// Template card title and description, used by main title and emphasis content
+#[derive(Clone, Debug, Serialize)]
+pub struct WeChatTemplateCardTitle {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub desc: String,
+}
+
+/// Template card image, news notice only
+#[derive(Clone, Debug, Serialize)]
+pub struct WeChatTemplateCardImage {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aspect_ratio: Option<f64>,
+}
+
+/// Template card horizontal content, `content_type` 0 is text, 1 is URL
+#[derive(Clone, Debug, Serialize)]
+pub struct WeChatTemplateCardHorizontalContent {
+    #[serde(rename = "type", skip_serializing_if = "is_zero")]
+    pub content_type: u8,
+    pub keyname: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub value: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub url: String,
+}
+
+/// Template card jump or card action, `action_type` 1 is URL, 2 is mini program
+#[derive(Clone, Debug, Serialize)]
+pub struct WeChatTemplateCardAction {
+    #[serde(rename = "type")]
+    pub action_type: u8,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub appid: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pagepath: String,
+}
+
+/// WeChat Work template card message, send by `DingTalk::send_template_card`
+///
+/// Document https://developer.work.weixin.qq.com/document/path/91770#模版卡片类型
+///
+/// Sample code:
+/// ```ignore
+/// let card = WeChatTemplateCard::text_notice()
+///     .main_title("Alert", "CPU usage high")
+///     .add_horizontal_content("host", "web-001")
+///     .card_action_url("https://grafana.example.com/");
+/// dt.send_template_card(&card).await?;
+/// ```
+#[derive(Clone, Debug, Serialize)]
+pub struct WeChatTemplateCard {
+    card_type: WeChatTemplateCardType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<WeChatTemplateCardSource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    main_title: Option<WeChatTemplateCardTitle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    emphasis_content: Option<WeChatTemplateCardTitle>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    card_image: Option<WeChatTemplateCardImage>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    sub_title_text: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    horizontal_content_list: Vec<WeChatTemplateCardHorizontalContent>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    jump_list: Vec<WeChatTemplateCardAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    card_action: Option<WeChatTemplateCardAction>,
+}
+
+impl WeChatTemplateCard {
+    /// New text notice template card
+    pub fn text_notice() -> Self {
+        Self::new(WeChatTemplateCardType::TextNotice)
+    }
+
+    /// New news notice template card, card image is required
+    pub fn news_notice() -> Self {
+        Self::new(WeChatTemplateCardType::NewsNotice)
+    }
+
+    fn new(card_type: WeChatTemplateCardType) -> Self {
+        WeChatTemplateCard {
+            card_type,
+            source: None,
+            main_title: None,
+            emphasis_content: None,
+            card_image: None,
+            sub_title_text: String::new(),
+            horizontal_content_list: vec![],
+            jump_list: vec![],
+            card_action: None,
+        }
+    }
+
+    /// Get card type
+    pub fn card_type(&self) -> WeChatTemplateCardType {
+        self.card_type
+    }
+
+    /// Set source
+    pub fn source(mut self, icon_url: &str, desc: &str) -> Self {
+        self.source = Some(WeChatTemplateCardSource {
+            icon_url: icon_url.into(),
+            desc: desc.into(),
+        });
+        self
+    }
+
+    /// Set main title
+    pub fn main_title(mut self, title: &str, desc: &str) -> Self {
+        self.main_title = Some(WeChatTemplateCardTitle {
+            title: title.into(),
+            desc: desc.into(),
+        });
+        self
+    }
+
+    /// Set emphasis content, text notice only
+    pub fn emphasis_content(mut self, title: &str, desc: &str) -> Self {
+        self.emphasis_content = Some(WeChatTemplateCardTitle {
+            title: title.into(),
+            desc: desc.into(),
+        });
+        self
+    }
+
+    /// Set card image, news notice only
+    pub fn card_image(mut self, url: &str, aspect_ratio: Option<f64>) -> Self {
+        self.card_image = Some(WeChatTemplateCardImage {
+            url: url.into(),
+            aspect_ratio,
+        });
+        self
+    }
+
+    /// Set sub title text
+    pub fn sub_title_text(mut self, sub_title_text: &str) -> Self {
+        self.sub_title_text = sub_title_text.into();
+        self
+    }
+
+    /// Add horizontal text content
+    pub fn add_horizontal_content(mut self, keyname: &str, value: &str) -> Self {
+        self.horizontal_content_list
+            .push(WeChatTemplateCardHorizontalContent {
+                content_type: 0,
+                keyname: keyname.into(),
+                value: value.into(),
+                url: String::new(),
+            });
+        self
+    }
+
+    /// Add horizontal URL content
+    pub fn add_horizontal_content_url(mut self, keyname: &str, value: &str, url: &str) -> Self {
+        self.horizontal_content_list
+            .push(WeChatTemplateCardHorizontalContent {
+                content_type: 1,
+                keyname: keyname.into(),
+                value: value.into(),
+                url: url.into(),
+            });
+        self
+    }
+
+    /// Add jump URL
+    pub fn add_jump_url(mut self, title: &str, url: &str) -> Self {
+        self.jump_list.push(WeChatTemplateCardAction {
+            action_type: 1,
+            title: title.into(),
+            url: url.into(),
+            appid: String::new(),
+            pagepath: String::new(),
+        });
+        self
+    }
+
+    /// Set card action to open URL
+    pub fn card_action_url(mut self, url: &str) -> Self {
+        self.card_action = Some(WeChatTemplateCardAction {
+            action_type: 1,
+            title: String::new(),
+            url: url.into(),
+            appid: String::new(),
+            pagepath: String::new(),
+        });
+        self
+    }
+
+    /// Set card action to open mini program
+    pub fn card_action_mini_program(mut self, appid: &str, pagepath: &str) -> Self {
+        self.card_action = Some(WeChatTemplateCardAction {
+            action_type: 2,
+            title: String::new(),
+            url: String::new(),
+            appid: appid.into(),
+            pagepath: pagepath.into(),
+        });
+        self
+    }
+
+    /// Check required fields, WeChat Work replies errcode 44004 for invalid card
+    pub fn validate(&self) -> XResult<()> {
+        let mut problems: Vec<String> = vec![];
+        match &self.card_action {
+            None => problems.push("card_action is required".into()),
+            Some(action) => {
+                if action.action_type == 1 && action.url.is_empty() {
+                    problems.push("card_action url is required".into());
+                }
+                if action.action_type == 2 && action.appid.is_empty() {
+                    problems.push("card_action appid is required".into());
+                }
+            }
+        }
+        let has_main_title = self
+            .main_title
+            .as_ref()
+            .map(|t| !t.title.is_empty())
+            .unwrap_or(false);
+        match self.card_type {
+            WeChatTemplateCardType::TextNotice => {
+                if !has_main_title && self.sub_title_text.is_empty() {
+                    problems.push("main_title title or sub_title_text is required".into());
+                }
+            }
+            WeChatTemplateCardType::NewsNotice => {
+                if !has_main_title {
+                    problems.push("main_title title is required".into());
+                }
+                if self
+                    .card_image
+                    .as_ref()
+                    .map(|i| i.url.is_empty())
+                    .unwrap_or(true)
+                {
+                    problems.push("card_image url is required".into());
+                }
+            }
+        }
+        if self.horizontal_content_list.len() > HORIZONTAL_CONTENT_MAX_COUNT {
+            problems.push(format!(
+                "horizontal_content_list is at most {} items",
+                HORIZONTAL_CONTENT_MAX_COUNT
+            ));
+        }
+        if self.jump_list.len() > JUMP_MAX_COUNT {
+            problems.push(format!("jump_list is at most {} items", JUMP_MAX_COUNT));
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(DingTalkError::Validation(format!(
+                "Template card invalid: {}",
+                problems.join("; ")
+            )))
+        }
+    }
+}
+
+fn is_zero(n: &u8) -> bool {
+    *n == 0
+}
@@ -0,0 +1,189 @@
+use serde::Serialize;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+use std::{collections::BTreeMap, error::Error, fs, path::Path};
+use tera::{Context, Tera};
+
+use crate::*;
+
+/// Template name of `DingTalk::send_markdown_tera` when template is not a file
+const INLINE_TEMPLATE_NAME: &str = "inline";
+
+/// Preloaded Tera templates referenced by name, feature `tera`
+///
+/// Templates are rendered without HTML escaping, they can extend or include each other.
+///
+/// Sample code:
+/// ```ignore
+/// let store = TemplateStore::from_dir("templates")?;
+/// dt.send_markdown_tera_template("Deploy", &store, "deploy.md", &json!({"hosts": hosts})).await?;
+/// ```
+pub struct TemplateStore {
+    tera: Tera,
+    sources: BTreeMap<String, String>,
+}
+
+impl Default for TemplateStore {
+    fn default() -> Self {
+        let mut tera = Tera::default();
+        tera.autoescape_on(vec![]);
+        TemplateStore {
+            tera,
+            sources: BTreeMap::new(),
+        }
+    }
+}
+
+impl TemplateStore {
+    /// New empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load all files in `dir` recursively, template name is path relative to `dir` with `/`,
+    /// e.g. `alerts/disk.md`, not available on wasm32
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> XResult<Self> {
+        let dir = dir.as_ref();
+        let mut files = vec![];
+        collect_files(dir, &mut files)?;
+        let mut templates = vec![];
+        for file in files {
+            let name = file
+                .strip_prefix(dir)
+                .unwrap_or(&file)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            templates.push((name, fs::read_to_string(&file)?));
+        }
+        let mut store = Self::new();
+        store.add_templates(templates)?;
+        Ok(store)
+    }
+
+    /// Add template, template with the same name is replaced
+    pub fn add(&mut self, name: &str, source: &str) -> XResult<()> {
+        self.add_templates(vec![(name.to_owned(), source.to_owned())])
+    }
+
+    /// Template names in order
+    pub fn names(&self) -> Vec<&str> {
+        self.sources.keys().map(String::as_str).collect()
+    }
+
+    /// Render template by name
+    pub fn render<S: Serialize>(&self, name: &str, context: &S) -> XResult<String> {
+        let context =
+            Context::from_serialize(context).map_err(|e| self.template_error(name, &e))?;
+        self.tera
+            .render(name, &context)
+            .map_err(|e| self.template_error(name, &e))
+    }
+
+    fn add_templates(&mut self, templates: Vec<(String, String)>) -> XResult<()> {
+        if let Err(e) = self.tera.add_raw_templates(templates.clone()) {
+            // parse error names the failed template as `Failed to parse 'name'`
+            let name = e
+                .to_string()
+                .split('\'')
+                .nth(1)
+                .map(str::to_owned)
+                .unwrap_or_else(|| templates[0].0.clone());
+            return Err(self.template_error(&name, &e));
+        }
+        self.sources.extend(templates);
+        Ok(())
+    }
+
+    /// Error with template name and line, line is from parse error or the line referring
+    /// to the missing variable
+    fn template_error(&self, name: &str, error: &tera::Error) -> DingTalkError {
+        let mut messages = vec![error.to_string()];
+        let mut source = error.source();
+        while let Some(e) = source {
+            messages.push(e.to_string());
+            source = e.source();
+        }
+        let mut line = None;
+        let mut message = messages.join(": ");
+        for m in &messages {
+            if let Some(position) = m.trim_start().strip_prefix("--> ") {
+                line = position.split(':').next().and_then(|l| l.parse().ok());
+                message = m
+                    .lines()
+                    .find_map(|l| l.trim_start().strip_prefix("= "))
+                    .unwrap_or(&messages[0])
+                    .to_owned();
+            } else if let Some(variable) = m
+                .strip_prefix("Variable `")
+                .and_then(|v| v.split('`').next())
+            {
+                line = self.sources.get(name).and_then(|source| {
+                    source
+                        .lines()
+                        .position(|l| l.contains(variable))
+                        .map(|i| i + 1)
+                });
+                message = m.clone();
+            }
+        }
+        DingTalkError::Template {
+            name: name.to_owned(),
+            line,
+            message,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> XResult<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.path());
+    for entry in entries {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Markdown rendered by Tera templates, feature `tera`
+impl DingTalk {
+    /// Render Tera template and send as markdown message
+    ///
+    /// `template` is path of template file when the file exists, otherwise it is template source.
+    pub async fn send_markdown_tera<S: Serialize>(
+        &self,
+        title: &str,
+        template: &str,
+        context: &S,
+    ) -> XResult<()> {
+        let mut store = TemplateStore::new();
+        let name = if Path::new(template).is_file() {
+            store.add(template, &fs::read_to_string(template)?)?;
+            template
+        } else {
+            store.add(INLINE_TEMPLATE_NAME, template)?;
+            INLINE_TEMPLATE_NAME
+        };
+        let text = store.render(name, context)?;
+        self.send_markdown(title, &text).await
+    }
+
+    /// Render template of `TemplateStore` by name and send as markdown message
+    pub async fn send_markdown_tera_template<S: Serialize>(
+        &self,
+        title: &str,
+        store: &TemplateStore,
+        name: &str,
+        context: &S,
+    ) -> XResult<()> {
+        let text = store.render(name, context)?;
+        self.send_markdown(title, &text).await
+    }
+}
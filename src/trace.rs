@@ -0,0 +1,113 @@
+use crate::{DingTalkError, DingTalkMessageType, DingTalkResponse, DingTalkType, XResult};
+use std::time::{Duration, Instant};
+use tracing::{field::Empty, Span};
+
+/// Span of one `DingTalk::send`, `status`, `errcode`, `attempts` and `elapsed_ms` are recorded later
+///
+/// Never add access token, sec token or signed URL here, span fields are exported as is.
+pub(crate) fn send_span(dingtalk_type: DingTalkType, payload_bytes: usize) -> Span {
+    tracing::info_span!(
+        "dingtalk.send",
+        robot_type = ?dingtalk_type,
+        payload_bytes,
+        status = Empty,
+        errcode = Empty,
+        attempts = Empty,
+        elapsed_ms = Empty,
+    )
+}
+
+/// Record result fields on span created by `send_span`
+pub(crate) fn record_send(
+    span: &Span,
+    result: &XResult<DingTalkResponse>,
+    attempts: u32,
+    started: Instant,
+) {
+    span.record("attempts", attempts);
+    span.record("elapsed_ms", started.elapsed().as_millis() as u64);
+    let errcode = match result {
+        Ok(response) => Some(response.errcode),
+        Err(e) => e.server_errcode(),
+    };
+    if let Some(errcode) = errcode {
+        span.record("errcode", errcode);
+    }
+}
+
+/// Record HTTP status on current span, it is `dingtalk.send` span when called from `send_once`
+pub(crate) fn record_status(status: u16) {
+    Span::current().record("status", status);
+}
+
+/// Retryable failure, error is logged by kind only, `reqwest::Error` displays the signed URL
+pub(crate) fn retry_failure(attempt: u32, error: &DingTalkError, delay: Duration) {
+    tracing::warn!(
+        attempt,
+        error_kind = error.kind(),
+        delay_ms = delay.as_millis() as u64,
+        "send failed, retrying"
+    );
+}
+
+/// Failure of the last attempt, the error is returned to caller
+pub(crate) fn final_failure(attempt: u32, error: &DingTalkError) {
+    tracing::debug!(
+        attempt,
+        error_kind = error.kind(),
+        "send failed, no more retry"
+    );
+}
+
+/// Hook of `DingTalk::on_send` panicked, `index` is the order it was added
+pub(crate) fn send_hook_panicked(index: usize) {
+    tracing::error!(hook_index = index, "send hook panicked");
+}
+
+/// `Content-Type` of custom headers is ignored, value is not logged
+pub(crate) fn content_type_header_ignored() {
+    tracing::warn!("Content-Type header is ignored, body is always application/json");
+}
+
+/// Failure of `DingTalk::send_message_silent`, the error is dropped
+pub(crate) fn silent_failure(error: &DingTalkError) {
+    tracing::warn!(
+        error_kind = error.kind(),
+        errcode = error.server_errcode(),
+        "silent send failed, error is dropped"
+    );
+}
+
+/// Stream mode connection is not established, error is logged by kind only
+#[cfg(feature = "stream")]
+pub(crate) fn stream_connect_failed(error: &DingTalkError) {
+    tracing::warn!(error_kind = error.kind(), "stream connection failed");
+}
+
+/// Stream mode reconnects after `delay`
+#[cfg(feature = "stream")]
+pub(crate) fn stream_reconnect(delay: Duration) {
+    tracing::info!(
+        delay_ms = delay.as_millis() as u64,
+        "stream disconnected, reconnecting"
+    );
+}
+
+/// Dry run send, body is logged instead of sent, it has no token
+pub(crate) fn dry_run(dingtalk_type: DingTalkType, body: &str) {
+    tracing::info!(robot_type = ?dingtalk_type, body, "dry run, message is not sent");
+}
+
+/// Message is sent without security keyword, or the keyword is prefixed
+pub(crate) fn missing_security_keyword(message_type: DingTalkMessageType, keyword: &str) {
+    tracing::warn!(message_type = ?message_type, keyword, "message missing security keyword");
+}
+
+/// Config has both literal `key` and `env_key`, the literal is used, values are not logged
+pub(crate) fn config_env_key_ignored(key: &str, env_key: &str) {
+    tracing::warn!(
+        key,
+        env_key,
+        "config has both literal and env key, env key is ignored"
+    );
+}
@@ -0,0 +1,287 @@
+use std::{
+    cell::Cell,
+    fmt::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+use crate::{
+    forward::{self, ForwardConfig, ForwardRecord, ForwardSender},
+    DingTalk,
+};
+
+thread_local! {
+    /// Set while an event is rendered, events emitted by rendering(e.g. by `Debug` of a field)
+    /// are ignored
+    static IN_EVENT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// `tracing_subscriber::Layer` forwards events at or above level to DingTalk as markdown,
+/// enabled by feature `tracing-forward`
+///
+/// Event fields and the chain of spans it is in are rendered in markdown, then put into a
+/// channel, events are sent by a background tokio task so tracing never waits for HTTP. Batching,
+/// per minute cap and dropping are the same as `DingTalkLogger`.
+///
+/// Events of `dingtalk`, `reqwest`, `hyper`, `h2`, `rustls` and `native_tls`, events emitted
+/// within background task and events emitted while rendering are ignored, so sending never
+/// forwards events again. Panic of a field `Debug` is caught and the event is dropped.
+///
+/// Sample code:
+/// ```ignore
+/// let layer = DingTalkLayer::builder(dt).level(Level::ERROR).build();
+/// tracing_subscriber::registry().with(layer).init();
+/// tracing::error!(rows = 3, "disk full");
+/// ```
+pub struct DingTalkLayer {
+    level: Level,
+    targets: Vec<String>,
+    sender: ForwardSender,
+}
+
+/// Builder of `DingTalkLayer`, created by `DingTalkLayer::builder`
+pub struct DingTalkLayerBuilder {
+    dingtalk: Arc<DingTalk>,
+    level: Level,
+    targets: Vec<String>,
+    config: ForwardConfig,
+}
+
+/// Rendered fields of span, stored in span extensions
+struct SpanFields(String);
+
+impl DingTalkLayer {
+    /// Create builder, default level is `WARN`, all targets, batch window 5s, at most 20
+    /// events per message and 10 messages per minute, channel capacity 1000 events
+    pub fn builder(dingtalk: impl Into<Arc<DingTalk>>) -> DingTalkLayerBuilder {
+        DingTalkLayerBuilder {
+            dingtalk: dingtalk.into(),
+            level: Level::WARN,
+            targets: vec![],
+            config: ForwardConfig::default(),
+        }
+    }
+
+    /// How many events were dropped because channel or batch was full
+    pub fn dropped_count(&self) -> u64 {
+        self.sender.dropped_count()
+    }
+
+    fn is_target_enabled(&self, target: &str) -> bool {
+        !forward::is_ignored_target(target)
+            && (self.targets.is_empty()
+                || self
+                    .targets
+                    .iter()
+                    .any(|prefix| forward::is_target_of(target, prefix)))
+    }
+}
+
+impl DingTalkLayerBuilder {
+    /// Forward events at or above `level`
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Forward events of `target` and its sub modules only, e.g. `myapp` matches `myapp` and
+    /// `myapp::db`, can be called multiple times. Default forwards all targets.
+    pub fn target(mut self, target: &str) -> Self {
+        self.targets.push(target.into());
+        self
+    }
+
+    /// Events within `batch_window` after the first one are sent in one message
+    pub fn batch_window(mut self, batch_window: Duration) -> Self {
+        self.config.batch_window = batch_window;
+        self
+    }
+
+    /// At most `max_batch_records` events in one message, less than 1 is treated as 1
+    pub fn max_batch_records(mut self, max_batch_records: usize) -> Self {
+        self.config.max_batch_records = max_batch_records.max(1);
+        self
+    }
+
+    /// At most `max_messages_per_minute` messages are sent in every minute, robot allows 20
+    pub fn max_messages_per_minute(mut self, max_messages_per_minute: usize) -> Self {
+        self.config.max_messages_per_minute = max_messages_per_minute;
+        self
+    }
+
+    /// At most `capacity` events waiting in channel, less than 1 is treated as 1
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.config.capacity = capacity.max(1);
+        self
+    }
+
+    /// Create layer and spawn background task, must be called within a tokio runtime
+    pub fn build(self) -> DingTalkLayer {
+        DingTalkLayer {
+            level: self.level,
+            targets: self.targets,
+            sender: ForwardSender::spawn(self.dingtalk, self.config),
+        }
+    }
+}
+
+impl<S> Layer<S> for DingTalkLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let mut visitor = SpanVisitor::default();
+        let rendered = panic::catch_unwind(AssertUnwindSafe(|| {
+            attrs.record(&mut visitor);
+            visitor.0
+        }));
+        if let (Ok(fields), Some(span)) = (rendered, ctx.span(id)) {
+            span.extensions_mut().insert(SpanFields(fields));
+        }
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+            let mut visitor = SpanVisitor(std::mem::take(fields));
+            let rendered = panic::catch_unwind(AssertUnwindSafe(|| {
+                values.record(&mut visitor);
+                visitor.0
+            }));
+            *fields = rendered.unwrap_or_default();
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if *metadata.level() > self.level
+            || !self.is_target_enabled(metadata.target())
+            || forward::is_forwarding()
+        {
+            return;
+        }
+        // `try_with` fails while thread local is destroyed at thread exit, event is ignored
+        let _ = IN_EVENT.try_with(|in_event| {
+            if in_event.replace(true) {
+                return;
+            }
+            let rendered = panic::catch_unwind(AssertUnwindSafe(|| render_event(event, &ctx)));
+            in_event.set(false);
+            if let Ok(markdown) = rendered {
+                self.sender.send(ForwardRecord {
+                    severity: severity(metadata.level()),
+                    level: metadata.level().as_str(),
+                    markdown,
+                });
+            }
+        });
+    }
+}
+
+/// 1 is `ERROR`, 5 is `TRACE`, same as `log::Level`
+fn severity(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 1,
+        Level::WARN => 2,
+        Level::INFO => 3,
+        Level::DEBUG => 4,
+        Level::TRACE => 5,
+    }
+}
+
+/// Render event as markdown with level, target, file:line, spans, fields and message
+fn render_event<S>(event: &Event<'_>, ctx: &Context<'_, S>) -> String
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let metadata = event.metadata();
+    let location = match (metadata.file(), metadata.line()) {
+        (Some(file), Some(line)) => format!("{}:{}", file, line),
+        (Some(file), None) => file.to_owned(),
+        _ => "unknown".to_owned(),
+    };
+    let mut markdown = format!(
+        "#### {} {}\n\n- location: `{}`",
+        metadata.level(),
+        metadata.target(),
+        location
+    );
+    if let Some(scope) = ctx.event_scope(event) {
+        let spans = scope
+            .from_root()
+            .map(|span| {
+                let extensions = span.extensions();
+                match extensions.get::<SpanFields>() {
+                    Some(SpanFields(fields)) if !fields.is_empty() => {
+                        format!("`{}{{{}}}`", span.name(), fields)
+                    }
+                    _ => format!("`{}`", span.name()),
+                }
+            })
+            .collect::<Vec<_>>();
+        let _ = write!(markdown, "\n- spans: {}", spans.join(" > "));
+    }
+    let mut visitor = EventVisitor::default();
+    event.record(&mut visitor);
+    for (name, value) in &visitor.fields {
+        let _ = write!(markdown, "\n- {}: {}", name, value);
+    }
+    if let Some(message) = visitor.message {
+        let _ = write!(markdown, "\n\n{}", message);
+    }
+    markdown
+}
+
+/// Collect `message` and other fields of event
+#[derive(Default)]
+struct EventVisitor {
+    message: Option<String>,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl EventVisitor {
+    fn push(&mut self, field: &Field, value: String) {
+        if field.name() == "message" {
+            self.message = Some(value);
+        } else {
+            self.fields.push((field.name(), value));
+        }
+    }
+}
+
+impl Visit for EventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, value.to_owned());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.push(field, format!("{:?}", value));
+    }
+}
+
+/// Render span fields as `name=value, name=value`
+#[derive(Default)]
+struct SpanVisitor(String);
+
+impl Visit for SpanVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{}", value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push_str(", ");
+        }
+        let _ = write!(self.0, "{}={:?}", field.name(), value);
+    }
+}
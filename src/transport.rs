@@ -0,0 +1,259 @@
+use crate::*;
+#[cfg(not(target_arch = "wasm32"))]
+use futures::future::BoxFuture;
+#[cfg(target_arch = "wasm32")]
+use futures::future::LocalBoxFuture;
+use std::{collections::VecDeque, sync::Mutex};
+
+/// HTTP response returned by `Transport`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransportResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl TransportResponse {
+    /// New response
+    pub fn new(status: u16, body: &str) -> Self {
+        TransportResponse {
+            status,
+            headers: vec![],
+            body: body.into(),
+        }
+    }
+
+    /// Add header
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Get first header value by name, name is case-insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// HTTP 200 with body `{"errcode":0,"errmsg":"ok"}`
+    pub fn ok() -> Self {
+        Self::new(200, r#"{"errcode":0,"errmsg":"ok"}"#)
+    }
+}
+
+/// HTTP transport of `DingTalk::send`, POSTs JSON body to webhook URL
+///
+/// Default is `ReqwestTransport`, custom transport can be installed by `DingTalk::with_transport`,
+/// e.g. `RecordingTransport` for tests without network.
+///
+/// Sample code:
+/// ```ignore
+/// let transport = Arc::new(RecordingTransport::new());
+/// let dt = DingTalk::new("<token>", "").with_transport(transport.clone());
+/// dt.send_text("Hello world!").await?;
+/// assert_eq!(1, transport.requests().len());
+/// ```
+pub trait Transport: Send + Sync {
+    /// POST `body` as `application/json` to `url`, HTTP status and body are checked by caller
+    fn post<'a>(&'a self, url: &'a str, body: &'a str) -> TransportFuture<'a>;
+
+    /// POST `request` with its extra headers, default ignores headers and calls `post`,
+    /// override it to support headers added by `Interceptor`
+    fn post_request<'a>(&'a self, request: &'a RequestParts) -> TransportFuture<'a> {
+        self.post(&request.url, &request.body)
+    }
+}
+
+/// Future returned by `Transport::post`, same as `BoxFuture<'a, XResult<TransportResponse>>`
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> = BoxFuture<'a, XResult<TransportResponse>>;
+
+/// Future returned by `Transport::post`, futures of fetch API are not `Send` on wasm32
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> = LocalBoxFuture<'a, XResult<TransportResponse>>;
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn post<'a>(&'a self, url: &'a str, body: &'a str) -> TransportFuture<'a> {
+        (**self).post(url, body)
+    }
+
+    fn post_request<'a>(&'a self, request: &'a RequestParts) -> TransportFuture<'a> {
+        (**self).post_request(request)
+    }
+}
+
+/// Transport by `reqwest::Client`
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+}
+
+impl ReqwestTransport {
+    /// Create `ReqwestTransport` by client, proxy and other settings are kept by client
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport {
+            client,
+            timeout: None,
+            user_agent: None,
+        }
+    }
+
+    /// Set timeout of each request
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set `User-Agent` header of each request, `None` is header of client
+    pub fn user_agent(mut self, user_agent: Option<&str>) -> Self {
+        self.user_agent = user_agent.map(str::to_owned);
+        self
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn post<'a>(&'a self, url: &'a str, body: &'a str) -> TransportFuture<'a> {
+        Box::pin(async move { self.post_request(&RequestParts::new(url, body)).await })
+    }
+
+    fn post_request<'a>(&'a self, request_parts: &'a RequestParts) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let header_map = request_parts.header_map()?;
+            #[allow(unused_mut)]
+            let mut request = self
+                .client
+                .post(&request_parts.url)
+                .header(CONTENT_TYPE, APPLICATION_JSON_UTF8)
+                .body(request_parts.body.clone());
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(timeout) = self.timeout {
+                request = request.timeout(timeout);
+            }
+            if let Some(user_agent) = &self.user_agent {
+                request = request.header(USER_AGENT, user_agent);
+            }
+            let response = request.headers(header_map).send().await?;
+            let status = response.status().as_u16();
+            let headers = response
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                    (name.as_str().to_owned(), value)
+                })
+                .collect();
+            let body = if is_success_status(status) {
+                response.text().await?
+            } else {
+                read_error_body(response).await
+            };
+            Ok(TransportResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+/// Read at most `ERROR_BODY_MAX_BYTES` of error response body, invalid UTF-8 is replaced,
+/// read error is ignored since the status is already an error
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn read_error_body(mut response: reqwest::Response) -> String {
+    let mut bytes = vec![];
+    while bytes.len() < ERROR_BODY_MAX_BYTES {
+        match response.chunk().await {
+            Ok(Some(chunk)) => bytes.extend_from_slice(&chunk),
+            _ => break,
+        }
+    }
+    bytes.truncate(ERROR_BODY_MAX_BYTES);
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Read error response body, fetch API has no chunked read so the whole body is read then
+/// truncated to `ERROR_BODY_MAX_BYTES`
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn read_error_body(response: reqwest::Response) -> String {
+    let bytes = response.bytes().await.unwrap_or_default();
+    let bytes = &bytes[..bytes.len().min(ERROR_BODY_MAX_BYTES)];
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Request captured by `RecordingTransport`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedRequest {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+/// In-memory transport, captures requests and replies queued responses
+///
+/// Replies `TransportResponse::ok()` when no response is queued.
+#[derive(Debug, Default)]
+pub struct RecordingTransport {
+    requests: Mutex<Vec<RecordedRequest>>,
+    responses: Mutex<VecDeque<TransportResponse>>,
+}
+
+impl RecordingTransport {
+    /// New `RecordingTransport`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue response, responses are replied in queued order
+    pub fn reply(&self, response: TransportResponse) -> &Self {
+        self.responses.lock().unwrap().push_back(response);
+        self
+    }
+
+    /// Captured requests
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Captured request URLs
+    pub fn urls(&self) -> Vec<String> {
+        self.requests().into_iter().map(|r| r.url).collect()
+    }
+
+    /// Captured request bodies
+    pub fn bodies(&self) -> Vec<String> {
+        self.requests().into_iter().map(|r| r.body).collect()
+    }
+
+    fn record(&self, request: RecordedRequest) -> TransportFuture<'_> {
+        self.requests.lock().unwrap().push(request);
+        let response = self
+            .responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(TransportResponse::ok);
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+impl Transport for RecordingTransport {
+    fn post<'a>(&'a self, url: &'a str, body: &'a str) -> TransportFuture<'a> {
+        self.record(RecordedRequest {
+            url: url.into(),
+            headers: vec![],
+            body: body.into(),
+        })
+    }
+
+    fn post_request<'a>(&'a self, request: &'a RequestParts) -> TransportFuture<'a> {
+        self.record(RecordedRequest {
+            url: request.url.clone(),
+            headers: request.headers.clone(),
+            body: request.body.clone(),
+        })
+    }
+}
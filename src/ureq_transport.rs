@@ -0,0 +1,142 @@
+use crate::*;
+use std::{io::Read, sync::OnceLock};
+
+/// `Transport` by `ureq`, blocking, feature `minimal-http`
+///
+/// Blocking API uses it when feature `blocking` is off, it can also be installed by
+/// `DingTalk::with_transport`, then `DingTalk::send` blocks current thread while sending.
+///
+/// One ureq agent is built on first request and kept, so keep-alive connections are reused,
+/// clones share the agent.
+///
+/// Sample code:
+/// ```ignore
+/// let dt = DingTalk::new("<token>", "").with_transport(UreqTransport::new());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct UreqTransport {
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<String>,
+    user_agent: Option<String>,
+    agent: Arc<OnceLock<ureq::Agent>>,
+}
+
+impl UreqTransport {
+    /// New transport with ureq defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set request timeout, `None` is ureq default
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self.agent = Default::default();
+        self
+    }
+
+    /// Set connect timeout, `None` is ureq default
+    pub fn connect_timeout(mut self, connect_timeout: Option<Duration>) -> Self {
+        self.connect_timeout = connect_timeout;
+        self.agent = Default::default();
+        self
+    }
+
+    /// Set proxy, e.g. `http://127.0.0.1:8080`
+    pub fn proxy(mut self, proxy: Option<&str>) -> Self {
+        self.proxy = proxy.map(str::to_owned);
+        self.agent = Default::default();
+        self
+    }
+
+    /// Set `User-Agent` header, `None` is ureq default
+    pub fn user_agent(mut self, user_agent: Option<&str>) -> Self {
+        self.user_agent = user_agent.map(str::to_owned);
+        self.agent = Default::default();
+        self
+    }
+
+    /// POST `body` as `application/json` to `url`, blocking
+    pub fn post_blocking(&self, url: &str, body: &str) -> XResult<TransportResponse> {
+        self.post_request_blocking(&RequestParts::new(url, body))
+    }
+
+    /// POST `request` with its extra headers, blocking
+    pub fn post_request_blocking(&self, request: &RequestParts) -> XResult<TransportResponse> {
+        request.header_map()?;
+        let mut ureq_request = self.agent()?.post(&request.url);
+        for (name, value) in &request.headers {
+            if !name.eq_ignore_ascii_case(CONTENT_TYPE) {
+                ureq_request = ureq_request.set(name, value);
+            }
+        }
+        let response = match ureq_request
+            .set(CONTENT_TYPE, APPLICATION_JSON_UTF8)
+            .send_string(&request.body)
+        {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(e)) => return Err(DingTalkError::Transport(e.to_string())),
+        };
+
+        let status = response.status();
+        let headers = response
+            .headers_names()
+            .into_iter()
+            .filter_map(|name| {
+                let value = response.header(&name)?.to_owned();
+                Some((name, value))
+            })
+            .collect();
+        let body = if is_success_status(status) {
+            response.into_string()?
+        } else {
+            // read error is ignored since the status is already an error
+            let mut bytes = vec![];
+            let _ = response
+                .into_reader()
+                .take(ERROR_BODY_MAX_BYTES as u64)
+                .read_to_end(&mut bytes);
+            String::from_utf8_lossy(&bytes).into_owned()
+        };
+        Ok(TransportResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+    /// Agent built on first request, proxy error is returned until the proxy is fixed
+    fn agent(&self) -> XResult<&ureq::Agent> {
+        if let Some(agent) = self.agent.get() {
+            return Ok(agent);
+        }
+        let mut builder = ureq::AgentBuilder::new();
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.timeout_connect(connect_timeout);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(parse_proxy(proxy)?);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        Ok(self.agent.get_or_init(|| builder.build()))
+    }
+}
+
+/// Parse proxy URL of ureq
+pub(crate) fn parse_proxy(proxy: &str) -> XResult<ureq::Proxy> {
+    ureq::Proxy::new(proxy).map_err(|e| DingTalkError::Config(format!("Invalid proxy: {}", e)))
+}
+
+impl Transport for UreqTransport {
+    fn post<'a>(&'a self, url: &'a str, body: &'a str) -> TransportFuture<'a> {
+        Box::pin(futures::future::ready(self.post_blocking(url, body)))
+    }
+
+    fn post_request<'a>(&'a self, request: &'a RequestParts) -> TransportFuture<'a> {
+        Box::pin(futures::future::ready(self.post_request_blocking(request)))
+    }
+}
@@ -0,0 +1,195 @@
+use crate::*;
+
+/// Schemes of clickable URLs, `dingtalk://` is DingTalk deep link
+const ACTION_URL_SCHEMES: &[&str] = &["http", "https", "dingtalk"];
+const PIC_URL_SCHEMES: &[&str] = &["http", "https"];
+
+impl DingTalkMessage {
+    /// Check structural requirements of message, used by `DingTalk::strict_validation`
+    ///
+    /// * Text - text content
+    /// * Markdown - title and content
+    /// * Link - title, text, pic URL and message URL
+    /// * ActionCard - title and text, title and action URL of every button, single button or
+    ///   1 to 5 buttons in list, not both
+    /// * FeedCard - 1 to 10 links, title and message URL of every link
+    ///
+    /// URLs of link, buttons and feed card links must be valid `http`/`https` URLs, action and
+    /// message URLs can also be `dingtalk://` deep links.
+    ///
+    /// Returns `DingTalkError::Validation` listing every problem found.
+    pub fn validate(&self) -> XResult<()> {
+        let mut problems: Vec<String> = vec![];
+        match self.message_type {
+            DingTalkMessageType::Text => require(&mut problems, &self.text_content, "text_content"),
+            DingTalkMessageType::Markdown => {
+                require(&mut problems, &self.markdown_title, "markdown_title");
+                require(&mut problems, &self.markdown_content, "markdown_content");
+            }
+            DingTalkMessageType::Link => {
+                require(&mut problems, &self.link_title, "link_title");
+                require(&mut problems, &self.link_text, "link_text");
+                require(&mut problems, &self.link_pic_url, "link_pic_url");
+                require(&mut problems, &self.link_message_url, "link_message_url");
+                check_url(
+                    &mut problems,
+                    &self.link_pic_url,
+                    "link_pic_url",
+                    PIC_URL_SCHEMES,
+                );
+                check_url(
+                    &mut problems,
+                    &self.link_message_url,
+                    "link_message_url",
+                    ACTION_URL_SCHEMES,
+                );
+            }
+            DingTalkMessageType::ActionCard => {
+                require(&mut problems, &self.action_card_title, "action_card_title");
+                require(&mut problems, &self.action_card_text, "action_card_text");
+                if let Err(DingTalkError::Validation(problem)) = check_action_card_btns(self) {
+                    problems.push(problem);
+                }
+                if let Some(single_btn) = &self.action_card_single_btn {
+                    require(
+                        &mut problems,
+                        &single_btn.title,
+                        "action_card_single_btn title",
+                    );
+                    require(
+                        &mut problems,
+                        &single_btn.action_url,
+                        "action_card_single_btn action_url",
+                    );
+                    check_url(
+                        &mut problems,
+                        &single_btn.action_url,
+                        "action_card_single_btn action_url",
+                        ACTION_URL_SCHEMES,
+                    );
+                }
+                for (i, btn) in self.action_card_btns.iter().enumerate() {
+                    require(
+                        &mut problems,
+                        &btn.title,
+                        &format!("action_card_btns[{}] title", i),
+                    );
+                    let name = format!("action_card_btns[{}] action_url", i);
+                    require(&mut problems, &btn.action_url, &name);
+                    check_url(&mut problems, &btn.action_url, &name, ACTION_URL_SCHEMES);
+                }
+            }
+            DingTalkMessageType::FeedCard => {
+                if self.feed_card_links.is_empty() {
+                    problems.push("feed_card_links requires at least one link".into());
+                } else if self.feed_card_links.len() > FEED_CARD_MAX_LINKS {
+                    problems.push(format!(
+                        "feed_card_links has {} links, at most {}",
+                        self.feed_card_links.len(),
+                        FEED_CARD_MAX_LINKS
+                    ));
+                }
+                for (i, link) in self.feed_card_links.iter().enumerate() {
+                    require(
+                        &mut problems,
+                        &link.title,
+                        &format!("feed_card_links[{}] title", i),
+                    );
+                    let name = format!("feed_card_links[{}] message_url", i);
+                    require(&mut problems, &link.message_url, &name);
+                    check_url(&mut problems, &link.message_url, &name, ACTION_URL_SCHEMES);
+                    check_url(
+                        &mut problems,
+                        &link.pic_url,
+                        &format!("feed_card_links[{}] pic_url", i),
+                        PIC_URL_SCHEMES,
+                    );
+                }
+            }
+            DingTalkMessageType::Image => {
+                require(&mut problems, &self.image_base64, "image_base64")
+            }
+            DingTalkMessageType::News => {
+                if self.news_articles.is_empty() {
+                    problems.push("news_articles requires at least one article".into());
+                }
+                for (i, article) in self.news_articles.iter().enumerate() {
+                    require(
+                        &mut problems,
+                        &article.title,
+                        &format!("news_articles[{}] title", i),
+                    );
+                    require(
+                        &mut problems,
+                        &article.url,
+                        &format!("news_articles[{}] url", i),
+                    );
+                }
+            }
+            DingTalkMessageType::File | DingTalkMessageType::Voice => {
+                require(&mut problems, &self.media_id, "media_id")
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(DingTalkError::Validation(format!(
+                "Message invalid: {}",
+                problems.join("; ")
+            )))
+        }
+    }
+}
+
+/// Single button and button list of action card are exclusive, otherwise the list was ignored,
+/// without single button the list has 1 to `ACTION_CARD_MAX_BTNS` buttons
+pub(crate) fn check_action_card_btns(dingtalk_message: &DingTalkMessage) -> XResult<()> {
+    if dingtalk_message.message_type != DingTalkMessageType::ActionCard {
+        return Ok(());
+    }
+    let btns_len = dingtalk_message.action_card_btns.len();
+    if dingtalk_message.action_card_single_btn.is_some() {
+        if btns_len > 0 {
+            return Err(DingTalkError::Validation(format!(
+                "Action card has both single button and {} buttons in list, set only one of them",
+                btns_len
+            )));
+        }
+    } else if btns_len == 0 || btns_len > ACTION_CARD_MAX_BTNS {
+        return Err(DingTalkError::Validation(format!(
+            "Action card buttons count {} is not in range 1 to {}, or set single button",
+            btns_len, ACTION_CARD_MAX_BTNS
+        )));
+    }
+    Ok(())
+}
+
+fn require(problems: &mut Vec<String>, value: &str, name: &str) {
+    if value.is_empty() {
+        problems.push(format!("{} is required", name));
+    }
+}
+
+/// Check non-empty URL parses with host and one of `schemes`, empty one is left to `require`
+fn check_url(problems: &mut Vec<String>, value: &str, name: &str, schemes: &[&str]) {
+    if value.is_empty() {
+        return;
+    }
+    let url = match reqwest::Url::parse(value) {
+        Ok(url) => url,
+        Err(e) => {
+            problems.push(format!("{} is malformed: {}, {}", name, value, e));
+            return;
+        }
+    };
+    if !schemes.contains(&url.scheme()) {
+        problems.push(format!(
+            "{} scheme must be one of {}: {}",
+            name,
+            schemes.join("/"),
+            value
+        ));
+    } else if url.host_str().is_none_or(str::is_empty) {
+        problems.push(format!("{} has no host: {}", name, value));
+    }
+}
@@ -0,0 +1,202 @@
+use serde_json::json;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::{
+    app::{call_api, DEFAULT_TOKEN_REFRESH_MARGIN},
+    clock::system_now,
+    *,
+};
+
+const DEFAULT_WECHAT_API_URL: &str = "https://qyapi.weixin.qq.com";
+/// `errcode` of invalid and expired access token
+const INVALID_TOKEN_ERRCODES: [i64; 2] = [40014, 42001];
+/// `errcode` of invalid chatid
+const INVALID_CHATID_ERRCODE: i64 = 86001;
+/// `errcode` of chat not exists
+const CHAT_NOT_EXIST_ERRCODE: i64 = 86003;
+
+/// WeChat Work(WeCom) app, sends to group chat created by app(appchat) by access token of
+/// `corpid` and `corpsecret`
+///
+/// Access token is cached and refreshed 5 minutes(see `WeChatApp::with_token_refresh_margin`)
+/// before it expires, same as `DingTalkApp`.
+///
+/// Document https://developer.work.weixin.qq.com/document/path/90248
+///
+/// Sample code:
+/// ```ignore
+/// let app = WeChatApp::new("<corpid>", "<corpsecret>");
+/// app.send_appchat("<chatid>", &DingTalkMessage::new_text("Hello world!")).await?;
+/// ```
+pub struct WeChatApp {
+    pub corpid: String,
+    pub corpsecret: String,
+    api_url: String,
+    token_refresh_margin: Duration,
+    client: reqwest::Client,
+    token: Mutex<Option<AppAccessToken>>,
+}
+
+impl WeChatApp {
+    /// Create `WeChatApp`
+    pub fn new(corpid: &str, corpsecret: &str) -> Self {
+        WeChatApp {
+            corpid: corpid.into(),
+            corpsecret: corpsecret.into(),
+            api_url: DEFAULT_WECHAT_API_URL.into(),
+            token_refresh_margin: DEFAULT_TOKEN_REFRESH_MARGIN,
+            client: reqwest::Client::new(),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Set API URL, default is `https://qyapi.weixin.qq.com`, e.g. mock server in tests
+    pub fn with_api_url(mut self, api_url: &str) -> Self {
+        self.api_url = api_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Set margin before expiry to refresh cached access token, default is 5 minutes
+    pub fn with_token_refresh_margin(mut self, margin: Duration) -> Self {
+        self.token_refresh_margin = margin;
+        self
+    }
+
+    /// Access token, cached until `token_refresh_margin` before it expires
+    pub async fn access_token(&self) -> XResult<String> {
+        let mut token = self.token.lock().await;
+        if let Some(cached) = token.as_ref() {
+            if system_now() + self.token_refresh_margin < cached.expires_at {
+                return Ok(cached.access_token.clone());
+            }
+        }
+        let fetched = self.fetch_access_token().await?;
+        let access_token = fetched.access_token.clone();
+        *token = Some(fetched);
+        Ok(access_token)
+    }
+
+    /// Drop cached access token, the next `WeChatApp::access_token` fetches a new one
+    ///
+    /// Called automatically when API replies `errcode` 40014(invalid access token) or
+    /// 42001(access token expired).
+    pub async fn invalidate_token(&self) {
+        *self.token.lock().await = None;
+    }
+
+    /// Get new access token by `/cgi-bin/gettoken`, not cached
+    pub async fn fetch_access_token(&self) -> XResult<AppAccessToken> {
+        let request = self.client.get(self.endpoint("/cgi-bin/gettoken")).query(&[
+            ("corpid", self.corpid.as_str()),
+            ("corpsecret", self.corpsecret.as_str()),
+        ]);
+        let response = self.call(request).await?;
+        let access_token = match response.raw["access_token"].as_str() {
+            Some(access_token) if !access_token.is_empty() => access_token.to_owned(),
+            _ => {
+                return Err(DingTalkError::Server {
+                    errcode: -1,
+                    errmsg: format!("No access_token in response: {}", response.raw),
+                })
+            }
+        };
+        let expires_in = response.raw["expires_in"].as_u64().unwrap_or_default();
+        Ok(AppAccessToken {
+            access_token,
+            expires_at: system_now() + Duration::from_secs(expires_in),
+        })
+    }
+
+    /// Send message to group chat by `/cgi-bin/appchat/send`
+    ///
+    /// Text, Markdown and News messages are supported, markdown title is not sent. Chat must be
+    /// created by `appchat/create` of the same app, `errcode` 86001(invalid chatid) and
+    /// 86003(chat not exists) are returned as `DingTalkError::Server` with chatid in `errmsg`.
+    pub async fn send_appchat(
+        &self,
+        chatid: &str,
+        dingtalk_message: &DingTalkMessage,
+    ) -> XResult<DingTalkResponse> {
+        if chatid.is_empty() {
+            return Err(DingTalkError::Validation(
+                "Appchat requires chatid".to_owned(),
+            ));
+        }
+        let mut body = appchat_msg(dingtalk_message)?;
+        body["chatid"] = chatid.into();
+        let access_token = self.access_token().await?;
+        let request = self
+            .client
+            .post(self.endpoint("/cgi-bin/appchat/send"))
+            .query(&[("access_token", access_token.as_str())])
+            .header(CONTENT_TYPE, APPLICATION_JSON_UTF8)
+            .body(body.to_string());
+        self.call(request).await.map_err(|e| match e {
+            DingTalkError::Server { errcode, errmsg }
+                if errcode == INVALID_CHATID_ERRCODE || errcode == CHAT_NOT_EXIST_ERRCODE =>
+            {
+                DingTalkError::Server {
+                    errcode,
+                    errmsg: format!("{}, chatid: {}", errmsg, chatid),
+                }
+            }
+            e => e,
+        })
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}{}", self.api_url, path)
+    }
+
+    /// Send API request, HTTP status and `errcode` are checked, invalid access token is dropped
+    async fn call(&self, request: reqwest::RequestBuilder) -> XResult<DingTalkResponse> {
+        let result = call_api(request).await;
+        if let Err(DingTalkError::Server { errcode, .. }) = &result {
+            if INVALID_TOKEN_ERRCODES.contains(errcode) {
+                self.invalidate_token().await;
+            }
+        }
+        result
+    }
+}
+
+/// Convert message to appchat payload without `chatid`
+fn appchat_msg(dingtalk_message: &DingTalkMessage) -> XResult<Value> {
+    match dingtalk_message.message_type {
+        DingTalkMessageType::Text => Ok(json!({
+            "msgtype": "text",
+            "text": { "content": dingtalk_message.text_content },
+        })),
+        DingTalkMessageType::Markdown => Ok(json!({
+            "msgtype": "markdown",
+            "markdown": { "content": dingtalk_message.markdown_content },
+        })),
+        DingTalkMessageType::News => {
+            let articles_len = dingtalk_message.news_articles.len();
+            if articles_len == 0 || articles_len > WECHAT_WORK_NEWS_MAX_ARTICLES {
+                return Err(DingTalkError::Validation(format!(
+                    "News articles count {} is not in range 1 to {}",
+                    articles_len, WECHAT_WORK_NEWS_MAX_ARTICLES
+                )));
+            }
+            let articles = dingtalk_message
+                .news_articles
+                .iter()
+                .map(|article| {
+                    json!({
+                        "title": article.title,
+                        "description": article.description,
+                        "url": article.url,
+                        "picurl": article.pic_url,
+                    })
+                })
+                .collect::<Vec<_>>();
+            Ok(json!({ "msgtype": "news", "news": { "articles": articles } }))
+        }
+        message_type => Err(DingTalkError::Unsupported(format!(
+            "Message type {:?} is unsupported for appchat",
+            message_type
+        ))),
+    }
+}
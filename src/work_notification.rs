@@ -0,0 +1,111 @@
+use crate::*;
+use serde_json::json;
+
+/// Max `userid_list` size of one `asyncsend_v2` call
+const WORK_NOTIFICATION_MAX_USERIDS: usize = 100;
+
+/// Work notification, document https://open.dingtalk.com/document/orgapp/asynchronous-sending-of-enterprise-session-messages
+impl DingTalkApp {
+    /// Send work notification to users by `topapi/message/corpconversation/asyncsend_v2`
+    ///
+    /// Text, Markdown, Link and ActionCard messages are supported, users are sent in batches
+    /// of 100 and `task_id` of each batch is returned. Failed batch returns its `errcode` and
+    /// `errmsg` as `DingTalkError::Server`, batches before it are already sent.
+    pub async fn send_work_notification(
+        &self,
+        agent_id: i64,
+        userids: &[&str],
+        dingtalk_message: &DingTalkMessage,
+    ) -> XResult<Vec<i64>> {
+        if userids.is_empty() {
+            return Err(DingTalkError::Validation(
+                "Work notification requires userids".to_owned(),
+            ));
+        }
+        let msg = work_notification_msg(dingtalk_message)?;
+        let mut task_ids =
+            Vec::with_capacity(userids.len().div_ceil(WORK_NOTIFICATION_MAX_USERIDS));
+        for batch in userids.chunks(WORK_NOTIFICATION_MAX_USERIDS) {
+            let body = json!({
+                "agent_id": agent_id,
+                "userid_list": batch.join(","),
+                "msg": msg,
+            });
+            let access_token = self.access_token().await?;
+            let request = self
+                .client
+                .post(self.endpoint("/topapi/message/corpconversation/asyncsend_v2"))
+                .query(&[("access_token", access_token.as_str())])
+                .header(CONTENT_TYPE, APPLICATION_JSON_UTF8)
+                .body(body.to_string());
+            let response = self.call(request).await?;
+            match response.raw["task_id"].as_i64() {
+                Some(task_id) => task_ids.push(task_id),
+                None => {
+                    return Err(DingTalkError::Server {
+                        errcode: -1,
+                        errmsg: format!("No task_id in response: {}", response.raw),
+                    })
+                }
+            }
+        }
+        Ok(task_ids)
+    }
+}
+
+/// Convert message to `msg` of work notification(OA message schema)
+fn work_notification_msg(dingtalk_message: &DingTalkMessage) -> XResult<Value> {
+    validate::check_action_card_btns(dingtalk_message)?;
+    match dingtalk_message.message_type {
+        DingTalkMessageType::Text => Ok(json!({
+            "msgtype": "text",
+            "text": { "content": dingtalk_message.text_content },
+        })),
+        DingTalkMessageType::Markdown => Ok(json!({
+            "msgtype": "markdown",
+            "markdown": {
+                "title": dingtalk_message.markdown_title,
+                "text": dingtalk_message.markdown_content,
+            },
+        })),
+        DingTalkMessageType::Link => Ok(json!({
+            "msgtype": "link",
+            "link": {
+                "title": dingtalk_message.link_title,
+                "text": dingtalk_message.link_text,
+                "picUrl": dingtalk_message.link_pic_url,
+                "messageUrl": dingtalk_message.link_message_url,
+            },
+        })),
+        DingTalkMessageType::ActionCard => {
+            let mut action_card = json!({
+                "title": dingtalk_message.action_card_title,
+                "markdown": dingtalk_message.action_card_text,
+            });
+            match &dingtalk_message.action_card_single_btn {
+                Some(single_btn) => {
+                    action_card["single_title"] = single_btn.title.as_str().into();
+                    action_card["single_url"] = single_btn.action_url.as_str().into();
+                }
+                None => {
+                    action_card["btn_orientation"] =
+                        match dingtalk_message.action_card_btn_orientation {
+                            DingTalkMessageActionCardBtnOrientation::Vertical => "0",
+                            DingTalkMessageActionCardBtnOrientation::Landscape => "1",
+                        }
+                        .into();
+                    action_card["btn_json_list"] = dingtalk_message
+                        .action_card_btns
+                        .iter()
+                        .map(|btn| json!({ "title": btn.title, "action_url": btn.action_url }))
+                        .collect();
+                }
+            }
+            Ok(json!({ "msgtype": "action_card", "action_card": action_card }))
+        }
+        message_type => Err(DingTalkError::Unsupported(format!(
+            "Message type {:?} is unsupported for work notification",
+            message_type
+        ))),
+    }
+}
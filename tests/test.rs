@@ -44,7 +44,7 @@ async fn _test_send() -> XResult<()> {
 
     dt.send_message(
         DingTalkMessage::new_action_card("action card 001", "action card text 001")
-            .set_action_card_signle_btn(DingTalkMessageActionCardBtn {
+            .set_action_card_single_btn(DingTalkMessageActionCardBtn {
                 title: "test signle btn title".into(),
                 action_url: "https://hatter.ink/".into(),
             }),
@@ -0,0 +1,200 @@
+mod common;
+
+use common::{MockRequest, MockResponse, MockServer};
+use dingtalk::*;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
+};
+
+fn token_response(access_token: &str, expires_in: u64) -> MockResponse {
+    MockResponse::new(
+        200,
+        &format!(
+            r#"{{"errcode":0,"access_token":"{}","errmsg":"ok","expires_in":{}}}"#,
+            access_token, expires_in
+        ),
+    )
+}
+
+#[test]
+fn app_fetch_access_token() {
+    let server = MockServer::reply(token_response("token001", 7200));
+    let app = DingTalkApp::new("key001", "secret 001").with_api_url(&server.base_url());
+    let started = SystemTime::now();
+    let token = tokio_test::block_on(app.fetch_access_token()).unwrap();
+    assert_eq!("token001", token.access_token);
+    assert!(token.expires_at >= started + Duration::from_secs(7200));
+    assert!(token.expires_at <= SystemTime::now() + Duration::from_secs(7200));
+
+    let requests = server.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!("GET", requests[0].method);
+    assert_eq!(
+        "/gettoken?appkey=key001&appsecret=secret+001",
+        requests[0].path
+    );
+}
+
+#[test]
+fn app_access_token_cached() {
+    let server = MockServer::reply(token_response("token001", 7200));
+    let app = DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url());
+    tokio_test::block_on(async {
+        assert_eq!("token001", app.access_token().await.unwrap());
+        assert_eq!("token001", app.access_token().await.unwrap());
+    });
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn app_access_token_refreshed_before_expired() {
+    let server = MockServer::reply(token_response("token001", 60));
+    let app = DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url());
+    tokio_test::block_on(async {
+        app.access_token().await.unwrap();
+        app.access_token().await.unwrap();
+    });
+    assert_eq!(2, server.request_count());
+}
+
+#[test]
+fn app_access_token_errcode() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":40089,"errmsg":"invalid appkey or appsecret"}"#,
+    ));
+    let app = DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url());
+    let err = tokio_test::block_on(app.access_token()).unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Server {
+            errcode: 40089,
+            ref errmsg,
+        } if errmsg == "invalid appkey or appsecret"
+    ));
+}
+
+#[test]
+fn app_access_token_missing() {
+    let server = MockServer::reply(MockResponse::ok());
+    let app = DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url());
+    let err = tokio_test::block_on(app.access_token()).unwrap_err();
+    assert!(matches!(err, DingTalkError::Server { errcode: -1, .. }));
+}
+
+#[test]
+fn app_access_token_bad_status() {
+    let server = MockServer::reply(MockResponse::new(502, "bad gateway"));
+    let app = DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url());
+    let err = tokio_test::block_on(app.access_token()).unwrap_err();
+    assert!(matches!(err, DingTalkError::Status { status: 502, .. }));
+}
+
+/// Server replies `token001`, `token002`, ... for each `/gettoken` after `delay`,
+/// and `api_response` for other APIs
+fn counting_server(delay: Duration, api_response: &'static str) -> MockServer {
+    let count = AtomicUsize::new(0);
+    MockServer::start(move |request: &MockRequest| {
+        if request.path.starts_with("/gettoken?") {
+            let n = count.fetch_add(1, Ordering::SeqCst) + 1;
+            token_response(&format!("token{:03}", n), 7200).delay(delay)
+        } else {
+            MockResponse::new(200, api_response)
+        }
+    })
+}
+
+fn token_request_count(server: &MockServer) -> usize {
+    server
+        .requests()
+        .iter()
+        .filter(|request| request.path.starts_with("/gettoken?"))
+        .count()
+}
+
+#[test]
+fn app_access_token_concurrent_refresh() {
+    let server = counting_server(Duration::from_millis(200), "{}");
+    let app = DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url());
+    let tokens = tokio_test::block_on(futures::future::join_all(
+        (0..16).map(|_| app.access_token()),
+    ));
+    for token in tokens {
+        assert_eq!("token001", token.unwrap());
+    }
+    assert_eq!(1, token_request_count(&server));
+}
+
+#[test]
+fn app_access_token_concurrent_refresh_multi_thread() {
+    let server = counting_server(Duration::from_millis(200), "{}");
+    let app = Arc::new(DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url()));
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .enable_all()
+        .build()
+        .unwrap();
+    let tokens = runtime.block_on(async {
+        let tasks: Vec<_> = (0..16)
+            .map(|_| {
+                let app = app.clone();
+                tokio::spawn(async move { app.access_token().await })
+            })
+            .collect();
+        let mut tokens = vec![];
+        for task in tasks {
+            tokens.push(task.await.unwrap().unwrap());
+        }
+        tokens
+    });
+    assert_eq!(vec!["token001".to_string(); 16], tokens);
+    assert_eq!(1, token_request_count(&server));
+}
+
+#[test]
+fn app_access_token_refresh_margin() {
+    let server = MockServer::reply(token_response("token001", 60));
+    let app = DingTalkApp::new("key001", "secret001")
+        .with_api_url(&server.base_url())
+        .with_token_refresh_margin(Duration::from_secs(30));
+    tokio_test::block_on(async {
+        app.access_token().await.unwrap();
+        app.access_token().await.unwrap();
+    });
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn app_invalidate_token() {
+    let server = counting_server(Duration::ZERO, "{}");
+    let app = DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url());
+    tokio_test::block_on(async {
+        assert_eq!("token001", app.access_token().await.unwrap());
+        app.invalidate_token().await;
+        assert_eq!("token002", app.access_token().await.unwrap());
+        assert_eq!("token002", app.access_token().await.unwrap());
+    });
+    assert_eq!(2, token_request_count(&server));
+}
+
+#[test]
+fn app_invalid_token_errcode_drops_token() {
+    let server = counting_server(
+        Duration::ZERO,
+        r#"{"errcode":40014,"errmsg":"invalid access_token"}"#,
+    );
+    let app = DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url());
+    tokio_test::block_on(async {
+        let err = app
+            .send_work_notification(10001, &["user001"], &DingTalkMessage::new_text("hello"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DingTalkError::Server { errcode: 40014, .. }));
+        assert_eq!("token002", app.access_token().await.unwrap());
+    });
+    assert_eq!(2, token_request_count(&server));
+}
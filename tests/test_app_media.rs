@@ -0,0 +1,112 @@
+mod common;
+
+use common::{MockRequest, MockResponse, MockServer};
+use dingtalk::*;
+
+const TOKEN_RESPONSE: &str =
+    r#"{"errcode":0,"access_token":"token001","errmsg":"ok","expires_in":7200}"#;
+
+/// Server replies access token, and `upload_response` for `media/upload`
+fn app_server(upload_response: &'static str) -> MockServer {
+    MockServer::start(move |request: &MockRequest| {
+        if request.path.starts_with("/gettoken?") {
+            MockResponse::new(200, TOKEN_RESPONSE)
+        } else {
+            MockResponse::new(200, upload_response)
+        }
+    })
+}
+
+fn app(server: &MockServer) -> DingTalkApp {
+    DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url())
+}
+
+#[test]
+fn app_upload_media() {
+    let server = app_server(
+        r#"{"errcode":0,"errmsg":"ok","media_id":"@lAjPDeC2uGvK","created_at":1605863153573,"type":"image"}"#,
+    );
+    let media = tokio_test::block_on(app(&server).upload_media(
+        MediaType::Image,
+        "chart.png",
+        b"png bytes".to_vec(),
+    ))
+    .unwrap();
+    assert_eq!(
+        MediaId {
+            media_id: "@lAjPDeC2uGvK".into(),
+            created_at: 1605863153573,
+        },
+        media
+    );
+
+    let requests = server.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!(
+        "/media/upload?access_token=token001&type=image",
+        requests[1].path
+    );
+    assert!(requests[1]
+        .header("content-type")
+        .unwrap()
+        .starts_with("multipart/form-data"));
+    let body = requests[1].body_str();
+    assert!(body.contains(r#"name="media"; filename="chart.png""#));
+    assert!(body.contains("png bytes"));
+}
+
+#[test]
+fn app_upload_media_types() {
+    let server = app_server(
+        r#"{"errcode":0,"errmsg":"ok","media_id":"media001","created_at":"1605863153573"}"#,
+    );
+    let app = app(&server);
+    for (media_type, name) in [(MediaType::Voice, "voice"), (MediaType::File, "file")] {
+        let media =
+            tokio_test::block_on(app.upload_media(media_type, "a.bin", b"bytes".to_vec())).unwrap();
+        assert_eq!("media001", media.media_id);
+        assert_eq!(1605863153573, media.created_at);
+        assert_eq!(name, media_type.as_str());
+        assert!(server
+            .requests()
+            .last()
+            .unwrap()
+            .path
+            .ends_with(&format!("&type={}", name)));
+    }
+}
+
+#[test]
+fn app_upload_media_invalid_file_type() {
+    let server = app_server(r#"{"errcode":40005,"errmsg":"不合法的文件类型"}"#);
+    let err = tokio_test::block_on(app(&server).upload_media(
+        MediaType::Image,
+        "chart.exe",
+        b"exe bytes".to_vec(),
+    ))
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Server {
+            errcode: 40005,
+            ref errmsg,
+        } if errmsg == "不合法的文件类型"
+    ));
+}
+
+#[test]
+fn app_upload_media_size_limit() {
+    let server = app_server(r#"{"errcode":0,"errmsg":"ok","media_id":"media001"}"#);
+    let app = app(&server);
+    for (media_type, len) in [
+        (MediaType::Image, 20 * 1024 * 1024 + 1),
+        (MediaType::File, 20 * 1024 * 1024 + 1),
+        (MediaType::Voice, 2 * 1024 * 1024 + 1),
+        (MediaType::File, 0),
+    ] {
+        let err = tokio_test::block_on(app.upload_media(media_type, "a.bin", vec![0_u8; len]))
+            .unwrap_err();
+        assert!(matches!(err, DingTalkError::Validation(_)));
+    }
+    assert_eq!(0, server.request_count());
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::time::{Duration, Instant};
+
+fn failing_on_bad() -> MockServer {
+    MockServer::start(|request| {
+        if request.body_str().contains("bad") {
+            MockResponse::new(200, r#"{"errcode":300001,"errmsg":"bad message"}"#)
+        } else {
+            MockResponse::ok()
+        }
+    })
+}
+
+fn messages() -> Vec<DingTalkMessage> {
+    ["m1", "bad", "m3"]
+        .iter()
+        .map(|t| DingTalkMessage::new_text(t))
+        .collect()
+}
+
+#[test]
+fn send_messages_in_order_continue_on_error() {
+    let server = failing_on_bad();
+    let dt = DingTalk::from_url(&server.url);
+    let results = tokio_test::block_on(dt.send_messages(messages(), None));
+    assert_eq!(3, results.len());
+    assert!(results[0].is_ok());
+    assert!(matches!(
+        results[1],
+        Err(DingTalkError::Server {
+            errcode: 300001,
+            ..
+        })
+    ));
+    assert!(results[2].is_ok());
+    let bodies: Vec<String> = server.requests().iter().map(|r| r.body_str()).collect();
+    assert!(bodies[0].contains("m1"));
+    assert!(bodies[1].contains("bad"));
+    assert!(bodies[2].contains("m3"));
+}
+
+#[test]
+fn send_messages_stop_on_error() {
+    let server = failing_on_bad();
+    let dt = DingTalk::from_url(&server.url);
+    let results = tokio_test::block_on(dt.send_messages_stop_on_error(messages(), None));
+    assert_eq!(2, results.len());
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert_eq!(2, server.request_count());
+}
+
+#[test]
+fn send_messages_pacing() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let start = Instant::now();
+    let results = tokio_test::block_on(dt.send_messages(
+        vec![
+            DingTalkMessage::new_text("m1"),
+            DingTalkMessage::new_text("m2"),
+            DingTalkMessage::new_text("m3"),
+        ],
+        Some(Duration::from_millis(100)),
+    ));
+    assert!(results.iter().all(|r| r.is_ok()));
+    // sleeps between sends only, not after the last one
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    assert!(start.elapsed() < Duration::from_millis(1000));
+}
@@ -0,0 +1,202 @@
+#![cfg(any(feature = "blocking", feature = "minimal-http"))]
+
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+#[test]
+fn send_text_blocking() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    dt.send_text_blocking("hello").unwrap();
+    let requests = server.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!(
+        r#"{"msgtype":"text","text":{"content":"hello"}}"#,
+        requests[0].body_str()
+    );
+}
+
+#[test]
+fn send_markdown_blocking() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    dt.send_markdown_blocking("title", "# text").unwrap();
+    assert_eq!(
+        r##"{"markdown":{"text":"# text","title":"title"},"msgtype":"markdown"}"##,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn send_message_blocking_errcode() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":300001,"errmsg":"token is not exist"}"#,
+    ));
+    let dt = DingTalk::from_url(&server.url);
+    let err = dt
+        .send_message_blocking(DingTalkMessage::new_text("hello").at_all())
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Server {
+            errcode: 300001,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn send_message_with_response_blocking() {
+    let server = MockServer::reply(MockResponse::new(200, r#"{"errcode":0,"errmsg":"ok"}"#));
+    let dt = DingTalk::from_url(&server.url);
+    let response = dt
+        .send_message_with_response_blocking(DingTalkMessage::new_text("hello"))
+        .unwrap();
+    assert_eq!("ok", response.errmsg);
+}
+
+#[test]
+fn send_text_blocking_rate_limited() {
+    let server = MockServer::reply(MockResponse::new(429, "").header("Retry-After", "5"));
+    let dt = DingTalk::from_url(&server.url);
+    let err = dt.send_text_blocking("hello").unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::RateLimited {
+            retry_after: Some(d)
+        } if d == std::time::Duration::from_secs(5)
+    ));
+}
+
+#[test]
+fn send_text_blocking_bad_status_body() {
+    let server = MockServer::reply(MockResponse::new(400, "keywords not in content"));
+    let dt = DingTalk::from_url(&server.url);
+    let err = dt.send_text_blocking("hello").unwrap_err();
+    assert_eq!(
+        "Unknown status: 400, body: keywords not in content",
+        err.to_string()
+    );
+}
+
+#[test]
+fn send_text_blocking_session_expired() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_session_webhook(&server.url, 1613635652738);
+    let err = dt.send_text_blocking("hello").unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::SessionExpired {
+            expired_at_ms: 1613635652738
+        }
+    ));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn send_text_blocking_dry_run() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).dry_run(true);
+    let response = dt
+        .send_with_response_blocking(r#"{"msgtype":"text","text":{"content":"hello"}}"#)
+        .unwrap();
+    assert_eq!("dry run", response.errmsg);
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn send_text_blocking_dedup() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).dedup_window(std::time::Duration::from_secs(60));
+    dt.send_text_blocking("hello").unwrap();
+    let response = dt
+        .send_message_with_response_blocking(DingTalkMessage::new_text("hello"))
+        .unwrap();
+    assert!(response.is_suppressed());
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn send_text_blocking_auto_split() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).auto_split(true);
+    dt.send_text_blocking(&"0123456789\n".repeat(2500)).unwrap();
+    let requests = server.requests();
+    assert_eq!(2, requests.len());
+    assert!(requests[1].body_str().contains("(2/2)"));
+}
+
+#[test]
+fn send_message_ref_blocking() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let message = DingTalkMessage::new_text("hello");
+    dt.send_message_ref_blocking(&message).unwrap();
+    let response = dt
+        .send_message_with_response_ref_blocking(&message)
+        .unwrap();
+    assert_eq!("ok", response.errmsg);
+    let requests = server.requests();
+    assert_eq!(requests[0].body_str(), requests[1].body_str());
+}
+
+#[test]
+fn send_text_blocking_user_agent() {
+    let server = MockServer::reply(MockResponse::ok());
+    let mut dt = DingTalk::from_url(&server.url);
+    dt.send_text_blocking("hello").unwrap();
+    dt.set_user_agent("alert-bot/1.0").unwrap();
+    dt.send_text_blocking("hello").unwrap();
+    let requests = server.requests();
+    assert_eq!(
+        Some(concat!("dingtalk-rs/", env!("CARGO_PKG_VERSION"))),
+        requests[0].header("user-agent")
+    );
+    assert_eq!(Some("alert-bot/1.0"), requests[1].header("user-agent"));
+}
+
+#[test]
+fn send_text_blocking_on_send() {
+    let server = MockServer::reply(MockResponse::ok());
+    let statuses = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+    let dt = DingTalk::from_url(&server.url).on_send({
+        let statuses = statuses.clone();
+        move |attempt: &SendAttempt| statuses.lock().unwrap().push(attempt.status)
+    });
+    dt.send_text_blocking("hello").unwrap();
+    assert_eq!(vec![Some(200)], *statuses.lock().unwrap());
+}
+
+#[test]
+fn send_text_blocking_interceptor() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url)
+        .with_interceptor(|request: RequestParts| Ok(request.with_header("X-Internal", "1")));
+    dt.send_text_blocking("hello").unwrap();
+    assert_eq!(Some("1"), server.requests()[0].header("X-Internal"));
+}
+
+#[test]
+fn send_message_with_headers_blocking() {
+    let server = MockServer::reply(MockResponse::ok());
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Org-Token", HeaderValue::from_static("org-1"));
+    let dt = DingTalk::from_url(&server.url);
+    dt.send_message_with_headers_blocking(&DingTalkMessage::new_text("hello"), headers)
+        .unwrap();
+    assert_eq!(Some("org-1"), server.requests()[0].header("X-Org-Token"));
+}
+
+#[test]
+fn send_text_blocking_reuses_connection() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    dt.send_text_blocking("message 1").unwrap();
+    dt.send_text_blocking("message 2").unwrap();
+    dt.clone().send_text_blocking("message 3").unwrap();
+    assert_eq!(3, server.request_count());
+    assert_eq!(1, server.connection_count());
+}
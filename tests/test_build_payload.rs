@@ -0,0 +1,379 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use serde_json::json;
+
+fn btn(title: &str, action_url: &str) -> DingTalkMessageActionCardBtn {
+    DingTalkMessageActionCardBtn {
+        title: title.into(),
+        action_url: action_url.into(),
+    }
+}
+
+#[test]
+fn build_payload_text_at() {
+    let dt = DingTalk::new("token", "");
+    let message = DingTalkMessage::new_text("hello")
+        .at_mobiles(&["13800000000".to_string()])
+        .at_all();
+    assert_eq!(
+        json!({
+            "msgtype": "text",
+            "text": {"content": "hello"},
+            "at": {"atMobiles": ["13800000000"], "isAtAll": true},
+        }),
+        dt.build_payload(&message).unwrap()
+    );
+}
+
+#[test]
+fn build_payload_link() {
+    let dt = DingTalk::new("token", "");
+    assert_eq!(
+        json!({
+            "msgtype": "link",
+            "link": {
+                "title": "title",
+                "text": "text",
+                "picUrl": "https://example.com/a.png",
+                "messageUrl": "https://example.com/",
+            },
+        }),
+        dt.build_payload(&DingTalkMessage::new_link(
+            "title",
+            "text",
+            "https://example.com/a.png",
+            "https://example.com/"
+        ))
+        .unwrap()
+    );
+}
+
+#[test]
+fn build_payload_action_card_single_btn() {
+    let dt = DingTalk::new("token", "");
+    let message = DingTalkMessage::new_action_card("title", "text")
+        .set_action_card_single_btn(btn("Open", "https://example.com/"));
+    assert_eq!(
+        json!({
+            "msgtype": "actionCard",
+            "actionCard": {
+                "title": "title",
+                "text": "text",
+                "hideAvatar": "0",
+                "btnOrientation": "0",
+                "singleTitle": "Open",
+                "singleURL": "https://example.com/",
+            },
+        }),
+        dt.build_payload(&message).unwrap()
+    );
+}
+
+#[test]
+fn build_payload_action_card_btns() {
+    let dt = DingTalk::new("token", "");
+    let message = DingTalkMessage::new_action_card("title", "text")
+        .action_card_hide_avatar()
+        .action_card_btn_landscape()
+        .add_action_card_btn(btn("Yes", "https://example.com/yes"))
+        .add_action_card_btn(btn("No", "https://example.com/no"));
+    assert_eq!(
+        json!({
+            "msgtype": "actionCard",
+            "actionCard": {
+                "title": "title",
+                "text": "text",
+                "hideAvatar": "1",
+                "btnOrientation": "1",
+                "btns": [
+                    {"title": "Yes", "actionURL": "https://example.com/yes"},
+                    {"title": "No", "actionURL": "https://example.com/no"},
+                ],
+            },
+        }),
+        dt.build_payload(&message).unwrap()
+    );
+}
+
+#[test]
+fn build_payload_feed_card() {
+    let dt = DingTalk::new("token", "");
+    let message = DingTalkMessage::new_feed_card().add_feed_card_link_detail(
+        "title",
+        "https://example.com/",
+        "https://example.com/a.png",
+    );
+    assert_eq!(
+        json!({
+            "msgtype": "feedCard",
+            "feedCard": {"links": [{
+                "title": "title",
+                "messageURL": "https://example.com/",
+                "picURL": "https://example.com/a.png",
+            }]},
+        }),
+        dt.build_payload(&message).unwrap()
+    );
+}
+
+#[test]
+fn build_payload_same_as_sent() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let message = || {
+        DingTalkMessage::new_markdown("title", "text")
+            .at_mobiles(&["13800000000".to_string()])
+            .at_user_id("user001")
+    };
+    let payload = dt.build_payload(&message()).unwrap();
+    tokio_test::block_on(dt.send_message(message())).unwrap();
+    assert_eq!(
+        serde_json::to_string(&payload).unwrap(),
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn build_payload_unsupported() {
+    let dt = DingTalk::new("token", "");
+    assert!(matches!(
+        dt.build_payload(&DingTalkMessage::new_wechat_file("media001")),
+        Err(DingTalkError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn cloned_message_same_payload() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let message = DingTalkMessage::new_action_card("title", "text")
+        .action_card_hide_avatar()
+        .add_action_card_btn(btn("Yes", "https://example.com/yes"))
+        .add_action_card_btn(btn("No", "https://example.com/no"))
+        .at_mobiles(&["13800000000".to_string()])
+        .at_user_id("user001");
+    let cloned = message.clone();
+    assert_eq!(
+        dt.build_payload(&message).unwrap(),
+        dt.build_payload(&cloned).unwrap()
+    );
+    tokio_test::block_on(dt.send_message(message)).unwrap();
+    tokio_test::block_on(dt.send_message(cloned)).unwrap();
+    let requests = server.requests();
+    assert_eq!(requests[0].body_str(), requests[1].body_str());
+}
+
+#[test]
+fn build_payload_security_keyword_prefixed() {
+    let dt = DingTalk::new("token", "").security_keyword("ALERT");
+    assert_eq!(
+        json!({"msgtype": "text", "text": {"content": "[ALERT] disk full"}}),
+        dt.build_payload(&DingTalkMessage::new_text("disk full"))
+            .unwrap()
+    );
+    assert_eq!(
+        json!({
+            "msgtype": "markdown",
+            "markdown": {"title": "Disk", "text": "[ALERT]\n\n# disk full"},
+        }),
+        dt.build_payload(&DingTalkMessage::new_markdown("Disk", "# disk full"))
+            .unwrap()
+    );
+    let payload = dt
+        .build_payload(
+            &DingTalkMessage::new_action_card("Disk", "disk full")
+                .set_action_card_single_btn(btn("Open", "https://example.com/")),
+        )
+        .unwrap();
+    assert_eq!("[ALERT] disk full", payload["actionCard"]["text"]);
+}
+
+#[test]
+fn build_payload_security_keyword_present() {
+    let dt = DingTalk::new("token", "").security_keyword("ALERT");
+    assert_eq!(
+        json!({"msgtype": "text", "text": {"content": "disk full ALERT"}}),
+        dt.build_payload(&DingTalkMessage::new_text("disk full ALERT"))
+            .unwrap()
+    );
+    assert_eq!(
+        json!({
+            "msgtype": "markdown",
+            "markdown": {"title": "ALERT: disk", "text": "disk full"},
+        }),
+        dt.build_payload(&DingTalkMessage::new_markdown("ALERT: disk", "disk full"))
+            .unwrap()
+    );
+    let link = DingTalkMessage::new_link("disk", "disk full", "", "https://example.com/");
+    assert_eq!(
+        DingTalk::new("token", "").build_payload(&link).unwrap(),
+        dt.build_payload(&link).unwrap()
+    );
+}
+
+#[test]
+fn build_payload_security_keyword_warn_only() {
+    let dt = DingTalk::new("token", "")
+        .security_keyword("ALERT")
+        .security_keyword_warn_only(true);
+    assert_eq!(
+        json!({"msgtype": "text", "text": {"content": "disk full"}}),
+        dt.build_payload(&DingTalkMessage::new_text("disk full"))
+            .unwrap()
+    );
+}
+
+#[test]
+fn build_payload_security_keyword_from_json() {
+    let dt =
+        DingTalk::from_json(r#"{"access_token": "token", "security_keyword": "ALERT"}"#).unwrap();
+    assert_eq!(Some("ALERT"), dt.security_keyword.as_deref());
+    let server = MockServer::reply(MockResponse::ok());
+    let mut dt = dt;
+    dt.direct_url = server.url.clone();
+    tokio_test::block_on(dt.send_text("disk full")).unwrap();
+    assert_eq!(
+        r#"{"msgtype":"text","text":{"content":"[ALERT] disk full"}}"#,
+        server.requests()[0].body_str()
+    );
+
+    let dt = DingTalk::from_json(
+        r#"{"access_token": "token", "security_keyword": "ALERT", "security_keyword_warn_only": true}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        "disk full",
+        dt.build_payload(&DingTalkMessage::new_text("disk full"))
+            .unwrap()["text"]["content"]
+    );
+}
+
+fn action_card_with_btns(count: usize) -> DingTalkMessage {
+    (0..count).fold(
+        DingTalkMessage::new_action_card("title", "text"),
+        |message, i| message.add_action_card_btn(btn(&format!("btn{}", i), "https://example.com/")),
+    )
+}
+
+#[test]
+fn build_payload_action_card_btns_count() {
+    let dt = DingTalk::new("token", "");
+    for count in [0, 6] {
+        match dt.build_payload(&action_card_with_btns(count)) {
+            Err(DingTalkError::Validation(message)) => assert_eq!(
+                format!(
+                    "Action card buttons count {} is not in range 1 to 5, or set single button",
+                    count
+                ),
+                message
+            ),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+    for count in [1, 5] {
+        let payload = dt.build_payload(&action_card_with_btns(count)).unwrap();
+        assert_eq!(
+            count,
+            payload["actionCard"]["btns"].as_array().unwrap().len()
+        );
+    }
+}
+
+#[test]
+fn build_payload_action_card_btns_count_single_btn() {
+    let dt = DingTalk::new("token", "");
+    let message =
+        action_card_with_btns(0).set_action_card_single_btn(btn("Open", "https://example.com/"));
+    let payload = dt.build_payload(&message).unwrap();
+    assert_eq!("Open", payload["actionCard"]["singleTitle"]);
+}
+
+#[test]
+fn send_action_card_btns_count_no_request() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_message(action_card_with_btns(6))).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn build_payload_action_card_single_btn_and_btns() {
+    let dt = DingTalk::new("token", "");
+    let message =
+        action_card_with_btns(2).set_action_card_single_btn(btn("Open", "https://example.com/"));
+    match dt.build_payload(&message) {
+        Err(DingTalkError::Validation(message)) => assert_eq!(
+            "Action card has both single button and 2 buttons in list, set only one of them",
+            message
+        ),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(message.validate().is_err());
+
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_message(message)).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+#[allow(deprecated)]
+fn set_action_card_signle_btn_alias() {
+    let dt = DingTalk::new("token", "");
+    let btn = btn("Open", "https://example.com/");
+    assert_eq!(
+        dt.build_payload(
+            &DingTalkMessage::new_action_card("title", "text")
+                .set_action_card_single_btn(btn.clone())
+        )
+        .unwrap(),
+        dt.build_payload(
+            &DingTalkMessage::new_action_card("title", "text").set_action_card_signle_btn(btn)
+        )
+        .unwrap()
+    );
+}
+
+fn feed_card_with_links(count: usize) -> DingTalkMessage {
+    (0..count).fold(DingTalkMessage::new_feed_card(), |message, i| {
+        message.add_feed_card_link_detail(
+            &format!("link{}", i),
+            "https://example.com/",
+            "https://example.com/a.png",
+        )
+    })
+}
+
+#[test]
+fn build_payload_feed_card_links_count() {
+    let dt = DingTalk::new("token", "");
+    for count in [0, 11] {
+        match dt.build_payload(&feed_card_with_links(count)) {
+            Err(DingTalkError::Validation(message)) => assert_eq!(
+                format!("Feed card links count {} is not in range 1 to 10", count),
+                message
+            ),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+    for count in [1, 10] {
+        let payload = dt.build_payload(&feed_card_with_links(count)).unwrap();
+        assert_eq!(
+            count,
+            payload["feedCard"]["links"].as_array().unwrap().len()
+        );
+    }
+}
+
+#[test]
+fn send_feed_card_links_count_no_request() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_message(feed_card_with_links(0))).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+    assert_eq!(0, server.request_count());
+}
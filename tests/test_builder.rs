@@ -0,0 +1,106 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::time::Duration;
+
+fn config_error(builder: DingTalkBuilder) -> String {
+    match builder.build() {
+        Err(DingTalkError::Config(message)) => message,
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("build should fail"),
+    }
+}
+
+#[test]
+fn builder_dingtalk() {
+    let dt = DingTalk::builder()
+        .access_token("token001")
+        .sec_token("secret001")
+        .build()
+        .unwrap();
+    assert!(matches!(dt.dingtalk_type, DingTalkType::DingTalk));
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/send",
+        dt.default_webhook_url
+    );
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+}
+
+#[test]
+fn builder_wechat_webhook_url() {
+    let dt = DingTalk::builder()
+        .wechat()
+        .access_token("key001")
+        .webhook_url("https://wecom.example.com/send")
+        .build()
+        .unwrap();
+    assert_eq!(
+        "https://wecom.example.com/send?key=key001",
+        dt.generate_signed_url().unwrap()
+    );
+}
+
+#[test]
+fn builder_direct_url_with_timeout() {
+    let server = MockServer::reply(MockResponse::ok().delay(Duration::from_millis(500)));
+    let dt = DingTalk::builder()
+        .direct_url(&server.url)
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Timeout(_)));
+}
+
+#[test]
+fn builder_invalid_combinations() {
+    assert_eq!(
+        "direct_url and access_token are mutually exclusive",
+        config_error(
+            DingTalk::builder()
+                .access_token("token001")
+                .direct_url("https://example.com/")
+        )
+    );
+    assert_eq!(
+        "direct_url and webhook_url are mutually exclusive",
+        config_error(
+            DingTalk::builder()
+                .webhook_url("https://example.com/")
+                .direct_url("https://example.com/")
+        )
+    );
+    assert_eq!(
+        "access_token or direct_url is required",
+        config_error(DingTalk::builder().sec_token("secret001"))
+    );
+    assert_eq!(
+        "sec_token is not applied to direct_url",
+        config_error(
+            DingTalk::builder()
+                .direct_url("https://example.com/")
+                .sec_token("secret001")
+        )
+    );
+    assert_eq!(
+        "sec_token is not supported by robot type WeChatWork",
+        config_error(
+            DingTalk::builder()
+                .wechat()
+                .access_token("key001")
+                .sec_token("secret001")
+        )
+    );
+    assert_eq!(
+        "direct_url or webhook_url is required",
+        config_error(DingTalk::builder().dingtalk_type(DingTalkType::Slack))
+    );
+    assert!(config_error(
+        DingTalk::builder()
+            .access_token("token001")
+            .proxy("socks5://127.0.0.1:1080")
+    )
+    .starts_with("Proxy URL"));
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use common::{MockRequest, MockResponse, MockServer};
+use dingtalk::*;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+fn card_server(send_response: MockResponse) -> MockServer {
+    MockServer::start(move |request: &MockRequest| {
+        if request.path.starts_with("/gettoken") {
+            MockResponse::new(
+                200,
+                r#"{"errcode":0,"errmsg":"ok","access_token":"token001","expires_in":7200}"#,
+            )
+        } else {
+            send_response.clone()
+        }
+    })
+}
+
+fn robot_client(server: &MockServer) -> RobotCodeClient {
+    let app = DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url());
+    RobotCodeClient::new(Arc::new(app), "robot001").with_api_url(&server.base_url())
+}
+
+fn send_bodies(server: &MockServer) -> Vec<Value> {
+    server
+        .requests()
+        .iter()
+        .filter(|r| r.path == "/v1.0/im/interactiveCards/send")
+        .map(|r| {
+            assert_eq!(Some("token001"), r.header("x-acs-dingtalk-access-token"));
+            serde_json::from_str(&r.body_str()).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn send_interactive_card_to_group() {
+    let server = card_server(MockResponse::new(
+        200,
+        r#"{"success":true,"result":{"processQueryKey":"key001"}}"#,
+    ));
+    let robot = robot_client(&server);
+    let card = InteractiveCard::new("template001", "track001")
+        .card_data(json!({ "title": "Deploy", "count": 3 }))
+        .callback_route_key("route001")
+        .group("cid001");
+    let out_track_id = tokio_test::block_on(robot.send_interactive_card(&card)).unwrap();
+    assert_eq!("track001", out_track_id);
+    assert_eq!(
+        vec![json!({
+            "cardTemplateId": "template001",
+            "outTrackId": "track001",
+            "robotCode": "robot001",
+            "conversationType": 1,
+            "openConversationId": "cid001",
+            "callbackRouteKey": "route001",
+            // values which are not string are sent as JSON string
+            "cardData": { "cardParamMap": { "title": "Deploy", "count": "3" } },
+        })],
+        send_bodies(&server)
+    );
+}
+
+#[test]
+fn send_interactive_card_to_users() {
+    let server = card_server(MockResponse::new(200, r#"{"success":true}"#));
+    let robot = robot_client(&server);
+    let card = InteractiveCard::new("template001", "track001").users(&["user1", "user2"]);
+    tokio_test::block_on(robot.send_interactive_card(&card)).unwrap();
+    let body = &send_bodies(&server)[0];
+    assert_eq!(0, body["conversationType"]);
+    assert_eq!(json!(["user1", "user2"]), body["receiverUserIdList"]);
+    assert_eq!(None, body.get("openConversationId"));
+    assert_eq!(None, body.get("callbackRouteKey"));
+}
+
+#[test]
+fn interactive_card_validate() {
+    let card = InteractiveCard::new("", " ").card_data(json!(["not", "object"]));
+    let err = card.validate().unwrap_err();
+    match err {
+        DingTalkError::Validation(message) => {
+            assert!(
+                message.contains("card_template_id is required"),
+                "{}",
+                message
+            );
+            assert!(message.contains("out_track_id is required"), "{}", message);
+            assert!(
+                message.contains("card_data must be JSON object, got array"),
+                "{}",
+                message
+            );
+            assert!(message.contains("receiver is required"), "{}", message);
+        }
+        err => panic!("{:?}", err),
+    }
+    assert!(InteractiveCard::new("template001", "track001")
+        .users(&[])
+        .validate()
+        .is_err());
+    assert!(InteractiveCard::new("template001", "track001")
+        .group("cid001")
+        .validate()
+        .is_ok());
+}
+
+#[test]
+fn send_invalid_interactive_card() {
+    let server = card_server(MockResponse::ok());
+    let robot = robot_client(&server);
+    let card = InteractiveCard::new("template001", "")
+        .card_data(json!("text"))
+        .group("cid001");
+    let err = tokio_test::block_on(robot.send_interactive_card(&card)).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)), "{:?}", err);
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn send_interactive_card_invalid_robot_code() {
+    let server = card_server(MockResponse::new(
+        400,
+        r#"{"code":"invalidParameter.robotCode","message":"robotCode is invalid"}"#,
+    ));
+    let robot = robot_client(&server);
+    let card = InteractiveCard::new("template001", "track001").group("cid001");
+    let err = tokio_test::block_on(robot.send_interactive_card(&card)).unwrap_err();
+    assert!(matches!(err, DingTalkError::Config(_)), "{:?}", err);
+}
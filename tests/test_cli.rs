@@ -0,0 +1,170 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use assert_cmd::Command;
+use common::{MockResponse, MockServer};
+use serde_json::{json, Value};
+use std::{env, fs};
+
+fn dingtalk_send(server: &MockServer) -> Command {
+    let mut cmd = Command::cargo_bin("dingtalk-send").unwrap();
+    cmd.arg("--token")
+        .arg(format!("{}?access_token=token001", server.url));
+    cmd
+}
+
+fn sent_body(server: &MockServer) -> Value {
+    let requests = server.requests();
+    assert_eq!(1, requests.len());
+    serde_json::from_str(&requests[0].body_str()).unwrap()
+}
+
+#[test]
+fn cli_send_text_at() {
+    let server = MockServer::reply(MockResponse::ok());
+    dingtalk_send(&server)
+        .args([
+            "--at-all",
+            "--at-mobile",
+            "13800000000",
+            "text",
+            "disk full",
+        ])
+        .assert()
+        .success();
+    let body = sent_body(&server);
+    assert_eq!("text", body["msgtype"]);
+    assert_eq!("disk full", body["text"]["content"]);
+    assert_eq!(true, body["at"]["isAtAll"]);
+    assert_eq!(json!(["13800000000"]), body["at"]["atMobiles"]);
+}
+
+#[test]
+fn cli_send_markdown() {
+    let server = MockServer::reply(MockResponse::ok());
+    dingtalk_send(&server)
+        .args(["markdown", "--title", "Deploy", "# Deploy done"])
+        .assert()
+        .success();
+    let body = sent_body(&server);
+    assert_eq!("markdown", body["msgtype"]);
+    assert_eq!("Deploy", body["markdown"]["title"]);
+    assert_eq!("# Deploy done", body["markdown"]["text"]);
+}
+
+#[test]
+fn cli_send_link() {
+    let server = MockServer::reply(MockResponse::ok());
+    dingtalk_send(&server)
+        .args([
+            "link",
+            "--title",
+            "Report",
+            "--text",
+            "Daily report",
+            "--pic-url",
+            "https://example.com/pic.png",
+            "--message-url",
+            "https://example.com/report",
+        ])
+        .assert()
+        .success();
+    let body = sent_body(&server);
+    assert_eq!("link", body["msgtype"]);
+    assert_eq!("Report", body["link"]["title"]);
+    assert_eq!("https://example.com/report", body["link"]["messageUrl"]);
+}
+
+#[test]
+fn cli_send_raw() {
+    let server = MockServer::reply(MockResponse::ok());
+    let raw = r#"{"msgtype": "text", "text": {"content": "raw"}}"#;
+    dingtalk_send(&server).args(["raw", raw]).assert().success();
+    assert_eq!(
+        json!({"msgtype": "text", "text": {"content": "raw"}}),
+        sent_body(&server)
+    );
+
+    let output = dingtalk_send(&server)
+        .args(["--at-all", "raw", raw])
+        .assert()
+        .failure();
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr).to_string();
+    assert!(stderr.contains("not supported by raw"), "{}", stderr);
+}
+
+#[test]
+fn cli_server_error() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":310000,"errmsg":"sign not match"}"#,
+    ));
+    let output = dingtalk_send(&server)
+        .args(["text", "hello"])
+        .assert()
+        .code(1);
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr).to_string();
+    assert!(
+        stderr.contains("errcode: 310000, errmsg: sign not match"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn cli_config_profile() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dir = env::temp_dir().join(format!("dingtalk-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("dingtalk.json");
+    fs::write(
+        &config,
+        json!({
+            "profiles": {
+                "prod": {"access_token": "prod001", "default_webhook_url": server.url},
+                "dev": {"access_token": "dev001", "default_webhook_url": server.url}
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+    Command::cargo_bin("dingtalk-send")
+        .unwrap()
+        .env_remove("DINGTALK_PROFILE")
+        .args(["--config", config.to_str().unwrap(), "--profile", "prod"])
+        .args(["text", "hello"])
+        .assert()
+        .success();
+    assert_eq!(
+        "/robot/send?access_token=prod001",
+        server.requests()[0].path
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cli_markdown_from_stdin() {
+    let server = MockServer::reply(MockResponse::ok());
+    dingtalk_send(&server)
+        .args(["markdown", "--title", "nightly job"])
+        .write_stdin("job done\n")
+        .assert()
+        .success();
+    let body = sent_body(&server);
+    assert_eq!("nightly job", body["markdown"]["title"]);
+    assert_eq!("```\njob done\n```", body["markdown"]["text"]);
+}
+
+#[test]
+fn cli_markdown_from_empty_stdin() {
+    let server = MockServer::reply(MockResponse::ok());
+    let output = dingtalk_send(&server)
+        .args(["markdown", "--title", "nightly job"])
+        .write_stdin("")
+        .assert()
+        .code(1);
+    let stderr = String::from_utf8_lossy(&output.get_output().stderr).to_string();
+    assert!(stderr.contains("Input is empty"), "{}", stderr);
+    assert_eq!(0, server.request_count());
+}
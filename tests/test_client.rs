@@ -0,0 +1,160 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::{
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn dingtalk_is_send_sync() {
+    assert_send_sync::<DingTalk>();
+    assert_send_sync::<Arc<DingTalk>>();
+}
+
+#[test]
+fn client_reuses_connection() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(async {
+        for i in 0..5 {
+            dt.send_text(&format!("message {}", i)).await.unwrap();
+        }
+    });
+    assert_eq!(5, server.request_count());
+    assert_eq!(1, server.connection_count());
+}
+
+#[test]
+fn clones_share_client() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let tasks: Vec<_> = (0..8)
+            .map(|i| {
+                let dt = dt.clone();
+                tokio::spawn(async move { dt.send_text(&format!("message {}", i)).await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+    });
+    assert_eq!(8, server.request_count());
+    let connection_count = server.connection_count();
+    assert!(connection_count <= 8, "{}", connection_count);
+
+    // pooled connections of the shared client are reused by another clone
+    let cloned = dt.clone();
+    runtime.block_on(async {
+        for i in 0..4 {
+            cloned.send_text(&format!("again {}", i)).await.unwrap();
+        }
+    });
+    assert_eq!(12, server.request_count());
+    assert_eq!(connection_count, server.connection_count());
+}
+
+#[test]
+fn send_timeout() {
+    let server = MockServer::reply(MockResponse::ok().delay(Duration::from_millis(500)));
+    let mut dt = DingTalk::from_url(&server.url);
+    dt.set_timeout(Duration::from_millis(100));
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Timeout(_)));
+}
+
+#[test]
+fn send_connect_timeout() {
+    // listener never accepts, connects beyond the full backlog hang
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut backlog = vec![];
+    while let Ok(stream) = TcpStream::connect_timeout(&addr, Duration::from_millis(200)) {
+        backlog.push(stream);
+    }
+    let dt = DingTalk::builder()
+        .direct_url(&format!("http://{}/robot/send", addr))
+        .connect_timeout(Duration::from_millis(200))
+        .request_timeout(Duration::from_secs(10))
+        .build()
+        .unwrap();
+    let start = Instant::now();
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(
+        matches!(err, DingTalkError::ConnectTimeout(_)),
+        "unexpected error: {}",
+        err
+    );
+    assert!(err.is_retryable());
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn send_request_timeout_is_not_connect_timeout() {
+    let server = MockServer::reply(MockResponse::ok().delay(Duration::from_millis(500)));
+    let dt = DingTalk::builder()
+        .direct_url(&server.url)
+        .connect_timeout(Duration::from_secs(2))
+        .request_timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Timeout(_)));
+}
+
+#[test]
+fn send_without_timeout() {
+    let server = MockServer::reply(MockResponse::ok().delay(Duration::from_millis(200)));
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+}
+
+#[test]
+fn send_through_proxy() {
+    let proxy = MockServer::reply(MockResponse::ok());
+    let mut dt = DingTalk::from_url("http://dingtalk.invalid/robot/send");
+    dt.set_proxy(&proxy.base_url()).unwrap();
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    let requests = proxy.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!("http://dingtalk.invalid/robot/send", requests[0].path);
+}
+
+#[test]
+fn proxy_from_json() {
+    let proxy = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_json(&format!(
+        r#"{{"direct_url": "http://dingtalk.invalid/robot/send", "proxy": "{}"}}"#,
+        proxy.base_url()
+    ))
+    .unwrap();
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(1, proxy.request_count());
+}
+
+#[test]
+fn malformed_proxy() {
+    let mut dt = DingTalk::new("token", "");
+    assert!(matches!(
+        dt.set_proxy("not a url"),
+        Err(DingTalkError::Config(_))
+    ));
+    assert!(matches!(
+        dt.set_proxy("ftp://proxy.corp:21"),
+        Err(DingTalkError::Config(_))
+    ));
+    assert!(dt.set_proxy("http://proxy.corp:3128").is_ok());
+    #[cfg(any(feature = "blocking", not(feature = "minimal-http")))]
+    assert!(dt.set_proxy("https://proxy.corp:3128").is_ok());
+    assert!(DingTalk::from_json(r#"{"access_token": "token", "proxy": "bad"}"#).is_err());
+}
@@ -0,0 +1,84 @@
+use dingtalk::*;
+use serde_json::Value;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+const SECRET: &str = "SEC0123456789abcdef";
+
+struct FixedClock(SystemTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+fn at_millis(millis: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_millis(millis)
+}
+
+fn query_param(url: &str, name: &str) -> Option<String> {
+    url.split_once('?')?
+        .1
+        .split('&')
+        .find_map(|kv| kv.strip_prefix(&format!("{}=", name)))
+        .map(|v| v.to_string())
+}
+
+#[test]
+fn clock_dingtalk_sign_known_answer() {
+    let dt = DingTalk::new("token001", SECRET).with_clock(FixedClock(at_millis(1600000000000)));
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/send?access_token=token001\
+         &timestamp=1600000000000&sign=PPcRl2tf%2BBNekAadmEb8Cx1rGmXjHN8H%2B%2F0mvy5rUJk%3D",
+        dt.generate_signed_url().unwrap()
+    );
+}
+
+#[test]
+fn clock_closure() {
+    let dt = DingTalk::new("token001", SECRET).with_clock(|| at_millis(1600000000000));
+    let url = dt.generate_signed_url().unwrap();
+    assert_eq!(Some("1600000000000".into()), query_param(&url, "timestamp"));
+    assert_eq!(
+        Some("PPcRl2tf%2BBNekAadmEb8Cx1rGmXjHN8H%2B%2F0mvy5rUJk%3D".into()),
+        query_param(&url, "sign")
+    );
+}
+
+#[test]
+fn clock_without_sec_token() {
+    let dt = DingTalk::new("token001", "").with_clock(|| at_millis(1600000000000));
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/send?access_token=token001",
+        dt.generate_signed_url().unwrap()
+    );
+}
+
+#[test]
+fn clock_feishu_sign_known_answer() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new_feishu("hook001", SECRET)
+        .with_clock(|| at_millis(1600000000123))
+        .with_transport(transport.clone());
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+
+    let body: Value = serde_json::from_str(&transport.bodies()[0]).unwrap();
+    assert_eq!("1600000000", body["timestamp"]);
+    assert_eq!("G03Wi17AjqjoS5ixGoylc16+oEbyvIi6fHd/xPDigFk=", body["sign"]);
+}
+
+#[test]
+fn clock_default_system_clock() {
+    let dt = DingTalk::new("token001", SECRET);
+    let url = dt.generate_signed_url().unwrap();
+    let timestamp: u128 = query_param(&url, "timestamp").unwrap().parse().unwrap();
+    let now = SystemClock
+        .now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    assert!(now >= timestamp && now - timestamp < 60_000);
+}
@@ -0,0 +1,255 @@
+use dingtalk::*;
+use std::{env, fs};
+
+#[test]
+fn from_toml_keys() {
+    let dt = DingTalk::from_toml(
+        r#"
+type = "wechat"
+access_token = "key001"
+default_webhook_url = "https://wecom.example.com/send"
+unknown_key = 1
+"#,
+    )
+    .unwrap();
+    assert!(matches!(dt.dingtalk_type, DingTalkType::WeChatWork));
+    assert_eq!(
+        "https://wecom.example.com/send?key=key001",
+        dt.generate_signed_url().unwrap()
+    );
+}
+
+#[test]
+fn from_toml_format_error() {
+    match DingTalk::from_toml("access_token = \"token001\"\nsec_token = ") {
+        Err(DingTalkError::Config(message)) => {
+            assert!(message.starts_with("TOML format error"), "{}", message);
+            assert!(message.contains("line 2 column"), "{}", message);
+        }
+        _ => panic!("malformed TOML should be config error"),
+    }
+}
+
+#[test]
+fn from_file_toml_and_json() {
+    let dir = env::temp_dir().join(format!("dingtalk-config-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let toml_file = dir.join("dingtalk.toml");
+    fs::write(
+        &toml_file,
+        "access_token = \"token001\"\nsec_token = \"secret001\"\n",
+    )
+    .unwrap();
+    let json_file = dir.join("dingtalk.json");
+    fs::write(&json_file, r#"{"access_token": "token002"}"#).unwrap();
+
+    let dt = DingTalk::from_file(toml_file.to_str().unwrap()).unwrap();
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+    let dt = DingTalk::from_file(json_file.to_str().unwrap()).unwrap();
+    assert_eq!("token002", dt.access_token);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn from_json_expand_env() {
+    env::set_var("T5_DT_SEC", "secret001");
+    let dt = DingTalk::from_json(
+        r#"{
+            "access_token": "${T5_DT_TOKEN:-token001}",
+            "sec_token": "${T5_DT_SEC}",
+            "default_webhook_url": "https://example.com/$$robot/$send"
+        }"#,
+    )
+    .unwrap();
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+    assert_eq!("https://example.com/$robot/$send", dt.default_webhook_url);
+}
+
+#[test]
+fn from_json_expand_env_missing() {
+    match DingTalk::from_json(r#"{"access_token": "token001", "sec_token": "${T6_DT_MISSING}"}"#) {
+        Err(DingTalkError::Config(message)) => assert_eq!(
+            "Environment variable T6_DT_MISSING in config value is not set",
+            message
+        ),
+        _ => panic!("missing variable should be config error"),
+    }
+}
+
+#[test]
+fn from_toml_expand_env_off() {
+    let dt = DingTalk::from_toml(
+        "access_token = \"token001\"\nsec_token = \"${T7_DT_SEC}$$\"\nexpand_env = false\n",
+    )
+    .unwrap();
+    assert_eq!("${T7_DT_SEC}$$", dt.sec_token);
+}
+
+const PROFILES_JSON: &str = r#"{
+    "default_profile": "dev",
+    "sec_token": "shared001",
+    "profiles": {
+        "prod": {"access_token": "prod001", "sec_token": "prodsecret001"},
+        "dev": {"access_token": "dev001"}
+    }
+}"#;
+
+fn write_config(name: &str, content: &str) -> std::path::PathBuf {
+    let dir = env::temp_dir().join(format!("dingtalk-profile-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join(name);
+    fs::write(&file, content).unwrap();
+    file
+}
+
+#[test]
+fn from_file_profile_selects_profile() {
+    let file = write_config("profiles.json", PROFILES_JSON);
+    let dt = DingTalk::from_file_profile(file.to_str().unwrap(), "prod").unwrap();
+    assert_eq!("prod001", dt.access_token);
+    assert_eq!("prodsecret001", dt.sec_token);
+    // shared keys are used when profile does not override them
+    let dt = DingTalk::from_file_profile(file.to_str().unwrap(), "dev").unwrap();
+    assert_eq!("dev001", dt.access_token);
+    assert_eq!("shared001", dt.sec_token);
+
+    let file = write_config(
+        "profiles.toml",
+        "[profiles.prod]\naccess_token = \"prod002\"\n\n[profiles.dev]\naccess_token = \"dev002\"\n",
+    );
+    let dt = DingTalk::from_file_profile(file.to_str().unwrap(), "prod").unwrap();
+    assert_eq!("prod002", dt.access_token);
+}
+
+#[test]
+fn from_file_profile_missing() {
+    let file = write_config("missing.json", PROFILES_JSON);
+    match DingTalk::from_file_profile(file.to_str().unwrap(), "staging") {
+        Err(DingTalkError::Config(message)) => assert_eq!(
+            "Profile staging is not found, available profiles: dev, prod",
+            message
+        ),
+        _ => panic!("missing profile should be config error"),
+    }
+
+    let file = write_config("no-profiles.json", r#"{"access_token": "token001"}"#);
+    match DingTalk::from_file_profile(file.to_str().unwrap(), "prod") {
+        Err(DingTalkError::Config(message)) => {
+            assert_eq!("Profile prod is not found, config has no profiles", message)
+        }
+        _ => panic!("missing profile should be config error"),
+    }
+}
+
+// DINGTALK_PROFILE is process wide, so every case depends on it is in this test
+#[test]
+fn from_file_profile_env() {
+    let file = write_config("env.json", PROFILES_JSON);
+    let f = file.to_str().unwrap();
+
+    env::remove_var("DINGTALK_PROFILE");
+    assert_eq!("dev001", DingTalk::from_file(f).unwrap().access_token);
+
+    env::set_var("DINGTALK_PROFILE", "prod");
+    assert_eq!("prod001", DingTalk::from_file(f).unwrap().access_token);
+    assert_eq!(
+        "prod001",
+        DingTalk::from_json(PROFILES_JSON).unwrap().access_token
+    );
+    // explicit profile wins
+    assert_eq!(
+        "dev001",
+        DingTalk::from_file_profile(f, "dev").unwrap().access_token
+    );
+    // config without profiles loads as is
+    let plain = write_config("plain.json", r#"{"access_token": "token001"}"#);
+    assert_eq!(
+        "token001",
+        DingTalk::from_file(plain.to_str().unwrap())
+            .unwrap()
+            .access_token
+    );
+
+    env::set_var("DINGTALK_PROFILE", "qa");
+    match DingTalk::from_file(f) {
+        Err(DingTalkError::Config(message)) => assert_eq!(
+            "Profile qa is not found, available profiles: dev, prod",
+            message
+        ),
+        _ => panic!("missing profile should be config error"),
+    }
+
+    env::remove_var("DINGTALK_PROFILE");
+    match DingTalk::from_json(r#"{"profiles": {"prod": {"access_token": "prod001"}}}"#) {
+        Err(DingTalkError::Config(message)) => {
+            assert!(message.contains("DINGTALK_PROFILE"), "{}", message);
+            assert!(message.ends_with("available profiles: prod"), "{}", message);
+        }
+        _ => panic!("unselected profile should be config error"),
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+#[test]
+fn from_json_keyring_ref_requires_feature() {
+    match DingTalk::from_json(r#"{"access_token": "token001", "sec_token": "keyring:prod/ops"}"#) {
+        Err(DingTalkError::Unsupported(message)) => assert_eq!(
+            "Config sec_token references keyring keyring:prod/ops, which requires feature `keyring`",
+            message
+        ),
+        _ => panic!("keyring reference without feature should be unsupported"),
+    }
+}
+
+#[test]
+fn from_json_token_env_keys() {
+    env::set_var("T97_DT_TOKEN", "token001");
+    env::set_var("T97_DT_SEC", "secret001");
+    let dt = DingTalk::from_json(
+        r#"{"access_token_env": "T97_DT_TOKEN", "sec_token_env": "T97_DT_SEC"}"#,
+    )
+    .unwrap();
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+
+    let dt = DingTalk::from_toml("access_token_env = \"T97_DT_TOKEN\"\n").unwrap();
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("", dt.sec_token);
+}
+
+#[test]
+fn from_json_token_env_keys_literal_wins() {
+    env::set_var("T97_DT_TOKEN_LITERAL", "token_from_env");
+    let dt = DingTalk::from_json(
+        r#"{"access_token": "token001", "access_token_env": "T97_DT_TOKEN_LITERAL"}"#,
+    )
+    .unwrap();
+    assert_eq!("token001", dt.access_token);
+    // empty literal is treated as missing
+    let dt =
+        DingTalk::from_json(r#"{"access_token": "", "access_token_env": "T97_DT_TOKEN_LITERAL"}"#)
+            .unwrap();
+    assert_eq!("token_from_env", dt.access_token);
+}
+
+#[test]
+fn from_json_token_env_keys_missing() {
+    match DingTalk::from_json(r#"{"access_token": "token001", "sec_token_env": "T97_DT_MISSING"}"#)
+    {
+        Err(DingTalkError::Config(message)) => assert_eq!(
+            "Environment variable T97_DT_MISSING of config sec_token_env is not set",
+            message
+        ),
+        _ => panic!("missing variable should be config error"),
+    }
+    match DingTalk::from_json(r#"{"access_token_env": 1}"#) {
+        Err(DingTalkError::Config(message)) => assert_eq!(
+            "Config access_token_env must be environment variable name",
+            message
+        ),
+        _ => panic!("invalid env key should be config error"),
+    }
+}
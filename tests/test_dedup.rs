@@ -0,0 +1,107 @@
+use dingtalk::*;
+use serde_json::{json, Value};
+use std::{sync::Arc, thread, time::Duration};
+
+fn dedup_dingtalk(transport: &Arc<RecordingTransport>, window: Duration) -> DingTalk {
+    DingTalk::new("token001", "")
+        .dedup_window(window)
+        .with_transport(transport.clone())
+}
+
+fn text_contents(transport: &RecordingTransport) -> Vec<String> {
+    transport
+        .bodies()
+        .iter()
+        .map(|body| {
+            let body: Value = serde_json::from_str(body).unwrap();
+            body["text"]["content"].as_str().unwrap().to_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn dedup_suppresses_duplicates() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = dedup_dingtalk(&transport, Duration::from_secs(60));
+    tokio_test::block_on(async {
+        let response = dt
+            .send_message_with_response(DingTalkMessage::new_text("disk full"))
+            .await
+            .unwrap();
+        assert!(!response.is_suppressed());
+        for duplicates in 1..=2 {
+            let response = dt
+                .send_message_with_response(DingTalkMessage::new_text("disk full"))
+                .await
+                .unwrap();
+            assert!(response.is_suppressed());
+            assert!(response.is_success());
+            assert_eq!("suppressed", response.errmsg);
+            assert_eq!(
+                json!({"suppressed": true, "duplicates": duplicates}),
+                response.raw
+            );
+        }
+        dt.send_text("disk full").await.unwrap();
+        dt.send_text("cpu high").await.unwrap();
+    });
+    assert_eq!(vec!["disk full", "cpu high"], text_contents(&transport));
+}
+
+#[test]
+fn dedup_off_by_default() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "").with_transport(transport.clone());
+    tokio_test::block_on(async {
+        dt.send_text("disk full").await.unwrap();
+        dt.send_text("disk full").await.unwrap();
+    });
+    assert_eq!(2, transport.requests().len());
+}
+
+#[test]
+fn dedup_window_expires() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = dedup_dingtalk(&transport, Duration::from_millis(50));
+    tokio_test::block_on(dt.send_text("disk full")).unwrap();
+    tokio_test::block_on(dt.send_text("disk full")).unwrap();
+    thread::sleep(Duration::from_millis(80));
+    tokio_test::block_on(dt.send_text("disk full")).unwrap();
+    assert_eq!(vec!["disk full", "disk full"], text_contents(&transport));
+}
+
+#[test]
+fn dedup_summary_after_window() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = dedup_dingtalk(&transport, Duration::from_millis(50)).dedup_summary(true);
+    tokio_test::block_on(async {
+        dt.send_text("disk full\non web-01").await.unwrap();
+        dt.send_text("disk full\non web-01").await.unwrap();
+        dt.send_text("disk full\non web-01").await.unwrap();
+        dt.send_text("cpu high").await.unwrap();
+    });
+    thread::sleep(Duration::from_millis(80));
+    tokio_test::block_on(dt.send_text("memory low")).unwrap();
+    assert_eq!(
+        vec![
+            "disk full\non web-01",
+            "cpu high",
+            "Suppressed 2 duplicate message(s) in 50ms: disk full",
+            "memory low",
+        ],
+        text_contents(&transport)
+    );
+}
+
+#[test]
+fn dedup_failed_message_not_recorded() {
+    let transport = Arc::new(RecordingTransport::new());
+    transport.reply(TransportResponse::new(500, ""));
+    let dt = dedup_dingtalk(&transport, Duration::from_secs(60));
+    tokio_test::block_on(async {
+        assert!(dt.send_text("disk full").await.is_err());
+        dt.send_text("disk full").await.unwrap();
+        dt.send_text("disk full").await.unwrap();
+    });
+    assert_eq!(2, transport.requests().len());
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+#[test]
+fn discord_send_text_no_content() {
+    let server = MockServer::reply(MockResponse::new(204, ""));
+    let dt = DingTalk::new_discord(&server.url);
+    tokio_test::block_on(
+        dt.send_message(
+            DingTalkMessage::new_text("hello")
+                .at_user_id("1001")
+                .at_all(),
+        ),
+    )
+    .unwrap();
+    let requests = server.requests();
+    assert_eq!("/robot/send", requests[0].path);
+    assert_eq!(
+        r#"{"content":"hello <@1001> @everyone"}"#,
+        requests[0].body_str()
+    );
+}
+
+#[test]
+fn discord_send_markdown() {
+    let server = MockServer::reply(MockResponse::new(204, ""));
+    let dt = DingTalk::new_discord(&server.url);
+    tokio_test::block_on(dt.send_markdown("title", "*italic*")).unwrap();
+    assert_eq!(
+        r#"{"content":"**title**\n*italic*"}"#,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn discord_send_link() {
+    let server = MockServer::reply(MockResponse::new(204, ""));
+    let mut dt = DingTalk::from_url(&server.url);
+    dt.dingtalk_type = DingTalkType::Discord;
+    tokio_test::block_on(dt.send_link(
+        "title",
+        "text",
+        "https://example.com/a.png",
+        "https://example.com/",
+    ))
+    .unwrap();
+    assert_eq!(
+        r#"{"embeds":[{"description":"text","thumbnail":{"url":"https://example.com/a.png"},"title":"title","url":"https://example.com/"}]}"#,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn discord_send_feed_card() {
+    let server = MockServer::reply(MockResponse::new(204, ""));
+    let dt = DingTalk::new_discord(&server.url);
+    let message = DingTalkMessage::new_feed_card()
+        .add_feed_card_link_detail("a", "https://example.com/a", "")
+        .add_feed_card_link_detail("b", "https://example.com/b", "");
+    tokio_test::block_on(dt.send_message(message)).unwrap();
+    assert_eq!(
+        r#"{"embeds":[{"title":"a","url":"https://example.com/a"},{"title":"b","url":"https://example.com/b"}]}"#,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn discord_unsupported_action_card() {
+    let server = MockServer::reply(MockResponse::new(204, ""));
+    let dt = DingTalk::new_discord(&server.url);
+    let err =
+        tokio_test::block_on(dt.send_message(DingTalkMessage::new_action_card("title", "text")))
+            .unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn discord_error_status() {
+    let server = MockServer::reply(MockResponse::new(
+        400,
+        r#"{"message":"Cannot send an empty message","code":50006}"#,
+    ));
+    let dt = DingTalk::new_discord(&server.url);
+    let err = tokio_test::block_on(dt.send_text("")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Status { status: 400, .. }));
+}
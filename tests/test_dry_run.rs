@@ -0,0 +1,86 @@
+use dingtalk::*;
+use serde_json::json;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+fn dry_run_dingtalk(transport: &Arc<RecordingTransport>) -> DingTalk {
+    DingTalk::new("token001", "secret001")
+        .dry_run(true)
+        .with_transport(transport.clone())
+}
+
+#[test]
+fn dry_run_makes_no_request() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = dry_run_dingtalk(&transport);
+    let response = tokio_test::block_on(
+        dt.send_message_with_response(DingTalkMessage::new_markdown("title", "**hello**")),
+    )
+    .unwrap();
+    assert!(response.is_success());
+    assert_eq!("dry run", response.errmsg);
+    assert_eq!(
+        json!({
+            "dry_run": true,
+            "body": {"msgtype": "markdown", "markdown": {"title": "title", "text": "**hello**"}},
+        }),
+        response.raw
+    );
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    tokio_test::block_on(dt.send(r#"{"msgtype":"text","text":{"content":"hi"}}"#)).unwrap();
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn dry_run_skips_rate_limit_and_retry() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = dry_run_dingtalk(&transport)
+        .rate_limit(1, Duration::from_secs(60))
+        .with_retry(3, Duration::from_secs(1));
+    tokio_test::block_on(async {
+        for _ in 0..3 {
+            dt.send_text("hello").await.unwrap();
+        }
+    });
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn dry_run_still_validates() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = dry_run_dingtalk(&transport).strict_validation(true);
+    let err = tokio_test::block_on(dt.send_text("")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+
+    let feishu = DingTalk::new_feishu("token001", "secret001")
+        .dry_run(true)
+        .with_transport(transport.clone());
+    let err = tokio_test::block_on(feishu.send("[]")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn dry_run_signed_feishu_body() {
+    let transport = Arc::new(RecordingTransport::new());
+    let feishu = DingTalk::new_feishu("token001", "secret001")
+        .dry_run(true)
+        .with_clock(|| SystemTime::UNIX_EPOCH + Duration::from_secs(1600000000))
+        .with_transport(transport.clone());
+    let response =
+        tokio_test::block_on(feishu.send_message_with_response(DingTalkMessage::new_text("hello")))
+            .unwrap();
+    assert_eq!("1600000000", response.raw["body"]["timestamp"]);
+    assert!(response.raw["body"]["sign"].is_string());
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn dry_run_off() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = dry_run_dingtalk(&transport).dry_run(false);
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(1, transport.requests().len());
+}
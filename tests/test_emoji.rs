@@ -0,0 +1,61 @@
+use dingtalk::emoji::{replace_shortcodes, shortcode, SHORTCODES};
+use dingtalk::*;
+
+#[test]
+fn replace_known_shortcodes() {
+    assert_eq!("🔥 disk full", replace_shortcodes(":fire: disk full"));
+    assert_eq!(
+        "✅ deployed 🚀🎉",
+        replace_shortcodes(":white_check_mark: deployed :rocket::tada:")
+    );
+    assert_eq!("👍 👎", replace_shortcodes(":+1: :-1:"));
+}
+
+#[test]
+fn keep_unknown_shortcodes() {
+    assert_eq!(
+        ":no_such_emoji: ok",
+        replace_shortcodes(":no_such_emoji: ok")
+    );
+    assert_eq!(":unknown🔥", replace_shortcodes(":unknown:fire:"));
+    assert_eq!("time 12:30:45", replace_shortcodes("time 12:30:45"));
+    assert_eq!(":Fire: :fire", replace_shortcodes(":Fire: :fire"));
+    assert_eq!("::🔥", replace_shortcodes(":::fire:"));
+    assert_eq!("", replace_shortcodes(""));
+}
+
+#[test]
+fn shortcode_table() {
+    for (name, emoji) in SHORTCODES {
+        assert_eq!(Some(*emoji), shortcode(name), "{}", name);
+        assert_eq!(*emoji, replace_shortcodes(&format!(":{}:", name)));
+    }
+    assert_eq!(None, shortcode("no_such_emoji"));
+}
+
+#[test]
+fn convert_emoji_shortcodes_in_payload() {
+    let dt = DingTalk::new("token", "").convert_emoji_shortcodes(true);
+    let payload = dt
+        .build_payload(&DingTalkMessage::new_text(":fire: disk full"))
+        .unwrap();
+    assert_eq!("🔥 disk full", payload["text"]["content"]);
+
+    let payload = dt
+        .build_payload(&DingTalkMessage::new_markdown(
+            ":warning: Disk",
+            "# :warning: disk full\n* :x: web-01",
+        ))
+        .unwrap();
+    assert_eq!("⚠️ Disk", payload["markdown"]["title"]);
+    assert_eq!("# ⚠️ disk full\n* ❌ web-01", payload["markdown"]["text"]);
+}
+
+#[test]
+fn convert_emoji_shortcodes_default_off() {
+    let dt = DingTalk::new("token", "");
+    let payload = dt
+        .build_payload(&DingTalkMessage::new_text(":fire: disk full"))
+        .unwrap();
+    assert_eq!(":fire: disk full", payload["text"]["content"]);
+}
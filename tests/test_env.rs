@@ -0,0 +1,57 @@
+use dingtalk::*;
+use std::env;
+
+// Each test uses its own prefix, tests run in parallel threads of one process
+
+#[test]
+fn from_env_prefixed_dingtalk() {
+    env::set_var("T1_DT_ACCESS_TOKEN", "token001");
+    env::set_var("T1_DT_SEC_TOKEN", "secret001");
+    let dt = DingTalk::from_env_prefixed("T1_DT_").unwrap();
+    assert!(matches!(dt.dingtalk_type, DingTalkType::DingTalk));
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/send",
+        dt.default_webhook_url
+    );
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+}
+
+#[test]
+fn from_env_prefixed_wechatwork() {
+    env::set_var("T2_DT_TYPE", "wechatwork");
+    env::set_var("T2_DT_ACCESS_TOKEN", "key001");
+    env::set_var("T2_DT_WEBHOOK_URL", "https://wecom.example.com/send");
+    let dt = DingTalk::from_env_prefixed("T2_DT_").unwrap();
+    assert!(matches!(dt.dingtalk_type, DingTalkType::WeChatWork));
+    assert_eq!(
+        "https://wecom.example.com/send?key=key001",
+        dt.generate_signed_url().unwrap()
+    );
+}
+
+#[test]
+fn from_env_prefixed_missing_access_token() {
+    env::set_var("T3_DT_SEC_TOKEN", "secret001");
+    match DingTalk::from_env_prefixed("T3_DT_") {
+        Err(DingTalkError::Config(message)) => assert_eq!(
+            "Environment variable T3_DT_ACCESS_TOKEN is not set",
+            message
+        ),
+        _ => panic!("missing access token should be config error"),
+    }
+}
+
+#[test]
+fn from_env_prefixed_slack_webhook_url() {
+    env::set_var("T4_DT_TYPE", "slack");
+    env::set_var(
+        "T4_DT_WEBHOOK_URL",
+        "https://hooks.slack.com/services/T0/B0/X0",
+    );
+    let dt = DingTalk::from_env_prefixed("T4_DT_").unwrap();
+    assert_eq!(
+        "https://hooks.slack.com/services/T0/B0/X0",
+        dt.generate_signed_url().unwrap()
+    );
+}
@@ -0,0 +1,125 @@
+use dingtalk::*;
+use serde_json::Value;
+use std::{
+    error::Error,
+    fmt,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+/// Error with message and optional cause
+#[derive(Debug)]
+struct ChainError {
+    message: String,
+    source: Option<Box<ChainError>>,
+}
+
+impl ChainError {
+    /// Error of `messages[0]` caused by `messages[1]` and so on
+    fn chain(messages: &[&str]) -> ChainError {
+        messages
+            .iter()
+            .rev()
+            .fold(None, |source, message| {
+                Some(ChainError {
+                    message: message.to_string(),
+                    source: source.map(Box::new),
+                })
+            })
+            .unwrap()
+    }
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ChainError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+    }
+}
+
+fn dingtalk(transport: &Arc<RecordingTransport>) -> DingTalk {
+    std::env::set_var("HOSTNAME", "test-host");
+    DingTalk::new("token001", "")
+        .with_transport(transport.clone())
+        .with_clock(|| SystemTime::UNIX_EPOCH + Duration::from_millis(1600000000000))
+}
+
+fn markdown(transport: &RecordingTransport) -> (String, String) {
+    let bodies = transport.bodies();
+    assert_eq!(1, bodies.len());
+    let body: Value = serde_json::from_str(&bodies[0]).unwrap();
+    (
+        body["markdown"]["title"].as_str().unwrap().to_owned(),
+        body["markdown"]["text"].as_str().unwrap().to_owned(),
+    )
+}
+
+#[test]
+fn send_error_report_nested_causes() {
+    let transport = Arc::new(RecordingTransport::new());
+    let err = ChainError::chain(&[
+        "sync orders failed",
+        "query database failed",
+        "connection reset\nby peer",
+    ]);
+    tokio_test::block_on(dingtalk(&transport).send_error_report("Nightly sync", &err)).unwrap();
+    assert_eq!(
+        (
+            "Nightly sync".to_owned(),
+            "#### Nightly sync\n\n\
+             - time: `2020-09-13 12:26:40 UTC`\n\
+             - host: `test-host`\n\n\
+             **Error:**\n\n\
+             - sync orders failed\n  \
+             - query database failed\n    \
+             - connection reset by peer"
+                .to_owned()
+        ),
+        markdown(&transport)
+    );
+}
+
+#[test]
+fn send_error_report_with_context_table() {
+    let transport = Arc::new(RecordingTransport::new());
+    let err = ChainError::chain(&["upload failed"]);
+    tokio_test::block_on(dingtalk(&transport).send_error_report_with(
+        "Upload",
+        &err,
+        &[("order", "1024"), ("path", "a|b")],
+    ))
+    .unwrap();
+    assert_eq!(
+        "#### Upload\n\n\
+         - time: `2020-09-13 12:26:40 UTC`\n\
+         - host: `test-host`\n\n\
+         | key | value |\n\
+         | --- | --- |\n\
+         | order | 1024 |\n\
+         | path | a\\|b |\n\n\
+         **Error:**\n\n\
+         - upload failed",
+        markdown(&transport).1
+    );
+}
+
+#[test]
+fn send_error_report_deep_chain_is_truncated() {
+    let transport = Arc::new(RecordingTransport::new());
+    let messages = (0..100).map(|i| format!("cause {}", i)).collect::<Vec<_>>();
+    let messages = messages.iter().map(String::as_str).collect::<Vec<_>>();
+    let err = ChainError::chain(&messages);
+    tokio_test::block_on(dingtalk(&transport).send_error_report("Deep", &err)).unwrap();
+    let text = markdown(&transport).1;
+    assert!(text.contains(&format!("\n{}- cause 9\n", "  ".repeat(9))));
+    assert!(!text.contains("cause 10"));
+    assert!(text.ends_with(&format!(
+        "\n{}- …(more causes are omitted)",
+        "  ".repeat(10)
+    )));
+}
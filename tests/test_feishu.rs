@@ -0,0 +1,93 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use hmac::{Hmac, Mac, NewMac};
+use serde_json::Value;
+use sha2::Sha256;
+
+fn feishu(server: &MockServer, sec_token: &str) -> DingTalk {
+    let mut dt = DingTalk::new_feishu("hook001", sec_token);
+    dt.set_default_webhook_url(&format!("{}/open-apis/bot/v2/hook/", server.base_url()));
+    dt
+}
+
+#[test]
+fn feishu_send_text() {
+    let server = MockServer::reply(MockResponse::new(200, r#"{"code":0,"msg":"success"}"#));
+    let dt = feishu(&server, "");
+    tokio_test::block_on(dt.send_message(DingTalkMessage::new_text("hello").at_all())).unwrap();
+    let requests = server.requests();
+    assert_eq!("/open-apis/bot/v2/hook/hook001", requests[0].path);
+    assert_eq!(
+        r#"{"content":{"text":"hello <at user_id=\"all\">所有人</at>"},"msg_type":"text"}"#,
+        requests[0].body_str()
+    );
+}
+
+#[test]
+fn feishu_send_markdown() {
+    let server = MockServer::reply(MockResponse::new(200, r#"{"code":0,"msg":"success"}"#));
+    let dt = feishu(&server, "");
+    tokio_test::block_on(dt.send_markdown("title", "**bold**")).unwrap();
+    assert_eq!(
+        r#"{"card":{"elements":[{"content":"**bold**","tag":"markdown"}],"header":{"title":{"content":"title","tag":"plain_text"}}},"msg_type":"interactive"}"#,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn feishu_sign_in_body() {
+    let server = MockServer::reply(MockResponse::new(200, r#"{"code":0,"msg":"success"}"#));
+    let dt = feishu(&server, "secret001");
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    let request = &server.requests()[0];
+    assert_eq!("/open-apis/bot/v2/hook/hook001", request.path);
+    let body: Value = serde_json::from_str(&request.body_str()).unwrap();
+    let timestamp = body["timestamp"].as_str().unwrap();
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(format!("{}\nsecret001", timestamp).as_bytes()).unwrap();
+    mac.update(b"");
+    assert_eq!(
+        base64::encode(mac.finalize().into_bytes()),
+        body["sign"].as_str().unwrap()
+    );
+    assert_eq!("hello", body["content"]["text"]);
+}
+
+#[test]
+fn feishu_error_code() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"code":19021,"msg":"sign match fail or timestamp is not within one hour from current time"}"#,
+    ));
+    let dt = feishu(&server, "secret001");
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Server { errcode: 19021, .. }));
+}
+
+#[test]
+fn feishu_unsupported_message() {
+    let dt = DingTalk::new_feishu("hook001", "");
+    let err = tokio_test::block_on(dt.send_message(DingTalkMessage::new_feed_card())).unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+}
+
+#[test]
+fn feishu_from_token_and_json() {
+    let dt = DingTalk::from_token("feishu:hook001?secret001").unwrap();
+    assert!(matches!(dt.dingtalk_type, DingTalkType::Feishu));
+    assert_eq!("hook001", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+    assert_eq!(
+        "https://open.feishu.cn/open-apis/bot/v2/hook/hook001",
+        dt.generate_signed_url().unwrap()
+    );
+
+    let dt = DingTalk::from_json(r#"{"type": "feishu", "access_token": "hook001"}"#).unwrap();
+    assert!(matches!(dt.dingtalk_type, DingTalkType::Feishu));
+    assert_eq!(
+        "https://open.feishu.cn/open-apis/bot/v2/hook/hook001",
+        dt.generate_signed_url().unwrap()
+    );
+}
@@ -0,0 +1,51 @@
+use dingtalk::*;
+
+#[test]
+fn parse_dingtalk_token() {
+    let dt: DingTalk = "dingtalk:token001?SEC001".parse().unwrap();
+    assert_eq!(DingTalkType::DingTalk, dt.dingtalk_type);
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("SEC001", dt.sec_token);
+
+    let dt: DingTalk = " dingtalk:token001\n".parse().unwrap();
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("", dt.sec_token);
+}
+
+#[test]
+fn parse_wechat_and_feishu_token() {
+    for token in ["wechatwork:key001", "wecom:key001"] {
+        let dt: DingTalk = token.parse().unwrap();
+        assert_eq!(DingTalkType::WeChatWork, dt.dingtalk_type);
+        assert_eq!("key001", dt.access_token);
+    }
+    let dt: DingTalk = "feishu:hook001?secret001".parse().unwrap();
+    assert_eq!(DingTalkType::Feishu, dt.dingtalk_type);
+    assert_eq!("hook001", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+}
+
+#[test]
+fn parse_webhook_url() {
+    for url in [
+        "https://oapi.dingtalk.com/robot/send?access_token=token001",
+        "http://127.0.0.1:8080/robot/send",
+    ] {
+        let dt: DingTalk = url.parse().unwrap();
+        assert_eq!(url, dt.generate_signed_url().unwrap());
+    }
+}
+
+#[test]
+fn parse_malformed_token() {
+    for token in ["", "token001", "slack:token001", "ftp://example.com/"] {
+        match token.parse::<DingTalk>() {
+            Err(DingTalkError::Config(message)) => {
+                assert!(message.starts_with("Token format error"), "{}", message);
+                assert!(message.contains("dingtalk:<access_token>[?<sec_token>]"));
+                assert!(message.contains("http(s) webhook URL"));
+            }
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use dingtalk::*;
+
+#[test]
+fn try_from_url_direct_url() {
+    let dt =
+        DingTalk::try_from_url(" http://127.0.0.1:8080/robot/sendBySession?session=s\n").unwrap();
+    assert_eq!(
+        "http://127.0.0.1:8080/robot/sendBySession?session=s",
+        dt.direct_url
+    );
+    assert_eq!("", dt.access_token);
+    assert_eq!(dt.direct_url, dt.generate_signed_url().unwrap());
+}
+
+#[test]
+fn try_from_url_dingtalk_webhook() {
+    let dt = DingTalk::try_from_url("https://oapi.dingtalk.com/robot/send?access_token=token001")
+        .unwrap();
+    assert_eq!("", dt.direct_url);
+    assert_eq!("token001", dt.access_token);
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/send",
+        dt.default_webhook_url
+    );
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/send?access_token=token001",
+        dt.generate_signed_url().unwrap()
+    );
+
+    let mut dt =
+        DingTalk::from_url("https://oapi.dingtalk.com/robot/send?foo=bar&access_token=token001");
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/send?foo=bar",
+        dt.default_webhook_url
+    );
+    dt.sec_token = "SEC001".into();
+    let signed_url = dt.generate_signed_url().unwrap();
+    assert!(
+        signed_url.starts_with(
+            "https://oapi.dingtalk.com/robot/send?foo=bar&access_token=token001&timestamp="
+        ),
+        "{}",
+        signed_url
+    );
+    assert!(signed_url.contains("&sign="));
+}
+
+#[test]
+fn try_from_url_invalid() {
+    for (url, message) in [
+        ("", "Direct URL is malformed: relative URL without a base"),
+        (
+            "not a url",
+            "Direct URL is malformed: relative URL without a base",
+        ),
+        (
+            "ftp://example.com/robot/send",
+            "Direct URL scheme must be http or https: ftp://example.com/robot/send",
+        ),
+        (
+            "mailto:robot@example.com",
+            "Direct URL scheme must be http or https: mailto:robot@example.com",
+        ),
+        ("http:", "Direct URL is malformed: empty host"),
+    ] {
+        match DingTalk::try_from_url(url) {
+            Err(DingTalkError::Config(e)) => assert_eq!(message, e),
+            other => panic!("unexpected result of {}: {:?}", url, other),
+        }
+    }
+}
+
+#[test]
+fn try_from_url_error_redacts_token() {
+    match DingTalk::try_from_url("ftp://example.com/send?access_token=token001") {
+        Err(DingTalkError::Config(e)) => assert!(!e.contains("token001"), "{}", e),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Invalid url")]
+fn from_url_invalid_debug_assert() {
+    DingTalk::from_url("not a url");
+}
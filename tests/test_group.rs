@@ -0,0 +1,128 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+fn body_json(server: &MockServer) -> Value {
+    serde_json::from_str(&server.requests()[0].body_str()).unwrap()
+}
+
+#[test]
+fn group_send_per_robot_type() {
+    let dingtalk_server = MockServer::reply(MockResponse::ok());
+    let wechat_server = MockServer::reply(MockResponse::ok());
+    let group = DingTalkGroup::new()
+        .add("ops", DingTalk::from_url(&dingtalk_server.url))
+        .add(
+            "wechat",
+            DingTalk::builder()
+                .wechat()
+                .access_token("key001")
+                .webhook_url(&wechat_server.url)
+                .build()
+                .unwrap(),
+        );
+    let results = tokio_test::block_on(group.send_markdown("title", "# hello"));
+    assert_eq!(vec!["ops", "wechat"], results.keys().collect::<Vec<_>>());
+    assert!(results.values().all(|r| r.is_ok()));
+
+    let dingtalk_body = body_json(&dingtalk_server);
+    assert_eq!("title", dingtalk_body["markdown"]["title"]);
+    assert_eq!("# hello", dingtalk_body["markdown"]["text"]);
+    let wechat_body = body_json(&wechat_server);
+    assert_eq!("# title\n# hello", wechat_body["markdown"]["content"]);
+    assert!(wechat_body["markdown"]["title"].is_null());
+}
+
+#[test]
+fn group_partial_failure() {
+    let ok_server = MockServer::reply(MockResponse::ok());
+    let bad_server = MockServer::reply(MockResponse::new(500, ""));
+    let other_server = MockServer::reply(MockResponse::ok());
+    let group = DingTalkGroup::new()
+        .add("a", DingTalk::from_url(&ok_server.url))
+        .add("b", DingTalk::from_url(&bad_server.url))
+        .add("c", DingTalk::from_url(&other_server.url));
+    let results = tokio_test::block_on(group.send_text("hello"));
+    assert_eq!(3, results.len());
+    assert!(results["a"].is_ok());
+    assert!(matches!(
+        results["b"],
+        Err(DingTalkError::Status { status: 500, .. })
+    ));
+    assert!(results["c"].is_ok());
+    assert_eq!(1, ok_server.request_count());
+    assert_eq!(1, bad_server.request_count());
+    assert_eq!(1, other_server.request_count());
+}
+
+#[test]
+fn group_send_concurrently() {
+    let servers: Vec<MockServer> = (0..3)
+        .map(|_| MockServer::reply(MockResponse::ok().delay(Duration::from_millis(300))))
+        .collect();
+    let group = servers
+        .iter()
+        .enumerate()
+        .fold(DingTalkGroup::new(), |group, (i, server)| {
+            group.add(&format!("robot{}", i), DingTalk::from_url(&server.url))
+        });
+    let start = Instant::now();
+    let results = tokio_test::block_on(group.send_text("hello"));
+    assert!(results.values().all(|r| r.is_ok()));
+    assert!(start.elapsed() < Duration::from_millis(800));
+}
+
+#[test]
+fn group_add_replaces_same_name() {
+    let group = DingTalkGroup::new()
+        .add("ops", DingTalk::new("token001", ""))
+        .add("ops", DingTalk::new("token002", ""));
+    assert_eq!(1, group.len());
+    assert_eq!("token002", group.get("ops").unwrap().access_token);
+    assert!(group.get("dev").is_none());
+}
+
+#[test]
+fn group_from_json() {
+    let group = DingTalkGroup::from_json(
+        r#"[
+            {"name": "ops", "access_token": "token001", "sec_token": "secret001"},
+            {"name": "wechat", "type": "wechat", "access_token": "key001"}
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(vec!["ops", "wechat"], group.names());
+    let ops = group.get("ops").unwrap();
+    assert_eq!("token001", ops.access_token);
+    assert_eq!("secret001", ops.sec_token);
+    let wechat = group.get("wechat").unwrap();
+    assert!(matches!(wechat.dingtalk_type, DingTalkType::WeChatWork));
+    assert_eq!("key001", wechat.access_token);
+}
+
+#[test]
+fn group_from_json_invalid() {
+    let config_error = |json: &str| match DingTalkGroup::from_json(json) {
+        Err(DingTalkError::Config(msg)) => msg,
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("expected error"),
+    };
+    assert_eq!(
+        "Group config must be JSON array",
+        config_error(r#"{"name": "ops", "access_token": "token001"}"#)
+    );
+    assert_eq!(
+        "Group robot config requires name",
+        config_error(r#"[{"access_token": "token001"}]"#)
+    );
+    assert_eq!(
+        "Group robot name is duplicated: ops",
+        config_error(
+            r#"[{"name": "ops", "access_token": "token001"},
+                {"name": "ops", "access_token": "token002"}]"#
+        )
+    );
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::sync::Arc;
+
+fn org_token_headers(token: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("X-Org-Token", HeaderValue::from_str(token).unwrap());
+    headers
+}
+
+#[test]
+fn default_headers_on_every_request() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).default_headers(org_token_headers("org-1"));
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    tokio_test::block_on(dt.send_markdown("title", "# hello")).unwrap();
+    let requests = server.requests();
+    assert_eq!(2, requests.len());
+    for request in requests {
+        assert_eq!(Some("org-1"), request.header("X-Org-Token"));
+    }
+}
+
+#[test]
+fn default_headers_replace_same_name() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "")
+        .with_transport(transport.clone())
+        .default_headers(org_token_headers("org-1"))
+        .default_headers(org_token_headers("org-2"));
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(
+        vec![("x-org-token".to_string(), "org-2".to_string())],
+        transport.requests()[0].headers
+    );
+}
+
+#[test]
+fn default_headers_content_type_ignored() {
+    let server = MockServer::reply(MockResponse::ok());
+    let mut headers = org_token_headers("org-1");
+    headers.insert("content-type", HeaderValue::from_static("text/plain"));
+    let dt = DingTalk::from_url(&server.url).default_headers(headers);
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    let request = &server.requests()[0];
+    assert_eq!(Some("org-1"), request.header("X-Org-Token"));
+    let content_types = request
+        .headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("Content-Type"))
+        .map(|(_, value)| value.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["application/json; charset=utf-8"], content_types);
+}
+
+#[test]
+fn send_message_with_headers() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).default_headers(org_token_headers("org-1"));
+    let mut headers = org_token_headers("org-2");
+    headers.insert("X-Request-Id", HeaderValue::from_static("req-1"));
+    let message = DingTalkMessage::new_text("hello");
+    tokio_test::block_on(dt.send_message_with_headers(&message, headers)).unwrap();
+    tokio_test::block_on(dt.send_message_ref(&message)).unwrap();
+    let requests = server.requests();
+    assert_eq!(Some("org-2"), requests[0].header("X-Org-Token"));
+    assert_eq!(Some("req-1"), requests[0].header("X-Request-Id"));
+    // headers of one call are not kept
+    assert_eq!(Some("org-1"), requests[1].header("X-Org-Token"));
+    assert_eq!(None, requests[1].header("X-Request-Id"));
+}
+
+#[test]
+fn default_headers_before_interceptors() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "")
+        .with_transport(transport.clone())
+        .default_headers(org_token_headers("org-1"))
+        .with_interceptor(|request: RequestParts| {
+            let token = request.header("X-Org-Token").unwrap().to_uppercase();
+            Ok(request.with_header("X-Org-Token", &token))
+        });
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(
+        vec![("X-Org-Token".to_string(), "ORG-1".to_string())],
+        transport.requests()[0].headers
+    );
+}
+
+#[test]
+fn default_headers_on_upload() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":0,"errmsg":"ok","type":"file","media_id":"media_id_001"}"#,
+    ));
+    let mut dt = DingTalk::new_wechat("key001")
+        .default_headers(org_token_headers("org-1"))
+        .with_interceptor(|request: RequestParts| {
+            assert!(request.body.is_empty());
+            Ok(request.with_header("X-Trace-Id", "trace-1"))
+        });
+    dt.set_default_webhook_url(&server.url);
+    tokio_test::block_on(dt.upload_wechat_media("report.txt", b"daily report".to_vec())).unwrap();
+    let requests = server.requests();
+    assert_eq!("/robot/upload_media?key=key001&type=file", requests[0].path);
+    assert_eq!(Some("org-1"), requests[0].header("X-Org-Token"));
+    assert_eq!(Some("trace-1"), requests[0].header("X-Trace-Id"));
+    assert!(requests[0]
+        .header("content-type")
+        .unwrap()
+        .starts_with("multipart/form-data"));
+}
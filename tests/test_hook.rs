@@ -0,0 +1,127 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::{
+    net::TcpListener,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+/// Owned fields of `SendAttempt`
+#[derive(Debug, PartialEq)]
+struct Attempt {
+    message_type: Option<DingTalkMessageType>,
+    payload_bytes: usize,
+    attempt: u32,
+    status: Option<u16>,
+    errcode: Option<i64>,
+    success: bool,
+}
+
+fn recording(dt: DingTalk) -> (DingTalk, Arc<Mutex<Vec<Attempt>>>) {
+    let attempts = Arc::new(Mutex::new(vec![]));
+    let recorded = attempts.clone();
+    let dt = dt.on_send(move |attempt: &SendAttempt| {
+        recorded.lock().unwrap().push(Attempt {
+            message_type: attempt.message_type,
+            payload_bytes: attempt.payload_bytes,
+            attempt: attempt.attempt,
+            status: attempt.status,
+            errcode: attempt.errcode,
+            success: attempt.is_success(),
+        });
+    });
+    (dt, attempts)
+}
+
+#[test]
+fn on_send_success() {
+    let server = MockServer::reply(MockResponse::ok());
+    let (dt, attempts) = recording(DingTalk::from_url(&server.url));
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(
+        vec![Attempt {
+            message_type: Some(DingTalkMessageType::Text),
+            payload_bytes: r#"{"msgtype":"text","text":{"content":"hello"}}"#.len(),
+            attempt: 1,
+            status: Some(200),
+            errcode: Some(0),
+            success: true,
+        }],
+        *attempts.lock().unwrap()
+    );
+}
+
+#[test]
+fn on_send_each_retry() {
+    let count = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| match count.fetch_add(1, Ordering::SeqCst) {
+        0 => MockResponse::new(503, ""),
+        _ => MockResponse::new(200, r#"{"errcode":300001,"errmsg":"token is not exist"}"#),
+    });
+    let (dt, attempts) =
+        recording(DingTalk::from_url(&server.url).with_retry(3, Duration::from_millis(10)));
+    let message = DingTalkMessage::new_markdown("title", "text");
+    assert!(tokio_test::block_on(dt.send_message_ref(&message)).is_err());
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(2, attempts.len());
+    assert_eq!(
+        (1, Some(503), None, false),
+        (
+            attempts[0].attempt,
+            attempts[0].status,
+            attempts[0].errcode,
+            attempts[0].success
+        )
+    );
+    assert_eq!(
+        (2, Some(200), Some(300001), false),
+        (
+            attempts[1].attempt,
+            attempts[1].status,
+            attempts[1].errcode,
+            attempts[1].success
+        )
+    );
+    assert_eq!(
+        Some(DingTalkMessageType::Markdown),
+        attempts[1].message_type
+    );
+}
+
+#[test]
+fn on_send_no_response() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/robot/send", listener.local_addr().unwrap());
+    drop(listener);
+    let (dt, attempts) = recording(DingTalk::from_url(&url));
+    assert!(tokio_test::block_on(dt.send_text("hello")).is_err());
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(1, attempts.len());
+    assert_eq!(None, attempts[0].status);
+    assert!(!attempts[0].success);
+}
+
+#[test]
+fn on_send_hook_panic_is_caught() {
+    let server = MockServer::reply(MockResponse::ok());
+    let (dt, attempts) =
+        recording(DingTalk::from_url(&server.url).on_send(|_: &SendAttempt| panic!("hook failed")));
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    tokio_test::block_on(dt.send_text("hello again")).unwrap();
+    assert_eq!(2, server.request_count());
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(2, attempts.len());
+    assert!(attempts.iter().all(|attempt| attempt.success));
+}
+
+#[test]
+fn on_send_not_called_on_dry_run() {
+    let (dt, attempts) = recording(DingTalk::new("token001", "").dry_run(true));
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert!(attempts.lock().unwrap().is_empty());
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn interceptor_injects_header() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).with_interceptor(|request: RequestParts| {
+        let sign = format!("len-{}", request.body.len());
+        Ok(request.with_header("X-Internal-Sign", &sign))
+    });
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    let requests = server.requests();
+    assert_eq!(1, requests.len());
+    let sign = format!("len-{}", requests[0].body.len());
+    assert_eq!(Some(sign.as_str()), requests[0].header("X-Internal-Sign"));
+    assert_eq!(
+        Some("application/json; charset=utf-8"),
+        requests[0].header("Content-Type")
+    );
+}
+
+#[test]
+fn interceptors_applied_in_order() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "")
+        .with_transport(transport.clone())
+        .with_interceptor(|request: RequestParts| Ok(request.with_header("X-Step", "1")))
+        .with_interceptor(|request: RequestParts| {
+            let step = format!("{}-2", request.header("x-step").unwrap());
+            Ok(request.with_header("X-Step", &step))
+        });
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    let requests = transport.requests();
+    assert_eq!(
+        vec![("X-Step".to_string(), "1-2".to_string())],
+        requests[0].headers
+    );
+}
+
+#[test]
+fn interceptor_reroutes_url() {
+    let server = MockServer::reply(MockResponse::ok());
+    let gateway_url = format!("{}/gateway", server.base_url());
+    let dt = DingTalk::new("token001", "").with_interceptor(move |mut request: RequestParts| {
+        request.url = format!("{}?target={}", gateway_url, request.url.len());
+        Ok(request)
+    });
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    let requests = server.requests();
+    assert_eq!(1, requests.len());
+    assert!(requests[0].path.starts_with("/gateway?target="));
+    assert!(requests[0].body_str().contains("hello"));
+}
+
+#[test]
+fn interceptor_error_aborts_send() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "")
+        .with_transport(transport.clone())
+        .with_retry(3, std::time::Duration::from_millis(1))
+        .with_interceptor(|_: RequestParts| -> XResult<RequestParts> {
+            Err(DingTalkError::Config("gateway is down".into()))
+        });
+    let e = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(e.to_string().contains("gateway is down"), "{}", e);
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn interceptor_can_not_override_content_type() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).with_interceptor(|request: RequestParts| {
+        Ok(request.with_header("content-type", "text/plain"))
+    });
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    let content_types = server.requests()[0]
+        .headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("Content-Type"))
+        .map(|(_, value)| value.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["application/json; charset=utf-8"], content_types);
+}
+
+#[test]
+fn interceptor_invalid_header() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url)
+        .with_interceptor(|request: RequestParts| Ok(request.with_header("X-Bad", "a\nb")));
+    let e = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(e, DingTalkError::Config(_)), "{:?}", e);
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn logging_interceptor_redacts_url() {
+    let lines = Arc::new(Mutex::new(vec![]));
+    let logged = lines.clone();
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token0123456789", "")
+        .with_transport(transport.clone())
+        .with_interceptor(|request: RequestParts| Ok(request.with_header("X-Trace-Id", "t-1")))
+        .with_interceptor(LoggingInterceptor::with_logger(move |line: &str| {
+            logged.lock().unwrap().push(line.to_string())
+        }));
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    let lines = lines.lock().unwrap();
+    assert_eq!(1, lines.len());
+    assert!(
+        lines[0].starts_with("POST https://oapi.dingtalk.com/robot/send?access_token="),
+        "{}",
+        lines[0]
+    );
+    assert!(!lines[0].contains("token0123456789"), "{}", lines[0]);
+    assert!(lines[0].contains("headers: [X-Trace-Id]"), "{}", lines[0]);
+    assert!(!lines[0].contains("t-1"), "{}", lines[0]);
+    let body_bytes = transport.bodies()[0].len();
+    assert!(lines[0].ends_with(&format!("body: {} bytes", body_bytes)));
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "keyring")]
+
+use dingtalk::*;
+use keyring::credential::{Credential, CredentialApi, CredentialBuilderApi};
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, Mutex, Once},
+};
+
+type Store = Arc<Mutex<HashMap<(String, String), Vec<u8>>>>;
+
+/// In memory credential store shared by entries, real OS keyring is not touched by tests
+#[derive(Debug, Default)]
+struct MemoryBuilder {
+    store: Store,
+}
+
+#[derive(Debug)]
+struct MemoryCredential {
+    store: Store,
+    key: (String, String),
+}
+
+impl CredentialApi for MemoryCredential {
+    fn set_secret(&self, secret: &[u8]) -> keyring::Result<()> {
+        self.store
+            .lock()
+            .unwrap()
+            .insert(self.key.clone(), secret.to_vec());
+        Ok(())
+    }
+
+    fn get_secret(&self) -> keyring::Result<Vec<u8>> {
+        self.store
+            .lock()
+            .unwrap()
+            .get(&self.key)
+            .cloned()
+            .ok_or(keyring::Error::NoEntry)
+    }
+
+    fn delete_credential(&self) -> keyring::Result<()> {
+        self.store.lock().unwrap().remove(&self.key);
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl CredentialBuilderApi for MemoryBuilder {
+    fn build(
+        &self,
+        _target: Option<&str>,
+        service: &str,
+        user: &str,
+    ) -> keyring::Result<Box<Credential>> {
+        Ok(Box::new(MemoryCredential {
+            store: self.store.clone(),
+            key: (service.into(), user.into()),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn memory_keyring() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| keyring::set_default_credential_builder(Box::<MemoryBuilder>::default()));
+}
+
+#[test]
+fn store_and_load_keyring() {
+    memory_keyring();
+    DingTalk::new("token001", "secret001")
+        .store_to_keyring("dingtalk-roundtrip", "ops")
+        .unwrap();
+    let dt = DingTalk::from_keyring("dingtalk-roundtrip", "ops").unwrap();
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+
+    let dt = DingTalk::from_json(
+        r#"{"access_token": "token002", "sec_token": "keyring:dingtalk-roundtrip/ops"}"#,
+    )
+    .unwrap();
+    assert_eq!("token002", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+}
+
+#[test]
+fn keyring_ref_default_account() {
+    memory_keyring();
+    DingTalk::new("token001", "")
+        .store_to_keyring("dingtalk-default", "default")
+        .unwrap();
+    let dt = DingTalk::from_json(r#"{"access_token": "keyring:dingtalk-default"}"#).unwrap();
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("", dt.sec_token);
+}
+
+#[test]
+fn keyring_entry_missing() {
+    memory_keyring();
+    match DingTalk::from_keyring("dingtalk-missing", "ops") {
+        Err(DingTalkError::Config(message)) => assert_eq!(
+            "Keyring entry service=dingtalk-missing account=ops is not found, \
+             store it by DingTalk::store_to_keyring",
+            message
+        ),
+        _ => panic!("missing keyring entry should be config error"),
+    }
+    assert!(DingTalk::from_json(r#"{"sec_token": "keyring:dingtalk-missing/ops"}"#).is_err());
+}
+
+#[test]
+fn keyring_entry_not_json() {
+    memory_keyring();
+    let entry = keyring::Entry::new("dingtalk-not-json", "ops").unwrap();
+    entry.set_password("not json").unwrap();
+    match DingTalk::from_keyring("dingtalk-not-json", "ops") {
+        Err(DingTalkError::Config(message)) => assert!(
+            message.starts_with("Keyring entry service=dingtalk-not-json account=ops is not JSON"),
+            "{}",
+            message
+        ),
+        _ => panic!("invalid keyring entry should be config error"),
+    }
+}
@@ -0,0 +1,176 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+#[test]
+fn length_limits_by_type() {
+    assert_eq!(
+        Some(20000),
+        DingTalkType::DingTalk.length_limits().text_content
+    );
+    let dingtalk_limits = DingTalkType::DingTalk.length_limits();
+    assert_eq!(Some(20000), dingtalk_limits.markdown_title);
+    assert_eq!(Some(20000), dingtalk_limits.link_title);
+    assert_eq!(Some(20000), dingtalk_limits.link_text);
+    assert_eq!(Some(20000), dingtalk_limits.feed_card_title);
+    let wechat_limits = DingTalkType::WeChatWork.length_limits();
+    assert_eq!(Some(2048), wechat_limits.text_content);
+    assert_eq!(Some(4096), wechat_limits.markdown_content);
+    assert_eq!(Some(128), wechat_limits.news_title);
+    assert_eq!(Some(512), wechat_limits.news_description);
+    assert_eq!(
+        DingTalkLengthLimits::default(),
+        DingTalkType::Slack.length_limits()
+    );
+}
+
+#[test]
+fn enforce_length_limits_wechat_text() {
+    let server = MockServer::reply(MockResponse::ok());
+    let mut dt = DingTalk::new_wechat("key001").enforce_length_limits(true);
+    dt.direct_url = server.url.clone();
+    let err = tokio_test::block_on(dt.send_text(&"a".repeat(2049))).unwrap_err();
+    match err {
+        DingTalkError::TooLong { field, len, max } => {
+            assert_eq!("text_content", field);
+            assert_eq!(2049, len);
+            assert_eq!(2048, max);
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+    assert_eq!(0, server.request_count());
+
+    tokio_test::block_on(dt.send_text(&"a".repeat(2048))).unwrap();
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn enforce_length_limits_wechat_news() {
+    let server = MockServer::reply(MockResponse::ok());
+    let mut dt = DingTalk::new_wechat("key001").enforce_length_limits(true);
+    dt.direct_url = server.url.clone();
+    let message = DingTalkMessage::new_news()
+        .add_news_article("first", "ok", "https://example.com/1", "")
+        .add_news_article("second", &"a".repeat(513), "https://example.com/2", "");
+    let err = tokio_test::block_on(dt.send_message(message)).unwrap_err();
+    match err {
+        DingTalkError::TooLong { field, len, max } => {
+            assert_eq!("news_articles[1] description", field);
+            assert_eq!(513, len);
+            assert_eq!(512, max);
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+    let message = DingTalkMessage::new_news().add_news_article(
+        &"a".repeat(129),
+        "ok",
+        "https://example.com/1",
+        "",
+    );
+    let err = tokio_test::block_on(dt.send_message(message));
+    assert!(matches!(
+        err,
+        Err(DingTalkError::TooLong {
+            len: 129,
+            max: 128,
+            ..
+        })
+    ));
+    assert_eq!(0, server.request_count());
+
+    let message = DingTalkMessage::new_news().add_news_article(
+        &"a".repeat(128),
+        &"a".repeat(512),
+        "https://example.com/1",
+        "",
+    );
+    tokio_test::block_on(dt.send_message(message)).unwrap();
+    assert_eq!(1, server.request_count());
+}
+
+fn too_long_field(dt: &DingTalk, message: &DingTalkMessage) -> (String, usize, usize) {
+    match dt.build_payload(message) {
+        Err(DingTalkError::TooLong { field, len, max }) => (field, len, max),
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+fn enforce_length_limits_titles_and_link_text() {
+    let dt = DingTalk::new("token001", "").enforce_length_limits(true);
+    let long = "a".repeat(20001);
+    let message = DingTalkMessage::new_markdown(&long, "content");
+    assert_eq!(
+        ("markdown_title".to_owned(), 20001, 20000),
+        too_long_field(&dt, &message)
+    );
+    let message = DingTalkMessage::new_link(&long, "text", "", "https://example.com");
+    assert_eq!(
+        ("link_title".to_owned(), 20001, 20000),
+        too_long_field(&dt, &message)
+    );
+    let message = DingTalkMessage::new_link("title", &long, "", "https://example.com");
+    assert_eq!(
+        ("link_text".to_owned(), 20001, 20000),
+        too_long_field(&dt, &message)
+    );
+    let message = DingTalkMessage::new_feed_card()
+        .add_feed_card_link_detail("first", "https://example.com/1", "")
+        .add_feed_card_link_detail(&long, "https://example.com/2", "");
+    assert_eq!(
+        ("feed_card_links[1] title".to_owned(), 20001, 20000),
+        too_long_field(&dt, &message)
+    );
+}
+
+#[test]
+fn enforce_length_limits_checks_content_as_sent() {
+    let dt = DingTalk::new("token001", "").enforce_length_limits(true);
+    // content fits, appended `\n\n@13800000000` does not
+    let message =
+        DingTalkMessage::new_markdown("title", &"a".repeat(19990)).at_mobiles(["13800000000"]);
+    assert!(DingTalkType::DingTalk
+        .length_limits()
+        .check(&message)
+        .is_ok());
+    assert_eq!(
+        ("markdown_content".to_owned(), 20004, 20000),
+        too_long_field(&dt, &message)
+    );
+
+    let dt = DingTalk::new_wechat("key001").enforce_length_limits(true);
+    // content fits, folded `# title\n` heading does not
+    let message = DingTalkMessage::new_markdown("title", &"a".repeat(4090));
+    assert_eq!(
+        ("markdown_content".to_owned(), 4098, 4096),
+        too_long_field(&dt, &message)
+    );
+    let message = DingTalkMessage::new_markdown("title", &"a".repeat(4088));
+    assert!(dt.build_payload(&message).is_ok());
+}
+
+#[test]
+fn enforce_length_limits_counts_bytes() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).enforce_length_limits(true);
+    // 6669 chars, 20001 bytes in UTF-8
+    let content = format!("{}abc", "中".repeat(6666));
+    let err = tokio_test::block_on(dt.send_markdown("title", &content));
+    assert!(matches!(
+        err,
+        Err(DingTalkError::TooLong {
+            len: 20001,
+            max: 20000,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn length_limits_off_by_default() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_text(&"a".repeat(20001))).unwrap();
+    assert_eq!(1, server.request_count());
+}
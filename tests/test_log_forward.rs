@@ -0,0 +1,156 @@
+#![cfg(feature = "log-forward")]
+
+use dingtalk::*;
+use log::{Level, LevelFilter, Log, Record};
+use serde_json::Value;
+use std::{sync::Arc, time::Duration};
+
+fn record_of<'a>(level: Level, target: &'a str, args: std::fmt::Arguments<'a>) -> Record<'a> {
+    Record::builder()
+        .level(level)
+        .target(target)
+        .file(Some("src/db.rs"))
+        .line(Some(42))
+        .args(args)
+        .build()
+}
+
+fn markdowns(transport: &RecordingTransport) -> Vec<(String, String)> {
+    transport
+        .bodies()
+        .iter()
+        .map(|body| {
+            let body: Value = serde_json::from_str(body).unwrap();
+            (
+                body["markdown"]["title"].as_str().unwrap().to_owned(),
+                body["markdown"]["text"].as_str().unwrap().to_owned(),
+            )
+        })
+        .collect()
+}
+
+fn logger_builder(transport: &Arc<RecordingTransport>) -> DingTalkLoggerBuilder {
+    DingTalkLogger::builder(DingTalk::new("token001", "").with_transport(transport.clone()))
+        .batch_window(Duration::from_millis(50))
+}
+
+#[test]
+fn log_forward_record_markdown() {
+    let transport = Arc::new(RecordingTransport::new());
+    tokio_test::block_on(async {
+        let logger = logger_builder(&transport).build();
+        logger.log(&record_of(
+            Level::Error,
+            "myapp::db",
+            format_args!("connection lost"),
+        ));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    });
+    assert_eq!(
+        vec![(
+            "[ERROR] log record".to_owned(),
+            "#### ERROR myapp::db\n\n- location: `src/db.rs:42`\n\nconnection lost".to_owned()
+        )],
+        markdowns(&transport)
+    );
+}
+
+#[test]
+fn log_forward_level_and_target_filter() {
+    let transport = Arc::new(RecordingTransport::new());
+    tokio_test::block_on(async {
+        let logger = logger_builder(&transport)
+            .level(LevelFilter::Warn)
+            .target("myapp")
+            .build();
+        logger.log(&record_of(Level::Info, "myapp", format_args!("info")));
+        logger.log(&record_of(Level::Error, "other", format_args!("other")));
+        logger.log(&record_of(Level::Error, "myapp2", format_args!("myapp2")));
+        logger.log(&record_of(
+            Level::Error,
+            "hyper::proto",
+            format_args!("hyper"),
+        ));
+        logger.log(&record_of(
+            Level::Warn,
+            "myapp::db",
+            format_args!("db warn"),
+        ));
+        logger.log(&record_of(Level::Error, "myapp", format_args!("app error")));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    });
+    let markdowns = markdowns(&transport);
+    assert_eq!(1, markdowns.len());
+    let (title, text) = &markdowns[0];
+    assert_eq!("[ERROR] 2 log records", title);
+    assert!(text.contains("db warn"), "{}", text);
+    assert!(text.contains("app error"), "{}", text);
+    for filtered in ["info", "other", "myapp2", "hyper"] {
+        assert!(!text.contains(&format!("\n\n{}", filtered)), "{}", text);
+    }
+}
+
+#[test]
+fn log_forward_ignores_dingtalk_target() {
+    let transport = Arc::new(RecordingTransport::new());
+    tokio_test::block_on(async {
+        let logger = logger_builder(&transport).build();
+        assert!(!logger.enabled(
+            &log::Metadata::builder()
+                .level(Level::Error)
+                .target("dingtalk::log_forward")
+                .build()
+        ));
+        logger.log(&record_of(Level::Error, "dingtalk", format_args!("loop")));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    });
+    assert!(transport.bodies().is_empty());
+}
+
+#[test]
+fn log_forward_rate_limited_and_dropped() {
+    let transport = Arc::new(RecordingTransport::new());
+    tokio_test::block_on(async {
+        let logger = logger_builder(&transport)
+            .max_batch_records(2)
+            .max_messages_per_minute(1)
+            .build();
+        for i in 0..5 {
+            logger.log(&record_of(
+                Level::Error,
+                "myapp",
+                format_args!("error {}", i),
+            ));
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        // 2 records are in batch, 3 are dropped
+        assert_eq!(3, logger.dropped_count());
+        logger.log(&record_of(Level::Error, "myapp", format_args!("error 5")));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    });
+    // second message waits for rate limit of 1 message per minute
+    let markdowns = markdowns(&transport);
+    assert_eq!(1, markdowns.len());
+    let (title, text) = &markdowns[0];
+    assert_eq!("[ERROR] 2 log records", title);
+    assert!(text.contains("error 0"), "{}", text);
+    assert!(text.contains("error 1"), "{}", text);
+    assert!(text.ends_with("*3 records dropped*"), "{}", text);
+}
+
+#[test]
+fn log_forward_init_global_logger() {
+    let transport = Arc::new(RecordingTransport::new());
+    tokio_test::block_on(async {
+        logger_builder(&transport)
+            .level(LevelFilter::Error)
+            .init()
+            .unwrap();
+        log::warn!(target: "myapp", "not forwarded");
+        log::error!(target: "myapp", "disk full");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    });
+    let markdowns = markdowns(&transport);
+    assert_eq!(1, markdowns.len());
+    assert!(markdowns[0].1.ends_with("disk full"), "{}", markdowns[0].1);
+}
@@ -0,0 +1,110 @@
+use dingtalk::*;
+
+#[test]
+fn markdown_builder_blocks() {
+    let markdown = MarkdownBuilder::new()
+        .heading(3, "CPU usage")
+        .text("web-01 is **95%** busy")
+        .image("https://example.com/cpu.png")
+        .image_sized("https://example.com/mem.png", 400)
+        .build()
+        .unwrap();
+    assert_eq!(
+        "### CPU usage\n\n\
+         web-01 is **95%** busy\n\n\
+         ![image](https://example.com/cpu.png)\n\n\
+         <img src=\"https://example.com/mem.png\" width=\"400\" />",
+        markdown
+    );
+}
+
+#[test]
+fn markdown_builder_sized_image_without_html() {
+    for dingtalk_type in [DingTalkType::WeChatWork, DingTalkType::Feishu] {
+        let markdown = MarkdownBuilder::for_type(dingtalk_type)
+            .image_sized("https://example.com/mem.png", 400)
+            .build()
+            .unwrap();
+        assert_eq!("![image](https://example.com/mem.png)", markdown);
+    }
+    let markdown = MarkdownBuilder::for_type(DingTalkType::DingTalk)
+        .image_sized("https://example.com/mem.png", 400)
+        .build()
+        .unwrap();
+    assert_eq!(
+        "<img src=\"https://example.com/mem.png\" width=\"400\" />",
+        markdown
+    );
+}
+
+#[test]
+fn markdown_builder_heading_level_clamped() {
+    let markdown = MarkdownBuilder::new()
+        .heading(0, "a")
+        .heading(9, "b")
+        .build()
+        .unwrap();
+    assert_eq!("# a\n\n###### b", markdown);
+}
+
+#[test]
+fn markdown_builder_escapes_image_url() {
+    let markdown = MarkdownBuilder::new()
+        .image("https://example.com/render (1).png?q=\"x\"")
+        .build()
+        .unwrap();
+    assert_eq!(
+        "![image](https://example.com/render%20%281%29.png?q=%22x%22)",
+        markdown
+    );
+}
+
+#[test]
+fn markdown_builder_invalid_images() {
+    let err = MarkdownBuilder::new()
+        .image("http://example.com/cpu.png")
+        .image("not a url")
+        .image_sized("https://example.com/mem.png", 0)
+        .build()
+        .unwrap_err();
+    match err {
+        DingTalkError::Validation(message) => {
+            assert!(
+                message.contains("image URL must be https: http://example.com/cpu.png"),
+                "{}",
+                message
+            );
+            assert!(
+                message.contains("image URL is malformed: not a url"),
+                "{}",
+                message
+            );
+            assert!(
+                message.contains("image width must be positive"),
+                "{}",
+                message
+            );
+        }
+        err => panic!("{:?}", err),
+    }
+}
+
+#[test]
+fn markdown_with_image_message() {
+    let message = DingTalkMessage::markdown_with_image(
+        "CPU usage",
+        "web-01 is busy",
+        "https://example.com/cpu.png",
+    )
+    .unwrap();
+    assert_eq!(DingTalkMessageType::Markdown, message.message_type);
+    assert_eq!("CPU usage", message.markdown_title);
+    assert_eq!(
+        "web-01 is busy\n\n![image](https://example.com/cpu.png)",
+        message.markdown_content
+    );
+    message.validate().unwrap();
+    assert!(
+        DingTalkMessage::markdown_with_image("t", "text", "http://example.com/cpu.png").is_err()
+    );
+}
@@ -0,0 +1,140 @@
+use dingtalk::*;
+
+fn assert_same(expected: DingTalkMessage, json: &str) {
+    assert_eq!(expected, DingTalkMessage::from_json(json).unwrap());
+}
+
+#[test]
+fn message_from_json_text() {
+    assert_same(
+        DingTalkMessage::new_text("hello")
+            .at_mobiles(&["13800000000".to_string()])
+            .at_all(),
+        r#"{"message_type": "text", "text_content": "hello", "at_mobiles": ["13800000000"], "at_all": true}"#,
+    );
+}
+
+#[test]
+fn message_from_json_defaults() {
+    assert_same(DingTalkMessage::default(), "{}");
+    assert_same(
+        DingTalkMessage::new_markdown("title", "# text"),
+        r##"{"message_type": "markdown", "markdown_title": "title", "markdown_content": "# text"}"##,
+    );
+}
+
+#[test]
+fn message_from_json_action_card() {
+    assert_same(
+        DingTalkMessage::new_action_card("title", "text")
+            .action_card_hide_avatar()
+            .action_card_btn_landscape()
+            .add_action_card_btn(DingTalkMessageActionCardBtn {
+                title: "Open".into(),
+                action_url: "https://example.com/".into(),
+            }),
+        r#"{
+            "message_type": "actionCard",
+            "action_card_title": "title",
+            "action_card_text": "text",
+            "action_card_hide_avatar": "hide",
+            "action_card_btn_orientation": "landscape",
+            "action_card_btns": [{"title": "Open", "action_url": "https://example.com/"}]
+        }"#,
+    );
+}
+
+#[test]
+fn message_from_json_feed_card() {
+    assert_same(
+        DingTalkMessage::new_feed_card().add_feed_card_link_detail(
+            "title",
+            "https://example.com/",
+            "https://example.com/a.png",
+        ),
+        r#"{
+            "message_type": "feedCard",
+            "feed_card_links": [{
+                "title": "title",
+                "message_url": "https://example.com/",
+                "pic_url": "https://example.com/a.png"
+            }]
+        }"#,
+    );
+}
+
+#[test]
+fn message_from_json_unknown_type() {
+    match DingTalkMessage::from_json(r#"{"message_type": "sticker"}"#) {
+        Err(DingTalkError::Serialization(e)) => {
+            assert!(e.to_string().contains("`sticker`"), "{}", e)
+        }
+        _ => panic!("unknown message type should be error"),
+    }
+}
+
+#[test]
+fn public_serde_not_wire_encoding() {
+    assert_eq!(
+        r#""hide""#,
+        serde_json::to_string(&DingTalkMessageActionCardHideAvatar::Hide).unwrap()
+    );
+    assert_eq!(
+        r#""landscape""#,
+        serde_json::to_string(&DingTalkMessageActionCardBtnOrientation::Landscape).unwrap()
+    );
+    assert_eq!(
+        r#"{"title":"Open","action_url":"https://example.com/"}"#,
+        serde_json::to_string(&DingTalkMessageActionCardBtn {
+            title: "Open".into(),
+            action_url: "https://example.com/".into(),
+        })
+        .unwrap()
+    );
+    assert_eq!(
+        r#"{"title":"title","message_url":"https://example.com/","pic_url":""}"#,
+        serde_json::to_string(&DingTalkMessageFeedCardLink {
+            title: "title".into(),
+            message_url: "https://example.com/".into(),
+            pic_url: "".into(),
+        })
+        .unwrap()
+    );
+
+    // wire value is still accepted
+    let hide_avatar: DingTalkMessageActionCardHideAvatar = serde_json::from_str(r#""1""#).unwrap();
+    assert!(matches!(
+        hide_avatar,
+        DingTalkMessageActionCardHideAvatar::Hide
+    ));
+}
+
+#[test]
+fn message_serde_round_trip() {
+    let message = DingTalkMessage::new_action_card("title", "text")
+        .action_card_hide_avatar()
+        .action_card_btn_landscape()
+        .add_action_card_btn(DingTalkMessageActionCardBtn {
+            title: "Open".into(),
+            action_url: "https://example.com/".into(),
+        });
+    let json = serde_json::to_string(&message).unwrap();
+    assert!(
+        json.contains(r#""action_card_hide_avatar":"hide""#),
+        "{}",
+        json
+    );
+    assert!(
+        json.contains(r#""action_card_btn_orientation":"landscape""#),
+        "{}",
+        json
+    );
+    assert_same(message, &json);
+
+    // payload keeps wire encoding
+    let payload = DingTalk::new("token", "")
+        .build_payload(&DingTalkMessage::from_json(&json).unwrap())
+        .unwrap();
+    assert_eq!("1", payload["actionCard"]["hideAvatar"]);
+    assert_eq!("1", payload["actionCard"]["btnOrientation"]);
+}
@@ -0,0 +1,146 @@
+use dingtalk::*;
+
+fn btn(title: &str) -> DingTalkMessageActionCardBtn {
+    DingTalkMessageActionCardBtn {
+        title: title.into(),
+        action_url: format!("https://example.com/{}", title),
+    }
+}
+
+#[test]
+fn setters_same_as_builder_action_card() {
+    let built = DingTalkMessage::new_action_card("title", "text")
+        .action_card_hide_avatar()
+        .action_card_btn_landscape()
+        .add_action_card_btn(btn("a"))
+        .add_action_card_btn(btn("b"))
+        .at_mobiles(&["13800000000".to_string()])
+        .at_user_id("user001")
+        .at_all();
+
+    let mut message = DingTalkMessage::new_action_card("title", "text");
+    message
+        .set_action_card_hide_avatar(true)
+        .set_action_card_btn_landscape(true);
+    for title in ["a", "b"] {
+        message.push_action_card_btn(btn(title));
+    }
+    message
+        .push_at_mobile("13800000000")
+        .push_at_user_id("user001")
+        .set_at_all(true);
+
+    assert_eq!(built, message);
+}
+
+#[test]
+fn setters_same_as_builder_content() {
+    let mut message = DingTalkMessage::new(DingTalkMessageType::Text);
+    message.set_text("hello");
+    assert_eq!(DingTalkMessage::new_text("hello"), message);
+
+    let mut message = DingTalkMessage::new(DingTalkMessageType::Markdown);
+    message.set_markdown("title", "text");
+    assert_eq!(DingTalkMessage::new_markdown("title", "text"), message);
+
+    let mut message = DingTalkMessage::new(DingTalkMessageType::Link);
+    message.set_link("title", "text", "pic", "url");
+    assert_eq!(
+        DingTalkMessage::new_link("title", "text", "pic", "url"),
+        message
+    );
+}
+
+#[test]
+fn setters_same_as_builder_lists() {
+    let built = DingTalkMessage::new_feed_card().add_feed_card_link_detail("a", "url", "pic");
+    let mut message = DingTalkMessage::new_feed_card();
+    message.push_feed_card_link(DingTalkMessageFeedCardLink {
+        title: "a".into(),
+        message_url: "url".into(),
+        pic_url: "pic".into(),
+    });
+    assert_eq!(built, message);
+
+    let built = DingTalkMessage::new_news().add_news_article("a", "desc", "url", "pic");
+    let mut message = DingTalkMessage::new_news();
+    message.push_news_article("a", "desc", "url", "pic");
+    assert_eq!(built, message);
+}
+
+#[test]
+fn setters_can_unset() {
+    let mut message = DingTalkMessage::new_text("hello").at_all();
+    message.set_at_all(false);
+    message.set_action_card_hide_avatar(false);
+    assert_eq!(DingTalkMessage::new_text("hello"), message);
+}
+
+#[test]
+fn builder_action_card_fields_eq() {
+    let message = DingTalkMessage::new_action_card("title", "text")
+        .action_card_hide_avatar()
+        .action_card_btn_landscape()
+        .add_action_card_btn(btn("a"));
+    assert_eq!(DingTalkMessageType::ActionCard, message.message_type);
+    assert_eq!(
+        DingTalkMessageActionCardHideAvatar::Hide,
+        message.action_card_hide_avatar
+    );
+    assert_eq!(
+        DingTalkMessageActionCardBtnOrientation::Landscape,
+        message.action_card_btn_orientation
+    );
+    assert_eq!(vec![btn("a")], message.action_card_btns);
+    assert_eq!(None, message.action_card_single_btn);
+
+    let message = DingTalkMessage::new_action_card("title", "text")
+        .action_card_show_avatar()
+        .action_card_btn_vertical()
+        .set_action_card_single_btn(btn("open"));
+    assert_eq!(
+        DingTalkMessageActionCardHideAvatar::Show,
+        message.action_card_hide_avatar
+    );
+    assert_eq!(
+        DingTalkMessageActionCardBtnOrientation::Vertical,
+        message.action_card_btn_orientation
+    );
+    assert_eq!(Some(btn("open")), message.action_card_single_btn);
+    assert_ne!(btn("a"), btn("b"));
+}
+
+#[test]
+fn builder_feed_card_and_message_eq() {
+    let message = DingTalkMessage::new_feed_card().add_feed_card_link_detail("a", "url", "pic");
+    assert_eq!(
+        vec![DingTalkMessageFeedCardLink {
+            title: "a".into(),
+            message_url: "url".into(),
+            pic_url: "pic".into(),
+        }],
+        message.feed_card_links
+    );
+    assert_eq!(message.clone(), message);
+    assert_ne!(
+        DingTalkMessage::new_text("a"),
+        DingTalkMessage::new_text("b")
+    );
+    assert_ne!(
+        DingTalkMessage::new_text("a"),
+        DingTalkMessage::new_text("a").at_all()
+    );
+}
+
+#[test]
+fn dingtalk_type_eq() {
+    assert_eq!(
+        DingTalkType::DingTalk,
+        DingTalk::new("token", "").dingtalk_type
+    );
+    assert_eq!(
+        DingTalkType::WeChatWork,
+        DingTalk::new_wechat("key").dingtalk_type
+    );
+    assert_ne!(DingTalkType::Slack, DingTalkType::Discord);
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "minimal-http")]
+
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::{net::TcpListener, time::Duration};
+
+#[test]
+fn ureq_transport_post() {
+    let server = MockServer::reply(MockResponse::ok().header("X-Test", "yes"));
+    let response = UreqTransport::new()
+        .timeout(Some(Duration::from_secs(5)))
+        .post_blocking(&server.url, r#"{"msgtype":"text"}"#)
+        .unwrap();
+    assert_eq!(200, response.status);
+    assert_eq!(Some("yes"), response.header("x-test"));
+    assert_eq!(r#"{"errcode":0,"errmsg":"ok"}"#, response.body);
+
+    let requests = server.requests();
+    assert_eq!("POST", requests[0].method);
+    assert_eq!("/robot/send", requests[0].path);
+    assert_eq!(
+        Some("application/json; charset=utf-8"),
+        requests[0].header("Content-Type")
+    );
+    assert_eq!(r#"{"msgtype":"text"}"#, requests[0].body_str());
+}
+
+#[test]
+fn ureq_transport_reuses_connection() {
+    let server = MockServer::reply(MockResponse::ok());
+    let transport = UreqTransport::new();
+    transport.post_blocking(&server.url, "{}").unwrap();
+    transport.clone().post_blocking(&server.url, "{}").unwrap();
+    assert_eq!(2, server.request_count());
+    assert_eq!(1, server.connection_count());
+}
+
+#[test]
+fn ureq_transport_error_status_body() {
+    let server = MockServer::reply(MockResponse::new(502, "bad gateway"));
+    let response = UreqTransport::new()
+        .post_blocking(&server.url, "{}")
+        .unwrap();
+    assert_eq!(502, response.status);
+    assert_eq!("bad gateway", response.body);
+}
+
+#[test]
+fn ureq_transport_with_transport() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).with_transport(UreqTransport::new());
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(
+        r#"{"msgtype":"text","text":{"content":"hello"}}"#,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn ureq_transport_rate_limited() {
+    let server = MockServer::reply(MockResponse::new(429, "").header("Retry-After", "3"));
+    let dt = DingTalk::from_url(&server.url).with_transport(UreqTransport::new());
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::RateLimited {
+            retry_after: Some(d)
+        } if d == Duration::from_secs(3)
+    ));
+}
+
+#[test]
+fn ureq_transport_connection_refused() {
+    let url = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}/robot/send", listener.local_addr().unwrap())
+    };
+    let err = UreqTransport::new().post_blocking(&url, "{}").unwrap_err();
+    assert!(matches!(err, DingTalkError::Transport(_)));
+    assert!(err.is_retryable());
+}
+
+#[test]
+fn ureq_transport_invalid_proxy() {
+    let err = UreqTransport::new()
+        .proxy(Some("ftp://127.0.0.1:1"))
+        .post_blocking("http://127.0.0.1:1/robot/send", "{}")
+        .unwrap_err();
+    assert!(matches!(err, DingTalkError::Config(_)));
+}
+
+#[cfg(not(feature = "blocking"))]
+#[test]
+fn unsupported_proxy_is_config_error() {
+    let mut dt = DingTalk::new("token001", "");
+    assert!(matches!(
+        dt.set_proxy("https://proxy.corp:3128"),
+        Err(DingTalkError::Config(_))
+    ));
+    assert!(dt.set_proxy("http://proxy.corp:3128").is_ok());
+    assert!(matches!(
+        DingTalk::from_json(r#"{"access_token": "token001", "proxy": "https://proxy.corp:3128"}"#),
+        Err(DingTalkError::Config(_))
+    ));
+}
@@ -0,0 +1,277 @@
+use dingtalk::outgoing::{
+    verify_signature, verify_signature_at, OutgoingAtUser, OutgoingContent, OutgoingMessage,
+    OutgoingRichTextItem,
+};
+use dingtalk::*;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const APP_SECRET: &str = "this is secret";
+const TIMESTAMP: &str = "1577808000000";
+const SIGN: &str = "ijHEivi6YiCNPZOq4hZstmvZ3sPfbioAiMhP30ae7W0=";
+
+fn at_millis(millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis)
+}
+
+#[test]
+fn outgoing_verify_known_vectors() {
+    verify_signature_at(APP_SECRET, TIMESTAMP, SIGN, at_millis(1577808000000)).unwrap();
+    verify_signature_at(
+        "SEC0123456789abcdef",
+        "1600000000000",
+        "PPcRl2tf+BNekAadmEb8Cx1rGmXjHN8H+/0mvy5rUJk=",
+        at_millis(1600000000000),
+    )
+    .unwrap();
+}
+
+#[test]
+fn outgoing_verify_within_window() {
+    for now in [1577808000000 - 3600 * 1000, 1577808000000 + 3600 * 1000] {
+        verify_signature_at(APP_SECRET, TIMESTAMP, SIGN, at_millis(now)).unwrap();
+    }
+}
+
+#[test]
+fn outgoing_verify_expired() {
+    for now in [
+        1577808000000 - 3600 * 1000 - 1,
+        1577808000000 + 3600 * 1000 + 1,
+    ] {
+        let err = verify_signature_at(APP_SECRET, TIMESTAMP, SIGN, at_millis(now)).unwrap_err();
+        assert!(matches!(
+            err,
+            DingTalkError::SignatureExpired {
+                timestamp: 1577808000000
+            }
+        ));
+    }
+}
+
+#[test]
+fn outgoing_verify_mismatch() {
+    let now = at_millis(1577808000000);
+    for (secret, sign) in [
+        ("other secret", SIGN),
+        (APP_SECRET, "jjHEivi6YiCNPZOq4hZstmvZ3sPfbioAiMhP30ae7W0="),
+        (APP_SECRET, "ijHEivi6YiCNPZOq4hZstmvZ3sPfbioAiMhP30ae7W0%3D"),
+        (APP_SECRET, ""),
+    ] {
+        let err = verify_signature_at(secret, TIMESTAMP, sign, now).unwrap_err();
+        assert!(matches!(err, DingTalkError::SignatureMismatch), "{}", sign);
+    }
+}
+
+#[test]
+fn outgoing_verify_invalid_timestamp() {
+    let err = verify_signature(APP_SECRET, "yesterday", SIGN).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+}
+
+#[test]
+fn outgoing_verify_now() {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let sign = sign::compute_sign(APP_SECRET, timestamp).unwrap();
+    verify_signature(APP_SECRET, &timestamp.to_string(), &sign).unwrap();
+    let err = verify_signature(APP_SECRET, TIMESTAMP, SIGN).unwrap_err();
+    assert!(matches!(err, DingTalkError::SignatureExpired { .. }));
+}
+
+const TEXT_MESSAGE: &str = r#"{
+    "conversationId": "cid001",
+    "atUsers": [{"dingtalkId": "$:LWCP_v1:$robot001"}, {"dingtalkId": "$:LWCP_v1:$user002", "staffId": "user002"}],
+    "chatbotUserId": "$:LWCP_v1:$robot001",
+    "msgId": "msg001",
+    "senderNick": "Alice",
+    "isAdmin": true,
+    "senderStaffId": "user001",
+    "sessionWebhookExpiredTime": 1613635652738,
+    "createAt": 1613630252678,
+    "conversationType": "2",
+    "senderId": "$:LWCP_v1:$user001",
+    "conversationTitle": "ops",
+    "isInAtList": true,
+    "sessionWebhook": "https://oapi.dingtalk.com/robot/sendBySession?session=session001",
+    "text": {"content": " @robot  deploy api "},
+    "robotCode": "normal",
+    "msgtype": "text"
+}"#;
+
+#[test]
+fn outgoing_parse_text() {
+    let message = OutgoingMessage::from_json(TEXT_MESSAGE).unwrap();
+    assert_eq!("msg001", message.msg_id);
+    assert_eq!("cid001", message.conversation_id);
+    assert_eq!("ops", message.conversation_title);
+    assert!(message.is_group());
+    assert_eq!("Alice", message.sender_nick);
+    assert_eq!("user001", message.sender_staff_id);
+    assert_eq!("$:LWCP_v1:$user001", message.sender_id);
+    assert!(message.is_in_at_list);
+    assert_eq!(
+        vec![
+            OutgoingAtUser {
+                dingtalk_id: "$:LWCP_v1:$robot001".into(),
+                staff_id: "".into(),
+            },
+            OutgoingAtUser {
+                dingtalk_id: "$:LWCP_v1:$user002".into(),
+                staff_id: "user002".into(),
+            },
+        ],
+        message.at_users
+    );
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/sendBySession?session=session001",
+        message.session_webhook
+    );
+    assert_eq!(1613635652738, message.session_webhook_expired_time);
+    assert_eq!(1613630252678, message.create_at);
+    assert_eq!(
+        OutgoingContent::Text(" @robot  deploy api ".into()),
+        message.content
+    );
+    assert_eq!(Some(" @robot  deploy api ".into()), message.raw_text());
+    assert_eq!(Some("deploy api".into()), message.text());
+}
+
+#[test]
+fn outgoing_text_strip_mention() {
+    let text_of = |content: &str| {
+        let json = serde_json::json!({"msgtype": "text", "text": {"content": content}});
+        OutgoingMessage::from_json(&json.to_string())
+            .unwrap()
+            .text()
+            .unwrap()
+    };
+    assert_eq!("deploy api", text_of(" deploy api"));
+    assert_eq!("deploy api", text_of("@robot\u{2005}deploy api"));
+    assert_eq!("deploy a@b", text_of("@robot deploy a@b"));
+    assert_eq!("", text_of("@robot"));
+    assert_eq!("", text_of(""));
+}
+
+#[test]
+fn outgoing_parse_rich_text() {
+    let message = OutgoingMessage::from_json(
+        r#"{
+            "conversationType": "1",
+            "senderNick": "Alice",
+            "msgtype": "richText",
+            "content": {"richText": [
+                {"text": "@robot look "},
+                {"type": "picture", "downloadCode": "code001", "pictureDownloadCode": "pic001"},
+                {"text": "at this"},
+                {"type": "video"}
+            ]}
+        }"#,
+    )
+    .unwrap();
+    assert!(!message.is_group());
+    assert_eq!(
+        OutgoingContent::RichText(vec![
+            OutgoingRichTextItem::Text("@robot look ".into()),
+            OutgoingRichTextItem::Picture {
+                download_code: "code001".into()
+            },
+            OutgoingRichTextItem::Text("at this".into()),
+            OutgoingRichTextItem::Other(serde_json::json!({"type": "video"})),
+        ]),
+        message.content
+    );
+    assert_eq!(Some("look at this".into()), message.text());
+}
+
+#[test]
+fn outgoing_parse_other_type() {
+    let message = OutgoingMessage::from_json(
+        r#"{"senderNick": "Alice", "msgtype": "picture", "content": {"downloadCode": "code001"}}"#,
+    )
+    .unwrap();
+    assert_eq!("Alice", message.sender_nick);
+    assert_eq!(
+        OutgoingContent::Other(serde_json::json!({
+            "msgtype": "picture",
+            "content": {"downloadCode": "code001"},
+        })),
+        message.content
+    );
+    assert_eq!(None, message.raw_text());
+    assert_eq!(None, message.text());
+}
+
+#[test]
+fn outgoing_parse_invalid() {
+    assert!(matches!(
+        OutgoingMessage::from_json("not json"),
+        Err(DingTalkError::Serialization(_))
+    ));
+    assert!(matches!(
+        OutgoingMessage::from_json(r#"{"msgtype": "text", "senderNick": 1}"#),
+        Err(DingTalkError::Serialization(_))
+    ));
+}
+
+const SESSION_WEBHOOK: &str = "https://oapi.dingtalk.com/robot/sendBySession?session=session001";
+
+#[test]
+fn outgoing_reply_target() {
+    let message = OutgoingMessage::from_json(TEXT_MESSAGE).unwrap();
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = message
+        .reply_target()
+        .unwrap()
+        .with_clock(|| at_millis(1613635652737))
+        .with_transport(transport.clone());
+    tokio_test::block_on(dt.send_markdown("Deploy", "Deploy **started**")).unwrap();
+    assert_eq!(vec![SESSION_WEBHOOK.to_string()], transport.urls());
+    assert_eq!(
+        vec![
+            r#"{"markdown":{"text":"Deploy **started**","title":"Deploy"},"msgtype":"markdown"}"#
+                .to_string()
+        ],
+        transport.bodies()
+    );
+}
+
+#[test]
+fn outgoing_reply_target_without_session_webhook() {
+    let message = OutgoingMessage::from_json(r#"{"msgtype": "text"}"#).unwrap();
+    assert!(matches!(
+        message.reply_target(),
+        Err(DingTalkError::Validation(_))
+    ));
+}
+
+#[test]
+fn session_webhook_not_signed() {
+    let mut dt = DingTalk::from_session_webhook(SESSION_WEBHOOK, 1613635652738)
+        .with_clock(|| at_millis(1613630252678));
+    dt.sec_token = "secret001".into();
+    assert_eq!(SESSION_WEBHOOK, dt.generate_signed_url().unwrap());
+}
+
+#[test]
+fn session_webhook_expired() {
+    for now in [1613635652738, 1613635652739] {
+        let transport = Arc::new(RecordingTransport::new());
+        let dt = DingTalk::from_session_webhook(SESSION_WEBHOOK, 1613635652738)
+            .with_clock(move || at_millis(now))
+            .with_transport(transport.clone());
+        let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+        assert!(matches!(
+            err,
+            DingTalkError::SessionExpired {
+                expired_at_ms: 1613635652738
+            }
+        ));
+        assert_eq!("Session webhook expired at 1613635652738", err.to_string());
+        assert!(transport.requests().is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "axum")]
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use dingtalk::outgoing::OutgoingMessage;
+use dingtalk::outgoing_axum::{outgoing_handler, ReplyHandle};
+use dingtalk::sign::compute_sign;
+use std::{
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tower::ServiceExt;
+
+const APP_SECRET: &str = "this is secret";
+const SESSION_WEBHOOK: &str = "https://oapi.dingtalk.com/robot/sendBySession?session=session001";
+
+fn body() -> String {
+    serde_json::json!({
+        "msgtype": "text",
+        "text": {"content": "@robot deploy api"},
+        "senderNick": "Alice",
+        "sessionWebhook": SESSION_WEBHOOK,
+        "sessionWebhookExpiredTime": 4102444800000_i64,
+    })
+    .to_string()
+}
+
+/// Router records `(text, session webhook)` of handled messages
+fn app(handled: Arc<Mutex<Vec<(String, String)>>>) -> Router {
+    Router::new().route(
+        "/dingtalk",
+        outgoing_handler(
+            APP_SECRET,
+            move |message: OutgoingMessage, reply: ReplyHandle| {
+                let handled = handled.clone();
+                async move {
+                    handled.lock().unwrap().push((
+                        message.text().unwrap_or_default(),
+                        reply.dingtalk().direct_url.clone(),
+                    ));
+                    "handled"
+                }
+            },
+        ),
+    )
+}
+
+fn request(headers: &[(&str, &str)], body: String) -> Request<Body> {
+    let mut builder = Request::post("/dingtalk");
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    builder.body(Body::from(body)).unwrap()
+}
+
+fn signed_request(body: String) -> Request<Body> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let sign = compute_sign(APP_SECRET, timestamp).unwrap();
+    request(
+        &[("timestamp", &timestamp.to_string()), ("sign", &sign)],
+        body,
+    )
+}
+
+fn call(request: Request<Body>) -> (StatusCode, String, Vec<(String, String)>) {
+    let handled = Arc::new(Mutex::new(vec![]));
+    tokio_test::block_on(async {
+        let response = app(handled.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let handled = handled.lock().unwrap().clone();
+        (status, String::from_utf8(body.to_vec()).unwrap(), handled)
+    })
+}
+
+#[test]
+fn axum_handle_signed_message() {
+    let (status, body, handled) = call(signed_request(body()));
+    assert_eq!(StatusCode::OK, status);
+    assert_eq!("handled", body);
+    assert_eq!(
+        vec![("deploy api".to_string(), SESSION_WEBHOOK.to_string())],
+        handled
+    );
+}
+
+#[test]
+fn axum_reject_invalid_signature() {
+    for headers in [
+        vec![],
+        vec![("timestamp", "1577808000000")],
+        vec![
+            ("timestamp", "1577808000000"),
+            ("sign", "ijHEivi6YiCNPZOq4hZstmvZ3sPfbioAiMhP30ae7W0="),
+        ],
+        vec![("timestamp", "yesterday"), ("sign", "sign001")],
+    ] {
+        let (status, _, handled) = call(request(&headers, body()));
+        assert_eq!(StatusCode::UNAUTHORIZED, status);
+        assert!(handled.is_empty());
+    }
+
+    let mut request = signed_request(body());
+    let headers = request.headers_mut();
+    headers.insert(
+        "sign",
+        "ijHEivi6YiCNPZOq4hZstmvZ3sPfbioAiMhP30ae7W0="
+            .parse()
+            .unwrap(),
+    );
+    let (status, _, handled) = call(request);
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+    assert!(handled.is_empty());
+}
+
+#[test]
+fn axum_reject_malformed_body() {
+    for body in [
+        "not json".to_string(),
+        r#"{"msgtype": "text", "senderNick": 1}"#.to_string(),
+        r#"{"msgtype": "text", "text": {"content": "hello"}}"#.to_string(),
+    ] {
+        let (status, _, handled) = call(signed_request(body));
+        assert_eq!(StatusCode::BAD_REQUEST, status);
+        assert!(handled.is_empty());
+    }
+}
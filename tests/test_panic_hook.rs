@@ -0,0 +1,68 @@
+use dingtalk::*;
+use serde_json::Value;
+use std::{
+    panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+static PREVIOUS_HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+struct PanicTransport;
+
+impl Transport for PanicTransport {
+    fn post<'a>(&'a self, _url: &'a str, _body: &'a str) -> TransportFuture<'a> {
+        panic!("transport panic")
+    }
+}
+
+fn panic_in_worker() {
+    let result = thread::Builder::new()
+        .name("worker".into())
+        .spawn(|| panic!("boom {}", 42))
+        .unwrap()
+        .join();
+    assert!(result.is_err());
+}
+
+// panic hook is global, all cases are in one test so they are not run in parallel
+#[test]
+fn panic_hook_reports_panic() {
+    std::env::set_var("RUST_BACKTRACE", "1");
+    panic::set_hook(Box::new(|_| {
+        PREVIOUS_HOOK_CALLS.fetch_add(1, Ordering::SeqCst);
+    }));
+    let transport = Arc::new(RecordingTransport::new());
+    dingtalk::panic_hook::install(
+        DingTalk::new("token001", "").with_transport(transport.clone()),
+        "billing",
+    );
+    panic_in_worker();
+    assert_eq!(1, PREVIOUS_HOOK_CALLS.load(Ordering::SeqCst));
+    let bodies = transport.bodies();
+    assert_eq!(1, bodies.len());
+    let body: Value = serde_json::from_str(&bodies[0]).unwrap();
+    assert_eq!("billing panicked", body["markdown"]["title"]);
+    let text = body["markdown"]["text"].as_str().unwrap();
+    assert!(text.starts_with(
+        "#### billing panicked\n\n- thread: `worker`\n- location: `tests/test_panic_hook.rs:"
+    ));
+    assert!(text.contains("`\n\nboom 42\n\n```\n"));
+    assert!(text.ends_with("\n```"));
+
+    // panic while reporting is swallowed, only the previous hook sees the panic of worker
+    let _ = panic::take_hook();
+    panic::set_hook(Box::new(|_| {
+        PREVIOUS_HOOK_CALLS.fetch_add(1, Ordering::SeqCst);
+    }));
+    dingtalk::panic_hook::install(
+        DingTalk::new("token001", "").with_transport(PanicTransport),
+        "billing",
+    );
+    panic_in_worker();
+    assert_eq!(2, PREVIOUS_HOOK_CALLS.load(Ordering::SeqCst));
+    let _ = panic::take_hook();
+}
@@ -0,0 +1,307 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+fn sent_payload(dt: &mut DingTalk, message: DingTalkMessage) -> String {
+    let server = MockServer::reply(MockResponse::ok());
+    dt.set_default_webhook_url(&server.url);
+    tokio_test::block_on(dt.send_message(message)).unwrap();
+    server.requests()[0].body_str()
+}
+
+#[test]
+fn payload_at_mobiles() {
+    let mut dt = DingTalk::new("token", "");
+    assert_eq!(
+        r#"{"at":{"atMobiles":["13800000000"],"isAtAll":false},"msgtype":"text","text":{"content":"hello"}}"#,
+        sent_payload(
+            &mut dt,
+            DingTalkMessage::new_text("hello").at_mobiles(&["13800000000".to_string()])
+        )
+    );
+}
+
+#[test]
+fn payload_at_user_ids() {
+    let mut dt = DingTalk::new("token", "");
+    assert_eq!(
+        r#"{"at":{"atMobiles":[],"atUserIds":["user001","user002"],"isAtAll":false},"msgtype":"text","text":{"content":"hello"}}"#,
+        sent_payload(
+            &mut dt,
+            DingTalkMessage::new_text("hello")
+                .at_user_id("user001")
+                .at_user_ids(&["user002".to_string()])
+        )
+    );
+}
+
+#[test]
+fn payload_at_mobiles_string_like_inputs() {
+    let expected = r#"{"at":{"atMobiles":["13800000000","13900000000"],"isAtAll":false},"msgtype":"text","text":{"content":"hello"}}"#;
+    let mobile_strs: &[&str] = &["13800000000", "13900000000"];
+    let mobiles = vec!["13800000000".to_string(), "13900000000".to_string()];
+    for message in [
+        DingTalkMessage::new_text("hello").at_mobiles(mobile_strs),
+        DingTalkMessage::new_text("hello").at_mobiles(&mobiles),
+        DingTalkMessage::new_text("hello").at_mobiles(mobiles.clone()),
+        DingTalkMessage::new_text("hello").at_mobiles((8..=9).map(|i| format!("13{}00000000", i))),
+    ] {
+        let mut dt = DingTalk::new("token", "");
+        assert_eq!(expected, sent_payload(&mut dt, message));
+    }
+}
+
+#[test]
+fn payload_at_user_ids_string_like_inputs() {
+    let expected = r#"{"at":{"atMobiles":[],"atUserIds":["user001","user002"],"isAtAll":false},"msgtype":"text","text":{"content":"hello"}}"#;
+    let user_id_strs: &[&str] = &["user001", "user002"];
+    let user_ids = vec!["user001".to_string(), "user002".to_string()];
+    for message in [
+        DingTalkMessage::new_text("hello").at_user_ids(user_id_strs),
+        DingTalkMessage::new_text("hello").at_user_ids(&user_ids),
+        DingTalkMessage::new_text("hello").at_user_ids(user_ids.clone()),
+        DingTalkMessage::new_text("hello").at_user_ids((1..=2).map(|i| format!("user00{}", i))),
+    ] {
+        let mut dt = DingTalk::new("token", "");
+        assert_eq!(expected, sent_payload(&mut dt, message));
+    }
+}
+
+#[test]
+fn payload_at_all_mobiles_and_user_ids() {
+    let mut dt = DingTalk::new("token", "");
+    assert_eq!(
+        r#"{"at":{"atMobiles":["13800000000"],"atUserIds":["user001"],"isAtAll":true},"msgtype":"text","text":{"content":"hello"}}"#,
+        sent_payload(
+            &mut dt,
+            DingTalkMessage::new_text("hello")
+                .at_all()
+                .at_mobiles(&["13800000000".to_string()])
+                .at_user_id("user001")
+        )
+    );
+}
+
+#[test]
+fn payload_without_at() {
+    let mut dt = DingTalk::new("token", "");
+    assert_eq!(
+        r#"{"msgtype":"text","text":{"content":"hello"}}"#,
+        sent_payload(&mut dt, DingTalkMessage::new_text("hello"))
+    );
+}
+
+#[test]
+fn payload_wechat_work_mentions() {
+    let mut dt = DingTalk::new_wechat("key");
+    assert_eq!(
+        r#"{"msgtype":"text","text":{"content":"hello","mentioned_list":["user001","@all"],"mentioned_mobile_list":["13800000000"]}}"#,
+        sent_payload(
+            &mut dt,
+            DingTalkMessage::new_text("hello")
+                .at_all()
+                .at_mobiles(&["13800000000".to_string()])
+                .at_user_id("user001")
+        )
+    );
+}
+
+#[test]
+fn payload_wechat_work_mobiles_only() {
+    let mut dt = DingTalk::new_wechat("key");
+    assert_eq!(
+        r#"{"msgtype":"text","text":{"content":"hello","mentioned_mobile_list":["13800000000"]}}"#,
+        sent_payload(
+            &mut dt,
+            DingTalkMessage::new_text("hello").at_mobiles(&["13800000000".to_string()])
+        )
+    );
+}
+
+#[test]
+fn payload_wechat_work_markdown_no_mentions() {
+    let mut dt = DingTalk::new_wechat("key");
+    let payload = sent_payload(
+        &mut dt,
+        DingTalkMessage::new_markdown("title", "text").at_all(),
+    );
+    assert!(!payload.contains("mentioned"));
+    assert!(!payload.contains("\"at\""));
+}
+
+#[test]
+fn payload_dingtalk_markdown() {
+    let mut dt = DingTalk::new("token", "");
+    assert_eq!(
+        r#"{"markdown":{"text":"* line 0","title":"title"},"msgtype":"markdown"}"#,
+        sent_payload(&mut dt, DingTalkMessage::new_markdown("title", "* line 0"))
+    );
+}
+
+#[test]
+fn payload_dingtalk_markdown_appends_at_mobiles() {
+    let mut dt = DingTalk::new("token", "");
+    assert_eq!(
+        r#"{"at":{"atMobiles":["13800000000","13900000000"],"isAtAll":false},"markdown":{"text":"hi @13800000000\n\n@13900000000","title":"title"},"msgtype":"markdown"}"#,
+        sent_payload(
+            &mut dt,
+            DingTalkMessage::new_markdown("title", "hi @13800000000")
+                .at_mobiles(&["13800000000".to_string(), "13900000000".to_string()])
+        )
+    );
+}
+
+#[test]
+fn payload_dingtalk_markdown_at_mobile_prefix_of_other_mention() {
+    let mut dt = DingTalk::new("token", "");
+    assert_eq!(
+        r#"{"at":{"atMobiles":["1380000","13800001111"],"isAtAll":false},"markdown":{"text":"hi @13800001111 @13800001111x\n\n@1380000","title":"title"},"msgtype":"markdown"}"#,
+        sent_payload(
+            &mut dt,
+            DingTalkMessage::new_markdown("title", "hi @13800001111 @13800001111x")
+                .at_mobiles(&["1380000".to_string(), "13800001111".to_string()])
+        )
+    );
+}
+
+#[test]
+fn payload_dingtalk_text_at_mobiles_unchanged() {
+    let mut dt = DingTalk::new("token", "");
+    assert_eq!(
+        r#"{"at":{"atMobiles":["13900000000"],"isAtAll":false},"msgtype":"text","text":{"content":"hi"}}"#,
+        sent_payload(
+            &mut dt,
+            DingTalkMessage::new_text("hi").at_mobiles(&["13900000000".to_string()])
+        )
+    );
+}
+
+#[test]
+fn payload_wechat_work_markdown() {
+    let mut dt = DingTalk::new_wechat("key");
+    assert_eq!(
+        r##"{"markdown":{"content":"# title\n* line 0"},"msgtype":"markdown"}"##,
+        sent_payload(&mut dt, DingTalkMessage::new_markdown("title", "* line 0"))
+    );
+    assert_eq!(
+        r#"{"markdown":{"content":"* line 0"},"msgtype":"markdown"}"#,
+        sent_payload(&mut dt, DingTalkMessage::new_markdown("", "* line 0"))
+    );
+}
+
+#[test]
+fn payload_wechat_work_image() {
+    let mut dt = DingTalk::new_wechat("key");
+    assert_eq!(
+        r#"{"image":{"base64":"aGVsbG8=","md5":"5d41402abc4b2a76b9719d911017c592"},"msgtype":"image"}"#,
+        sent_payload(&mut dt, DingTalkMessage::new_wechat_image(b"hello"))
+    );
+}
+
+#[test]
+fn dingtalk_image_unsupported() {
+    let dt = DingTalk::new("token", "");
+    let err = tokio_test::block_on(dt.send_message(DingTalkMessage::new_wechat_image(b"hello")))
+        .unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+}
+
+#[test]
+fn wechat_work_image_size_limit() {
+    let dt = DingTalk::new_wechat("key");
+    let image = vec![0_u8; 2 * 1024 * 1024 + 1];
+    let err = tokio_test::block_on(dt.send_message(DingTalkMessage::new_wechat_image(&image)))
+        .unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+
+    let mut dt = DingTalk::new_wechat("key");
+    let image = vec![0_u8; 2 * 1024 * 1024];
+    sent_payload(&mut dt, DingTalkMessage::new_wechat_image(&image));
+}
+
+#[test]
+fn payload_wechat_work_news() {
+    let mut dt = DingTalk::new_wechat("key");
+    assert_eq!(
+        r#"{"msgtype":"news","news":{"articles":[{"description":"description 001","picurl":"https://hatter.ink/favicon.png","title":"title 001","url":"https://hatter.ink/"}]}}"#,
+        sent_payload(
+            &mut dt,
+            DingTalkMessage::new_news().add_news_article(
+                "title 001",
+                "description 001",
+                "https://hatter.ink/",
+                "https://hatter.ink/favicon.png"
+            )
+        )
+    );
+}
+
+#[test]
+fn wechat_work_news_articles_count() {
+    let dt = DingTalk::new_wechat("key");
+    let err = tokio_test::block_on(dt.send_message(DingTalkMessage::new_news())).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+
+    let mut message = DingTalkMessage::new_news();
+    for i in 0..9 {
+        message = message.add_news_article(&format!("title {}", i), "", "https://hatter.ink/", "");
+    }
+    let err = tokio_test::block_on(dt.send_message(message)).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+}
+
+#[test]
+fn dingtalk_news_unsupported() {
+    let dt = DingTalk::new("token", "");
+    let message =
+        DingTalkMessage::new_news().add_news_article("title", "", "https://hatter.ink/", "");
+    let err = tokio_test::block_on(dt.send_message(message)).unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+}
+
+fn sent_at_payload(markdown: bool, mobiles: &[&str], at_all: bool) -> String {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    if markdown {
+        tokio_test::block_on(dt.send_markdown_at("title", "hi", mobiles, at_all)).unwrap();
+    } else {
+        tokio_test::block_on(dt.send_text_at("hi", mobiles, at_all)).unwrap();
+    }
+    server.requests()[0].body_str()
+}
+
+#[test]
+fn payload_send_text_at() {
+    assert_eq!(
+        r#"{"at":{"atMobiles":["13800000000","13900000000"],"isAtAll":false},"msgtype":"text","text":{"content":"hi"}}"#,
+        sent_at_payload(false, &["13800000000", "13900000000"], false)
+    );
+    assert_eq!(
+        r#"{"at":{"atMobiles":[],"isAtAll":true},"msgtype":"text","text":{"content":"hi"}}"#,
+        sent_at_payload(false, &[], true)
+    );
+}
+
+#[test]
+fn payload_send_text_at_nobody() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_text("hi")).unwrap();
+    assert_eq!(
+        server.requests()[0].body_str(),
+        sent_at_payload(false, &[], false)
+    );
+}
+
+#[test]
+fn payload_send_markdown_at() {
+    assert_eq!(
+        r#"{"at":{"atMobiles":["13800000000"],"isAtAll":true},"markdown":{"text":"hi\n\n@13800000000","title":"title"},"msgtype":"markdown"}"#,
+        sent_at_payload(true, &["13800000000"], true)
+    );
+    assert_eq!(
+        r#"{"markdown":{"text":"hi","title":"title"},"msgtype":"markdown"}"#,
+        sent_at_payload(true, &[], false)
+    );
+}
@@ -0,0 +1,79 @@
+use dingtalk::*;
+
+#[test]
+fn pc_slide_url_docs_example() {
+    assert_eq!(
+        "dingtalk://dingtalkclient/page/link?url=https%3A%2F%2Fwww.dingtalk.com&pc_slide=true",
+        DingTalkMessageActionCardBtn::pc_slide_url("https://www.dingtalk.com")
+    );
+}
+
+#[test]
+fn pc_slide_url_with_query() {
+    assert_eq!(
+        "dingtalk://dingtalkclient/page/link?url=https%3A%2F%2Fexample.com%2Freport%3Fid%3D1%26tab%3Da%20b%23top&pc_slide=true",
+        DingTalkMessageActionCardBtn::pc_slide_url("https://example.com/report?id=1&tab=a b#top")
+    );
+    assert_eq!(
+        "dingtalk://dingtalkclient/page/link?url=https%3A%2F%2Fexample.com%2F%E5%91%8A%E8%AD%A6&pc_slide=true",
+        DingTalkMessageActionCardBtn::pc_slide_url("https://example.com/告警")
+    );
+}
+
+#[test]
+fn pc_slide_url_keeps_deep_link() {
+    let deep_link =
+        "dingtalk://dingtalkclient/page/link?url=https%3A%2F%2Fwww.dingtalk.com&pc_slide=false";
+    assert_eq!(
+        deep_link,
+        DingTalkMessageActionCardBtn::pc_slide_url(deep_link)
+    );
+}
+
+#[test]
+fn with_pc_slide_btn() {
+    let btn = DingTalkMessageActionCardBtn::with_pc_slide("Open", "https://www.dingtalk.com");
+    assert_eq!("Open", btn.title);
+    assert_eq!(
+        "dingtalk://dingtalkclient/page/link?url=https%3A%2F%2Fwww.dingtalk.com&pc_slide=true",
+        btn.action_url
+    );
+}
+
+#[test]
+fn action_card_pc_slide_rewrites_btns() {
+    let dt = DingTalk::new("token", "");
+    let message = DingTalkMessage::new_action_card("title", "text")
+        .add_action_card_btn(DingTalkMessageActionCardBtn {
+            title: "Yes".into(),
+            action_url: "https://example.com/vote?answer=yes".into(),
+        })
+        .add_action_card_btn(DingTalkMessageActionCardBtn::with_pc_slide(
+            "No",
+            "https://example.com/vote?answer=no",
+        ))
+        .action_card_pc_slide();
+    message.validate().unwrap();
+    let payload = dt.build_payload(&message).unwrap();
+    let btns = payload["actionCard"]["btns"].as_array().unwrap();
+    assert_eq!(
+        "dingtalk://dingtalkclient/page/link?url=https%3A%2F%2Fexample.com%2Fvote%3Fanswer%3Dyes&pc_slide=true",
+        btns[0]["actionURL"]
+    );
+    assert_eq!(
+        "dingtalk://dingtalkclient/page/link?url=https%3A%2F%2Fexample.com%2Fvote%3Fanswer%3Dno&pc_slide=true",
+        btns[1]["actionURL"]
+    );
+
+    let message = DingTalkMessage::new_action_card("title", "text")
+        .set_action_card_single_btn(DingTalkMessageActionCardBtn {
+            title: "Open".into(),
+            action_url: "https://www.dingtalk.com".into(),
+        })
+        .action_card_pc_slide();
+    let payload = dt.build_payload(&message).unwrap();
+    assert_eq!(
+        "dingtalk://dingtalkclient/page/link?url=https%3A%2F%2Fwww.dingtalk.com&pc_slide=true",
+        payload["actionCard"]["singleURL"]
+    );
+}
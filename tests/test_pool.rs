@@ -0,0 +1,157 @@
+mod common;
+
+use common::{MockRequest, MockResponse, MockServer};
+use dingtalk::*;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+fn token_of(request: &MockRequest) -> String {
+    request
+        .path
+        .split(['?', '&'])
+        .find_map(|kv| kv.strip_prefix("access_token="))
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn pool(server: &MockServer, count: usize) -> DingTalkPool {
+    DingTalkPool::from_dingtalks(
+        (0..count)
+            .map(|i| {
+                DingTalk::builder()
+                    .access_token(&format!("token{}", i))
+                    .webhook_url(&server.url)
+                    .build()
+                    .unwrap()
+            })
+            .collect(),
+    )
+}
+
+fn send_index(pool: &DingTalkPool) -> usize {
+    tokio_test::block_on(pool.send_text("hello")).unwrap().index
+}
+
+#[test]
+fn pool_round_robin() {
+    let server = MockServer::reply(MockResponse::ok());
+    let pool = pool(&server, 3);
+    let indexes: Vec<usize> = (0..6).map(|_| send_index(&pool)).collect();
+    assert_eq!(vec![0, 1, 2, 0, 1, 2], indexes);
+    assert_eq!(vec![2, 2, 2], pool.recent_send_counts());
+    let tokens: Vec<String> = server.requests().iter().map(token_of).collect();
+    assert_eq!(
+        vec!["token0", "token1", "token2", "token0", "token1", "token2"],
+        tokens
+    );
+}
+
+#[test]
+fn pool_delivery_token() {
+    let server = MockServer::reply(MockResponse::ok());
+    let pool = pool(&server, 2);
+    let delivery = tokio_test::block_on(pool.send_markdown("title", "text")).unwrap();
+    assert_eq!(0, delivery.index);
+    assert_eq!("token0", delivery.access_token);
+    assert!(delivery.response.is_success());
+    let delivery = tokio_test::block_on(pool.send_text("hello")).unwrap();
+    assert_eq!("token1", delivery.access_token);
+}
+
+#[test]
+fn pool_skips_throttled() {
+    let server = MockServer::start(|request| match token_of(request).as_str() {
+        "token1" => MockResponse::new(429, "").header("Retry-After", "60"),
+        _ => MockResponse::ok(),
+    });
+    let pool = pool(&server, 3);
+    assert_eq!(0, send_index(&pool));
+    // token1 is throttled, message is sent by token2
+    assert_eq!(2, send_index(&pool));
+    assert!(pool.is_throttled(1));
+    let indexes: Vec<usize> = (0..4).map(|_| send_index(&pool)).collect();
+    assert_eq!(vec![0, 2, 0, 2], indexes);
+    let token1_requests = server
+        .requests()
+        .iter()
+        .filter(|r| token_of(r) == "token1")
+        .count();
+    assert_eq!(1, token1_requests);
+}
+
+#[test]
+fn pool_all_throttled() {
+    let server = MockServer::reply(MockResponse::new(429, "").header("Retry-After", "30"));
+    let pool = pool(&server, 2);
+    let err = tokio_test::block_on(pool.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::RateLimited { .. }));
+    assert_eq!(2, server.request_count());
+
+    // no request is sent when all robots are throttled
+    let err = tokio_test::block_on(pool.send_text("hello")).unwrap_err();
+    let retry_after = err.retry_after().unwrap();
+    assert!(retry_after > Duration::from_secs(25) && retry_after <= Duration::from_secs(30));
+    assert_eq!(2, server.request_count());
+}
+
+#[test]
+fn pool_prefers_least_used() {
+    let token0_count = AtomicUsize::new(0);
+    let server = MockServer::start(move |request| {
+        if token_of(request) == "token0" && token0_count.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockResponse::new(429, "").header("Retry-After", "1")
+        } else {
+            MockResponse::ok()
+        }
+    });
+    let pool = pool(&server, 3);
+    let indexes: Vec<usize> = (0..5).map(|_| send_index(&pool)).collect();
+    assert_eq!(vec![1, 2, 1, 2, 1], indexes);
+    assert_eq!(vec![1, 3, 2], pool.recent_send_counts());
+
+    thread::sleep(Duration::from_millis(1100));
+    assert!(!pool.is_throttled(0));
+    // token0 is the least used although round-robin is at token2, ties are taken round-robin
+    let indexes: Vec<usize> = (0..3).map(|_| send_index(&pool)).collect();
+    assert_eq!(vec![0, 2, 0], indexes);
+    assert_eq!(vec![3, 3, 3], pool.recent_send_counts());
+}
+
+#[test]
+fn pool_window() {
+    let server = MockServer::reply(MockResponse::ok());
+    let pool = pool(&server, 2).with_window(Duration::from_millis(200));
+    send_index(&pool);
+    send_index(&pool);
+    assert_eq!(vec![1, 1], pool.recent_send_counts());
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(vec![0, 0], pool.recent_send_counts());
+}
+
+#[test]
+fn pool_other_error_not_failover() {
+    let server = MockServer::reply(MockResponse::new(500, ""));
+    let pool = pool(&server, 3);
+    let err = tokio_test::block_on(pool.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Status { status: 500, .. }));
+    assert_eq!(1, server.request_count());
+    assert!(!pool.is_throttled(0));
+}
+
+#[test]
+fn pool_empty() {
+    let pool = DingTalkPool::new(&[]);
+    let err = tokio_test::block_on(pool.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Config(_)));
+}
+
+#[test]
+fn pool_new_tokens() {
+    let pool = DingTalkPool::new(&[("token0", "secret0"), ("token1", "")]);
+    assert_eq!(2, pool.robots().len());
+    assert_eq!("secret0", pool.robots()[0].sec_token);
+    assert_eq!("token1", pool.robots()[1].access_token);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+#[test]
+fn queue_sends_in_order_and_flushes() {
+    let server = MockServer::reply(MockResponse::ok());
+    tokio_test::block_on(async {
+        let queue = DingTalkQueue::new(DingTalk::from_url(&server.url), 10);
+        for text in ["m1", "m2", "m3"] {
+            queue.enqueue(DingTalkMessage::new_text(text)).unwrap();
+        }
+        queue.shutdown().await.unwrap();
+    });
+    let bodies: Vec<String> = server.requests().iter().map(|r| r.body_str()).collect();
+    assert_eq!(3, bodies.len());
+    assert!(bodies[0].contains("m1"));
+    assert!(bodies[1].contains("m2"));
+    assert!(bodies[2].contains("m3"));
+}
+
+#[test]
+fn queue_callback_observes_failures() {
+    let server = MockServer::start(|request| {
+        if request.body_str().contains("bad") {
+            MockResponse::new(200, r#"{"errcode":300001,"errmsg":"bad message"}"#)
+        } else {
+            MockResponse::ok()
+        }
+    });
+    let results: Arc<Mutex<Vec<(String, bool)>>> = Arc::new(Mutex::new(vec![]));
+    tokio_test::block_on(async {
+        let results = results.clone();
+        let queue = DingTalkQueue::with_callback(
+            DingTalk::from_url(&server.url),
+            10,
+            move |message, result| {
+                results
+                    .lock()
+                    .unwrap()
+                    .push((message.text_content, result.is_ok()));
+            },
+        );
+        queue.enqueue(DingTalkMessage::new_text("ok")).unwrap();
+        queue.enqueue(DingTalkMessage::new_text("bad")).unwrap();
+        queue.shutdown().await.unwrap();
+    });
+    assert_eq!(
+        vec![("ok".to_string(), true), ("bad".to_string(), false)],
+        *results.lock().unwrap()
+    );
+}
+
+#[test]
+fn queue_full() {
+    let server = MockServer::reply(MockResponse::ok());
+    tokio_test::block_on(async {
+        let queue = DingTalkQueue::new(DingTalk::from_url(&server.url), 1);
+        queue.enqueue(DingTalkMessage::new_text("m1")).unwrap();
+        assert_eq!(1, queue.len());
+        assert!(matches!(
+            queue.enqueue(DingTalkMessage::new_text("m2")),
+            Err(DingTalkError::Queue(_))
+        ));
+        queue.shutdown().await.unwrap();
+    });
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn queue_shutdown_cancel_safe() {
+    let server = MockServer::reply(MockResponse::ok().delay(Duration::from_millis(100)));
+    tokio_test::block_on(async {
+        let queue = DingTalkQueue::new(DingTalk::from_url(&server.url), 10);
+        queue.enqueue(DingTalkMessage::new_text("m1")).unwrap();
+        queue.enqueue(DingTalkMessage::new_text("m2")).unwrap();
+        // shutdown is cancelled before messages are sent
+        let shutdown = tokio::time::timeout(Duration::from_millis(10), queue.shutdown()).await;
+        assert!(shutdown.is_err());
+        for _ in 0..100 {
+            if server.request_count() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    });
+    assert_eq!(2, server.request_count());
+}
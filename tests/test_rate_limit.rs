@@ -0,0 +1,100 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+#[test]
+fn rate_limit_delays_exceeded_sends() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).rate_limit(2, Duration::from_millis(300));
+    let start = Instant::now();
+    tokio_test::block_on(async {
+        let (r1, r2, r3) = futures::join!(
+            dt.send_text("message 1"),
+            dt.send_text("message 2"),
+            dt.send_text("message 3"),
+        );
+        r1.unwrap();
+        r2.unwrap();
+        r3.unwrap();
+    });
+    assert!(start.elapsed() >= Duration::from_millis(300));
+    assert_eq!(3, server.request_count());
+    assert_eq!(1, dt.rate_limiter().unwrap().delayed_count());
+}
+
+#[test]
+fn rate_limit_wechat_work() {
+    let server = MockServer::reply(MockResponse::ok());
+    let mut dt = DingTalk::new_wechat("key").rate_limit(1, Duration::from_millis(100));
+    dt.set_default_webhook_url(&server.url);
+    tokio_test::block_on(async {
+        dt.send_text("message 1").await.unwrap();
+        dt.send_text("message 2").await.unwrap();
+    });
+    assert_eq!(1, dt.rate_limiter().unwrap().delayed_count());
+}
+
+#[test]
+fn rate_limiter_acquire() {
+    let limiter = RateLimiter::new(1, Duration::from_millis(100));
+    tokio_test::block_on(async {
+        assert_eq!(Duration::ZERO, limiter.acquire().await);
+        assert!(limiter.acquire().await >= Duration::from_millis(90));
+    });
+    assert_eq!(1, limiter.delayed_count());
+}
+
+#[test]
+fn no_rate_limit_by_default() {
+    assert!(DingTalk::new("token", "").rate_limiter().is_none());
+}
+
+#[test]
+fn rate_limit_applies_to_retries() {
+    let received = Arc::new(Mutex::new(vec![]));
+    let server = {
+        let received = received.clone();
+        MockServer::start(move |_| {
+            let mut received = received.lock().unwrap();
+            received.push(Instant::now());
+            if received.len() < 3 {
+                MockResponse::new(500, "busy")
+            } else {
+                MockResponse::ok()
+            }
+        })
+    };
+    let dt = DingTalk::from_url(&server.url)
+        .rate_limit(2, Duration::from_millis(300))
+        .with_retry(3, Duration::from_millis(10));
+    let response =
+        tokio_test::block_on(dt.send_message_with_response(DingTalkMessage::new_text("message 1")))
+            .unwrap();
+    let received = received.lock().unwrap();
+    assert_eq!(3, received.len());
+    // the third attempt waits until the first one leaves the window
+    assert!(received[2].duration_since(received[0]) >= Duration::from_millis(290));
+    assert!(response.rate_limit_delay.unwrap() >= Duration::from_millis(200));
+    assert_eq!(1, dt.rate_limiter().unwrap().delayed_count());
+}
+
+#[test]
+fn rate_limit_delay_of_each_send() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).rate_limit(1, Duration::from_millis(200));
+    tokio_test::block_on(async {
+        let message = DingTalkMessage::new_text("message");
+        let first = dt
+            .send_message_with_response(message.clone())
+            .await
+            .unwrap();
+        assert_eq!(None, first.rate_limit_delay);
+        let second = dt.send_message_with_response(message).await.unwrap();
+        assert!(second.rate_limit_delay.unwrap() >= Duration::from_millis(180));
+    });
+}
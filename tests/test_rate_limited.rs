@@ -0,0 +1,113 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+fn rate_limited(err: DingTalkError) -> Option<Duration> {
+    match err {
+        DingTalkError::RateLimited { retry_after } => retry_after,
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn rate_limited_retry_after_seconds() {
+    let server = MockServer::reply(MockResponse::new(429, "").header("Retry-After", "7"));
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert_eq!(Some(Duration::from_secs(7)), err.retry_after());
+    assert_eq!(Some(Duration::from_secs(7)), rate_limited(err));
+}
+
+#[test]
+fn rate_limited_retry_after_http_date() {
+    let server = MockServer::reply(
+        MockResponse::new(429, "").header("Retry-After", "Sun, 06 Nov 1994 08:49:37 GMT"),
+    );
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert_eq!(Some(Duration::ZERO), rate_limited(err));
+}
+
+#[test]
+fn rate_limited_without_retry_after() {
+    let server = MockServer::reply(MockResponse::new(429, "Too Many Requests"));
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert_eq!("Rate limited", err.to_string());
+    assert_eq!(None, rate_limited(err));
+}
+
+#[test]
+fn rate_limited_errcode() {
+    for body in [
+        r#"{"errcode":130101,"errmsg":"send too fast, exceed 20 times per minute"}"#,
+        r#"{"errcode":45009,"errmsg":"api freq out of limit"}"#,
+    ] {
+        let server = MockServer::reply(MockResponse::new(200, body).header("Retry-After", "3"));
+        let dt = DingTalk::from_url(&server.url);
+        let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+        assert_eq!(Some(Duration::from_secs(3)), rate_limited(err));
+    }
+}
+
+#[test]
+fn rate_limited_retry_waits_retry_after() {
+    let count = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| match count.fetch_add(1, Ordering::SeqCst) {
+        0 => MockResponse::new(429, "").header("Retry-After", "1"),
+        _ => MockResponse::ok(),
+    });
+    let dt = DingTalk::from_url(&server.url).with_retry(2, Duration::from_millis(10));
+    let start = Instant::now();
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert_eq!(2, server.request_count());
+}
+
+#[test]
+fn rate_limited_retry_without_retry_after() {
+    let count = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| match count.fetch_add(1, Ordering::SeqCst) {
+        0 => MockResponse::new(429, ""),
+        _ => MockResponse::ok(),
+    });
+    let dt = DingTalk::from_url(&server.url).with_retry(2, Duration::from_millis(10));
+    let start = Instant::now();
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(2, server.request_count());
+}
+
+#[test]
+fn rate_limited_retry_exhausted() {
+    let server = MockServer::reply(MockResponse::new(429, "").header("Retry-After", "0"));
+    let dt = DingTalk::from_url(&server.url).with_retry(3, Duration::from_millis(10));
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert_eq!(Some(Duration::ZERO), err.retry_after());
+    match err {
+        DingTalkError::Retry { attempts, source } => {
+            assert_eq!(3, attempts);
+            assert_eq!(Some(Duration::ZERO), rate_limited(*source));
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+    assert_eq!(3, server.request_count());
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limited_retry_after_is_capped() {
+    let transport = std::sync::Arc::new(RecordingTransport::new());
+    transport.reply(TransportResponse::new(429, "").with_header("Retry-After", "999999999"));
+    let dt = DingTalk::from_url("http://dingtalk.invalid/robot/send")
+        .with_transport(transport.clone())
+        .with_retry(2, Duration::from_millis(10));
+    let start = tokio::time::Instant::now();
+    dt.send_text("hello").await.unwrap();
+    assert_eq!(Duration::from_secs(30), start.elapsed());
+    assert_eq!(2, transport.requests().len());
+}
@@ -0,0 +1,94 @@
+use dingtalk::*;
+use serde_json::Value;
+use std::sync::Arc;
+
+fn markdown_sent(transport: &RecordingTransport) -> String {
+    let body: Value = serde_json::from_str(&transport.bodies()[0]).unwrap();
+    body["markdown"]["text"].as_str().unwrap().to_owned()
+}
+
+#[test]
+fn send_reader_code_block() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "").with_transport(transport.clone());
+    let input: &[u8] = b"job started\njob done\n";
+    let response = tokio_test::block_on(dt.send_reader("nightly job", input)).unwrap();
+    assert!(!response.is_truncated());
+    let body: Value = serde_json::from_str(&transport.bodies()[0]).unwrap();
+    assert_eq!("nightly job", body["markdown"]["title"]);
+    assert_eq!("```\njob started\njob done\n```", markdown_sent(&transport));
+}
+
+#[test]
+fn send_reader_longer_fence() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "").with_transport(transport.clone());
+    let input: &[u8] = b"```rust\nfn main() {}\n```";
+    tokio_test::block_on(dt.send_reader("code", input)).unwrap();
+    assert_eq!(
+        "````\n```rust\nfn main() {}\n```\n````",
+        markdown_sent(&transport)
+    );
+}
+
+#[test]
+fn send_reader_empty_input() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "").with_transport(transport.clone());
+    for input in [&b""[..], &b" \n\t\n"[..]] {
+        match tokio_test::block_on(dt.send_reader("empty", input)) {
+            Err(DingTalkError::Validation(message)) => {
+                assert_eq!("Input is empty, message is not sent", message)
+            }
+            _ => panic!("empty input should be validation error"),
+        }
+    }
+    assert!(transport.bodies().is_empty());
+}
+
+#[test]
+fn send_reader_invalid_utf8() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "").with_transport(transport.clone());
+    let input: &[u8] = b"ok \xff\xfe end";
+    tokio_test::block_on(dt.send_reader("binary", input)).unwrap();
+    assert_eq!(
+        "```\nok \u{fffd}\u{fffd} end\n```",
+        markdown_sent(&transport)
+    );
+}
+
+#[test]
+fn send_reader_truncates_to_limit() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "").with_transport(transport.clone());
+    // 50000 bytes, DingTalk markdown limit is 20000 bytes
+    let input = "line\n".repeat(10000);
+    let response = tokio_test::block_on(dt.send_reader("long", input.as_bytes())).unwrap();
+    let markdown = markdown_sent(&transport);
+    assert_eq!(20000, markdown.len());
+    assert!(markdown.starts_with("```\nline\n"), "{}", markdown);
+    assert!(markdown.ends_with("\nlin…(truncated)\n```"), "{}", markdown);
+    // 20000 bytes less 8 bytes of fences and 14 bytes of ellipsis are kept, trailing newline
+    // of the 20000 bytes read is trimmed instead of cut
+    assert_eq!(Some(50000 - 19978 - 1), response.truncated_bytes);
+}
+
+#[test]
+fn send_reader_custom_ellipsis() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new_wechat("key001")
+        .truncate_ellipsis("...")
+        .with_transport(transport.clone());
+    let input = "中".repeat(2000);
+    let response = tokio_test::block_on(dt.send_reader("long", input.as_bytes())).unwrap();
+    assert!(response.is_truncated());
+    let body: Value = serde_json::from_str(&transport.bodies()[0]).unwrap();
+    let content = body["markdown"]["content"].as_str().unwrap();
+    // 4096 bytes less 8 bytes of fences and 3 bytes of ellipsis are 1361 chars
+    assert!(
+        content.ends_with(&format!("```\n{}...\n```", "中".repeat(1361))),
+        "{}",
+        content
+    );
+}
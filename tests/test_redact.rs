@@ -0,0 +1,91 @@
+use dingtalk::*;
+
+const ACCESS_TOKEN: &str = "abcd0123456789secretwxyz";
+const SEC_TOKEN: &str = "SEC0123456789abcdef";
+
+#[test]
+fn debug_redacts_tokens() {
+    let dt = DingTalk::new(ACCESS_TOKEN, SEC_TOKEN);
+    let debug = format!("{:?}", dt);
+    assert!(!debug.contains(ACCESS_TOKEN), "{}", debug);
+    assert!(!debug.contains("0123456789"), "{}", debug);
+    assert!(!debug.contains(SEC_TOKEN), "{}", debug);
+    assert!(
+        debug.contains(r#"access_token: "abcd***wxyz""#),
+        "{}",
+        debug
+    );
+    assert!(debug.contains(r#"sec_token: "***""#), "{}", debug);
+    let pretty = format!("{:#?}", dt);
+    assert!(!pretty.contains(ACCESS_TOKEN) && !pretty.contains(SEC_TOKEN));
+}
+
+#[test]
+fn debug_short_token_fully_masked() {
+    let debug = format!("{:?}", DingTalk::new("short", ""));
+    assert!(!debug.contains("short"), "{}", debug);
+    assert!(debug.contains(r#"access_token: "***""#), "{}", debug);
+    assert!(debug.contains(r#"sec_token: """#), "{}", debug);
+}
+
+#[test]
+fn debug_redacts_direct_url() {
+    let dt = DingTalk::from_url(&format!(
+        "https://qyapi.weixin.qq.com/cgi-bin/webhook/send?key={}&foo=bar",
+        ACCESS_TOKEN
+    ));
+    let debug = format!("{:?}", dt);
+    assert!(!debug.contains(ACCESS_TOKEN), "{}", debug);
+    assert!(
+        debug.contains("https://qyapi.weixin.qq.com/cgi-bin/webhook/send?key=***&foo=bar"),
+        "{}",
+        debug
+    );
+
+    let slack = "https://hooks.slack.com/services/T000/B000/XXXXXXXXXXXXXXXXXXXXXXXX";
+    let debug = format!("{:?}", DingTalk::new_slack(slack));
+    assert!(!debug.contains("XXXXXXXXXXXXXXXXXXXXXXXX"), "{}", debug);
+    assert!(
+        debug.contains("https://hooks.slack.com/services/T000/B000/***"),
+        "{}",
+        debug
+    );
+}
+
+#[test]
+fn redacted_webhook_url() {
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/send?access_token=***",
+        DingTalk::new(ACCESS_TOKEN, SEC_TOKEN).redacted_webhook_url()
+    );
+    assert_eq!(
+        "https://qyapi.weixin.qq.com/cgi-bin/webhook/send?key=***",
+        DingTalk::new_wechat(ACCESS_TOKEN).redacted_webhook_url()
+    );
+    assert_eq!(
+        "https://open.feishu.cn/open-apis/bot/v2/hook/***",
+        DingTalk::new_feishu(ACCESS_TOKEN, SEC_TOKEN).redacted_webhook_url()
+    );
+    assert_eq!(
+        "https://discord.com/api/webhooks/123/***",
+        DingTalk::new_discord(&format!(
+            "https://discord.com/api/webhooks/123/{}",
+            ACCESS_TOKEN
+        ))
+        .redacted_webhook_url()
+    );
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/sendBySession?session=***",
+        DingTalk::from_session_webhook(
+            "https://oapi.dingtalk.com/robot/sendBySession?session=abc",
+            0
+        )
+        .redacted_webhook_url()
+    );
+    assert_eq!(
+        "***",
+        DingTalk::from_json(r#"{"direct_url": "not a url"}"#)
+            .unwrap()
+            .redacted_webhook_url()
+    );
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::collections::HashMap;
+
+fn vars() -> HashMap<&'static str, &'static str> {
+    HashMap::from([("host", "web-01"), ("status", "down"), ("count", "3")])
+}
+
+fn render(template: &str) -> XResult<String> {
+    DingTalkMessage::render_template(template, &vars())
+}
+
+#[test]
+fn render_template_placeholders() {
+    assert_eq!(
+        "web-01 is down, 3 alerts",
+        render("{{host}} is {{ status }}, {{count}} alerts").unwrap()
+    );
+    assert_eq!(
+        "### web-01\n- status: **down**",
+        render("### {{host}}\n- status: **{{status}}**").unwrap()
+    );
+    assert_eq!("web-01web-01", render("{{host}}{{host}}").unwrap());
+    assert_eq!("no placeholder", render("no placeholder").unwrap());
+    assert_eq!("", render("").unwrap());
+    assert_eq!("中文 web-01 告警", render("中文 {{host}} 告警").unwrap());
+}
+
+#[test]
+fn render_template_value_not_rendered_again() {
+    let vars = HashMap::from([("a", "{{b}}"), ("b", "x")]);
+    assert_eq!(
+        "{{b}} x",
+        DingTalkMessage::render_template("{{a}} {{b}}", &vars).unwrap()
+    );
+}
+
+#[test]
+fn render_template_escape() {
+    assert_eq!("{{host}} web-01", render("\\{{host}} {{host}}").unwrap());
+    assert_eq!("{ } }} \\ {x}", render("{ } }} \\ {x}").unwrap());
+    assert_eq!(
+        r#"{"host":"web-01"}"#,
+        render(r#"{"host":"{{host}}"}"#).unwrap()
+    );
+}
+
+#[test]
+fn render_template_unresolved() {
+    let err = render("{{host}} {{region}} {{zone}} {{region}}").unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+    assert_eq!(
+        "Validation error: Unresolved template placeholders: region, zone",
+        err.to_string()
+    );
+}
+
+#[test]
+fn render_template_invalid() {
+    for template in ["{{host", "{{host}} {{", "{{}}", "{{  }}"] {
+        assert!(
+            matches!(render(template), Err(DingTalkError::Validation(_))),
+            "{}",
+            template
+        );
+    }
+}
+
+#[test]
+fn send_text_template() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_text_template("{{host}} is {{status}}", &vars())).unwrap();
+    assert_eq!(
+        r#"{"msgtype":"text","text":{"content":"web-01 is down"}}"#,
+        server.requests()[0].body_str()
+    );
+
+    let err = tokio_test::block_on(dt.send_text_template("{{region}}", &vars())).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+    assert_eq!(1, server.request_count());
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+#[test]
+fn send_ok_errcode() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn send_non_zero_errcode() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":310000,"errmsg":"sign not match"}"#,
+    ));
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    match err {
+        DingTalkError::Server { errcode, errmsg } => {
+            assert_eq!(310000, errcode);
+            assert_eq!("sign not match", errmsg);
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn send_non_json_body() {
+    let server = MockServer::reply(MockResponse::new(200, "OK"));
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+}
+
+#[test]
+fn send_bad_status() {
+    let server = MockServer::reply(MockResponse::new(500, ""));
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Status { status: 500, .. }));
+}
+
+fn status_error(response: MockResponse) -> (u16, String) {
+    let server = MockServer::reply(response);
+    let dt = DingTalk::from_url(&server.url);
+    match tokio_test::block_on(dt.send_text("hello")).unwrap_err() {
+        DingTalkError::Status { status, body } => (status, body),
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn send_bad_status_body() {
+    let server = MockServer::reply(MockResponse::new(400, "invalid webhook url"));
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert_eq!(
+        "Unknown status: 400, body: invalid webhook url",
+        err.to_string()
+    );
+    assert_eq!(
+        (400, "invalid webhook url".to_string()),
+        status_error(MockResponse::new(400, "invalid webhook url"))
+    );
+}
+
+#[test]
+fn send_bad_status_empty_body() {
+    let server = MockServer::reply(MockResponse::new(502, ""));
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert_eq!("Unknown status: 502", err.to_string());
+}
+
+#[test]
+fn send_bad_status_body_bounded() {
+    let (status, body) = status_error(MockResponse::new(400, &"x".repeat(100 * 1024)));
+    assert_eq!(400, status);
+    assert_eq!(8 * 1024, body.len());
+
+    // multi-byte char across the limit is dropped
+    let (_, body) = status_error(MockResponse::new(400, &format!("{}中", "x".repeat(8191))));
+    assert_eq!(8191, body.len());
+}
+
+#[test]
+fn send_bad_status_body_not_utf8() {
+    let mut response = MockResponse::new(400, "");
+    response.body = vec![b'b', b'a', b'd', 0xff, 0xfe];
+    let (status, body) = status_error(response);
+    assert_eq!(400, status);
+    assert_eq!("bad\u{fffd}\u{fffd}", body);
+}
+
+#[test]
+fn error_is_std_error() {
+    let err: Box<dyn std::error::Error> = DingTalk::from_token("bad").err().unwrap().into();
+    assert!(err.to_string().contains("bad"));
+}
+
+#[test]
+fn send_message_with_response_fields() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":0,"errmsg":"ok","request_id":"req001"}"#,
+    ));
+    let dt = DingTalk::from_url(&server.url);
+    let response =
+        tokio_test::block_on(dt.send_message_with_response(DingTalkMessage::new_text("hello")))
+            .unwrap();
+    assert_eq!(0, response.errcode);
+    assert_eq!("ok", response.errmsg);
+    assert!(response.is_success());
+    assert_eq!("req001", response.raw["request_id"]);
+}
+
+#[test]
+fn send_message_with_response_non_json() {
+    let server = MockServer::reply(MockResponse::new(200, "ok"));
+    let dt = DingTalk::from_url(&server.url);
+    let response =
+        tokio_test::block_on(dt.send_message_with_response(DingTalkMessage::new_text("hello")))
+            .unwrap();
+    assert_eq!(0, response.errcode);
+    assert_eq!("", response.errmsg);
+    assert_eq!(serde_json::Value::String("ok".into()), response.raw);
+}
+
+#[test]
+fn response_from_feishu_body() {
+    let response = DingTalkResponse::from_body(r#"{"code":19001,"msg":"param invalid"}"#);
+    assert_eq!(19001, response.errcode);
+    assert_eq!("param invalid", response.errmsg);
+    assert!(!response.is_success());
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+#[test]
+fn retry_until_success() {
+    let count = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| match count.fetch_add(1, Ordering::SeqCst) {
+        0 | 1 => MockResponse::new(503, ""),
+        _ => MockResponse::ok(),
+    });
+    let dt = DingTalk::from_url(&server.url).with_retry(3, Duration::from_millis(10));
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(3, server.request_count());
+}
+
+#[test]
+fn retry_exhausted() {
+    let server = MockServer::reply(MockResponse::new(500, ""));
+    let dt = DingTalk::from_url(&server.url).with_retry(3, Duration::from_millis(10));
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    match err {
+        DingTalkError::Retry { attempts, source } => {
+            assert_eq!(3, attempts);
+            assert!(matches!(*source, DingTalkError::Status { status: 500, .. }));
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+    assert_eq!(3, server.request_count());
+}
+
+#[test]
+fn no_retry_on_client_error() {
+    let server = MockServer::reply(MockResponse::new(400, ""));
+    let dt = DingTalk::from_url(&server.url).with_retry(3, Duration::from_millis(10));
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Status { status: 400, .. }));
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn no_retry_on_errcode() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":310000,"errmsg":"sign not match"}"#,
+    ));
+    let dt = DingTalk::from_url(&server.url).with_retry(3, Duration::from_millis(10));
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Server {
+            errcode: 310000,
+            ..
+        }
+    ));
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn no_retry_by_default() {
+    let server = MockServer::reply(MockResponse::new(500, ""));
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Status { status: 500, .. }));
+    assert_eq!(1, server.request_count());
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::{MockRequest, MockResponse, MockServer};
+use dingtalk::*;
+use serde_json::Value;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Replies token for `/gettoken` and `batch_send_response` for others
+fn robot_server(batch_send_response: MockResponse) -> MockServer {
+    MockServer::start(move |request: &MockRequest| {
+        if request.path.starts_with("/gettoken") {
+            MockResponse::new(
+                200,
+                r#"{"errcode":0,"errmsg":"ok","access_token":"token001","expires_in":7200}"#,
+            )
+        } else {
+            batch_send_response.clone()
+        }
+    })
+}
+
+fn robot_client(server: &MockServer) -> RobotCodeClient {
+    let app = DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url());
+    RobotCodeClient::new(Arc::new(app), "robot001").with_api_url(&server.base_url())
+}
+
+fn batch_send_bodies(server: &MockServer) -> Vec<Value> {
+    server
+        .requests()
+        .iter()
+        .filter(|r| r.path == "/v1.0/robot/oToMessages/batchSend")
+        .map(|r| {
+            assert_eq!("POST", r.method);
+            assert_eq!(Some("token001"), r.header("x-acs-dingtalk-access-token"));
+            serde_json::from_str(&r.body_str()).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn send_oto_text() {
+    let server = robot_server(MockResponse::new(200, r#"{"processQueryKey":"key001"}"#));
+    let robot = robot_client(&server);
+    let keys = tokio_test::block_on(robot.send_oto_text(&["user1", "user2"], "hello")).unwrap();
+    assert_eq!(vec!["key001"], keys);
+    assert_eq!(
+        vec![serde_json::json!({
+            "robotCode": "robot001",
+            "userIds": ["user1", "user2"],
+            "msgKey": "sampleText",
+            "msgParam": r#"{"content":"hello"}"#,
+        })],
+        batch_send_bodies(&server)
+    );
+}
+
+#[test]
+fn send_oto_markdown() {
+    let server = robot_server(MockResponse::new(200, r#"{"processQueryKey":"key001"}"#));
+    let robot = robot_client(&server);
+    tokio_test::block_on(robot.send_oto_markdown(&["user1"], "title", "# hello")).unwrap();
+    let bodies = batch_send_bodies(&server);
+    assert_eq!("sampleMarkdown", bodies[0]["msgKey"]);
+    let msg_param: Value = serde_json::from_str(bodies[0]["msgParam"].as_str().unwrap()).unwrap();
+    assert_eq!(
+        serde_json::json!({ "title": "title", "text": "# hello" }),
+        msg_param
+    );
+}
+
+#[test]
+fn send_oto_batches() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let batch_count = count.clone();
+    let server = MockServer::start(move |request: &MockRequest| {
+        if request.path.starts_with("/gettoken") {
+            return MockResponse::new(
+                200,
+                r#"{"errcode":0,"access_token":"token001","expires_in":7200}"#,
+            );
+        }
+        let index = batch_count.fetch_add(1, Ordering::SeqCst);
+        MockResponse::new(200, &format!(r#"{{"processQueryKey":"key{}"}}"#, index))
+    });
+    let robot = robot_client(&server);
+    let user_ids = (0..45).map(|i| format!("user{}", i)).collect::<Vec<_>>();
+    let user_ids = user_ids.iter().map(String::as_str).collect::<Vec<_>>();
+    let keys = tokio_test::block_on(robot.send_oto_text(&user_ids, "hello")).unwrap();
+    assert_eq!(vec!["key0", "key1", "key2"], keys);
+    let batch_sizes = batch_send_bodies(&server)
+        .iter()
+        .map(|body| body["userIds"].as_array().unwrap().len())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![20, 20, 5], batch_sizes);
+}
+
+#[test]
+fn send_oto_no_user_ids() {
+    let server = robot_server(MockResponse::ok());
+    let robot = robot_client(&server);
+    let err = tokio_test::block_on(robot.send_oto_text(&[], "hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)), "{:?}", err);
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn send_oto_invalid_robot_code() {
+    let server = robot_server(MockResponse::new(
+        400,
+        r#"{"code":"invalidParameter.robotCode","message":"robotCode is invalid","requestid":"r1"}"#,
+    ));
+    let robot = robot_client(&server);
+    let err = tokio_test::block_on(robot.send_oto_text(&["user1"], "hello")).unwrap_err();
+    assert!(
+        matches!(err, DingTalkError::Config(ref message)
+            if message == "Invalid robotCode robot001: robotCode is invalid"),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn send_oto_other_error_status() {
+    let server = robot_server(MockResponse::new(
+        400,
+        r#"{"code":"invalidParameter.userIds","message":"userIds is invalid"}"#,
+    ));
+    let robot = robot_client(&server);
+    let err = tokio_test::block_on(robot.send_oto_text(&["user1"], "hello")).unwrap_err();
+    assert!(
+        matches!(err, DingTalkError::Status { status: 400, .. }),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn send_oto_unauthorized_drops_token() {
+    let server = robot_server(MockResponse::new(
+        401,
+        r#"{"code":"InvalidAuthentication","message":"access token is invalid"}"#,
+    ));
+    let robot = robot_client(&server);
+    tokio_test::block_on(async {
+        robot.send_oto_text(&["user1"], "hello").await.unwrap_err();
+        robot.send_oto_text(&["user1"], "hello").await.unwrap_err();
+    });
+    let token_requests = server
+        .requests()
+        .iter()
+        .filter(|r| r.path.starts_with("/gettoken"))
+        .count();
+    assert_eq!(2, token_requests);
+}
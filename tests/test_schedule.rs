@@ -0,0 +1,66 @@
+use dingtalk::*;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+fn dingtalk(transport: &Arc<RecordingTransport>) -> DingTalk {
+    DingTalk::new("token001", "")
+        .with_transport(transport.clone())
+        .with_clock(|| SystemTime::UNIX_EPOCH + Duration::from_millis(1600000000000))
+}
+
+fn message() -> DingTalkMessage {
+    DingTalkMessage::new_text("maintenance finished")
+}
+
+#[tokio::test(start_paused = true)]
+async fn send_message_after_delay() {
+    let transport = Arc::new(RecordingTransport::new());
+    let scheduled = dingtalk(&transport).send_message_after(message(), Duration::from_secs(60));
+    tokio::time::sleep(Duration::from_secs(59)).await;
+    assert!(transport.bodies().is_empty());
+    assert!(scheduled.is_pending());
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    assert_eq!(1, transport.bodies().len());
+    assert!(transport.bodies()[0].contains("maintenance finished"));
+    assert!(!scheduled.is_pending());
+    assert!(!scheduled.cancel());
+}
+
+#[tokio::test(start_paused = true)]
+async fn send_message_after_cancelled() {
+    let transport = Arc::new(RecordingTransport::new());
+    let scheduled = dingtalk(&transport).send_message_after(message(), Duration::from_secs(60));
+    tokio::time::sleep(Duration::from_secs(30)).await;
+    assert!(scheduled.cancel());
+    assert!(!scheduled.cancel());
+    assert!(!scheduled.is_pending());
+    tokio::time::sleep(Duration::from_secs(120)).await;
+    assert!(transport.bodies().is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn send_message_after_handle_dropped() {
+    let transport = Arc::new(RecordingTransport::new());
+    drop(dingtalk(&transport).send_message_after(message(), Duration::from_secs(60)));
+    tokio::time::sleep(Duration::from_secs(61)).await;
+    assert_eq!(1, transport.bodies().len());
+}
+
+#[tokio::test(start_paused = true)]
+async fn send_message_at_time() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = dingtalk(&transport);
+    let now = SystemTime::UNIX_EPOCH + Duration::from_millis(1600000000000);
+    let _scheduled = dt.send_message_at(message(), now + Duration::from_secs(30));
+    tokio::time::sleep(Duration::from_secs(29)).await;
+    assert!(transport.bodies().is_empty());
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    assert_eq!(1, transport.bodies().len());
+
+    // time in the past is sent at once
+    let _scheduled = dt.send_message_at(message(), now - Duration::from_secs(30));
+    tokio::time::sleep(Duration::from_millis(1)).await;
+    assert_eq!(2, transport.bodies().len());
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::{MockRequest, MockResponse, MockServer};
+use dingtalk::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn send_message_ref_resend_after_failure() {
+    let count = AtomicUsize::new(0);
+    let server = MockServer::start(move |_: &MockRequest| {
+        if count.fetch_add(1, Ordering::SeqCst) == 0 {
+            MockResponse::new(200, r#"{"errcode":300001,"errmsg":"token is not exist"}"#)
+        } else {
+            MockResponse::ok()
+        }
+    });
+    let dt = DingTalk::from_url(&server.url);
+    let message =
+        DingTalkMessage::new_markdown("title", "# text").at_mobiles(&["13800000000".to_string()]);
+    assert!(tokio_test::block_on(dt.send_message_ref(&message)).is_err());
+    tokio_test::block_on(dt.send_message_ref(&message)).unwrap();
+    let requests = server.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!(requests[0].body_str(), requests[1].body_str());
+}
+
+#[test]
+fn send_message_ref_same_as_by_value() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let message = DingTalkMessage::new_text("hello").at_all();
+    let response = tokio_test::block_on(dt.send_message_with_response_ref(&message)).unwrap();
+    assert_eq!("ok", response.errmsg);
+    tokio_test::block_on(dt.send_message(message)).unwrap();
+    let requests = server.requests();
+    assert_eq!(requests[0].body_str(), requests[1].body_str());
+}
+
+#[test]
+fn send_message_ref_truncate_keeps_message() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).truncate_to_limit(true);
+    let text = "a".repeat(30000);
+    let message = DingTalkMessage::new_text(&text);
+    let response = tokio_test::block_on(dt.send_message_with_response_ref(&message)).unwrap();
+    assert!(response.is_truncated());
+    assert_eq!(text, message.text_content);
+    assert!(server.requests()[0].body_str().len() < text.len());
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use serde_json::json;
+use std::sync::Arc;
+
+#[test]
+fn send_value_signed() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "secret001").with_transport(transport.clone());
+    let value = json!({"msgtype": "text", "text": {"content": "hello"}});
+    tokio_test::block_on(dt.send_value(&value)).unwrap();
+
+    let url = &transport.urls()[0];
+    assert!(
+        url.starts_with("https://oapi.dingtalk.com/robot/send?access_token=token001&timestamp=")
+    );
+    assert!(url.contains("&sign="));
+    assert_eq!(
+        vec![r#"{"msgtype":"text","text":{"content":"hello"}}"#.to_string()],
+        transport.bodies()
+    );
+}
+
+#[test]
+fn send_value_custom_msgtype() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let value = json!({"msgtype": "newType", "newType": {"field": 1}});
+    let response = tokio_test::block_on(dt.send_value_with_response(&value)).unwrap();
+    assert!(response.is_success());
+    assert_eq!(
+        r#"{"msgtype":"newType","newType":{"field":1}}"#,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn send_value_shares_response_handling() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":310000,"errmsg":"sign not match"}"#,
+    ));
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_value(&json!({"msgtype": "text"}))).unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Server {
+            errcode: 310000,
+            ..
+        }
+    ));
+
+    let server = MockServer::reply(MockResponse::new(500, "oops"));
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_value(&json!({"msgtype": "text"}))).unwrap_err();
+    assert!(matches!(err, DingTalkError::Status { status: 500, .. }));
+}
+
+#[test]
+fn send_value_invalid() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "").with_transport(transport.clone());
+    for value in [
+        json!("text"),
+        json!([{"msgtype": "text"}]),
+        json!({"text": {"content": "hello"}}),
+        json!({"msgtype": ""}),
+        json!({"msgtype": 1}),
+        json!({"msg_type": "text"}),
+    ] {
+        let err = tokio_test::block_on(dt.send_value(&value)).unwrap_err();
+        assert!(matches!(err, DingTalkError::Validation(_)), "{}", value);
+    }
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn send_value_robot_types() {
+    let transport = Arc::new(RecordingTransport::new());
+    let feishu = DingTalk::new_feishu("token001", "").with_transport(transport.clone());
+    tokio_test::block_on(
+        feishu.send_value(&json!({"msg_type": "text", "content": {"text": "hi"}})),
+    )
+    .unwrap();
+    let err = tokio_test::block_on(feishu.send_value(&json!({"msgtype": "text"}))).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+
+    let slack = DingTalk::new_slack("https://hooks.slack.com/services/T/B/X")
+        .with_transport(transport.clone());
+    tokio_test::block_on(slack.send_value(&json!({"text": "hi"}))).unwrap();
+    assert_eq!(2, transport.requests().len());
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+struct MyChat {
+    url: String,
+}
+
+impl WebhookSender for MyChat {
+    fn build_payload(&self, dingtalk_message: &DingTalkMessage) -> XResult<Value> {
+        Ok(json!({
+            "kind": format!("{:?}", dingtalk_message.message_type),
+            "body": dingtalk_message.text_content,
+        }))
+    }
+
+    fn endpoint_url(&self) -> XResult<String> {
+        Ok(format!("{}?channel=ops", self.url))
+    }
+}
+
+#[test]
+fn custom_sender_receives_payload() {
+    let server = MockServer::reply(MockResponse::new(200, "ok"));
+    let dt = DingTalk::new("token001", "secret001").with_sender(MyChat {
+        url: server.url.clone(),
+    });
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    let request = &server.requests()[0];
+    assert_eq!("/robot/send?channel=ops", request.path);
+    assert_eq!(r#"{"body":"hello","kind":"Text"}"#, request.body_str());
+}
+
+#[test]
+fn custom_sender_keeps_retry() {
+    let server = MockServer::start(|_| MockResponse::new(502, "bad gateway"));
+    let dt = DingTalk::default()
+        .with_retry(2, Duration::from_millis(1))
+        .with_sender(MyChat {
+            url: server.url.clone(),
+        });
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Retry { attempts: 2, .. }));
+    assert_eq!(2, server.request_count());
+}
+
+#[test]
+fn custom_sender_errors_are_local() {
+    struct Failing;
+    impl WebhookSender for Failing {
+        fn build_payload(&self, _: &DingTalkMessage) -> XResult<Value> {
+            Err(DingTalkError::Unsupported("no payload".into()))
+        }
+        fn endpoint_url(&self) -> XResult<String> {
+            Ok("http://127.0.0.1:1/".into())
+        }
+    }
+    let dt = DingTalk::default().with_sender(Failing);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+}
+
+#[test]
+fn builtin_sender_payload_and_url() {
+    let dt = DingTalk::new_wechat("key001");
+    let sender: &dyn WebhookSender = &dt;
+    assert_eq!(
+        json!({"msgtype": "text", "text": {"content": "hello"}}),
+        sender
+            .build_payload(&DingTalkMessage::new_text("hello"))
+            .unwrap()
+    );
+    assert_eq!(
+        "https://qyapi.weixin.qq.com/cgi-bin/webhook/send?key=key001",
+        sender.endpoint_url().unwrap()
+    );
+}
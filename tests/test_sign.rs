@@ -0,0 +1,44 @@
+use dingtalk::sign::{compute_sign, signed_query};
+use dingtalk::*;
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn sign_known_vectors() {
+    assert_eq!(
+        "PPcRl2tf+BNekAadmEb8Cx1rGmXjHN8H+/0mvy5rUJk=",
+        compute_sign("SEC0123456789abcdef", 1600000000000).unwrap()
+    );
+    assert_eq!(
+        "ijHEivi6YiCNPZOq4hZstmvZ3sPfbioAiMhP30ae7W0=",
+        compute_sign("this is secret", 1577808000000).unwrap()
+    );
+    assert_eq!(
+        "Q9tEPDPpqPnAbOT2fNJuW8fHBSbcKNHJA/iXNesb6iA=",
+        compute_sign("", 0).unwrap()
+    );
+}
+
+#[test]
+fn sign_query_is_percent_encoded() {
+    assert_eq!(
+        "timestamp=1600000000000&sign=PPcRl2tf%2BBNekAadmEb8Cx1rGmXjHN8H%2B%2F0mvy5rUJk%3D",
+        signed_query("SEC0123456789abcdef", 1600000000000).unwrap()
+    );
+    assert_eq!(
+        "timestamp=1577808000000&sign=ijHEivi6YiCNPZOq4hZstmvZ3sPfbioAiMhP30ae7W0%3D",
+        signed_query("this is secret", 1577808000000).unwrap()
+    );
+}
+
+#[test]
+fn sign_same_as_signed_url() {
+    let dt = DingTalk::new("token001", "SEC0123456789abcdef")
+        .with_clock(|| SystemTime::UNIX_EPOCH + Duration::from_millis(1600000000000));
+    assert_eq!(
+        format!(
+            "https://oapi.dingtalk.com/robot/send?access_token=token001&{}",
+            signed_query("SEC0123456789abcdef", 1600000000000).unwrap()
+        ),
+        dt.generate_signed_url().unwrap()
+    );
+}
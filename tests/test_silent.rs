@@ -0,0 +1,96 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+#[test]
+fn send_message_silent_ok() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_message_silent(DingTalkMessage::new_text("hello")));
+    assert_eq!(1, server.request_count());
+    assert_eq!(0, dt.silent_failure_count());
+}
+
+#[test]
+fn send_message_silent_counts_failures() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":310000,"errmsg":"sign not match"}"#,
+    ));
+    let dt = DingTalk::from_url(&server.url);
+    let clone = dt.clone();
+    tokio_test::block_on(dt.send_message_silent(DingTalkMessage::new_text("hello")));
+    tokio_test::block_on(clone.send_message_silent(DingTalkMessage::new_text("hello")));
+    assert_eq!(2, server.request_count());
+    assert_eq!(2, dt.silent_failure_count());
+    // errors of `send_message` are returned, not counted
+    tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert_eq!(2, dt.silent_failure_count());
+}
+
+#[test]
+fn spawn_send_message_silent() {
+    let server = MockServer::reply(MockResponse::new(500, ""));
+    let dt = DingTalk::from_url(&server.url);
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let _guard = runtime.enter();
+    let handle = dt.spawn_send_message_silent(DingTalkMessage::new_text("hello"));
+    runtime.block_on(handle).unwrap();
+    assert_eq!(1, server.request_count());
+    assert_eq!(1, dt.silent_failure_count());
+}
+
+#[cfg(not(feature = "tracing"))]
+#[test]
+fn send_message_silent_counts_failures_without_tracing() {
+    let dt = DingTalk::from_url("http://127.0.0.1:1/robot/send");
+    tokio_test::block_on(dt.send_message_silent(DingTalkMessage::new_text("hello")));
+    tokio_test::block_on(dt.send_message_silent(DingTalkMessage::new_link("", "", "", "")));
+    assert_eq!(2, dt.silent_failure_count());
+}
+
+#[cfg(feature = "log-forward")]
+#[test]
+fn send_message_silent_logs_failure() {
+    use std::sync::Mutex;
+
+    struct CapturingLogger(Mutex<Vec<(log::Level, String, String)>>);
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            self.0.lock().unwrap().push((
+                record.level(),
+                record.target().to_owned(),
+                record.args().to_string(),
+            ));
+        }
+
+        fn flush(&self) {}
+    }
+
+    let logger: &'static CapturingLogger = Box::leak(Box::new(CapturingLogger(Mutex::default())));
+    log::set_logger(logger).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":310000,"errmsg":"sign not match"}"#,
+    ));
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_message_silent(DingTalkMessage::new_text("hello")));
+    assert_eq!(1, dt.silent_failure_count());
+    let records = logger.0.lock().unwrap();
+    assert!(records.contains(&(
+        log::Level::Warn,
+        "dingtalk::silent".to_owned(),
+        "silent send failed, error is dropped, error_kind: server, errcode: Some(310000)"
+            .to_owned()
+    )));
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+#[test]
+fn slack_send_text() {
+    let server = MockServer::reply(MockResponse::new(200, "ok"));
+    let dt = DingTalk::new_slack(&server.url);
+    tokio_test::block_on(
+        dt.send_message(
+            DingTalkMessage::new_text("hello")
+                .at_user_id("U001")
+                .at_all(),
+        ),
+    )
+    .unwrap();
+    let requests = server.requests();
+    assert_eq!("/robot/send", requests[0].path);
+    assert_eq!(
+        r#"{"text":"hello <@U001> <!channel>"}"#,
+        requests[0].body_str()
+    );
+}
+
+#[test]
+fn slack_send_markdown() {
+    let server = MockServer::reply(MockResponse::new(200, "ok"));
+    let dt = DingTalk::new_slack(&server.url);
+    tokio_test::block_on(dt.send_markdown("title", "_italic_")).unwrap();
+    assert_eq!(
+        r#"{"blocks":[{"text":{"text":"*title*\n_italic_","type":"mrkdwn"},"type":"section"}],"text":"title"}"#,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn slack_send_link() {
+    let server = MockServer::reply(MockResponse::new(200, "ok"));
+    let mut dt = DingTalk::from_url(&server.url);
+    dt.dingtalk_type = DingTalkType::Slack;
+    tokio_test::block_on(dt.send_link("title", "text", "", "https://example.com/")).unwrap();
+    assert_eq!(
+        r#"{"attachments":[{"text":"text","title":"title","title_link":"https://example.com/"}],"text":"title"}"#,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn slack_send_action_card() {
+    let server = MockServer::reply(MockResponse::new(200, "ok"));
+    let dt = DingTalk::new_slack(&server.url);
+    let message = DingTalkMessage::new_action_card("title", "text").add_action_card_btn(
+        DingTalkMessageActionCardBtn {
+            title: "Open".into(),
+            action_url: "https://example.com/".into(),
+        },
+    );
+    tokio_test::block_on(dt.send_message(message)).unwrap();
+    assert_eq!(
+        r#"{"blocks":[{"text":{"text":"*title*\ntext","type":"mrkdwn"},"type":"section"},{"elements":[{"text":{"text":"Open","type":"plain_text"},"type":"button","url":"https://example.com/"}],"type":"actions"}],"text":"title"}"#,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn slack_error_status() {
+    let server = MockServer::reply(MockResponse::new(400, "invalid_payload"));
+    let dt = DingTalk::new_slack(&server.url);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Status { status: 400, .. }));
+}
+
+#[test]
+fn slack_unsupported_message() {
+    let server = MockServer::reply(MockResponse::new(200, "ok"));
+    let dt = DingTalk::new_slack(&server.url);
+    let err = tokio_test::block_on(dt.send_message(DingTalkMessage::new_feed_card())).unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn slack_from_json_url_not_signed() {
+    let dt = DingTalk::from_json(
+        r#"{"type":"slack","direct_url":"https://hooks.slack.com/services/T0/B0/X0","sec_token":"secret001"}"#,
+    )
+    .unwrap();
+    assert!(matches!(dt.dingtalk_type, DingTalkType::Slack));
+    assert_eq!(
+        "https://hooks.slack.com/services/T0/B0/X0",
+        dt.generate_signed_url().unwrap()
+    );
+}
@@ -0,0 +1,138 @@
+use dingtalk::*;
+use serde_json::Value;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+fn strip_suffix(chunk: &str) -> &str {
+    &chunk[..chunk.rfind(" (").unwrap()]
+}
+
+#[test]
+fn split_text_within_limit() {
+    assert_eq!(vec!["hello"], DingTalkMessage::split_text("hello", 5));
+}
+
+#[test]
+fn split_text_on_lines() {
+    assert_eq!(
+        vec!["aaaa (1/3)", "bbbb (2/3)", "cccc (3/3)"],
+        DingTalkMessage::split_text("aaaa\nbbbb\ncccc", 13)
+    );
+}
+
+#[test]
+fn split_text_on_char_boundaries() {
+    let text = "钉钉机器人发送消息";
+    let chunks = DingTalkMessage::split_text(text, 13);
+    assert_eq!(
+        vec![
+            "钉钉 (1/5)",
+            "机器 (2/5)",
+            "人发 (3/5)",
+            "送消 (4/5)",
+            "息 (5/5)"
+        ],
+        chunks
+    );
+    assert!(chunks.iter().all(|chunk| chunk.len() <= 13));
+}
+
+#[test]
+fn split_text_suffix_digits() {
+    let text = "a".repeat(100);
+    let chunks = DingTalkMessage::split_text(&text, 10);
+    assert_eq!(50, chunks.len());
+    assert_eq!("aa (1/50)", chunks[0]);
+    assert_eq!("aa (50/50)", chunks[49]);
+    assert!(chunks.iter().all(|chunk| chunk.len() <= 10));
+}
+
+#[test]
+fn split_text_keeps_content() {
+    let text = "告警: 磁盘已满\nhost=web-01 使用率 99%\n\n".repeat(30);
+    for max_bytes in [40, 64, 100, 333] {
+        let chunks = DingTalkMessage::split_text(&text, max_bytes);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= max_bytes));
+        let joined: String = chunks.iter().map(|chunk| strip_suffix(chunk)).collect();
+        assert_eq!(text.replace('\n', ""), joined.replace('\n', ""));
+    }
+}
+
+fn long_text(lines: usize) -> String {
+    (0..lines)
+        .map(|i| format!("{:04} {}\n", i, "x".repeat(95)))
+        .collect()
+}
+
+#[test]
+fn auto_split_text_message() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "")
+        .auto_split(true)
+        .with_transport(transport.clone());
+    let text = long_text(450);
+    let response = tokio_test::block_on(
+        dt.send_message_with_response(DingTalkMessage::new_text(&text).at_all()),
+    )
+    .unwrap();
+    assert!(response.is_success());
+
+    let bodies: Vec<Value> = transport
+        .bodies()
+        .iter()
+        .map(|body| serde_json::from_str(body).unwrap())
+        .collect();
+    assert_eq!(3, bodies.len());
+    for (i, body) in bodies.iter().enumerate() {
+        let content = body["text"]["content"].as_str().unwrap();
+        assert!(content.len() <= 20000);
+        assert!(content.ends_with(&format!(" ({}/3)", i + 1)));
+        assert_eq!(i == 2, body["at"]["isAtAll"] == true);
+    }
+    assert!(bodies[0]["text"]["content"]
+        .as_str()
+        .unwrap()
+        .starts_with("0000 "));
+}
+
+#[test]
+fn auto_split_off() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "").with_transport(transport.clone());
+    tokio_test::block_on(dt.send_text(&long_text(450))).unwrap();
+    assert_eq!(1, transport.requests().len());
+}
+
+#[test]
+fn auto_split_stops_on_failure() {
+    let transport = Arc::new(RecordingTransport::new());
+    transport.reply(TransportResponse::new(500, ""));
+    let dt = DingTalk::new("token001", "")
+        .auto_split(true)
+        .with_transport(transport.clone());
+    let err = tokio_test::block_on(dt.send_text(&long_text(450))).unwrap_err();
+    assert!(matches!(err, DingTalkError::Status { status: 500, .. }));
+    assert_eq!(1, transport.requests().len());
+}
+
+#[test]
+fn send_text_chunked_paced() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new_wechat("key001").with_transport(transport.clone());
+    let started = Instant::now();
+    tokio_test::block_on(async {
+        dt.send_text_chunked(&long_text(50), Some(Duration::from_millis(20)))
+            .await
+            .unwrap();
+        dt.send_text_chunked("short", Some(Duration::from_millis(20)))
+            .await
+            .unwrap();
+    });
+    assert!(started.elapsed() >= Duration::from_millis(40));
+    let bodies = transport.bodies();
+    assert_eq!(4, bodies.len());
+    assert!(bodies[2].contains("(3/3)"));
+    assert!(bodies[3].contains(r#""content":"short""#));
+}
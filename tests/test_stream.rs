@@ -0,0 +1,202 @@
+#![cfg(feature = "stream")]
+
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::stream::{StreamClient, BOT_MESSAGE_TOPIC};
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::{future::Future, time::Duration};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{tungstenite::Message, WebSocketStream};
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+/// Open API replies WebSocket endpoint of `ws_port`
+fn open_server(ws_port: u16) -> MockServer {
+    MockServer::reply(MockResponse::new(
+        200,
+        &format!(
+            r#"{{"endpoint":"ws://127.0.0.1:{}/connect","ticket":"ticket 001"}}"#,
+            ws_port
+        ),
+    ))
+}
+
+fn stream_client(server: &MockServer) -> StreamClient {
+    StreamClient::new("key001", "secret001")
+        .with_api_url(&server.base_url())
+        .with_reconnect_delay(Duration::from_millis(10), Duration::from_millis(50))
+}
+
+fn callback_frame(message_id: &str, text: &str) -> Message {
+    let data = json!({
+        "msgtype": "text",
+        "text": { "content": text },
+        "senderNick": "Alice",
+        "conversationType": "1",
+        "sessionWebhook": "https://oapi.dingtalk.com/robot/sendBySession?session=s001",
+    });
+    let frame = json!({
+        "specVersion": "1.0",
+        "type": "CALLBACK",
+        "headers": { "topic": BOT_MESSAGE_TOPIC, "messageId": message_id },
+        "data": data.to_string(),
+    });
+    Message::Text(frame.to_string())
+}
+
+async fn accept(listener: &TcpListener) -> WebSocketStream<TcpStream> {
+    let (stream, _) = listener.accept().await.unwrap();
+    tokio_tungstenite::accept_async(stream).await.unwrap()
+}
+
+/// Next text frame from client as JSON, `None` when closed
+async fn next_text(websocket: &mut WebSocketStream<TcpStream>) -> Option<Value> {
+    while let Some(message) = websocket.next().await {
+        match message.unwrap() {
+            Message::Text(text) => return Some(serde_json::from_str(&text).unwrap()),
+            Message::Close(_) => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+async fn with_timeout<F: Future>(future: F) -> F::Output {
+    tokio::time::timeout(Duration::from_secs(10), future)
+        .await
+        .expect("timed out")
+}
+
+#[test]
+fn stream_open_connection() {
+    let server = open_server(9);
+    let client = stream_client(&server);
+    let connection = runtime().block_on(client.open_connection()).unwrap();
+    assert_eq!("ws://127.0.0.1:9/connect", connection.endpoint);
+    assert_eq!(
+        "ws://127.0.0.1:9/connect?ticket=ticket%20001",
+        connection.url()
+    );
+
+    let request = &server.requests()[0];
+    assert_eq!("/v1.0/gateway/connections/open", request.path);
+    let body: Value = serde_json::from_str(&request.body_str()).unwrap();
+    assert_eq!("key001", body["clientId"]);
+    assert_eq!("secret001", body["clientSecret"]);
+    assert_eq!(
+        json!([{ "type": "CALLBACK", "topic": "/v1.0/im/bot/messages/get" }]),
+        body["subscriptions"]
+    );
+}
+
+#[test]
+fn stream_delivers_and_acks_messages() {
+    let runtime = runtime();
+    runtime.block_on(with_timeout(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server = open_server(listener.local_addr().unwrap().port());
+        let mut messages = stream_client(&server).start();
+        let mut websocket = accept(&listener).await;
+
+        let ping = json!({
+            "type": "SYSTEM",
+            "headers": { "topic": "ping", "messageId": "m1" },
+            "data": r#"{"opaque":"o1"}"#,
+        });
+        websocket
+            .send(Message::Text(ping.to_string()))
+            .await
+            .unwrap();
+        let pong = next_text(&mut websocket).await.unwrap();
+        assert_eq!(200, pong["code"]);
+        assert_eq!("m1", pong["headers"]["messageId"]);
+        assert_eq!(r#"{"opaque":"o1"}"#, pong["data"]);
+
+        websocket.send(callback_frame("m2", "hello")).await.unwrap();
+        let message = messages.next().await.unwrap();
+        assert_eq!("Alice", message.sender_nick);
+        assert_eq!(Some("hello".to_string()), message.text());
+        let ack = next_text(&mut websocket).await.unwrap();
+        assert_eq!(200, ack["code"]);
+        assert_eq!("m2", ack["headers"]["messageId"]);
+
+        messages.shutdown().await;
+        // client closes WebSocket on shutdown
+        assert_eq!(None, next_text(&mut websocket).await);
+        assert_eq!(1, server.request_count());
+    }));
+}
+
+#[test]
+fn stream_reconnects_after_disconnect() {
+    let runtime = runtime();
+    runtime.block_on(with_timeout(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server = open_server(listener.local_addr().unwrap().port());
+        let mut messages = stream_client(&server).start();
+
+        let mut websocket = accept(&listener).await;
+        websocket.close(None).await.unwrap();
+        drop(websocket);
+
+        let mut websocket = accept(&listener).await;
+        websocket.send(callback_frame("m1", "again")).await.unwrap();
+        let message = messages.next().await.unwrap();
+        assert_eq!(Some("again".to_string()), message.text());
+        // a new ticket is requested for each connection
+        assert_eq!(2, server.request_count());
+
+        messages.shutdown().await;
+    }));
+}
+
+#[test]
+fn stream_reconnects_after_server_disconnect_frame() {
+    let runtime = runtime();
+    runtime.block_on(with_timeout(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server = open_server(listener.local_addr().unwrap().port());
+        let messages = stream_client(&server).start();
+
+        let mut websocket = accept(&listener).await;
+        let disconnect = json!({
+            "type": "SYSTEM",
+            "headers": { "topic": "disconnect", "messageId": "m1" },
+            "data": "{}",
+        });
+        websocket
+            .send(Message::Text(disconnect.to_string()))
+            .await
+            .unwrap();
+
+        let _websocket = accept(&listener).await;
+        assert_eq!(2, server.request_count());
+        messages.shutdown().await;
+    }));
+}
+
+#[test]
+fn stream_shutdown_while_reconnecting() {
+    let runtime = runtime();
+    runtime.block_on(with_timeout(async {
+        let server = MockServer::reply(MockResponse::new(500, ""));
+        let mut messages = StreamClient::new("key001", "secret001")
+            .with_api_url(&server.base_url())
+            .with_reconnect_delay(Duration::from_secs(60), Duration::from_secs(60))
+            .start();
+        while server.request_count() == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(futures::poll!(messages.next()).is_pending());
+        messages.shutdown().await;
+        assert_eq!(1, server.request_count());
+    }));
+}
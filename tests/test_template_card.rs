@@ -0,0 +1,77 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+#[test]
+fn send_text_notice() {
+    let server = MockServer::reply(MockResponse::ok());
+    let mut dt = DingTalk::new_wechat("key");
+    dt.set_default_webhook_url(&server.url);
+    let card = WeChatTemplateCard::text_notice()
+        .source("https://hatter.ink/favicon.png", "monitor")
+        .main_title("Alert", "CPU usage high")
+        .add_horizontal_content("host", "web-001")
+        .add_horizontal_content_url("dashboard", "grafana", "https://hatter.ink/")
+        .add_jump_url("detail", "https://hatter.ink/")
+        .card_action_url("https://hatter.ink/");
+    tokio_test::block_on(dt.send_template_card(&card)).unwrap();
+    assert_eq!(
+        concat!(
+            r#"{"msgtype":"template_card","template_card":{"#,
+            r#""card_action":{"type":1,"url":"https://hatter.ink/"},"#,
+            r#""card_type":"text_notice","#,
+            r#""horizontal_content_list":[{"keyname":"host","value":"web-001"},"#,
+            r#"{"keyname":"dashboard","type":1,"url":"https://hatter.ink/","value":"grafana"}],"#,
+            r#""jump_list":[{"title":"detail","type":1,"url":"https://hatter.ink/"}],"#,
+            r#""main_title":{"desc":"CPU usage high","title":"Alert"},"#,
+            r#""source":{"desc":"monitor","icon_url":"https://hatter.ink/favicon.png"}}}"#
+        ),
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn card_action_required() {
+    let card = WeChatTemplateCard::text_notice().main_title("Alert", "");
+    match card.validate().unwrap_err() {
+        DingTalkError::Validation(msg) => assert!(msg.contains("card_action")),
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn news_notice_required_fields() {
+    let card = WeChatTemplateCard::news_notice().card_action_url("https://hatter.ink/");
+    match card.validate().unwrap_err() {
+        DingTalkError::Validation(msg) => {
+            assert!(msg.contains("main_title"));
+            assert!(msg.contains("card_image"));
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+    let card = card
+        .main_title("News", "")
+        .card_image("https://hatter.ink/favicon.png", Some(1.3));
+    card.validate().unwrap();
+}
+
+#[test]
+fn template_card_validated_before_send() {
+    let server = MockServer::reply(MockResponse::ok());
+    let mut dt = DingTalk::new_wechat("key");
+    dt.set_default_webhook_url(&server.url);
+    let card = WeChatTemplateCard::text_notice().main_title("Alert", "");
+    assert!(tokio_test::block_on(dt.send_template_card(&card)).is_err());
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn dingtalk_template_card_unsupported() {
+    let dt = DingTalk::new("token", "");
+    let card = WeChatTemplateCard::text_notice()
+        .main_title("Alert", "")
+        .card_action_url("https://hatter.ink/");
+    let err = tokio_test::block_on(dt.send_template_card(&card)).unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+}
@@ -0,0 +1,177 @@
+#![cfg(feature = "tera")]
+
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use serde_json::json;
+use std::{env, fs, path::PathBuf};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("dingtalk_tera_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn sent_markdown(server: &MockServer) -> serde_json::Value {
+    serde_json::from_str(&server.requests()[0].body_str()).unwrap()
+}
+
+#[test]
+fn tera_send_markdown_inline() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_markdown_tera(
+        "Failed hosts",
+        "{% if hosts %}{% for host in hosts %}- **{{ host }}** <down>\n{% endfor %}{% else %}all ok{% endif %}",
+        &json!({"hosts": ["web-01", "web-02"]}),
+    ))
+    .unwrap();
+    assert_eq!(
+        json!({
+            "msgtype": "markdown",
+            "markdown": {"title": "Failed hosts", "text": "- **web-01** <down>\n- **web-02** <down>\n"},
+        }),
+        sent_markdown(&server)
+    );
+}
+
+#[test]
+fn tera_send_markdown_file() {
+    let dir = temp_dir("file");
+    let file = dir.join("deploy.md");
+    fs::write(&file, "Deploy {{ version }} done").unwrap();
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_markdown_tera(
+        "Deploy",
+        file.to_str().unwrap(),
+        &json!({"version": "1.2.0"}),
+    ))
+    .unwrap();
+    assert_eq!(
+        "Deploy 1.2.0 done",
+        sent_markdown(&server)["markdown"]["text"]
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn tera_template_store_from_dir() {
+    let dir = temp_dir("store");
+    fs::create_dir_all(dir.join("alerts")).unwrap();
+    fs::write(
+        dir.join("base.md"),
+        "# {% block title %}{% endblock %}\n{% block body %}{% endblock %}",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("alerts").join("disk.md"),
+        "{% extends \"base.md\" %}{% block title %}Disk{% endblock %}{% block body %}{% include \"footer.md\" %}{% endblock %}",
+    )
+    .unwrap();
+    fs::write(dir.join("footer.md"), "{{ host }} disk {{ usage }}%").unwrap();
+
+    let store = TemplateStore::from_dir(&dir).unwrap();
+    assert_eq!(
+        vec!["alerts/disk.md", "base.md", "footer.md"],
+        store.names()
+    );
+    assert_eq!(
+        "# Disk\nweb-01 disk 95%",
+        store
+            .render("alerts/disk.md", &json!({"host": "web-01", "usage": 95}))
+            .unwrap()
+    );
+
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_markdown_tera_template(
+        "Disk",
+        &store,
+        "footer.md",
+        &json!({"host": "db-01", "usage": 80}),
+    ))
+    .unwrap();
+    assert_eq!("db-01 disk 80%", sent_markdown(&server)["markdown"]["text"]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn tera_parse_error_has_name_and_line() {
+    let mut store = TemplateStore::new();
+    let err = store
+        .add("alert.md", "line 1\nline 2 {{ host | }}\n")
+        .unwrap_err();
+    match &err {
+        DingTalkError::Template {
+            name,
+            line,
+            message,
+        } => {
+            assert_eq!("alert.md", name);
+            assert_eq!(Some(2), *line);
+            assert!(message.contains("expected"), "{}", message);
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+    assert!(err
+        .to_string()
+        .starts_with("Template error: alert.md line 2: "));
+    assert!(store.names().is_empty());
+}
+
+#[test]
+fn tera_from_dir_parse_error_names_file() {
+    let dir = temp_dir("parse_error");
+    fs::write(dir.join("ok.md"), "ok").unwrap();
+    fs::write(dir.join("bad.md"), "{% if %}").unwrap();
+    let err = TemplateStore::from_dir(&dir).err().unwrap();
+    assert!(matches!(
+        err,
+        DingTalkError::Template { ref name, line: Some(1), .. } if name == "bad.md"
+    ));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn tera_render_error_has_name_and_line() {
+    let mut store = TemplateStore::new();
+    store
+        .add(
+            "alert.md",
+            "# Alert\n{{ host }}\n{% for h in failed_hosts %}{{ h }}{% endfor %}",
+        )
+        .unwrap();
+    let err = store
+        .render("alert.md", &json!({"host": "web-01"}))
+        .unwrap_err();
+    assert_eq!(
+        "Template error: alert.md line 3: Variable `failed_hosts` not found in context while rendering 'alert.md'",
+        err.to_string()
+    );
+
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let err = tokio_test::block_on(dt.send_markdown_tera("Alert", "{{ missing }}", &json!({})))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Template { ref name, line: Some(1), .. } if name == "inline"
+    ));
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn tera_render_unknown_or_invalid_context() {
+    let mut store = TemplateStore::new();
+    store.add("alert.md", "{{ host }}").unwrap();
+    let err = store.render("missing.md", &json!({})).unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Template { ref name, line: None, .. } if name == "missing.md"
+    ));
+    let err = store.render("alert.md", &json!(["web-01"])).unwrap_err();
+    assert!(matches!(err, DingTalkError::Template { line: None, .. }));
+}
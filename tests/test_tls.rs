@@ -0,0 +1,134 @@
+#![cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+
+use dingtalk::*;
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener},
+    sync::{mpsc, Arc},
+    thread,
+};
+
+const CA_CERT: &str = include_str!("tls/ca.crt");
+const SERVER_CERT: &str = include_str!("tls/localhost.crt");
+const SERVER_KEY: &str = include_str!("tls/localhost.key");
+
+/// DER of the first PEM block
+fn pem_to_der(pem: &str) -> Vec<u8> {
+    let base64_body: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    base64::decode(base64_body).unwrap()
+}
+
+/// HTTPS server replies one request, returns address and receiver of raw request
+fn start_tls_server(response_body: &'static str) -> (SocketAddr, mpsc::Receiver<String>) {
+    let config = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(
+            vec![rustls::Certificate(pem_to_der(SERVER_CERT))],
+            rustls::PrivateKey(pem_to_der(SERVER_KEY)),
+        )
+        .unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let connection = rustls::ServerConnection::new(Arc::new(config)).unwrap();
+        let mut tls = rustls::StreamOwned::new(connection, stream);
+        let mut raw = vec![];
+        let mut buf = [0_u8; 4096];
+        loop {
+            // handshake fails when client does not trust the certificate
+            let n = match tls.read(&mut buf) {
+                Ok(0) | Err(_) => return,
+                Ok(n) => n,
+            };
+            raw.extend_from_slice(&buf[..n]);
+            let request = String::from_utf8_lossy(&raw).into_owned();
+            if let Some((head, body)) = request.split_once("\r\n\r\n") {
+                let content_length = head
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        if name.eq_ignore_ascii_case("content-length") {
+                            value.trim().parse::<usize>().ok()
+                        } else {
+                            None
+                        }
+                    })
+                    .unwrap_or(0);
+                if body.len() >= content_length {
+                    let _ = tx.send(request);
+                    break;
+                }
+            }
+        }
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response_body.len(),
+            response_body
+        );
+        let _ = tls.write_all(response.as_bytes());
+        tls.conn.send_close_notify();
+        let _ = tls.flush();
+    });
+    (addr, rx)
+}
+
+fn tls_transport(addr: SocketAddr) -> ReqwestTransport {
+    let client = reqwest::Client::builder()
+        .add_root_certificate(reqwest::Certificate::from_pem(CA_CERT.as_bytes()).unwrap())
+        .resolve("localhost", addr)
+        .build()
+        .unwrap();
+    ReqwestTransport::new(client)
+}
+
+#[test]
+fn tls_send_signed_message() {
+    let (addr, rx) = start_tls_server(r#"{"errcode":0,"errmsg":"ok"}"#);
+    let dt = DingTalk::builder()
+        .access_token("token001")
+        .sec_token("secret001")
+        .webhook_url(&format!("https://localhost:{}/robot/send", addr.port()))
+        .build()
+        .unwrap()
+        .with_transport(tls_transport(addr));
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("POST /robot/send?access_token=token001&timestamp="));
+    assert!(request.contains("&sign="));
+    assert!(request.ends_with(r#"{"msgtype":"text","text":{"content":"hello"}}"#));
+}
+
+#[test]
+fn tls_server_error_code() {
+    let (addr, _rx) = start_tls_server(r#"{"errcode":310000,"errmsg":"sign not match"}"#);
+    let dt = DingTalk::from_url(&format!("https://localhost:{}/robot/send", addr.port()))
+        .with_transport(tls_transport(addr));
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Server {
+            errcode: 310000,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn tls_untrusted_certificate() {
+    let (addr, _rx) = start_tls_server(r#"{"errcode":0,"errmsg":"ok"}"#);
+    let client = reqwest::Client::builder()
+        .resolve("localhost", addr)
+        .build()
+        .unwrap();
+    let dt = DingTalk::from_url(&format!("https://localhost:{}/robot/send", addr.port()))
+        .with_transport(ReqwestTransport::new(client));
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Http(_)));
+}
@@ -0,0 +1,295 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+/// Recorded span or event, fields are `(name, value)` in record order
+#[derive(Clone, Debug)]
+struct Recorded {
+    metadata: &'static Metadata<'static>,
+    name: String,
+    level: tracing::Level,
+    fields: Vec<(String, String)>,
+}
+
+impl Recorded {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{:?}", value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+}
+
+#[derive(Clone, Default)]
+struct Collector {
+    next_id: Arc<AtomicU64>,
+    spans: Arc<Mutex<Vec<Recorded>>>,
+    events: Arc<Mutex<Vec<Recorded>>>,
+    entered: Arc<Mutex<Vec<span::Id>>>,
+}
+
+impl Collector {
+    fn span(&self, name: &str) -> Recorded {
+        self.spans
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|s| s.name == name)
+            .cloned()
+            .unwrap_or_else(|| panic!("span {} not recorded", name))
+    }
+
+    fn all_values(&self) -> Vec<String> {
+        let spans = self.spans.lock().unwrap();
+        let events = self.events.lock().unwrap();
+        spans
+            .iter()
+            .chain(events.iter())
+            .flat_map(|r| r.fields.iter().map(|(_, v)| v.clone()))
+            .collect()
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
+        let mut fields = vec![];
+        attrs.record(&mut FieldVisitor(&mut fields));
+        self.spans.lock().unwrap().push(Recorded {
+            metadata: attrs.metadata(),
+            name: attrs.metadata().name().to_string(),
+            level: *attrs.metadata().level(),
+            fields,
+        });
+        // span index + 1, `span::Id` must be non-zero
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        span::Id::from_u64(id)
+    }
+
+    fn record(&self, id: &span::Id, values: &span::Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let span = &mut spans[id.into_u64() as usize - 1];
+        values.record(&mut FieldVisitor(&mut span.fields));
+    }
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = vec![];
+        event.record(&mut FieldVisitor(&mut fields));
+        self.events.lock().unwrap().push(Recorded {
+            metadata: event.metadata(),
+            name: event.metadata().name().to_string(),
+            level: *event.metadata().level(),
+            fields,
+        });
+    }
+
+    fn enter(&self, id: &span::Id) {
+        self.entered.lock().unwrap().push(id.clone());
+    }
+
+    fn exit(&self, _: &span::Id) {
+        self.entered.lock().unwrap().pop();
+    }
+
+    fn current_span(&self) -> tracing_core::span::Current {
+        match self.entered.lock().unwrap().last() {
+            Some(id) => {
+                let metadata = self.spans.lock().unwrap()[id.into_u64() as usize - 1].metadata;
+                tracing_core::span::Current::new(id.clone(), metadata)
+            }
+            None => tracing_core::span::Current::none(),
+        }
+    }
+}
+
+fn signed_dingtalk(server: &MockServer) -> DingTalk {
+    DingTalk::builder()
+        .access_token("token001")
+        .sec_token("secret001")
+        .webhook_url(&server.url)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn tracing_send_message_fields() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = signed_dingtalk(&server);
+    let collector = Collector::default();
+    let message = DingTalkMessage::new_text("hello");
+    let payload_bytes = serde_json::to_string(&dt.build_payload(&message).unwrap())
+        .unwrap()
+        .len();
+    tracing::subscriber::with_default(collector.clone(), || {
+        tokio_test::block_on(dt.send_message(message)).unwrap();
+    });
+
+    let send_message = collector.span("dingtalk.send_message");
+    assert_eq!(Some("Text"), send_message.field("message_type"));
+    let send = collector.span("dingtalk.send");
+    assert_eq!(tracing::Level::INFO, send.level);
+    assert_eq!(Some("DingTalk"), send.field("robot_type"));
+    assert_eq!(
+        Some(payload_bytes.to_string().as_str()),
+        send.field("payload_bytes")
+    );
+    assert_eq!(Some("200"), send.field("status"));
+    assert_eq!(Some("0"), send.field("errcode"));
+    assert_eq!(Some("1"), send.field("attempts"));
+    assert!(send.field("elapsed_ms").is_some());
+}
+
+#[test]
+fn tracing_server_errcode() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":310000,"errmsg":"sign not match"}"#,
+    ));
+    let dt = signed_dingtalk(&server);
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), || {
+        tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    });
+
+    let send = collector.span("dingtalk.send");
+    assert_eq!(Some("310000"), send.field("errcode"));
+}
+
+#[test]
+fn tracing_retry_events() {
+    let count = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| match count.fetch_add(1, Ordering::SeqCst) {
+        0 => MockResponse::new(503, ""),
+        _ => MockResponse::ok(),
+    });
+    let dt = signed_dingtalk(&server).with_retry(3, Duration::from_millis(10));
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), || {
+        tokio_test::block_on(dt.send_text("hello")).unwrap();
+    });
+
+    let send = collector.span("dingtalk.send");
+    assert_eq!(Some("200"), send.field("status"));
+    assert_eq!(Some("2"), send.field("attempts"));
+    let events = collector.events.lock().unwrap();
+    let retry = events
+        .iter()
+        .find(|e| e.level == tracing::Level::WARN)
+        .expect("retry event not recorded");
+    assert_eq!(Some("1"), retry.field("attempt"));
+    assert_eq!(Some("status"), retry.field("error_kind"));
+    assert_eq!(Some("10"), retry.field("delay_ms"));
+}
+
+#[test]
+fn tracing_no_secrets() {
+    let server = MockServer::reply(MockResponse::new(500, ""));
+    let dt = signed_dingtalk(&server).with_retry(2, Duration::from_millis(10));
+    let signed_url = dt.generate_signed_url().unwrap();
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), || {
+        tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    });
+
+    let values = collector.all_values();
+    assert!(!values.is_empty());
+    for value in values {
+        assert!(!value.contains("token001"), "{}", value);
+        assert!(!value.contains("secret001"), "{}", value);
+        assert!(!value.contains(&server.url), "{}", value);
+        assert!(!value.contains(&signed_url), "{}", value);
+    }
+}
+
+#[test]
+fn tracing_content_type_header_ignored() {
+    let collector = Collector::default();
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-Type", HeaderValue::from_static("text/plain"));
+    tracing::subscriber::with_default(collector.clone(), || {
+        DingTalk::new("token001", "").default_headers(headers);
+    });
+
+    let events = collector.events.lock().unwrap();
+    assert_eq!(1, events.len());
+    assert_eq!(tracing::Level::WARN, events[0].level);
+}
+
+#[test]
+fn tracing_send_message_silent_failure() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":310000,"errmsg":"sign not match"}"#,
+    ));
+    let dt = signed_dingtalk(&server);
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), || {
+        tokio_test::block_on(dt.send_message_silent(DingTalkMessage::new_text("hello")));
+    });
+
+    let events = collector.events.lock().unwrap();
+    let failure = events
+        .iter()
+        .find(|e| e.level == tracing::Level::WARN)
+        .expect("silent failure event not recorded");
+    assert_eq!(Some("server"), failure.field("error_kind"));
+    assert_eq!(Some("310000"), failure.field("errcode"));
+    assert_eq!(1, dt.silent_failure_count());
+}
+
+#[test]
+fn tracing_config_env_key_ignored() {
+    std::env::set_var("T97_TRACING_DT_SEC", "secret_from_env");
+    let collector = Collector::default();
+    let dt = tracing::subscriber::with_default(collector.clone(), || {
+        DingTalk::from_json(
+            r#"{"access_token": "token001", "sec_token": "secret001", "sec_token_env": "T97_TRACING_DT_SEC"}"#,
+        )
+        .unwrap()
+    });
+    assert_eq!("secret001", dt.sec_token);
+
+    let events = collector.events.lock().unwrap();
+    let ignored = events
+        .iter()
+        .find(|e| e.level == tracing::Level::WARN)
+        .expect("env key ignored event not recorded");
+    assert_eq!(Some("sec_token"), ignored.field("key"));
+    assert_eq!(Some("sec_token_env"), ignored.field("env_key"));
+    drop(events);
+    assert!(!collector.all_values().iter().any(|v| v.contains("secret")));
+}
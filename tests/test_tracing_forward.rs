@@ -0,0 +1,156 @@
+#![cfg(feature = "tracing-forward")]
+
+use dingtalk::*;
+use serde_json::Value;
+use std::{fmt, sync::Arc, time::Duration};
+use tracing::Level;
+use tracing_subscriber::layer::SubscriberExt;
+
+fn markdowns(transport: &RecordingTransport) -> Vec<(String, String)> {
+    transport
+        .bodies()
+        .iter()
+        .map(|body| {
+            let body: Value = serde_json::from_str(body).unwrap();
+            (
+                body["markdown"]["title"].as_str().unwrap().to_owned(),
+                body["markdown"]["text"].as_str().unwrap().to_owned(),
+            )
+        })
+        .collect()
+}
+
+fn layer_builder(transport: &Arc<RecordingTransport>) -> DingTalkLayerBuilder {
+    DingTalkLayer::builder(DingTalk::new("token001", "").with_transport(transport.clone()))
+        .batch_window(Duration::from_millis(50))
+}
+
+/// Run `f` with subscriber of `layer`, then wait background task sends
+fn with_layer(layer: DingTalkLayer, f: impl FnOnce()) -> impl std::future::Future<Output = ()> {
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, f);
+    tokio::time::sleep(Duration::from_millis(200))
+}
+
+struct PanicDebug;
+
+impl fmt::Debug for PanicDebug {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        panic!("debug panic")
+    }
+}
+
+#[test]
+fn tracing_forward_event_markdown() {
+    let transport = Arc::new(RecordingTransport::new());
+    tokio_test::block_on(async {
+        let layer = layer_builder(&transport).build();
+        with_layer(layer, || {
+            let request = tracing::info_span!("request", id = 7, path = "/users");
+            let _request = request.enter();
+            let query = tracing::info_span!("query", table = tracing::field::Empty);
+            query.record("table", "users");
+            let _query = query.enter();
+            tracing::error!(target: "myapp::db", rows = 3, user = "alice", "connection lost");
+        })
+        .await;
+    });
+    let markdowns = markdowns(&transport);
+    assert_eq!(1, markdowns.len());
+    assert_eq!("[ERROR] log record", markdowns[0].0);
+    let text = &markdowns[0].1;
+    assert!(text.starts_with("#### ERROR myapp::db\n\n- location: `tests/test_tracing_forward.rs:"));
+    assert!(text.ends_with(
+        "\n- spans: `request{id=7, path=/users}` > `query{table=users}`\
+         \n- rows: 3\n- user: alice\n\nconnection lost"
+    ));
+}
+
+#[test]
+fn tracing_forward_level_and_target_filter() {
+    let transport = Arc::new(RecordingTransport::new());
+    tokio_test::block_on(async {
+        let layer = layer_builder(&transport)
+            .level(Level::WARN)
+            .target("myapp")
+            .build();
+        with_layer(layer, || {
+            tracing::info!(target: "myapp", "info");
+            tracing::error!(target: "other", "other");
+            tracing::error!(target: "myapp2", "myapp2");
+            tracing::error!(target: "hyper::proto", "hyper");
+            tracing::error!(target: "dingtalk::trace", "dingtalk");
+            tracing::warn!(target: "myapp::db", "db warn");
+            tracing::error!(target: "myapp", "app error");
+        })
+        .await;
+    });
+    let markdowns = markdowns(&transport);
+    assert_eq!(1, markdowns.len());
+    assert_eq!("[ERROR] 2 log records", markdowns[0].0);
+    let text = &markdowns[0].1;
+    assert!(text.contains("db warn"));
+    assert!(text.contains("\n\n---\n\n"));
+    assert!(text.ends_with("app error"));
+}
+
+#[test]
+fn tracing_forward_field_panic_is_caught() {
+    let transport = Arc::new(RecordingTransport::new());
+    tokio_test::block_on(async {
+        let layer = layer_builder(&transport).build();
+        with_layer(layer, || {
+            tracing::error!(value = ?PanicDebug, "panicked");
+            tracing::error!("after panic");
+        })
+        .await;
+    });
+    let markdowns = markdowns(&transport);
+    assert_eq!(1, markdowns.len());
+    assert_eq!("[ERROR] log record", markdowns[0].0);
+    assert!(markdowns[0].1.ends_with("\n\nafter panic"));
+}
+
+#[test]
+fn tracing_forward_messages_per_minute_cap() {
+    let transport = Arc::new(RecordingTransport::new());
+    tokio_test::block_on(async {
+        let layer = layer_builder(&transport)
+            .batch_window(Duration::from_millis(10))
+            .max_messages_per_minute(1)
+            .build();
+        let subscriber = tracing_subscriber::registry().with(layer);
+        let dispatch = tracing::Dispatch::new(subscriber);
+        tracing::dispatcher::with_default(&dispatch, || tracing::error!("first"));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        tracing::dispatcher::with_default(&dispatch, || tracing::error!("second"));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    });
+    let markdowns = markdowns(&transport);
+    assert_eq!(1, markdowns.len());
+    assert!(markdowns[0].1.ends_with("\n\nfirst"));
+}
+
+#[test]
+fn tracing_forward_sending_is_not_forwarded() {
+    let transport = Arc::new(RecordingTransport::new());
+    tokio_test::block_on(async {
+        let dt = DingTalk::new("token001", "")
+            .with_transport(transport.clone())
+            .with_interceptor(|request: RequestParts| {
+                tracing::error!(target: "myapp", "intercepted");
+                Ok(request)
+            });
+        let layer = DingTalkLayer::builder(dt)
+            .batch_window(Duration::from_millis(10))
+            .build();
+        let subscriber = tracing_subscriber::registry().with(layer);
+        let dispatch = tracing::Dispatch::new(subscriber);
+        let _guard = tracing::dispatcher::set_default(&dispatch);
+        tracing::error!(target: "myapp", "first");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    });
+    let markdowns = markdowns(&transport);
+    assert_eq!(1, markdowns.len());
+    assert!(markdowns[0].1.ends_with("\n\nfirst"));
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use futures::future::BoxFuture;
+use serde_json::Value;
+use std::{sync::Arc, time::Duration};
+
+#[test]
+fn transport_records_url_and_body() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "secret001").with_transport(transport.clone());
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(1, requests.len());
+    assert!(requests[0]
+        .url
+        .starts_with("https://oapi.dingtalk.com/robot/send?access_token=token001&timestamp="));
+    assert!(requests[0].url.contains("&sign="));
+    let body: Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!("text", body["msgtype"]);
+    assert_eq!("hello", body["text"]["content"]);
+}
+
+#[test]
+fn transport_feishu_signed_body() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new_feishu("hook001", "secret001").with_transport(transport.clone());
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+
+    assert_eq!(
+        vec!["https://open.feishu.cn/open-apis/bot/v2/hook/hook001".to_string()],
+        transport.urls()
+    );
+    let body: Value = serde_json::from_str(&transport.bodies()[0]).unwrap();
+    assert!(body["timestamp"].is_string());
+    assert!(body["sign"].is_string());
+}
+
+#[test]
+fn transport_replies_in_order() {
+    let transport = Arc::new(RecordingTransport::new());
+    transport
+        .reply(TransportResponse::new(
+            200,
+            r#"{"errcode":310000,"errmsg":"sign not match"}"#,
+        ))
+        .reply(TransportResponse::new(404, ""));
+    let dt = DingTalk::new("token001", "").with_transport(transport.clone());
+
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Server {
+            errcode: 310000,
+            ..
+        }
+    ));
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Status { status: 404, .. }));
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(3, transport.requests().len());
+}
+
+#[test]
+fn transport_rate_limited() {
+    let transport = Arc::new(RecordingTransport::new());
+    transport.reply(TransportResponse::new(429, "").with_header("Retry-After", "2"));
+    let dt = DingTalk::new("token001", "").with_transport(transport.clone());
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert_eq!(Some(Duration::from_secs(2)), err.retry_after());
+}
+
+#[test]
+fn transport_retry() {
+    let transport = Arc::new(RecordingTransport::new());
+    transport.reply(TransportResponse::new(503, ""));
+    let dt = DingTalk::new("token001", "")
+        .with_retry(3, Duration::from_millis(10))
+        .with_transport(transport.clone());
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(2, transport.requests().len());
+}
+
+struct FailingTransport;
+
+impl Transport for FailingTransport {
+    fn post<'a>(&'a self, _: &'a str, _: &'a str) -> BoxFuture<'a, XResult<TransportResponse>> {
+        Box::pin(async { Err(DingTalkError::Unsupported("offline".into())) })
+    }
+}
+
+#[test]
+fn transport_custom_error() {
+    let dt = DingTalk::new("token001", "").with_transport(FailingTransport);
+    let err = tokio_test::block_on(dt.send_text("hello")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+}
+
+#[test]
+fn transport_reqwest_default() {
+    let server = MockServer::reply(MockResponse::ok());
+    let transport = ReqwestTransport::new(reqwest::Client::new());
+    let response = tokio_test::block_on(transport.post(&server.url, r#"{"a":1}"#)).unwrap();
+    assert_eq!(200, response.status);
+    assert_eq!(TransportResponse::ok().body, response.body);
+    assert_eq!(Some("27"), response.header("content-length"));
+
+    let requests = server.requests();
+    assert_eq!("POST", requests[0].method);
+    assert_eq!(
+        Some("application/json; charset=utf-8"),
+        requests[0].header("Content-Type")
+    );
+    assert_eq!(r#"{"a":1}"#, requests[0].body_str());
+}
@@ -0,0 +1,101 @@
+use dingtalk::*;
+use serde_json::Value;
+use std::sync::Arc;
+
+fn truncate_wechat(transport: &Arc<RecordingTransport>) -> DingTalk {
+    DingTalk::new_wechat("key001")
+        .truncate_to_limit(true)
+        .with_transport(transport.clone())
+}
+
+fn sent_body(transport: &RecordingTransport) -> Value {
+    serde_json::from_str(&transport.bodies()[0]).unwrap()
+}
+
+#[test]
+fn truncate_text_on_char_boundary() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = truncate_wechat(&transport);
+    let text = "中".repeat(1000);
+    let response =
+        tokio_test::block_on(dt.send_message_with_response(DingTalkMessage::new_text(&text)))
+            .unwrap();
+    assert!(response.is_truncated());
+    // 2048 bytes limit, "…(truncated)" is 14 bytes, 2034 bytes are 678 chars
+    assert_eq!(Some(966), response.truncated_bytes);
+    let content = sent_body(&transport)["text"]["content"]
+        .as_str()
+        .unwrap()
+        .to_owned();
+    assert_eq!(format!("{}…(truncated)", "中".repeat(678)), content);
+    assert_eq!(2048, content.len());
+}
+
+#[test]
+fn truncate_markdown_custom_ellipsis() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new("token001", "")
+        .truncate_to_limit(true)
+        .truncate_ellipsis("...")
+        .with_transport(transport.clone());
+    let text = "钉".repeat(7000);
+    let response = tokio_test::block_on(
+        dt.send_message_with_response(DingTalkMessage::new_markdown("title", &text)),
+    )
+    .unwrap();
+    // 20000 bytes limit, 19997 bytes are 6665 chars
+    assert_eq!(Some(21000 - 19995), response.truncated_bytes);
+    let content = sent_body(&transport)["markdown"]["text"]
+        .as_str()
+        .unwrap()
+        .to_owned();
+    assert_eq!(format!("{}...", "钉".repeat(6665)), content);
+}
+
+#[test]
+fn truncate_keeps_graphemes() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = truncate_wechat(&transport).truncate_ellipsis("");
+    let family = "👨\u{200d}👩\u{200d}👧";
+    let text = family.repeat(200);
+    let response =
+        tokio_test::block_on(dt.send_message_with_response(DingTalkMessage::new_text(&text)))
+            .unwrap();
+    let content = sent_body(&transport)["text"]["content"]
+        .as_str()
+        .unwrap()
+        .to_owned();
+    assert_eq!(family.repeat(2048 / family.len()), content);
+    assert_eq!(Some(text.len() - content.len()), response.truncated_bytes);
+}
+
+#[test]
+fn truncate_not_needed() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = truncate_wechat(&transport);
+    let response =
+        tokio_test::block_on(dt.send_message_with_response(DingTalkMessage::new_text("hello")))
+            .unwrap();
+    assert!(!response.is_truncated());
+    assert_eq!("hello", sent_body(&transport)["text"]["content"]);
+}
+
+#[test]
+fn truncate_off_by_default() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = DingTalk::new_wechat("key001").with_transport(transport.clone());
+    let text = "中".repeat(1000);
+    let response =
+        tokio_test::block_on(dt.send_message_with_response(DingTalkMessage::new_text(&text)))
+            .unwrap();
+    assert!(!response.is_truncated());
+    assert_eq!(text, sent_body(&transport)["text"]["content"]);
+}
+
+#[test]
+fn truncate_before_length_check() {
+    let transport = Arc::new(RecordingTransport::new());
+    let dt = truncate_wechat(&transport).enforce_length_limits(true);
+    tokio_test::block_on(dt.send_text(&"x".repeat(5000))).unwrap();
+    assert_eq!(1, transport.requests().len());
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+const DEFAULT_USER_AGENT: &str = concat!("dingtalk-rs/", env!("CARGO_PKG_VERSION"));
+
+#[test]
+fn send_default_user_agent() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    assert_eq!(DEFAULT_USER_AGENT, dt.user_agent());
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(
+        Some(DEFAULT_USER_AGENT),
+        server.requests()[0].header("user-agent")
+    );
+}
+
+#[test]
+fn send_custom_user_agent() {
+    let server = MockServer::reply(MockResponse::ok());
+    let mut dt = DingTalk::from_url(&server.url);
+    dt.set_user_agent("alert-bot/1.0 (ops)").unwrap();
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    let request = &server.requests()[0];
+    assert_eq!(Some("alert-bot/1.0 (ops)"), request.header("user-agent"));
+    assert_eq!(
+        r#"{"msgtype":"text","text":{"content":"hello"}}"#,
+        request.body_str()
+    );
+}
+
+#[test]
+fn upload_custom_user_agent() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":0,"errmsg":"ok","type":"file","media_id":"media_id_001"}"#,
+    ));
+    let mut dt = DingTalk::new_wechat("key001");
+    dt.set_default_webhook_url(&server.url);
+    dt.set_user_agent("alert-bot/1.0").unwrap();
+    tokio_test::block_on(dt.upload_wechat_media("report.txt", b"daily report".to_vec())).unwrap();
+    assert_eq!(
+        Some("alert-bot/1.0"),
+        server.requests()[0].header("user-agent")
+    );
+}
+
+#[test]
+fn builder_user_agent() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::builder()
+        .direct_url(&server.url)
+        .user_agent("alert-bot/2.0")
+        .build()
+        .unwrap();
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    assert_eq!(
+        Some("alert-bot/2.0"),
+        server.requests()[0].header("user-agent")
+    );
+}
+
+#[test]
+fn invalid_user_agent() {
+    let mut dt = DingTalk::new("token001", "");
+    for user_agent in ["bad\nagent", "bad\u{7f}agent"] {
+        match dt.set_user_agent(user_agent) {
+            Err(DingTalkError::Config(message)) => {
+                assert!(message.starts_with("User agent is not a valid header value"))
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+    assert_eq!(DEFAULT_USER_AGENT, dt.user_agent());
+    let err = DingTalk::builder()
+        .access_token("token001")
+        .user_agent("bad\r\nagent")
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, DingTalkError::Config(_)));
+}
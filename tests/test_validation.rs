@@ -0,0 +1,160 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+fn validation_error(dt: &DingTalk, message: DingTalkMessage) -> String {
+    match tokio_test::block_on(dt.send_message(message)) {
+        Err(DingTalkError::Validation(message)) => message,
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(()) => panic!("send should fail"),
+    }
+}
+
+#[test]
+fn strict_validation_lists_problems() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).strict_validation(true);
+    assert_eq!(
+        "Message invalid: text_content is required",
+        validation_error(&dt, DingTalkMessage::new_text(""))
+    );
+    assert_eq!(
+        "Message invalid: markdown_title is required; markdown_content is required",
+        validation_error(&dt, DingTalkMessage::new_markdown("", ""))
+    );
+    assert_eq!(
+        "Message invalid: link_pic_url is required; link_message_url is required",
+        validation_error(&dt, DingTalkMessage::new_link("title", "text", "", ""))
+    );
+    assert_eq!(
+        "Message invalid: action_card_text is required; action_card_btns[1] action_url is required",
+        validation_error(
+            &dt,
+            DingTalkMessage::new_action_card("title", "")
+                .add_action_card_btn(DingTalkMessageActionCardBtn {
+                    title: "Yes".into(),
+                    action_url: "https://example.com/yes".into(),
+                })
+                .add_action_card_btn(DingTalkMessageActionCardBtn {
+                    title: "No".into(),
+                    action_url: "".into(),
+                })
+        )
+    );
+    assert_eq!(
+        "Message invalid: feed_card_links requires at least one link",
+        validation_error(&dt, DingTalkMessage::new_feed_card())
+    );
+    let feed_card = (0..11).fold(DingTalkMessage::new_feed_card(), |message, i| {
+        message.add_feed_card_link_detail(&format!("link{}", i), "https://example.com/", "")
+    });
+    assert_eq!(
+        "Message invalid: feed_card_links has 11 links, at most 10",
+        validation_error(&dt, feed_card)
+    );
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn strict_validation_valid_message_sent() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).strict_validation(true);
+    tokio_test::block_on(dt.send_markdown("title", "content")).unwrap();
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn default_validation_permissive() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    tokio_test::block_on(dt.send_text("")).unwrap();
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn strict_validation_malformed_urls() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).strict_validation(true);
+    assert_eq!(
+        "Message invalid: link_pic_url scheme must be one of http/https: ftp://example.com/a.png; \
+         link_message_url is malformed: https://, empty host",
+        validation_error(
+            &dt,
+            DingTalkMessage::new_link("title", "text", "ftp://example.com/a.png", "https://")
+        )
+    );
+    assert_eq!(
+        "Message invalid: action_card_single_btn action_url is malformed: example.com/open, \
+         relative URL without a base",
+        validation_error(
+            &dt,
+            DingTalkMessage::new_action_card("title", "text").set_action_card_single_btn(
+                DingTalkMessageActionCardBtn {
+                    title: "Open".into(),
+                    action_url: "example.com/open".into(),
+                }
+            )
+        )
+    );
+    assert_eq!(
+        "Message invalid: action_card_btns[1] action_url scheme must be one of \
+         http/https/dingtalk: javascript:alert(1)",
+        validation_error(
+            &dt,
+            DingTalkMessage::new_action_card("title", "text")
+                .add_action_card_btn(DingTalkMessageActionCardBtn {
+                    title: "Yes".into(),
+                    action_url: "https://example.com/yes".into(),
+                })
+                .add_action_card_btn(DingTalkMessageActionCardBtn {
+                    title: "No".into(),
+                    action_url: "javascript:alert(1)".into(),
+                })
+        )
+    );
+    assert_eq!(
+        "Message invalid: feed_card_links[0] message_url has no host: dingtalk:open; \
+         feed_card_links[0] pic_url scheme must be one of http/https: dingtalk://client/a.png",
+        validation_error(
+            &dt,
+            DingTalkMessage::new_feed_card().add_feed_card_link(DingTalkMessageFeedCardLink {
+                title: "title".into(),
+                message_url: "dingtalk:open".into(),
+                pic_url: "dingtalk://client/a.png".into(),
+            })
+        )
+    );
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn strict_validation_dingtalk_deep_link() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url).strict_validation(true);
+    let message = DingTalkMessage::new_action_card("title", "text").set_action_card_single_btn(
+        DingTalkMessageActionCardBtn {
+            title: "Open".into(),
+            action_url: "dingtalk://dingtalkclient/page/link?url=https%3A%2F%2Fexample.com%2F"
+                .into(),
+        },
+    );
+    tokio_test::block_on(dt.send_message(message)).unwrap();
+    let message = DingTalkMessage::new_link(
+        "title",
+        "text",
+        "https://example.com/a.png",
+        "dingtalk://dingtalkclient/action/sendmsg?dingtalk_id=user001",
+    );
+    tokio_test::block_on(dt.send_message(message)).unwrap();
+    assert_eq!(2, server.request_count());
+}
+
+#[test]
+fn default_validation_permissive_urls() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = DingTalk::from_url(&server.url);
+    let message = DingTalkMessage::new_link("title", "text", "ftp://a.png", "https://");
+    tokio_test::block_on(dt.send_message(message)).unwrap();
+    assert_eq!(1, server.request_count());
+}
@@ -0,0 +1,29 @@
+use std::{path::Path, process::Command};
+
+/// `cargo check` the library for wasm32-unknown-unknown, skipped when the target is not installed
+#[test]
+fn wasm32_compile_check() {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let sysroot = match Command::new(rustc).args(["--print", "sysroot"]).output() {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).trim().to_owned(),
+        Err(_) => return,
+    };
+    if !Path::new(&sysroot)
+        .join("lib/rustlib/wasm32-unknown-unknown")
+        .is_dir()
+    {
+        eprintln!("wasm32-unknown-unknown is not installed, skipped");
+        return;
+    }
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args(["check", "--lib", "--target", "wasm32-unknown-unknown"])
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(manifest_dir).join("target/wasm32-check"),
+        )
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
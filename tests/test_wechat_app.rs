@@ -0,0 +1,183 @@
+mod common;
+
+use common::{MockRequest, MockResponse, MockServer};
+use dingtalk::*;
+use serde_json::Value;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+fn token_response(access_token: &str, expires_in: u64) -> MockResponse {
+    MockResponse::new(
+        200,
+        &format!(
+            r#"{{"errcode":0,"errmsg":"ok","access_token":"{}","expires_in":{}}}"#,
+            access_token, expires_in
+        ),
+    )
+}
+
+/// Replies token for `/cgi-bin/gettoken` and `appchat_response` for others
+fn wechat_server(appchat_response: MockResponse) -> MockServer {
+    MockServer::start(move |request: &MockRequest| {
+        if request.path.starts_with("/cgi-bin/gettoken") {
+            token_response("token001", 7200)
+        } else {
+            appchat_response.clone()
+        }
+    })
+}
+
+fn appchat_body(server: &MockServer) -> Value {
+    let requests = server.requests();
+    let request = requests.last().unwrap();
+    assert_eq!("POST", request.method);
+    assert_eq!("/cgi-bin/appchat/send?access_token=token001", request.path);
+    serde_json::from_str(&request.body_str()).unwrap()
+}
+
+#[test]
+fn wechat_app_fetch_access_token() {
+    let server = MockServer::reply(token_response("token001", 7200));
+    let app = WeChatApp::new("corp001", "secret 001").with_api_url(&server.base_url());
+    let token = tokio_test::block_on(app.fetch_access_token()).unwrap();
+    assert_eq!("token001", token.access_token);
+    assert_eq!(
+        "/cgi-bin/gettoken?corpid=corp001&corpsecret=secret+001",
+        server.requests()[0].path
+    );
+}
+
+#[test]
+fn wechat_app_access_token_cached() {
+    let server = MockServer::reply(token_response("token001", 7200));
+    let app = WeChatApp::new("corp001", "secret001").with_api_url(&server.base_url());
+    tokio_test::block_on(async {
+        assert_eq!("token001", app.access_token().await.unwrap());
+        assert_eq!("token001", app.access_token().await.unwrap());
+    });
+    assert_eq!(1, server.request_count());
+}
+
+#[test]
+fn send_appchat_text() {
+    let server = wechat_server(MockResponse::ok());
+    let app = WeChatApp::new("corp001", "secret001").with_api_url(&server.base_url());
+    let message = DingTalkMessage::new_text("hello");
+    tokio_test::block_on(app.send_appchat("chat001", &message)).unwrap();
+    tokio_test::block_on(app.send_appchat("chat001", &message)).unwrap();
+    assert_eq!(3, server.request_count());
+    assert_eq!(
+        serde_json::json!({
+            "chatid": "chat001",
+            "msgtype": "text",
+            "text": { "content": "hello" },
+        }),
+        appchat_body(&server)
+    );
+}
+
+#[test]
+fn send_appchat_markdown() {
+    let server = wechat_server(MockResponse::ok());
+    let app = WeChatApp::new("corp001", "secret001").with_api_url(&server.base_url());
+    let message = DingTalkMessage::new_markdown("title", "# hello");
+    tokio_test::block_on(app.send_appchat("chat001", &message)).unwrap();
+    assert_eq!(
+        serde_json::json!({
+            "chatid": "chat001",
+            "msgtype": "markdown",
+            "markdown": { "content": "# hello" },
+        }),
+        appchat_body(&server)
+    );
+}
+
+#[test]
+fn send_appchat_news() {
+    let server = wechat_server(MockResponse::ok());
+    let app = WeChatApp::new("corp001", "secret001").with_api_url(&server.base_url());
+    let message = DingTalkMessage::new_news().add_news_article(
+        "title",
+        "description",
+        "https://example.com/",
+        "https://example.com/1.png",
+    );
+    tokio_test::block_on(app.send_appchat("chat001", &message)).unwrap();
+    assert_eq!(
+        serde_json::json!({
+            "chatid": "chat001",
+            "msgtype": "news",
+            "news": { "articles": [{
+                "title": "title",
+                "description": "description",
+                "url": "https://example.com/",
+                "picurl": "https://example.com/1.png",
+            }] },
+        }),
+        appchat_body(&server)
+    );
+}
+
+#[test]
+fn send_appchat_invalid_message() {
+    let app = WeChatApp::new("corp001", "secret001").with_api_url("http://127.0.0.1:1");
+    let err = tokio_test::block_on(app.send_appchat("chat001", &DingTalkMessage::new_news()))
+        .unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)), "{:?}", err);
+    let err = tokio_test::block_on(
+        app.send_appchat("chat001", &DingTalkMessage::new_link("t", "t", "", "")),
+    )
+    .unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)), "{:?}", err);
+    let err = tokio_test::block_on(app.send_appchat("", &DingTalkMessage::new_text("hello")))
+        .unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)), "{:?}", err);
+}
+
+#[test]
+fn send_appchat_chat_not_exist() {
+    let server = wechat_server(MockResponse::new(
+        200,
+        r#"{"errcode":86003,"errmsg":"chat not exist"}"#,
+    ));
+    let app = WeChatApp::new("corp001", "secret001").with_api_url(&server.base_url());
+    let err = tokio_test::block_on(app.send_appchat("chat404", &DingTalkMessage::new_text("hi")))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Server {
+            errcode: 86003,
+            ref errmsg,
+        } if errmsg == "chat not exist, chatid: chat404"
+    ));
+}
+
+#[test]
+fn send_appchat_invalid_token_dropped() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let appchat_count = count.clone();
+    let server = MockServer::start(move |request: &MockRequest| {
+        if request.path.starts_with("/cgi-bin/gettoken") {
+            return token_response("token001", 7200);
+        }
+        match appchat_count.fetch_add(1, Ordering::SeqCst) {
+            0 => MockResponse::new(200, r#"{"errcode":42001,"errmsg":"access_token expired"}"#),
+            _ => MockResponse::ok(),
+        }
+    });
+    let app = WeChatApp::new("corp001", "secret001").with_api_url(&server.base_url());
+    let message = DingTalkMessage::new_text("hello");
+    tokio_test::block_on(async {
+        app.send_appchat("chat001", &message).await.unwrap_err();
+        app.send_appchat("chat001", &message).await.unwrap();
+    });
+    let token_requests = server
+        .requests()
+        .iter()
+        .filter(|r| r.path.starts_with("/cgi-bin/gettoken"))
+        .count();
+    assert_eq!(2, token_requests);
+    assert_eq!(2, count.load(Ordering::SeqCst));
+}
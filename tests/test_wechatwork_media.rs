@@ -0,0 +1,151 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+use std::{env, fs};
+
+const UPLOAD_RESPONSE: &str = r#"{"errcode":0,"errmsg":"ok","type":"file","media_id":"media_id_001","created_at":"1380000000"}"#;
+
+fn wechat_work(server: &MockServer) -> DingTalk {
+    let mut dt = DingTalk::new_wechat("key001");
+    dt.set_default_webhook_url(&server.url);
+    dt
+}
+
+#[test]
+fn upload_wechat_media() {
+    let server = MockServer::reply(MockResponse::new(200, UPLOAD_RESPONSE));
+    let dt = wechat_work(&server);
+    let media_id =
+        tokio_test::block_on(dt.upload_wechat_media("report.txt", b"daily report".to_vec()))
+            .unwrap();
+    assert_eq!("media_id_001", media_id);
+    let requests = server.requests();
+    assert_eq!("/robot/upload_media?key=key001&type=file", requests[0].path);
+    assert!(requests[0]
+        .header("content-type")
+        .unwrap()
+        .starts_with("multipart/form-data"));
+    let body = requests[0].body_str();
+    assert!(body.contains(r#"name="media"; filename="report.txt""#));
+    assert!(body.contains("daily report"));
+}
+
+#[test]
+fn upload_wechat_media_errcode() {
+    let server = MockServer::reply(MockResponse::new(
+        200,
+        r#"{"errcode":93000,"errmsg":"invalid webhook url"}"#,
+    ));
+    let dt = wechat_work(&server);
+    let err = tokio_test::block_on(dt.upload_wechat_media("report.txt", b"daily report".to_vec()))
+        .unwrap_err();
+    assert!(matches!(err, DingTalkError::Server { errcode: 93000, .. }));
+}
+
+#[test]
+fn upload_wechat_media_size_limit() {
+    let server = MockServer::reply(MockResponse::new(200, UPLOAD_RESPONSE));
+    let dt = wechat_work(&server);
+    let err = tokio_test::block_on(
+        dt.upload_wechat_media("report.txt", vec![0_u8; 20 * 1024 * 1024 + 1]),
+    )
+    .unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn send_file() {
+    let server = MockServer::start(|request| {
+        if request.path.contains("upload_media") {
+            MockResponse::new(200, UPLOAD_RESPONSE)
+        } else {
+            MockResponse::ok()
+        }
+    });
+    let dt = wechat_work(&server);
+    let file_path = env::temp_dir().join("dingtalk_send_file_test.txt");
+    fs::write(&file_path, "daily report").unwrap();
+    tokio_test::block_on(dt.send_file(file_path.to_str().unwrap())).unwrap();
+    let requests = server.requests();
+    assert_eq!(2, requests.len());
+    assert!(requests[0]
+        .body_str()
+        .contains(r#"filename="dingtalk_send_file_test.txt""#));
+    assert_eq!(
+        r#"{"file":{"media_id":"media_id_001"},"msgtype":"file"}"#,
+        requests[1].body_str()
+    );
+}
+
+#[test]
+fn dingtalk_media_unsupported() {
+    let server = MockServer::reply(MockResponse::new(200, UPLOAD_RESPONSE));
+    let mut dt = DingTalk::new("token", "");
+    dt.set_default_webhook_url(&server.url);
+    let err = tokio_test::block_on(dt.upload_wechat_media("report.txt", b"daily report".to_vec()))
+        .unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+    let err = tokio_test::block_on(dt.send_wechat_file("media_id_001")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+    assert_eq!(0, server.request_count());
+}
+
+// AMR-NB 12.2kbps frames: 1 byte header(0x3c) + 31 bytes, 20ms each
+fn amr_voice(seconds: usize) -> Vec<u8> {
+    let mut voice = b"#!AMR\n".to_vec();
+    for _ in 0..seconds * 50 {
+        voice.push(0x3c);
+        voice.extend_from_slice(&[0_u8; 31]);
+    }
+    voice
+}
+
+#[test]
+fn upload_wechat_voice() {
+    let server = MockServer::reply(MockResponse::new(200, UPLOAD_RESPONSE));
+    let dt = wechat_work(&server);
+    let media_id =
+        tokio_test::block_on(dt.upload_wechat_voice("alert.amr", amr_voice(60))).unwrap();
+    assert_eq!("media_id_001", media_id);
+    assert_eq!(
+        "/robot/upload_media?key=key001&type=voice",
+        server.requests()[0].path
+    );
+}
+
+#[test]
+fn upload_wechat_voice_validation() {
+    let server = MockServer::reply(MockResponse::new(200, UPLOAD_RESPONSE));
+    let dt = wechat_work(&server);
+    let err =
+        tokio_test::block_on(dt.upload_wechat_voice("alert.mp3", b"ID3....".to_vec())).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+    let err = tokio_test::block_on(dt.upload_wechat_voice("alert.amr", amr_voice(61))).unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+    assert_eq!(0, server.request_count());
+}
+
+#[test]
+fn send_wechat_voice() {
+    let server = MockServer::reply(MockResponse::ok());
+    let dt = wechat_work(&server);
+    tokio_test::block_on(dt.send_wechat_voice("media_id_001")).unwrap();
+    assert_eq!(
+        r#"{"msgtype":"voice","voice":{"media_id":"media_id_001"}}"#,
+        server.requests()[0].body_str()
+    );
+}
+
+#[test]
+fn dingtalk_voice_unsupported() {
+    let dt = DingTalk::new("token", "");
+    let err = tokio_test::block_on(dt.send_wechat_voice("media_id_001")).unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+    for dt in [dt, DingTalk::new_feishu("token", "")] {
+        let err = tokio_test::block_on(dt.upload_wechat_voice("alert.mp3", b"ID3....".to_vec()))
+            .unwrap_err();
+        assert!(matches!(err, DingTalkError::Unsupported(_)));
+    }
+}
@@ -0,0 +1,229 @@
+mod common;
+
+use common::{MockRequest, MockResponse, MockServer};
+use dingtalk::*;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+const SEND_PATH: &str = "/topapi/message/corpconversation/asyncsend_v2?access_token=token001";
+
+/// Server replies access token and increasing `task_id` from 1001
+fn app_server() -> MockServer {
+    let task_id = AtomicI64::new(1001);
+    MockServer::start(move |request: &MockRequest| {
+        if request.path.starts_with("/gettoken?") {
+            MockResponse::new(
+                200,
+                r#"{"errcode":0,"access_token":"token001","errmsg":"ok","expires_in":7200}"#,
+            )
+        } else {
+            MockResponse::new(
+                200,
+                &format!(
+                    r#"{{"errcode":0,"task_id":{},"request_id":"request001"}}"#,
+                    task_id.fetch_add(1, Ordering::SeqCst)
+                ),
+            )
+        }
+    })
+}
+
+fn app(server: &MockServer) -> DingTalkApp {
+    DingTalkApp::new("key001", "secret001").with_api_url(&server.base_url())
+}
+
+fn send_bodies(server: &MockServer) -> Vec<Value> {
+    server
+        .requests()
+        .iter()
+        .filter(|request| request.path == SEND_PATH)
+        .map(|request| serde_json::from_str(&request.body_str()).unwrap())
+        .collect()
+}
+
+fn sent_msg(dingtalk_message: DingTalkMessage) -> Value {
+    let server = app_server();
+    tokio_test::block_on(app(&server).send_work_notification(
+        10001,
+        &["user001"],
+        &dingtalk_message,
+    ))
+    .unwrap();
+    send_bodies(&server)[0]["msg"].clone()
+}
+
+#[test]
+fn work_notification_text() {
+    let server = app_server();
+    let task_ids = tokio_test::block_on(app(&server).send_work_notification(
+        10001,
+        &["user001", "user002"],
+        &DingTalkMessage::new_text("hello"),
+    ))
+    .unwrap();
+    assert_eq!(vec![1001], task_ids);
+
+    let requests = server.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!("POST", requests[1].method);
+    assert_eq!(SEND_PATH, requests[1].path);
+    assert_eq!(
+        vec![json!({
+            "agent_id": 10001,
+            "userid_list": "user001,user002",
+            "msg": {"msgtype": "text", "text": {"content": "hello"}},
+        })],
+        send_bodies(&server)
+    );
+}
+
+#[test]
+fn work_notification_batches() {
+    let server = app_server();
+    let userids: Vec<String> = (0..250).map(|i| format!("user{:03}", i)).collect();
+    let userids: Vec<&str> = userids.iter().map(String::as_str).collect();
+    let task_ids = tokio_test::block_on(app(&server).send_work_notification(
+        10001,
+        &userids,
+        &DingTalkMessage::new_text("hello"),
+    ))
+    .unwrap();
+    assert_eq!(vec![1001, 1002, 1003], task_ids);
+
+    let bodies = send_bodies(&server);
+    let batch_sizes: Vec<usize> = bodies
+        .iter()
+        .map(|body| body["userid_list"].as_str().unwrap().split(',').count())
+        .collect();
+    assert_eq!(vec![100, 100, 50], batch_sizes);
+    assert!(bodies[1]["userid_list"]
+        .as_str()
+        .unwrap()
+        .starts_with("user100,user101,"));
+    // access token is cached
+    assert_eq!(4, server.request_count());
+}
+
+#[test]
+fn work_notification_markdown_and_link() {
+    assert_eq!(
+        json!({"msgtype": "markdown", "markdown": {"title": "title", "text": "# hello"}}),
+        sent_msg(DingTalkMessage::new_markdown("title", "# hello"))
+    );
+    assert_eq!(
+        json!({"msgtype": "link", "link": {
+            "title": "title",
+            "text": "text",
+            "picUrl": "https://example.com/pic.png",
+            "messageUrl": "https://example.com/",
+        }}),
+        sent_msg(DingTalkMessage::new_link(
+            "title",
+            "text",
+            "https://example.com/pic.png",
+            "https://example.com/"
+        ))
+    );
+}
+
+#[test]
+fn work_notification_action_card() {
+    let btn = |title: &str| DingTalkMessageActionCardBtn {
+        title: title.into(),
+        action_url: format!("https://example.com/{}", title),
+    };
+    assert_eq!(
+        json!({"msgtype": "action_card", "action_card": {
+            "title": "title",
+            "markdown": "text",
+            "single_title": "open",
+            "single_url": "https://example.com/open",
+        }}),
+        sent_msg(
+            DingTalkMessage::new_action_card("title", "text")
+                .set_action_card_single_btn(btn("open"))
+        )
+    );
+    assert_eq!(
+        json!({"msgtype": "action_card", "action_card": {
+            "title": "title",
+            "markdown": "text",
+            "btn_orientation": "1",
+            "btn_json_list": [
+                {"title": "a", "action_url": "https://example.com/a"},
+                {"title": "b", "action_url": "https://example.com/b"},
+            ],
+        }}),
+        sent_msg(
+            DingTalkMessage::new_action_card("title", "text")
+                .action_card_btn_landscape()
+                .add_action_card_btn(btn("a"))
+                .add_action_card_btn(btn("b"))
+        )
+    );
+}
+
+#[test]
+fn work_notification_errcode() {
+    let server = MockServer::start(|request: &MockRequest| {
+        if request.path.starts_with("/gettoken?") {
+            MockResponse::new(
+                200,
+                r#"{"errcode":0,"access_token":"token001","errmsg":"ok","expires_in":7200}"#,
+            )
+        } else {
+            MockResponse::new(200, r#"{"errcode":14,"errmsg":"agent_id is invalid"}"#)
+        }
+    });
+    let err = tokio_test::block_on(app(&server).send_work_notification(
+        10001,
+        &["user001"],
+        &DingTalkMessage::new_text("hello"),
+    ))
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        DingTalkError::Server {
+            errcode: 14,
+            ref errmsg,
+        } if errmsg == "agent_id is invalid"
+    ));
+}
+
+#[test]
+fn work_notification_invalid() {
+    let server = app_server();
+    let app = app(&server);
+    let err = tokio_test::block_on(app.send_work_notification(
+        10001,
+        &[],
+        &DingTalkMessage::new_text("hello"),
+    ))
+    .unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+
+    let err = tokio_test::block_on(app.send_work_notification(
+        10001,
+        &["user001"],
+        &DingTalkMessage::new(DingTalkMessageType::FeedCard),
+    ))
+    .unwrap_err();
+    assert!(matches!(err, DingTalkError::Unsupported(_)));
+
+    let btn = |title: &str| DingTalkMessageActionCardBtn {
+        title: title.into(),
+        action_url: format!("https://example.com/{}", title),
+    };
+    let err = tokio_test::block_on(
+        app.send_work_notification(
+            10001,
+            &["user001"],
+            &DingTalkMessage::new_action_card("title", "text")
+                .set_action_card_single_btn(btn("open"))
+                .add_action_card_btn(btn("a")),
+        ),
+    )
+    .unwrap_err();
+    assert!(matches!(err, DingTalkError::Validation(_)));
+    assert_eq!(0, server.request_count());
+}
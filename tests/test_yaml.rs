@@ -0,0 +1,64 @@
+#![cfg(feature = "yaml")]
+
+use dingtalk::*;
+use std::{env, fs};
+
+#[test]
+fn from_yaml_dingtalk_with_sec_token() {
+    let dt = DingTalk::from_yaml(
+        r#"
+access_token: token001
+sec_token: secret001
+"#,
+    )
+    .unwrap();
+    assert!(matches!(dt.dingtalk_type, DingTalkType::DingTalk));
+    assert_eq!(
+        "https://oapi.dingtalk.com/robot/send",
+        dt.default_webhook_url
+    );
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+}
+
+#[test]
+fn from_yaml_wechat_access_token_only() {
+    let dt = DingTalk::from_yaml("type: wechatwork\naccess_token: key001\n").unwrap();
+    assert!(matches!(dt.dingtalk_type, DingTalkType::WeChatWork));
+    assert_eq!("", dt.sec_token);
+    assert_eq!(
+        "https://qyapi.weixin.qq.com/cgi-bin/webhook/send?key=key001",
+        dt.generate_signed_url().unwrap()
+    );
+}
+
+#[test]
+fn from_yaml_format_error() {
+    assert!(matches!(
+        DingTalk::from_yaml("access_token: [token001"),
+        Err(DingTalkError::Config(_))
+    ));
+    assert!(matches!(
+        DingTalk::from_yaml("- token001"),
+        Err(DingTalkError::Config(_))
+    ));
+}
+
+#[test]
+fn from_file_yaml_and_yml() {
+    let dir = env::temp_dir().join(format!("dingtalk-yaml-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let yaml_file = dir.join("dingtalk.yaml");
+    fs::write(&yaml_file, "access_token: token001\nsec_token: secret001\n").unwrap();
+    let yml_file = dir.join("wechat.yml");
+    fs::write(&yml_file, "type: wechat\naccess_token: key001\n").unwrap();
+
+    let dt = DingTalk::from_file(yaml_file.to_str().unwrap()).unwrap();
+    assert_eq!("token001", dt.access_token);
+    assert_eq!("secret001", dt.sec_token);
+    let dt = DingTalk::from_file(yml_file.to_str().unwrap()).unwrap();
+    assert!(matches!(dt.dingtalk_type, DingTalkType::WeChatWork));
+    assert_eq!("key001", dt.access_token);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
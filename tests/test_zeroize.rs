@@ -0,0 +1,41 @@
+#![cfg(feature = "zeroize")]
+
+mod common;
+
+use common::{MockResponse, MockServer};
+use dingtalk::*;
+
+#[test]
+fn zeroize_signed_send() {
+    let server = MockServer::reply(MockResponse::ok());
+    let mut dt = DingTalk::new("token001", "SEC0123456789abcdef");
+    dt.set_default_webhook_url(&server.url);
+    tokio_test::block_on(dt.send_text("hello")).unwrap();
+    tokio_test::block_on(dt.send_text("hello again")).unwrap();
+    let requests = server.requests();
+    assert_eq!(2, requests.len());
+    assert!(requests[1]
+        .path
+        .contains("access_token=token001&timestamp="));
+    drop(dt);
+}
+
+#[test]
+fn zeroize_constructors_and_builder() {
+    let robots = vec![
+        DingTalk::from_url("https://example.com/robot/send?access_token=token001"),
+        DingTalk::from_session_webhook("https://example.com/robot/sendBySession?session=s", 0),
+        DingTalk::new_wechat("key001"),
+        DingTalk::new_feishu("token001", "SEC001"),
+        DingTalk::new_slack("https://hooks.slack.com/services/T/B/secret"),
+        DingTalk::new_discord("https://discord.com/api/webhooks/1/secret"),
+        DingTalk::builder()
+            .access_token("token001")
+            .sec_token("SEC001")
+            .build()
+            .unwrap(),
+    ];
+    assert_eq!("key001", robots[2].access_token);
+    assert_eq!("SEC001", robots[6].sec_token);
+    drop(robots);
+}